@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+static CACHE: OnceLock<Mutex<HashMap<String, Option<String>>>> = OnceLock::new();
+
+// Resolvable anywhere, including when reading another machine's EVTX files.
+fn well_known(sid: &str) -> Option<&'static str> {
+    match sid {
+        "S-1-1-0" => Some("Everyone"),
+        "S-1-5-7" => Some("NT AUTHORITY\\ANONYMOUS LOGON"),
+        "S-1-5-18" => Some("NT AUTHORITY\\SYSTEM"),
+        "S-1-5-19" => Some("NT AUTHORITY\\LOCAL SERVICE"),
+        "S-1-5-20" => Some("NT AUTHORITY\\NETWORK SERVICE"),
+        "S-1-5-32-544" => Some("BUILTIN\\Administrators"),
+        "S-1-5-32-545" => Some("BUILTIN\\Users"),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn lookup(sid: &str) -> Option<String> {
+    use windows_sys::Win32::Foundation::LocalFree;
+    use windows_sys::Win32::Security::Authorization::ConvertStringSidToSidW;
+    use windows_sys::Win32::Security::{LookupAccountSidW, PSID, SID_NAME_USE};
+    let wide: Vec<u16> = sid.encode_utf16().chain(Some(0)).collect();
+    let mut psid: PSID = std::ptr::null_mut();
+    unsafe {
+        if ConvertStringSidToSidW(wide.as_ptr(), &mut psid) == 0 { return None; }
+        let (mut name, mut dom) = ([0u16; 256], [0u16; 256]);
+        let (mut nlen, mut dlen) = (name.len() as u32, dom.len() as u32);
+        let mut kind: SID_NAME_USE = 0;
+        let ok = LookupAccountSidW(std::ptr::null(), psid, name.as_mut_ptr(), &mut nlen, dom.as_mut_ptr(), &mut dlen, &mut kind);
+        LocalFree(psid as _);
+        if ok == 0 { return None; }
+        let n = String::from_utf16_lossy(&name[..nlen as usize]);
+        let d = String::from_utf16_lossy(&dom[..dlen as usize]);
+        Some(if d.is_empty() { n } else { format!("{}\\{}", d, n) })
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn lookup(_sid: &str) -> Option<String> { None }
+
+// Domain SIDs may need a round trip to a domain controller, so every SID is looked up once per run.
+pub fn resolve(sid: &str) -> Option<String> {
+    if let Some(n) = well_known(sid) { return Some(n.to_string()); }
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some(v) = cache.lock().ok()?.get(sid) { return v.clone(); }
+    let v = lookup(sid);
+    if let Ok(mut c) = cache.lock() { c.insert(sid.to_string(), v.clone()); }
+    v
+}
+
+// `CONTOSO\alice (S-1-5-21-…)` when the SID resolves, the bare SID otherwise.
+pub fn display(sid: &str) -> String {
+    match resolve(sid) { Some(name) => format!("{} ({})", name, sid), None => sid.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn well_known_sids_resolve_offline() {
+        assert_eq!(display("S-1-5-18"), "NT AUTHORITY\\SYSTEM (S-1-5-18)");
+        assert_eq!(resolve("S-1-5-20").as_deref(), Some("NT AUTHORITY\\NETWORK SERVICE"));
+        #[cfg(not(target_os = "windows"))]
+        assert_eq!(display("S-1-5-21-1-2-3-1001"), "S-1-5-21-1-2-3-1001");
+    }
+}
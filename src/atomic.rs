@@ -0,0 +1,72 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+// Sibling temp file, so the final rename stays on the same volume.
+pub fn temp_path(dest: &Path) -> PathBuf {
+    let name = dest.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    dest.with_file_name(format!(".{}.{}.tmp", name, std::process::id()))
+}
+
+// Writes to a temp file and renames it over `dest` on commit; dropped without commit, the temp file is removed.
+pub struct AtomicFile {
+    dest: PathBuf,
+    tmp: PathBuf,
+    w: Option<BufWriter<File>>,
+}
+
+impl AtomicFile {
+    pub fn create(dest: impl AsRef<Path>) -> std::io::Result<Self> {
+        let dest = dest.as_ref().to_path_buf();
+        let tmp = temp_path(&dest);
+        let w = BufWriter::new(File::create(&tmp)?);
+        Ok(AtomicFile { dest, tmp, w: Some(w) })
+    }
+
+    pub fn commit(mut self) -> std::io::Result<()> {
+        let w = self.w.take().expect("writer present until commit");
+        let f = w.into_inner().map_err(|e| e.into_error())?;
+        f.sync_all()?;
+        drop(f);
+        std::fs::rename(&self.tmp, &self.dest)
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> { self.w.as_mut().expect("writer present until commit").write(buf) }
+    fn flush(&mut self) -> std::io::Result<()> { self.w.as_mut().expect("writer present until commit").flush() }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if self.w.take().is_some() { let _ = std::fs::remove_file(&self.tmp); }
+    }
+}
+
+pub fn write_atomic(dest: impl AsRef<Path>, data: &[u8]) -> std::io::Result<()> {
+    let mut f = AtomicFile::create(dest)?;
+    f.write_all(data)?;
+    f.commit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commit_replaces_and_drop_discards() {
+        let dir = std::env::temp_dir().join(format!("windoctor-atomic-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let dest = dir.join("report.html");
+        std::fs::write(&dest, "old").unwrap();
+        {
+            let mut f = AtomicFile::create(&dest).unwrap();
+            f.write_all(b"half").unwrap();
+        }
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "old");
+        assert!(!temp_path(&dest).exists());
+        write_atomic(&dest, b"new").unwrap();
+        assert_eq!(std::fs::read_to_string(&dest).unwrap(), "new");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,161 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// Losing more than this share of a full charge per hour on battery is a fast drain (a full charge lasting under 5 hours).
+const FAST_DRAIN_PER_HOUR: f64 = 20.0;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BatteryInfo {
+    pub name: String,
+    pub design_mwh: Option<u64>,
+    pub full_charge_mwh: Option<u64>,
+    pub health_percent: Option<u8>,
+    pub charge_percent: Option<u8>,
+    pub status: Option<String>,
+}
+
+// One stretch on battery: from unplugging (Kernel-Power 105, AC offline) to the next power source change.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Drain {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub from_percent: u8,
+    pub to_percent: u8,
+    pub percent_per_hour: f64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BatteryReport {
+    pub batteries: Vec<BatteryInfo>,
+    pub power_source_changes: usize,
+    pub drains: Vec<Drain>,
+    pub low_battery_sleeps: usize,
+    pub battery_errors: usize,
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn health(design: Option<u64>, full: Option<u64>) -> Option<u8> {
+    match (design, full) { (Some(d), Some(f)) if d > 0 && f > 0 => Some((f * 100 / d).min(100) as u8), _ => None }
+}
+
+fn is_battery_provider(p: &str) -> bool { p == "Microsoft-Windows-Battery" || p.eq_ignore_ascii_case("BatteryHealth") || p.eq_ignore_ascii_case("CmBatt") }
+
+// Kernel-Power 105 carries AcOnline, RemainingCapacity and FullChargeCapacity at every power source change.
+pub fn analyze(events: &[crate::EventItem]) -> Option<BatteryReport> {
+    let mut rep = BatteryReport::default();
+    let mut readings: Vec<(DateTime<Utc>, bool, u8)> = Vec::new();
+    for e in events {
+        let kernel_power = e.provider == "Microsoft-Windows-Kernel-Power";
+        if kernel_power && e.event_id == 105 {
+            rep.power_source_changes += 1;
+            let m = crate::event_xml::event_data_pairs_or_fallback(e.raw_xml.as_deref().unwrap_or(&e.content));
+            let num = |k: &str| m.get(k).and_then(|v| v.trim().parse::<u64>().ok());
+            if let (Some(ac), Some(pct)) = (m.get("AcOnline").map(|v| v.eq_ignore_ascii_case("true") || v == "1"), num("RemainingCapacity").zip(num("FullChargeCapacity")).filter(|(_, f)| *f > 0).map(|(r, f)| (r * 100 / f).min(100) as u8)) {
+                readings.push((e.time, ac, pct));
+            }
+        } else if kernel_power && e.event_id == 42 && e.content.to_lowercase().contains("battery") {
+            rep.low_battery_sleeps += 1;
+        } else if is_battery_provider(&e.provider) && (1..=3).contains(&e.level) {
+            rep.battery_errors += 1;
+        }
+    }
+    readings.sort_by_key(|r| r.0);
+    for w in readings.windows(2) {
+        let ((t0, ac, from), (t1, _, to)) = (w[0], w[1]);
+        let hours = (t1 - t0).num_seconds() as f64 / 3600.0;
+        if ac || hours < 0.25 || to >= from { continue; }
+        let rate = (from - to) as f64 / hours;
+        if rate > FAST_DRAIN_PER_HOUR { rep.drains.push(Drain { start: t0, end: t1, from_percent: from, to_percent: to, percent_per_hour: (rate * 10.0).round() / 10.0 }); }
+    }
+    (rep.power_source_changes > 0 || rep.low_battery_sleeps > 0 || rep.battery_errors > 0).then_some(rep)
+}
+
+fn hint(message: &str, severity: &str, count: usize, evidence: Vec<String>) -> crate::hints::NoviceHint {
+    crate::hints::NoviceHint { category: "Battery".to_string(), severity: severity.to_string(), message: message.to_string(), evidence, count, probability: if severity == "high" { 80 } else { 60 } }
+}
+
+pub fn event_hints(rep: &BatteryReport) -> Vec<crate::hints::NoviceHint> {
+    let mut out = Vec::new();
+    if !rep.drains.is_empty() {
+        let ev = rep.drains.iter().take(3).map(|d| format!("{}% → {}% in {} min ({:.0}%/h)", d.from_percent, d.to_percent, (d.end - d.start).num_minutes(), d.percent_per_hour)).collect();
+        out.push(hint("Battery drains quickly on battery power", "medium", rep.drains.len(), ev));
+    }
+    if rep.low_battery_sleeps > 0 { out.push(hint("System went to sleep or hibernated on low battery", "medium", rep.low_battery_sleeps, vec![])); }
+    if rep.battery_errors > 0 { out.push(hint("Battery or battery driver reported a problem", "medium", rep.battery_errors, vec![])); }
+    out
+}
+
+// `--battery-min-health`: batteries holding less than this share of their design capacity are worn.
+pub fn wear_hints(batteries: &[BatteryInfo], min_health: u8) -> Vec<crate::hints::NoviceHint> {
+    let worn: Vec<String> = batteries.iter().filter_map(|b| {
+        let h = b.health_percent.filter(|h| *h < min_health)?;
+        Some(format!("{}: {}% ({} of {} mWh)", b.name, h, b.full_charge_mwh.unwrap_or(0), b.design_mwh.unwrap_or(0)))
+    }).collect();
+    if worn.is_empty() { return vec![]; }
+    vec![hint(&format!("Battery worn below {}% of design capacity", min_health), "high", worn.len(), worn)]
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn status_name(s: u16) -> Option<&'static str> {
+    match s { 1 => Some("Discharging"), 2 => Some("On AC power"), 3 => Some("Fully charged"), 4 => Some("Low"), 5 => Some("Critical"), 6..=9 => Some("Charging"), 11 => Some("Partially charged"), _ => None }
+}
+
+// Win32_Battery leaves the capacities empty on most laptops; root\WMI has them per battery in the same order.
+#[cfg(target_os = "windows")]
+pub fn collect() -> Vec<BatteryInfo> {
+    use wmi::WMIConnection;
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct BatRow { Name: Option<String>, DesignCapacity: Option<u32>, FullChargeCapacity: Option<u32>, EstimatedChargeRemaining: Option<u16>, BatteryStatus: Option<u16> }
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct StaticRow { DesignedCapacity: Option<u32> }
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct FullRow { FullChargedCapacity: Option<u32> }
+    let Ok(wmi) = WMIConnection::new() else { return vec![] };
+    let rows = wmi.raw_query::<BatRow>("SELECT Name, DesignCapacity, FullChargeCapacity, EstimatedChargeRemaining, BatteryStatus FROM Win32_Battery").unwrap_or_default();
+    let root_wmi = WMIConnection::with_namespace_path("root\\WMI").ok();
+    let designed: Vec<Option<u32>> = root_wmi.as_ref().and_then(|w| w.raw_query::<StaticRow>("SELECT DesignedCapacity FROM BatteryStaticData").ok()).unwrap_or_default().into_iter().map(|r| r.DesignedCapacity).collect();
+    let full: Vec<Option<u32>> = root_wmi.as_ref().and_then(|w| w.raw_query::<FullRow>("SELECT FullChargedCapacity FROM BatteryFullChargedCapacity").ok()).unwrap_or_default().into_iter().map(|r| r.FullChargedCapacity).collect();
+    rows.into_iter().enumerate().map(|(i, r)| {
+        let design_mwh = r.DesignCapacity.or(designed.get(i).copied().flatten()).filter(|v| *v > 0).map(u64::from);
+        let full_charge_mwh = r.FullChargeCapacity.or(full.get(i).copied().flatten()).filter(|v| *v > 0).map(u64::from);
+        BatteryInfo { name: r.Name.map(|n| n.trim().to_string()).unwrap_or_else(|| format!("Battery {}", i + 1)), design_mwh, full_charge_mwh, health_percent: health(design_mwh, full_charge_mwh), charge_percent: r.EstimatedChargeRemaining.map(|c| c.min(100) as u8), status: r.BatteryStatus.and_then(status_name).map(str::to_string) }
+    }).collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn collect() -> Vec<BatteryInfo> { Vec::new() }
+
+// Lines shared by the text, HTML and Markdown renderers.
+pub fn lines(rep: &BatteryReport) -> Vec<String> {
+    let mut out: Vec<String> = rep.batteries.iter().map(|b| format!("{}: {}{}{}", b.name, b.health_percent.map(|h| format!("{}% of design capacity", h)).unwrap_or_else(|| "capacity unknown".to_string()), b.charge_percent.map(|c| format!(", charged {}%", c)).unwrap_or_default(), b.status.as_ref().map(|s| format!(" ({})", s)).unwrap_or_default())).collect();
+    if rep.power_source_changes > 0 { out.push(format!("Power source changes: {}", rep.power_source_changes)); }
+    for d in &rep.drains { out.push(format!("Fast drain {} → {}: {}% → {}% ({:.0}%/h)", d.start.format("%Y-%m-%d %H:%M"), d.end.format("%H:%M"), d.from_percent, d.to_percent, d.percent_per_hour)); }
+    if rep.low_battery_sleeps > 0 { out.push(format!("Sleeps on low battery: {}", rep.low_battery_sleeps)); }
+    if rep.battery_errors > 0 { out.push(format!("Battery errors: {}", rep.battery_errors)); }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drains_sleeps_and_wear() {
+        let t0 = DateTime::parse_from_rfc3339("2025-11-30T08:00:00Z").unwrap().with_timezone(&Utc);
+        let change = |min: i64, ac: bool, remaining: u32| crate::EventItem { provider: "Microsoft-Windows-Kernel-Power".to_string(), event_id: 105, time: t0 + chrono::Duration::minutes(min), raw_xml: Some(format!("<EventData><Data Name=\"AcOnline\">{}</Data><Data Name=\"RemainingCapacity\">{}</Data><Data Name=\"FullChargeCapacity\">50000</Data></EventData>", ac, remaining)), ..Default::default() };
+        let sleep = crate::EventItem { provider: "Microsoft-Windows-Kernel-Power".to_string(), event_id: 42, content: "The system is entering sleep. Sleep Reason: Battery is low".to_string(), ..Default::default() };
+        // 90% → 40% over 90 minutes unplugged is 33%/h; the slower second stretch is not a drain.
+        let rep = analyze(&[change(0, false, 45000), change(90, true, 20000), change(120, false, 40000), change(360, true, 30000), sleep]).unwrap();
+        assert_eq!((rep.power_source_changes, rep.low_battery_sleeps, rep.drains.len()), (4, 1, 1));
+        assert_eq!((rep.drains[0].from_percent, rep.drains[0].to_percent, rep.drains[0].percent_per_hour), (90, 40, 33.3));
+        assert_eq!(event_hints(&rep).len(), 2);
+        assert!(analyze(&[]).is_none());
+        let b = BatteryInfo { name: "DELL 7FHHV".to_string(), design_mwh: Some(54000), full_charge_mwh: Some(33480), health_percent: health(Some(54000), Some(33480)), charge_percent: Some(80), status: None };
+        assert_eq!(b.health_percent, Some(62));
+        assert_eq!(wear_hints(std::slice::from_ref(&b), 70)[0].evidence, vec!["DELL 7FHHV: 62% (33480 of 54000 mWh)"]);
+        assert!(wear_hints(&[b], 60).is_empty());
+    }
+}
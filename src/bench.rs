@@ -0,0 +1,102 @@
+use evtx::EvtxParser;
+use regex::Regex;
+use serde::Serialize;
+use std::time::Instant;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Stage {
+    pub name: String,
+    pub items: usize,
+    pub total_ms: f64,
+    pub per_item_us: f64,
+    pub items_per_sec: f64,
+    pub hits: usize,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BenchReport {
+    pub file: String,
+    pub bytes: u64,
+    pub records: usize,
+    pub read_errors: usize,
+    pub iterations: u32,
+    pub mb_per_sec: f64,
+    pub stages: Vec<Stage>,
+}
+
+// Runs `f` over every item `iterations` times; `f` returns whether the item was a hit (parsed, decoded, matched).
+fn time_stage<T>(name: &str, items: &[T], iterations: u32, mut f: impl FnMut(&T) -> bool) -> Stage {
+    let start = Instant::now();
+    let mut hits = 0usize;
+    for _ in 0..iterations { hits = items.iter().filter(|x| f(x)).count(); }
+    let secs = start.elapsed().as_secs_f64();
+    let n = items.len() * iterations as usize;
+    Stage { name: name.to_string(), items: items.len(), total_ms: secs * 1000.0, per_item_us: if n == 0 { 0.0 } else { secs * 1e6 / n as f64 }, items_per_sec: if secs > 0.0 { n as f64 / secs } else { 0.0 }, hits }
+}
+
+pub fn bench_xml(xmls: &[String], iterations: u32) -> Vec<Stage> {
+    let pairs: Vec<(crate::EventItem, &String)> = xmls.iter().filter_map(|x| crate::parse_event_xml(x, "Bench").map(|e| (e, x))).collect();
+    let patterns: Vec<Regex> = crate::DEFAULT_PATTERNS.iter().filter_map(|p| Regex::new(p).ok()).collect();
+    vec![
+        time_stage("xml_quick_xml", xmls, iterations, |x| crate::parse_event_xml_qx(x, "Bench").is_some()),
+        time_stage("xml_fallback", xmls, iterations, |x| crate::parse_event_xml_fallback(x, "Bench").is_some()),
+        time_stage("eventdata_quick_xml", xmls, iterations, |x| !crate::event_xml::event_data_pairs(x).is_empty()),
+        time_stage("eventdata_fallback", xmls, iterations, |x| !crate::event_xml::event_data_pairs_fallback(x).is_empty()),
+        time_stage("decode", &pairs, iterations, |(e, x)| crate::decoder::decode_event(&e.provider, e.event_id, x).is_some()),
+        time_stage("regex_default_patterns", &pairs, iterations, |(e, _)| patterns.iter().any(|re| re.is_match(&e.content))),
+    ]
+}
+
+pub fn run(path: &std::path::Path, iterations: u32, max_records: Option<usize>) -> Result<BenchReport, String> {
+    let bytes = std::fs::metadata(path).map_err(|e| e.to_string())?.len();
+    let limit = max_records.unwrap_or(usize::MAX);
+    let mut xmls: Vec<String> = Vec::new();
+    let mut read_errors = 0usize;
+    let start = Instant::now();
+    for i in 0..iterations {
+        let mut parser = EvtxParser::from_path(path).map_err(|e| e.to_string())?;
+        let mut n = 0usize;
+        for r in parser.records().take(limit) {
+            n += 1;
+            match r {
+                Ok(r) if i == 0 => xmls.push(r.data),
+                Err(_) if i == 0 => read_errors += 1,
+                _ => {}
+            }
+        }
+        if n == 0 { break; }
+    }
+    let secs = start.elapsed().as_secs_f64();
+    let records = xmls.len() + read_errors;
+    let read = Stage { name: "evtx_read".to_string(), items: records, total_ms: secs * 1000.0, per_item_us: if records == 0 { 0.0 } else { secs * 1e6 / (records * iterations as usize) as f64 }, items_per_sec: if secs > 0.0 { (records * iterations as usize) as f64 / secs } else { 0.0 }, hits: xmls.len() };
+    // Throughput is only meaningful for whole-file reads.
+    let mb_per_sec = if max_records.is_none() && secs > 0.0 { bytes as f64 * iterations as f64 / 1048576.0 / secs } else { 0.0 };
+    let mut stages = vec![read];
+    stages.extend(bench_xml(&xmls, iterations));
+    Ok(BenchReport { file: path.display().to_string(), bytes, records, read_errors, iterations, mb_per_sec, stages })
+}
+
+pub fn print(rep: &BenchReport) {
+    println!("File: {} ({:.1} MB, {} records, {} read errors, {} iterations)", rep.file, rep.bytes as f64 / 1048576.0, rep.records, rep.read_errors, rep.iterations);
+    if rep.mb_per_sec > 0.0 { println!("EVTX throughput: {:.1} MB/s", rep.mb_per_sec); }
+    println!("{:<24} {:>10} {:>12} {:>12} {:>14} {:>8}", "stage", "items", "total ms", "us/item", "items/s", "hits");
+    for s in &rep.stages {
+        println!("{:<24} {:>10} {:>12.1} {:>12.2} {:>14.0} {:>8}", s.name, s.items, s.total_ms, s.per_item_us, s.items_per_sec, s.hits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bench_xml_reports_every_stage() {
+        let xml = "<Event><System><Provider Name=\"Disk\"></Provider><EventID>7</EventID><Level>2</Level><TimeCreated SystemTime=\"2025-11-30T12:00:00.000Z\"></TimeCreated><Channel>System</Channel></System><EventData><Data Name=\"DeviceName\">\\Device\\Harddisk0\\DR0</Data></EventData></Event>".to_string();
+        let stages = bench_xml(&[xml.clone(), xml], 2);
+        let names: Vec<&str> = stages.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["xml_quick_xml", "xml_fallback", "eventdata_quick_xml", "eventdata_fallback", "decode", "regex_default_patterns"]);
+        assert!(stages.iter().all(|s| s.items == 2));
+        assert_eq!(stages[0].hits, 2);
+        assert_eq!(stages[1].hits, 2);
+    }
+}
@@ -0,0 +1,88 @@
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::PathBuf;
+
+// Values of these flags never reach a manifest that gets attached to a ticket.
+const SECRET_FLAGS: [&str; 2] = ["--password", "--splunk-hec-token"];
+
+pub fn redact_args(args: &[String]) -> Vec<String> {
+    let mut out = Vec::with_capacity(args.len());
+    let mut hide_next = false;
+    for a in args {
+        if hide_next { out.push("***".to_string()); hide_next = false; continue; }
+        match a.split_once('=') {
+            Some((flag, _)) if SECRET_FLAGS.contains(&flag) => out.push(format!("{}=***", flag)),
+            _ => { hide_next = SECRET_FLAGS.contains(&a.as_str()); out.push(a.clone()); }
+        }
+    }
+    out
+}
+
+fn sha256_hex(data: &[u8]) -> String { Sha256::digest(data).iter().map(|b| format!("{:02x}", b)).collect() }
+
+pub fn manifest(files: &[(String, Vec<u8>)], args: &[String], host: &str) -> serde_json::Value {
+    serde_json::json!({
+        "tool": "WinDoctor",
+        "version": env!("CARGO_PKG_VERSION"),
+        "created": chrono::Utc::now().to_rfc3339(),
+        "host": host,
+        "args": redact_args(args),
+        "files": files.iter().map(|(name, data)| serde_json::json!({ "name": name, "bytes": data.len(), "sha256": sha256_hex(data) })).collect::<Vec<_>>(),
+    })
+}
+
+// Flat archive: every file under its own name plus manifest.json; written next to the target and renamed into place.
+pub fn write(zip_path: &str, paths: &[PathBuf], args: &[String], host: &str) -> Result<(), std::io::Error> {
+    let mut files = Vec::new();
+    for p in paths { files.push((p.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(), std::fs::read(p)?)); }
+    let manifest = serde_json::to_vec_pretty(&manifest(&files, args, host))?;
+    let tmp = crate::atomic::temp_path(std::path::Path::new(zip_path));
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&tmp)?);
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, data) in files.iter().map(|(n, d)| (n.as_str(), d)).chain(std::iter::once(("manifest.json", &manifest))) {
+        zip.start_file(name, options)?;
+        zip.write_all(data)?;
+    }
+    zip.finish()?.sync_all()?;
+    std::fs::rename(&tmp, zip_path)
+}
+
+// `--sign-reports`: files are listed by the path they were written to; with a key, minisign writes `<manifest>.minisig` beside it.
+pub fn sign(manifest_path: &str, paths: &[PathBuf], args: &[String], host: &str, key: Option<&str>) -> Result<(), String> {
+    let mut files = Vec::new();
+    for p in paths { files.push((p.to_string_lossy().into_owned(), std::fs::read(p).map_err(|e| format!("{}: {}", p.display(), e))?)); }
+    crate::atomic::write_atomic(manifest_path, &serde_json::to_vec_pretty(&manifest(&files, args, host)).unwrap()).map_err(|e| format!("{}: {}", manifest_path, e))?;
+    let Some(key) = key else { return Ok(()) };
+    let status = std::process::Command::new("minisign").args(["-S", "-s", key, "-m", manifest_path]).status().map_err(|e| format!("minisign: {}", e))?;
+    if status.success() { Ok(()) } else { Err(format!("minisign {}", status)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_hashes_files_and_hides_secrets() {
+        let args: Vec<String> = ["--export-dir", "out", "--password", "hunter2", "--splunk-hec-token=abc"].iter().map(|s| s.to_string()).collect();
+        let m = manifest(&[("report.json".to_string(), b"abc".to_vec())], &args, "ws01");
+        assert_eq!(m["files"][0]["sha256"], "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(m["files"][0]["bytes"], 3);
+        assert_eq!(m["args"], serde_json::json!(["--export-dir", "out", "--password", "***", "--splunk-hec-token=***"]));
+        assert_eq!(m["host"], "ws01");
+    }
+
+    #[test]
+    fn sign_without_key_writes_manifest_of_written_paths() {
+        let dir = std::env::temp_dir().join(format!("windoctor-sign-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let report = dir.join("report.json");
+        std::fs::write(&report, b"abc").unwrap();
+        let manifest_path = dir.join("windoctor-manifest.json").to_string_lossy().into_owned();
+        sign(&manifest_path, std::slice::from_ref(&report), &[], "ws01", None).unwrap();
+        let m: serde_json::Value = serde_json::from_slice(&std::fs::read(&manifest_path).unwrap()).unwrap();
+        assert_eq!(m["files"][0]["name"], report.to_string_lossy().as_ref());
+        assert_eq!(m["files"][0]["sha256"], "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert!(sign(&manifest_path, &[dir.join("missing.json")], &[], "ws01", None).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChannelHealth {
+    pub name: String,
+    pub enabled: Option<bool>,
+    pub max_size_bytes: Option<u64>,
+    pub file_size_bytes: Option<u64>,
+    pub records: Option<u64>,
+    pub retention: String,
+    pub oldest_record: Option<DateTime<Utc>>,
+    pub history_hours: Option<i64>,
+    pub near_rollover: bool,
+    pub full: bool,
+}
+
+// The log file is this close to its maximum size.
+const NEAR_FULL: f64 = 0.9;
+// Less history than this on a wrapping log is worth a warning; under a day it is urgent.
+const SHORT_HISTORY_HOURS: i64 = 7 * 24;
+
+fn retention_policy(retention: Option<bool>, auto_backup: Option<bool>) -> &'static str {
+    match (retention, auto_backup) { (Some(false), _) => "Overwrite as needed", (Some(true), Some(true)) => "Archive when full", (Some(true), _) => "Do not overwrite", (None, _) => "Unknown" }
+}
+
+pub fn assess(info: &crate::windows_live::ChannelInfo, oldest: Option<DateTime<Utc>>, now: DateTime<Utc>) -> ChannelHealth {
+    let near_rollover = match (info.file_size_bytes, info.max_size_bytes) { (Some(f), Some(m)) if m > 0 => f as f64 >= m as f64 * NEAR_FULL, _ => false };
+    ChannelHealth {
+        name: info.name.clone(),
+        enabled: info.enabled,
+        max_size_bytes: info.max_size_bytes,
+        file_size_bytes: info.file_size_bytes,
+        records: info.records,
+        retention: retention_policy(info.retention, info.auto_backup).to_string(),
+        oldest_record: oldest,
+        history_hours: oldest.map(|t| (now - t).num_hours().max(0)),
+        near_rollover,
+        full: info.full.unwrap_or(false),
+    }
+}
+
+pub fn collect(channels: &[String]) -> Vec<ChannelHealth> {
+    let now = Utc::now();
+    channels.iter().map(|ch| assess(&crate::windows_live::channel_info(ch), crate::windows_live::oldest_event_time(ch), now)).collect()
+}
+
+pub fn format_span(hours: i64) -> String {
+    match hours { 0 => "less than an hour".to_string(), 1 => "1 hour".to_string(), h if h < 48 => format!("{} hours", h), h => format!("{} days", h / 24) }
+}
+
+// One-line summary shared by the text and Markdown renderers and the hint evidence.
+pub fn detail(c: &ChannelHealth) -> String {
+    let mb = |b: u64| format!("{:.1} MB", b as f64 / 1048576.0);
+    let size = match (c.file_size_bytes, c.max_size_bytes) { (Some(f), Some(m)) => format!("{} of {}", mb(f), mb(m)), (None, Some(m)) => format!("max {}", mb(m)), _ => "size unknown".to_string() };
+    let history = c.history_hours.map(|h| format!(" · history {}", format_span(h))).unwrap_or_default();
+    let state = if c.full { " · FULL" } else if c.near_rollover { " · near rollover" } else { "" };
+    format!("{} · {}{}{}{}", size, c.retention, c.records.map(|r| format!(" · {} records", r)).unwrap_or_default(), history, state)
+}
+
+pub fn hints(channels: &[ChannelHealth]) -> Vec<crate::hints::NoviceHint> {
+    let hint = |severity: &str, message: String, c: &ChannelHealth| crate::hints::NoviceHint { category: "Logging".to_string(), severity: severity.to_string(), message, evidence: vec![format!("{}: {}", c.name, detail(c))], count: 1, probability: 70 };
+    let mut out = Vec::new();
+    for c in channels {
+        if c.full && c.retention == "Do not overwrite" {
+            out.push(hint("high", format!("{} log is full and set to not overwrite; new events are being dropped", c.name), c));
+        } else if c.near_rollover && c.retention == "Overwrite as needed" && let Some(h) = c.history_hours.filter(|h| *h < SHORT_HISTORY_HOURS) {
+            out.push(hint(if h < 24 { "high" } else { "medium" }, format!("{} log retains only {} of history; increase log size", c.name, format_span(h)), c));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_history_on_a_wrapping_log_is_flagged() {
+        let now = DateTime::parse_from_rfc3339("2025-11-30T12:00:00Z").unwrap().with_timezone(&Utc);
+        let info = |name: &str, size: u64, retention: bool| crate::windows_live::ChannelInfo { name: name.to_string(), max_size_bytes: Some(20 * 1048576), file_size_bytes: Some(size), records: Some(5000), retention: Some(retention), auto_backup: Some(false), full: Some(size >= 20 * 1048576), ..Default::default() };
+        let system = assess(&info("System", 20 * 1048576 - 4096, false), Some(now - chrono::Duration::hours(4)), now);
+        assert!(system.near_rollover && !system.full);
+        assert_eq!(system.retention, "Overwrite as needed");
+        let app = assess(&info("Application", 2 * 1048576, false), Some(now - chrono::Duration::hours(2)), now);
+        let sec = assess(&info("Security", 20 * 1048576, true), None, now);
+        let h = hints(&[system, app, sec]);
+        assert_eq!(h.len(), 2);
+        assert_eq!((h[0].severity.as_str(), h[0].message.as_str()), ("high", "System log retains only 4 hours of history; increase log size"));
+        assert!(h[1].message.starts_with("Security log is full"));
+        assert_eq!(format_span(72), "3 days");
+    }
+}
@@ -0,0 +1,93 @@
+use crate::EventItem;
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FileProgress {
+    pub path: String,
+    pub last_record_id: u64,
+    pub matched: usize,
+    pub done: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub key: String,
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    pub scanned_records: usize,
+    pub parsed_events: usize,
+    pub files: Vec<FileProgress>,
+    pub events: Vec<EventItem>,
+}
+
+struct State { path: PathBuf, cp: Checkpoint, last_save: Instant }
+
+static STATE: OnceLock<Mutex<State>> = OnceLock::new();
+
+// Short scans finish before the first save and never leave a checkpoint behind.
+const SAVE_EVERY: Duration = Duration::from_secs(5);
+
+pub fn default_path() -> PathBuf { std::env::temp_dir().join("windoctor-checkpoint.json") }
+
+// A scan is identified by its effective configuration; changing any filter starts over.
+pub fn key_for<T: Serialize>(cfg: &T) -> String { serde_json::to_string(cfg).unwrap_or_default() }
+
+pub fn load(path: &Path, key: &str) -> Option<Checkpoint> {
+    let cp: Checkpoint = serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+    (cp.key == key).then_some(cp)
+}
+
+pub fn start(path: PathBuf, cp: Checkpoint) {
+    let _ = STATE.set(Mutex::new(State { path, cp, last_save: Instant::now() }));
+}
+
+pub fn progress(file: &str) -> Option<FileProgress> {
+    let st = STATE.get()?.lock().ok()?;
+    st.cp.files.iter().find(|f| f.path == file).cloned()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn record(file: &str, last_record_id: u64, matched: usize, done: bool, events: &[EventItem], scanned_records: usize, parsed_events: usize, force: bool) {
+    let Some(m) = STATE.get() else { return };
+    let Ok(mut st) = m.lock() else { return };
+    match st.cp.files.iter_mut().find(|f| f.path == file) {
+        Some(f) => { f.last_record_id = last_record_id; f.matched = matched; f.done = done; }
+        None => st.cp.files.push(FileProgress { path: file.to_string(), last_record_id, matched, done }),
+    }
+    if !force && st.last_save.elapsed() < SAVE_EVERY { return; }
+    st.cp.events = events.to_vec();
+    st.cp.scanned_records = scanned_records;
+    st.cp.parsed_events = parsed_events;
+    st.last_save = Instant::now();
+    match serde_json::to_vec(&st.cp) {
+        Ok(data) => if let Err(e) = crate::atomic::write_atomic(&st.path, &data) { log::warn!("Checkpoint write failed for {}: {}", st.path.display(), e); },
+        Err(e) => log::warn!("Checkpoint serialize failed: {}", e),
+    }
+}
+
+// Collection finished; the checkpoint is no longer needed.
+pub fn finish() {
+    if let Some(m) = STATE.get() && let Ok(st) = m.lock() { let _ = std::fs::remove_file(&st.path); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoint_roundtrip_requires_matching_key() {
+        let path = std::env::temp_dir().join(format!("windoctor-ckpt-test-{}.json", std::process::id()));
+        let now = Utc::now();
+        let cp = Checkpoint { key: "k1".to_string(), since: now, until: now, scanned_records: 10, parsed_events: 9, files: vec![FileProgress { path: "System.evtx".to_string(), last_record_id: 42, matched: 1, done: false }], events: vec![EventItem { time: now, level: 2, provider: "Disk".to_string(), ..Default::default() }] };
+        crate::atomic::write_atomic(&path, &serde_json::to_vec(&cp).unwrap()).unwrap();
+        let back = load(&path, "k1").unwrap();
+        assert_eq!(back.files[0].last_record_id, 42);
+        assert_eq!(back.events.len(), 1);
+        assert!(load(&path, "k2").is_none());
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::EventItem;
+
+// One link of a sequence: any of `providers`, one of `event_ids` (empty: any), content containing `contains`.
+pub struct Step { pub providers: &'static [&'static str], pub event_ids: &'static [u32], pub contains: Option<&'static str> }
+
+// Every step must follow the first within `within_minutes` on the same computer; `same_device` also keeps chains to one storage unit.
+pub struct Sequence { pub steps: &'static [Step], pub within_minutes: i64, pub same_device: bool, pub category: &'static str, pub severity: &'static str, pub message: &'static str }
+
+const STORPORT: &[&str] = &["Storport", "stornvme", "storahci", "iaStorA", "iaStorAC", "iaStorAVC"];
+
+pub const SEQUENCES: &[Sequence] = &[
+    Sequence { steps: &[Step { providers: STORPORT, event_ids: &[129], contains: None }, Step { providers: &["Disk"], event_ids: &[153], contains: None }], within_minutes: 5, same_device: true, category: "Storage", severity: "high", message: "Storage path degradation: device reset followed by retried I/O" },
+    Sequence { steps: &[Step { providers: &["Disk"], event_ids: &[7, 11, 51], contains: None }, Step { providers: &["Microsoft-Windows-Ntfs", "Ntfs"], event_ids: &[55, 98, 140], contains: None }], within_minutes: 60, same_device: false, category: "Storage", severity: "high", message: "Disk errors followed by file system corruption" },
+    Sequence { steps: &[Step { providers: &["volsnap"], event_ids: &[], contains: Some("aborted") }, Step { providers: &["Microsoft-Windows-Ntfs"], event_ids: &[55], contains: None }], within_minutes: 1440, same_device: false, category: "Storage", severity: "high", message: "Shadow copies aborted and NTFS corruption detected (sequence)" },
+    Sequence { steps: &[Step { providers: &["Microsoft-Windows-WHEA-Logger"], event_ids: &[18], contains: None }, Step { providers: &["Microsoft-Windows-Kernel-Power"], event_ids: &[41], contains: None }], within_minutes: 15, same_device: false, category: "Hardware", severity: "high", message: "Uncorrected hardware error followed by an unexpected restart" },
+    Sequence { steps: &[Step { providers: &["Display"], event_ids: &[4101], contains: None }, Step { providers: &["Microsoft-Windows-Kernel-Power"], event_ids: &[41], contains: None }], within_minutes: 10, same_device: false, category: "GPU", severity: "high", message: "Display driver reset followed by an unexpected restart" },
+];
+
+fn matches(s: &Step, e: &EventItem) -> bool {
+    s.providers.iter().any(|p| e.provider.eq_ignore_ascii_case(p)) && (s.event_ids.is_empty() || s.event_ids.contains(&e.event_id)) && s.contains.is_none_or(|c| e.content.to_lowercase().contains(c))
+}
+
+// Named device EventData, else the first `\Device\…` path (classic drivers only order their insertion strings).
+fn device(e: &EventItem) -> Option<String> {
+    crate::event_device(e).or_else(|| {
+        let xml = e.raw_xml.as_deref().unwrap_or(&e.content);
+        crate::event_xml::event_data_values(xml).into_iter().chain(std::iter::once(e.content.clone())).find_map(|v| v.find("\\Device\\").map(|i| v[i..].split_whitespace().next().unwrap_or_default().trim_end_matches(['.', ',']).to_string()))
+    })
+}
+
+// `\Device\RaidPort1` and `\Device\Harddisk1\DR1` both name unit 1: a port and the disk behind it share the number when each controller carries one disk.
+fn unit(dev: &str) -> Option<u32> {
+    let lower = dev.to_ascii_lowercase();
+    ["\\device\\raidport", "\\device\\harddisk"].iter().find_map(|p| lower.strip_prefix(p)).and_then(|r| r.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok())
+}
+
+// Links are only told apart when both name a unit.
+fn same_device(a: Option<&str>, b: Option<&str>) -> bool {
+    match (a.and_then(unit), b.and_then(unit)) { (Some(a), Some(b)) => a == b, _ => true }
+}
+
+// Chains of each sequence over the time-ordered events; an event starts or continues at most one chain per sequence.
+pub fn chains(events: &[EventItem]) -> Vec<(&'static Sequence, Vec<Vec<&EventItem>>)> {
+    let mut sorted: Vec<&EventItem> = events.iter().collect();
+    sorted.sort_by_key(|e| e.time);
+    let mut out = Vec::new();
+    for seq in SEQUENCES {
+        let mut used = vec![false; sorted.len()];
+        let mut found: Vec<Vec<&EventItem>> = Vec::new();
+        for i in 0..sorted.len() {
+            if used[i] || !matches(&seq.steps[0], sorted[i]) { continue; }
+            let first = sorted[i];
+            let dev = if seq.same_device { device(first) } else { None };
+            let deadline = first.time + chrono::Duration::minutes(seq.within_minutes);
+            let mut chain = vec![i];
+            for step in &seq.steps[1..] {
+                let last = *chain.last().unwrap_or(&i);
+                let next = (last + 1..sorted.len()).take_while(|&j| sorted[j].time <= deadline).find(|&j| !used[j] && sorted[j].computer == first.computer && matches(step, sorted[j]) && (!seq.same_device || same_device(dev.as_deref(), device(sorted[j]).as_deref())));
+                match next { Some(j) => chain.push(j), None => break }
+            }
+            if chain.len() == seq.steps.len() {
+                for &j in &chain { used[j] = true; }
+                found.push(chain.into_iter().map(|j| sorted[j]).collect());
+            }
+        }
+        if !found.is_empty() { out.push((seq, found)); }
+    }
+    out
+}
+
+// `Storport 129 2025-11-30 12:00 (\Device\RaidPort0) → Disk 153 2025-11-30 12:02 (\Device\Harddisk0\DR0)`; the port a reset
+// went to and the disk that retried are numbered independently, so each link names its own device.
+pub fn evidence(chain: &[&EventItem], tz: crate::TimeZone) -> String {
+    chain.iter().map(|e| {
+        let link = format!("{} {} {}", e.provider, e.event_id, crate::format_ts(e.time, tz, None));
+        match device(e) { Some(d) => format!("{} ({})", link, d), None => link }
+    }).collect::<Vec<_>>().join(" → ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chains_follow_order_and_window() {
+        let t0 = chrono::DateTime::parse_from_rfc3339("2025-11-30T12:00:00Z").unwrap().with_timezone(&chrono::Utc);
+        let ev = |p: &str, id: u32, min: i64, dev: &str| EventItem { provider: p.to_string(), event_id: id, time: t0 + chrono::Duration::minutes(min), raw_xml: Some(format!("<EventData><Data>{}</Data></EventData>", dev)), ..Default::default() };
+        let events = vec![
+            ev("Disk", 153, 0, "\\Device\\Harddisk0\\DR0"),
+            ev("stornvme", 129, 1, "\\Device\\RaidPort0"),
+            ev("Disk", 153, 3, "\\Device\\Harddisk0\\DR0"),
+            ev("stornvme", 129, 30, "\\Device\\RaidPort1"),
+            ev("Disk", 153, 40, "\\Device\\Harddisk1\\DR1"),
+            ev("Disk", 7, 50, "\\Device\\Harddisk1\\DR1"),
+            ev("Disk", 7, 51, "\\Device\\Harddisk2\\DR2"),
+            // A reset on one port and a retry on another disk are unrelated; a retry without a device still links.
+            ev("stornvme", 129, 60, "\\Device\\RaidPort1"),
+            ev("Disk", 153, 61, "\\Device\\Harddisk0\\DR0"),
+            ev("stornvme", 129, 90, "\\Device\\RaidPort1"),
+            ev("Disk", 153, 91, ""),
+        ];
+        let found = chains(&events);
+        assert_eq!(found.len(), 1);
+        let (seq, c) = &found[0];
+        assert_eq!((seq.message, c.len()), ("Storage path degradation: device reset followed by retried I/O", 2));
+        assert_eq!(c[1][0].time, t0 + chrono::Duration::minutes(90));
+        assert!(!same_device(Some("\\Device\\RaidPort1"), Some("\\Device\\Harddisk0\\DR0")));
+        assert_eq!(evidence(&c[0], crate::TimeZone::Utc), "stornvme 129 2025-11-30 12:01 (\\Device\\RaidPort0) → Disk 153 2025-11-30 12:03 (\\Device\\Harddisk0\\DR0)");
+    }
+}
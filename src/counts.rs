@@ -0,0 +1,142 @@
+use chrono::{DateTime, Utc};
+use evtx::EvtxParser;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CountReport {
+    pub window_start: Option<DateTime<Utc>>,
+    pub window_end: Option<DateTime<Utc>>,
+    pub scanned_records: usize,
+    pub matched: usize,
+    pub read_errors: usize,
+    pub by_level: Vec<(u8, usize)>,
+    pub by_channel: Vec<(String, usize)>,
+    pub by_provider: Vec<(String, usize)>,
+    pub by_event_id: Vec<(u32, usize)>,
+}
+
+// Accumulates counts; maps are turned into sorted vectors once at the end.
+#[derive(Default)]
+pub struct Counter {
+    scanned: usize,
+    matched: usize,
+    read_errors: usize,
+    level: HashMap<u8, usize>,
+    channel: HashMap<String, usize>,
+    provider: HashMap<String, usize>,
+    event_id: HashMap<u32, usize>,
+}
+
+// Only the System header fields are read, by plain substring search; no EventItem, EventData or regex.
+fn header(xml: &str) -> (u8, &str, u32, &str) {
+    let between = |start: &str, end: &str| xml.find(start).and_then(|s| { let from = s + start.len(); xml[from..].find(end).map(|e| &xml[from..from + e]) });
+    let level = between("<Level>", "</Level>").and_then(|s| s.trim().parse().ok()).unwrap_or(0);
+    let level = crate::keywords::audit_level(level, between("<Keywords>", "</Keywords>").map(crate::keywords::parse_xml_value).unwrap_or(0));
+    let provider = between("<Provider Name=\"", "\"").unwrap_or("");
+    // <EventID Qualifiers="..">7</EventID>
+    let event_id = between("<EventID", "</EventID>").and_then(|s| s.rsplit('>').next()).and_then(|s| s.trim().parse().ok()).unwrap_or(0);
+    let channel = between("<Channel>", "</Channel>").unwrap_or("");
+    (level, provider, event_id, channel)
+}
+
+impl Counter {
+    pub fn add_xml(&mut self, xml: &str, fallback_channel: &str, keep: impl Fn(u8, &str, u32) -> bool) {
+        self.scanned += 1;
+        let (level, provider, event_id, channel) = header(xml);
+        if !keep(level, provider, event_id) { return; }
+        self.count(level, if channel.is_empty() { fallback_channel } else { channel }, provider, event_id);
+    }
+
+    fn count(&mut self, level: u8, ch: &str, provider: &str, event_id: u32) {
+        self.matched += 1;
+        *self.level.entry(level).or_insert(0) += 1;
+        if let Some(c) = self.channel.get_mut(ch) { *c += 1; } else { self.channel.insert(ch.to_string(), 1); }
+        if let Some(c) = self.provider.get_mut(provider) { *c += 1; } else { self.provider.insert(provider.to_string(), 1); }
+        *self.event_id.entry(event_id).or_insert(0) += 1;
+    }
+
+    // Live subscriptions deliver decoded events, filtered by the caller.
+    pub fn add_event(&mut self, e: &crate::EventItem, kept: bool) {
+        self.scanned += 1;
+        if kept { self.count(e.level, &e.channel, &e.provider, e.event_id); }
+    }
+
+    pub fn skip(&mut self) { self.scanned += 1; }
+
+    // One-line running total for intermediate summaries.
+    pub fn progress_line(&self) -> String {
+        let level = |l: u8| self.level.get(&l).copied().unwrap_or(0);
+        let top = self.provider.iter().max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0))).map(|(p, c)| format!(" · top {} ({})", p, c)).unwrap_or_default();
+        format!("{} matched of {} · {} critical · {} errors · {} warnings{}", self.matched, self.scanned, level(1), level(2), level(3), top)
+    }
+
+    pub fn scan_file(&mut self, path: &Path, since: DateTime<Utc>, until: DateTime<Utc>, keep: &impl Fn(u8, &str, u32) -> bool) -> Result<(), String> {
+        let mut parser = EvtxParser::from_path(path).map_err(|e| e.to_string())?;
+        let ch = path.file_stem().and_then(|s| s.to_str()).unwrap_or("").to_string();
+        for r in parser.records() {
+            if crate::budget_exhausted() { break; }
+            match r {
+                // The record header carries the timestamp, so out-of-window records are never looked at.
+                Ok(r) if r.timestamp < since || r.timestamp > until => self.skip(),
+                Ok(r) => self.add_xml(&r.data, &ch, keep),
+                Err(_) => { self.scanned += 1; self.read_errors += 1; }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish(self, since: Option<DateTime<Utc>>, until: Option<DateTime<Utc>>) -> CountReport {
+        fn sorted<K: Ord + Clone>(m: HashMap<K, usize>) -> Vec<(K, usize)> {
+            let mut v: Vec<(K, usize)> = m.into_iter().collect();
+            v.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            v
+        }
+        let mut by_level: Vec<(u8, usize)> = self.level.into_iter().collect();
+        by_level.sort();
+        CountReport { window_start: since, window_end: until, scanned_records: self.scanned, matched: self.matched, read_errors: self.read_errors, by_level, by_channel: sorted(self.channel), by_provider: sorted(self.provider), by_event_id: sorted(self.event_id) }
+    }
+}
+
+pub fn print(rep: &CountReport, top: usize) {
+    println!("Scanned records: {} · matched: {}{}", rep.scanned_records, rep.matched, if rep.read_errors > 0 { format!(" · read errors: {}", rep.read_errors) } else { String::new() });
+    println!("By level:");
+    for (l, c) in &rep.by_level { println!("  {:<12} {}", crate::level_name(*l), c); }
+    println!("By channel:");
+    for (k, c) in rep.by_channel.iter().take(top) { println!("  {:<48} {}", k, c); }
+    println!("By provider:");
+    for (k, c) in rep.by_provider.iter().take(top) { println!("  {:<48} {}", k, c); }
+    println!("By event ID:");
+    for (k, c) in rep.by_event_id.iter().take(top) { println!("  {:<12} {}", k, c); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_header_fields_only() {
+        let xml = |p: &str, id: u32, l: u8| format!("<Event><System><Provider Name=\"{}\" Guid=\"{{x}}\"/><EventID Qualifiers=\"16384\">{}</EventID><Level>{}</Level><Channel>System</Channel></System><EventData><Data>ignored</Data></EventData></Event>", p, id, l);
+        let mut c = Counter::default();
+        let keep = |level: u8, _: &str, _: u32| level <= 3;
+        c.add_xml(&xml("Disk", 7, 2), "x", keep);
+        c.add_xml(&xml("Disk", 7, 2), "x", keep);
+        c.add_xml(&xml("Service Control Manager", 7036, 4), "x", keep);
+        c.add_xml(&xml("Ntfs", 55, 3), "x", keep);
+        c.skip();
+        let rep = c.finish(None, None);
+        assert_eq!((rep.scanned_records, rep.matched), (5, 3));
+        assert_eq!(rep.by_provider[0], ("Disk".to_string(), 2));
+        assert_eq!(rep.by_event_id[0], (7, 2));
+        assert_eq!(rep.by_level, vec![(2, 2), (3, 1)]);
+        assert_eq!(rep.by_channel, vec![("System".to_string(), 3)]);
+        let mut live = Counter::default();
+        let ev = |p: &str, l: u8| crate::EventItem { provider: p.to_string(), level: l, channel: "System".to_string(), ..Default::default() };
+        live.add_event(&ev("Disk", 2), true);
+        live.add_event(&ev("Disk", 1), true);
+        live.add_event(&ev("Ntfs", 3), true);
+        live.add_event(&ev("Service Control Manager", 4), false);
+        assert_eq!(live.progress_line(), "3 matched of 4 · 1 critical · 1 errors · 1 warnings · top Disk (2)");
+    }
+}
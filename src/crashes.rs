@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AppCrash {
+    pub app: String,
+    pub crashes: usize,
+    pub hangs: usize,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub top_module: Option<String>,
+    pub top_exception: Option<String>,
+}
+
+struct Fault { app: String, module: Option<String>, exception: Option<String>, hang: bool }
+
+// Application Error 1000 and Application Hang 1002; newer builds name the Data elements, older ones only order them.
+fn fault(e: &crate::EventItem) -> Option<Fault> {
+    let hang = match (e.provider.as_str(), e.event_id) { ("Application Error", 1000) => false, ("Application Hang", 1002) => true, _ => return None };
+    let xml = e.raw_xml.as_deref().filter(|x| x.contains("<Data")).unwrap_or(&e.content);
+    let named = crate::event_xml::event_data_pairs_or_fallback(xml);
+    let values = crate::event_xml::event_data_values(xml);
+    let pick = |names: &[&str], pos: usize| names.iter().find_map(|n| named.get(*n).cloned()).or_else(|| if named.is_empty() { values.get(pos).cloned() } else { None }).filter(|v| !v.is_empty());
+    let app = pick(&["FaultingApplicationName", "AppName"], 0)?;
+    let (module, exception) = if hang { (None, None) } else { (pick(&["FaultingModuleName", "ModuleName"], 3), pick(&["ExceptionCode"], 6).map(|c| if c.starts_with("0x") { c } else { format!("0x{}", c) })) };
+    Some(Fault { app, module, exception, hang })
+}
+
+fn most_common(counts: HashMap<String, usize>) -> Option<String> {
+    counts.into_iter().max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0))).map(|(k, _)| k)
+}
+
+pub fn top_crashing_apps(events: &[crate::EventItem], top: usize) -> Vec<AppCrash> {
+    struct Acc { crash: AppCrash, modules: HashMap<String, usize>, exceptions: HashMap<String, usize> }
+    let mut by_app: HashMap<String, Acc> = HashMap::new();
+    for e in events {
+        let Some(f) = fault(e) else { continue };
+        let acc = by_app.entry(f.app.to_lowercase()).or_insert_with(|| Acc { crash: AppCrash { app: f.app.clone(), crashes: 0, hangs: 0, first_seen: e.time, last_seen: e.time, top_module: None, top_exception: None }, modules: HashMap::new(), exceptions: HashMap::new() });
+        if f.hang { acc.crash.hangs += 1; } else { acc.crash.crashes += 1; }
+        acc.crash.first_seen = acc.crash.first_seen.min(e.time);
+        acc.crash.last_seen = acc.crash.last_seen.max(e.time);
+        if let Some(m) = f.module { *acc.modules.entry(m).or_insert(0) += 1; }
+        if let Some(x) = f.exception { *acc.exceptions.entry(x).or_insert(0) += 1; }
+    }
+    let mut out: Vec<AppCrash> = by_app.into_values().map(|a| AppCrash { top_module: most_common(a.modules), top_exception: most_common(a.exceptions), ..a.crash }).collect();
+    out.sort_by(|a, b| (b.crashes + b.hangs).cmp(&(a.crashes + a.hangs)).then_with(|| a.app.cmp(&b.app)));
+    out.truncate(top);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregates_named_and_positional_event_data() {
+        let t0 = DateTime::parse_from_rfc3339("2025-11-30T12:00:00Z").unwrap().with_timezone(&Utc);
+        let ev = |p: &str, id: u32, content: &str, min: i64| crate::EventItem { provider: p.to_string(), event_id: id, content: content.to_string(), time: t0 + chrono::Duration::minutes(min), ..Default::default() };
+        let named = |m: &str| format!("<EventData><Data Name=\"AppName\">Game.exe</Data><Data Name=\"ModuleName\">{}</Data><Data Name=\"ExceptionCode\">c0000005</Data></EventData>", m);
+        let events = vec![
+            ev("Application Error", 1000, &named("d3d11.dll"), 5),
+            ev("Application Error", 1000, &named("d3d11.dll"), 1),
+            ev("Application Error", 1000, &named("ntdll.dll"), 9),
+            ev("Application Hang", 1002, "<EventData><Data>game.exe</Data><Data>1.0</Data></EventData>", 20),
+            ev("Application Error", 1000, "<EventData><Data>Outlook.exe</Data><Data>16.0</Data><Data>0</Data><Data>KERNELBASE.dll</Data><Data>10.0</Data><Data>0</Data><Data>e0434352</Data></EventData>", 3),
+            ev("Application Error", 1001, &named("x.dll"), 0),
+        ];
+        let apps = top_crashing_apps(&events, 10);
+        assert_eq!(apps.len(), 2);
+        assert_eq!((apps[0].app.as_str(), apps[0].crashes, apps[0].hangs), ("Game.exe", 3, 1));
+        assert_eq!(apps[0].top_module.as_deref(), Some("d3d11.dll"));
+        assert_eq!(apps[0].top_exception.as_deref(), Some("0xc0000005"));
+        assert_eq!(apps[0].last_seen - apps[0].first_seen, chrono::Duration::minutes(19));
+        assert_eq!((apps[1].top_module.as_deref(), apps[1].top_exception.as_deref()), (Some("KERNELBASE.dll"), Some("0xe0434352")));
+    }
+}
@@ -0,0 +1,43 @@
+// `--watch`: one screen per refresh instead of a scrolling report.
+pub struct Snapshot {
+    pub refreshed: String,
+    pub window: String,
+    pub channels: Vec<String>,
+    pub total: usize,
+    pub criticals: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub risk: String,
+    pub top_providers: Vec<(String, usize)>,
+    pub top_hints: Vec<String>,
+    // Critical/Error lines first seen in this refresh; empty on the first pass.
+    pub fresh: Vec<String>,
+}
+
+// Clears the screen and homes the cursor, so each refresh redraws in place.
+pub const CLEAR: &str = "\x1b[2J\x1b[H";
+
+pub fn render(s: &Snapshot) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("WinDoctor watch — {} · window {} · {}\n\n", s.refreshed, s.window, s.channels.join(", ")));
+    out.push_str(&format!("Risk: {}   Total: {}   Critical: {}   Error: {}   Warning: {}\n", s.risk, s.total, s.criticals, s.errors, s.warnings));
+    if !s.top_providers.is_empty() { out.push_str(&format!("Top providers: {}\n", s.top_providers.iter().map(|(p, c)| format!("{} ({})", p, c)).collect::<Vec<_>>().join(", "))); }
+    for h in &s.top_hints { out.push_str(&format!("• {}\n", h)); }
+    out.push_str(&format!("\nNew critical/error since last refresh: {}\n", s.fresh.len()));
+    for l in &s.fresh { out.push_str(&format!("  {}\n", l)); }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dashboard_lists_counts_and_new_events() {
+        let s = Snapshot { refreshed: "12:00:30".to_string(), window: "1 hour".to_string(), channels: vec!["System".to_string(), "Application".to_string()], total: 12, criticals: 1, errors: 4, warnings: 7, risk: "High".to_string(), top_providers: vec![("Disk".to_string(), 5)], top_hints: vec!["Disk errors detected".to_string()], fresh: vec!["12:00:12 Error Disk 7".to_string()] };
+        let out = render(&s);
+        assert!(out.starts_with("WinDoctor watch — 12:00:30 · window 1 hour · System, Application\n"));
+        assert!(out.contains("Risk: High   Total: 12   Critical: 1   Error: 4   Warning: 7\nTop providers: Disk (5)\n• Disk errors detected\n"));
+        assert!(out.ends_with("New critical/error since last refresh: 1\n  12:00:12 Error Disk 7\n"));
+    }
+}
@@ -1,6 +1,11 @@
 
+// Built-in decoders first, then the templates from rules.json `decoders`.
 pub fn decode_event(provider: &str, event_id: u32, xml: &str) -> Option<String> {
     let m = crate::event_xml::event_data_pairs_or_fallback(xml);
+    decode_builtin(provider, event_id, xml, &m).or_else(|| crate::rules::decoder_message(provider, event_id, &m))
+}
+
+fn decode_builtin(provider: &str, event_id: u32, xml: &str, m: &std::collections::HashMap<String, String>) -> Option<String> {
     match provider {
         "Service Control Manager" => {
             let svc = m.get("ServiceName").or_else(|| m.get("param1")).cloned().unwrap_or_default();
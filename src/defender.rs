@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub const PROVIDER: &str = "Microsoft-Windows-Windows Defender";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DefenderEvent {
+    pub time: DateTime<Utc>,
+    pub event_id: u32,
+    pub kind: String,
+    pub threat: Option<String>,
+    pub path: Option<String>,
+    pub action: Option<String>,
+}
+
+// 1116 detected, 1117 action taken, 1118 action failed, 5001 real-time protection off, 5010 malware scanning off.
+pub fn kind(event_id: u32) -> Option<&'static str> {
+    match event_id {
+        1116 => Some("Threat detected"),
+        1117 => Some("Action taken"),
+        1118 => Some("Action failed"),
+        5001 => Some("Real-time protection disabled"),
+        5010 => Some("Malware scanning disabled"),
+        _ => None,
+    }
+}
+
+// Resources are `file:_C:\path`, `process:_pid:…` etc., several joined by ';'; the first one names the threat's location.
+fn clean_path(p: &str) -> String {
+    let first = p.split(';').map(str::trim).find(|s| !s.is_empty()).unwrap_or_default();
+    first.split_once(":_").map(|(_, rest)| rest).unwrap_or(first).to_string()
+}
+
+pub fn parse(e: &crate::EventItem) -> Option<DefenderEvent> {
+    if e.provider != PROVIDER { return None; }
+    let kind = kind(e.event_id)?;
+    let m = crate::event_xml::event_data_pairs_or_fallback(e.raw_xml.as_deref().unwrap_or(&e.content));
+    let get = |k: &str| m.get(k).map(|v| v.trim()).filter(|v| !v.is_empty()).map(str::to_string);
+    let action = match e.event_id { 1118 => get("Action Name").map(|a| match get("Error Description") { Some(err) => format!("{} ({})", a, err), None => a }), _ => get("Action Name") };
+    Some(DefenderEvent { time: e.time, event_id: e.event_id, kind: kind.to_string(), threat: get("Threat Name"), path: get("Path").map(|p| clean_path(&p)), action })
+}
+
+pub fn analyze(events: &[crate::EventItem]) -> Vec<DefenderEvent> {
+    let mut out: Vec<DefenderEvent> = events.iter().filter_map(parse).collect();
+    out.sort_by_key(|d| std::cmp::Reverse(d.time));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detections_carry_threat_path_and_action() {
+        let xml = "<EventData><Data Name=\"Threat Name\">Trojan:Win32/Wacatac.B!ml</Data><Data Name=\"Path\">file:_C:\\Users\\ana\\Downloads\\setup.exe; process:_pid:4120</Data><Data Name=\"Action Name\">Quarantine</Data><Data Name=\"Error Description\">The operation completed successfully.</Data></EventData>";
+        let ev = |id: u32| crate::EventItem { provider: PROVIDER.to_string(), event_id: id, raw_xml: Some(xml.to_string()), ..Default::default() };
+        let d = parse(&ev(1117)).unwrap();
+        assert_eq!((d.kind.as_str(), d.threat.as_deref(), d.path.as_deref(), d.action.as_deref()), ("Action taken", Some("Trojan:Win32/Wacatac.B!ml"), Some("C:\\Users\\ana\\Downloads\\setup.exe"), Some("Quarantine")));
+        assert_eq!(parse(&ev(1118)).unwrap().action.as_deref(), Some("Quarantine (The operation completed successfully.)"));
+        assert!(parse(&ev(1000)).is_none());
+        assert_eq!(analyze(&[ev(5001), ev(1116)]).len(), 2);
+    }
+}
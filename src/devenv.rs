@@ -0,0 +1,115 @@
+#![cfg_attr(not(target_os = "windows"), allow(dead_code))]
+
+use crate::EventItem;
+use serde::{Serialize, Deserialize};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DevEnvStatus {
+    pub wsl_status: Option<String>,
+    pub wsl_findings: Vec<String>,
+    pub docker_findings: Vec<String>,
+    pub vhdx: Vec<(String, u64)>,
+}
+
+// VHDX files above this size are reported as disk pressure (they grow but never shrink on their own).
+pub const VHDX_PRESSURE_BYTES: u64 = 64 * 1024 * 1024 * 1024;
+const MAX_FINDINGS: usize = 10;
+
+const DMESG_PATTERNS: [&str; 8] = ["out of memory", "oom-kill", "kernel panic", "ext4-fs error", "i/o error", "hung_task", "no space left on device", "segfault"];
+const DOCKER_PATTERNS: [&str; 6] = ["fatal", "panic", "no space left on device", "wsl distro terminated", "vm has stopped", "exited with code"];
+
+// wsl.exe writes UTF-16LE unless WSL_UTF8=1 is honoured (older builds ignore it).
+pub fn decode_wsl_output(bytes: &[u8]) -> String {
+    if bytes.len() >= 2 && bytes.iter().skip(1).step_by(2).take(16).all(|b| *b == 0) {
+        let wide: Vec<u16> = bytes.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+        String::from_utf16_lossy(&wide).replace('\u{feff}', "")
+    } else { String::from_utf8_lossy(bytes).into_owned() }
+}
+
+fn scan_lines(text: &str, patterns: &[&str]) -> Vec<String> {
+    let mut out: Vec<String> = text.lines().filter(|l| { let l = l.to_lowercase(); patterns.iter().any(|p| l.contains(p)) }).map(|l| l.trim().to_string()).collect();
+    if out.len() > MAX_FINDINGS { out.drain(..out.len() - MAX_FINDINGS); }
+    out
+}
+
+pub fn scan_dmesg(text: &str) -> Vec<String> { scan_lines(text, &DMESG_PATTERNS) }
+
+pub fn scan_docker_log(text: &str) -> Vec<String> { scan_lines(text, &DOCKER_PATTERNS) }
+
+// `wsl --list --running --quiet` prints one distro name per line, or a notice when none is running.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn running_distros(text: &str) -> Vec<String> {
+    text.lines().map(|l| l.trim().trim_end_matches(" (Default)")).filter(|l| !l.is_empty() && !l.to_lowercase().contains("no running")).map(str::to_string).collect()
+}
+
+#[cfg(target_os = "windows")]
+fn run_wsl(args: &[&str]) -> Option<String> {
+    let out = std::process::Command::new("wsl.exe").args(args).env("WSL_UTF8", "1").output().ok()?;
+    if !out.status.success() { return None; }
+    Some(decode_wsl_output(&out.stdout))
+}
+
+#[cfg(target_os = "windows")]
+pub fn collect_dev_env() -> DevEnvStatus {
+    let mut st = DevEnvStatus { wsl_status: run_wsl(&["--status"]).map(|s| s.lines().map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>().join("; ")), ..Default::default() };
+    // dmesg only from a distro that is already up, so the diagnostic never boots the WSL VM.
+    let running = if st.wsl_status.is_some() { run_wsl(&["--list", "--running", "--quiet"]).map(|s| running_distros(&s)).unwrap_or_default() } else { vec![] };
+    if let Some(distro) = running.first() && let Some(d) = run_wsl(&["-d", distro, "-u", "root", "-e", "dmesg"]) { st.wsl_findings = scan_dmesg(&d); }
+    let Ok(local) = std::env::var("LOCALAPPDATA") else { return st };
+    let local = std::path::PathBuf::from(local);
+    let mut docker = Vec::new();
+    for dir in [local.join("Docker").join("log").join("host"), local.join("Docker")] {
+        let Ok(rd) = std::fs::read_dir(&dir) else { continue };
+        for ent in rd.flatten() {
+            let p = ent.path();
+            if p.extension().and_then(|x| x.to_str()).is_some_and(|x| x.eq_ignore_ascii_case("log") || x.eq_ignore_ascii_case("txt")) && let Ok(text) = std::fs::read_to_string(&p) {
+                docker.extend(scan_docker_log(&text).into_iter().map(|l| format!("{}: {}", p.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(), l)));
+            }
+        }
+    }
+    if docker.len() > MAX_FINDINGS { docker.drain(..docker.len() - MAX_FINDINGS); }
+    st.docker_findings = docker;
+    let mut disks = vec![local.join("Docker").join("wsl").join("data").join("ext4.vhdx"), local.join("Docker").join("wsl").join("disk").join("docker_data.vhdx")];
+    if let Ok(rd) = std::fs::read_dir(local.join("Packages")) { disks.extend(rd.flatten().map(|e| e.path().join("LocalState").join("ext4.vhdx"))); }
+    for p in disks {
+        if let Ok(m) = std::fs::metadata(&p) { st.vhdx.push((p.display().to_string(), m.len())); }
+    }
+    st
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn collect_dev_env() -> DevEnvStatus { DevEnvStatus::default() }
+
+pub fn dev_env_hints(st: &DevEnvStatus, events: &[EventItem]) -> Vec<crate::hints::NoviceHint> {
+    let hint = |severity: &str, message: String, evidence: Vec<String>, probability: u8| crate::hints::NoviceHint { category: "DevEnv".to_string(), severity: severity.to_string(), count: evidence.len().max(1), message, evidence, probability };
+    let mut out = Vec::new();
+    let vm: Vec<String> = events.iter().filter(|e| (1..=2).contains(&e.level) && (e.provider.contains("Lxss") || e.provider == "Microsoft-Windows-Hyper-V-Compute")).take(MAX_FINDINGS).map(|e| format!("{} · {} · {}", e.provider, e.event_id, crate::event_message(e))).collect();
+    if !vm.is_empty() { out.push(hint("high", "WSL2 utility VM reported errors (crash or failed start)".to_string(), vm, 70)); }
+    if !st.wsl_findings.is_empty() { out.push(hint("medium", "WSL kernel log shows memory, filesystem or I/O problems".to_string(), st.wsl_findings.clone(), 65)); }
+    if !st.docker_findings.is_empty() { out.push(hint("medium", "Docker Desktop logs contain fatal errors or VM stops".to_string(), st.docker_findings.clone(), 60)); }
+    let big: Vec<String> = st.vhdx.iter().filter(|(_, n)| *n >= VHDX_PRESSURE_BYTES).map(|(p, n)| format!("{} ({:.1} GiB)", p, *n as f64 / 1073741824.0)).collect();
+    if !big.is_empty() { out.push(hint("medium", "WSL/Docker virtual disks are large; compact them or prune images to relieve host disk pressure".to_string(), big, 55)); }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_utf16_and_scans_logs() {
+        let wide: Vec<u8> = "Default Version: 2\r\n".encode_utf16().flat_map(|c| c.to_le_bytes()).collect();
+        assert_eq!(decode_wsl_output(&wide).trim(), "Default Version: 2");
+        assert_eq!(decode_wsl_output(b"plain"), "plain");
+        assert_eq!(running_distros("Ubuntu-22.04\r\ndocker-desktop\r\n"), vec!["Ubuntu-22.04", "docker-desktop"]);
+        assert!(running_distros("There are no running distributions.\r\n").is_empty());
+        let d = scan_dmesg("[ 1.0] ok\n[ 2.0] Out of memory: Killed process 42 (node)\n[ 3.0] EXT4-fs error (device sdc)\n");
+        assert_eq!(d.len(), 2);
+        let st = DevEnvStatus { wsl_findings: d, vhdx: vec![("ext4.vhdx".to_string(), VHDX_PRESSURE_BYTES + 1), ("small.vhdx".to_string(), 1)], ..Default::default() };
+        let ev = EventItem { level: 2, provider: "Microsoft-Windows-Hyper-V-Compute".to_string(), event_id: 12030, ..Default::default() };
+        let log_always = EventItem { level: 0, ..ev.clone() };
+        let hints = dev_env_hints(&st, &[ev, log_always]);
+        assert_eq!(hints.len(), 3);
+        assert_eq!(hints[2].evidence.len(), 1);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::EventItem;
+use serde_json::json;
+
+// ECS `event.severity` is numeric; Windows levels already read lower = worse.
+pub fn document(e: &EventItem) -> serde_json::Value {
+    let mut doc = json!({
+        "@timestamp": e.time.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        "message": crate::event_message_redacted(e),
+        "event": { "kind": "event", "code": e.event_id.to_string(), "provider": e.provider, "severity": e.level, "reason": crate::event_cause_redacted(e), "module": "windoctor" },
+        "log": { "level": crate::event_level_name(e).to_lowercase() },
+        "winlog": { "channel": e.channel, "provider_name": e.provider, "event_id": e.event_id.to_string(), "computer_name": e.computer },
+        "host": { "name": e.computer },
+    });
+    if let Some(r) = e.record_id { doc["winlog"]["record_id"] = json!(r.to_string()); }
+    if let Some(u) = e.user.as_ref() { doc["user"] = json!({ "name": u }); }
+    doc
+}
+
+// Action line + document line. With a record ID the `_id` is stable, so re-posting the same window does not duplicate events.
+pub fn bulk_lines(e: &EventItem) -> String {
+    let action = match e.record_id {
+        Some(r) => json!({ "index": { "_id": format!("{}:{}:{}", e.computer, e.channel, r) } }),
+        None => json!({ "index": {} }),
+    };
+    format!("{}\n{}", action, document(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bulk_pairs_action_and_ecs_document() {
+        let t = chrono::DateTime::parse_from_rfc3339("2025-11-30T12:00:00Z").unwrap().with_timezone(&chrono::Utc);
+        let e = EventItem { time: t, level: 2, provider: "Disk".to_string(), channel: "System".to_string(), event_id: 7, computer: "ws01".to_string(), content: "Bad block".to_string(), record_id: Some(42), ..Default::default() };
+        let s = bulk_lines(&e);
+        let (action, doc) = s.split_once('\n').unwrap();
+        assert_eq!(action, r#"{"index":{"_id":"ws01:System:42"}}"#);
+        let d: serde_json::Value = serde_json::from_str(doc).unwrap();
+        assert_eq!((d["@timestamp"].as_str(), d["event"]["code"].as_str(), d["log"]["level"].as_str(), d["host"]["name"].as_str()), (Some("2025-11-30T12:00:00.000Z"), Some("7"), Some("error"), Some("ws01")));
+        assert!(bulk_lines(&EventItem::default()).starts_with("{\"index\":{}}\n"));
+    }
+}
@@ -70,6 +70,27 @@ pub fn event_data_pairs_fallback(xml: &str) -> HashMap<String, String> {
     res
 }
 
+// Data values in document order, for classic providers whose <Data> elements carry no Name.
+pub fn event_data_values(xml: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = xml;
+    while let Some(i) = rest.find("<Data") {
+        rest = &rest[i + 5..];
+        if !rest.starts_with(['>', ' ', '/']) { continue; }
+        let Some(gt) = rest.find('>') else { break };
+        if rest[..gt].ends_with('/') { out.push(String::new()); rest = &rest[gt + 1..]; continue; }
+        let Some(ve) = rest[gt + 1..].find("</Data>") else { break };
+        out.push(rest[gt + 1..gt + 1 + ve].trim().to_string());
+        rest = &rest[gt + 1 + ve + 7..];
+    }
+    out
+}
+
+// Field names declared by a publisher's event template: <template><data name="X" inType=".."/>...</template>.
+pub fn template_fields(template: &str) -> Vec<String> {
+    template.split("<data ").skip(1).filter_map(|t| { let from = t.find("name=\"")? + 6; t[from..].find('"').map(|e| t[from..from + e].to_string()) }).collect()
+}
+
 pub fn event_data_pairs_or_fallback(xml: &str) -> HashMap<String, String> {
     let m = event_data_pairs(xml);
     if m.is_empty() { event_data_pairs_fallback(xml) } else { m }
@@ -91,4 +112,15 @@ mod tests {
         let m = event_data_pairs_or_fallback(xml);
         assert_eq!(m.get("Odd").unwrap(), "Value");
     }
+    #[test]
+    fn values_in_document_order() {
+        let xml = "<Event><EventData><Data>app.exe</Data><Data/><Data Name=\"X\">ntdll.dll</Data><DataItem>no</DataItem></EventData></Event>";
+        assert_eq!(event_data_values(xml), vec!["app.exe", "", "ntdll.dll"]);
+    }
+    #[test]
+    fn template_field_names() {
+        let t = "<template xmlns=\"http://schemas.microsoft.com/win/2004/08/events\">\n  <data name=\"DeviceName\" inType=\"win:UnicodeString\" outType=\"xs:string\"/>\n  <data inType=\"win:UInt32\" name=\"ErrorCode\"/>\n</template>";
+        assert_eq!(template_fields(t), vec!["DeviceName", "ErrorCode"]);
+        assert!(template_fields("").is_empty());
+    }
 }
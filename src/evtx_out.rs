@@ -0,0 +1,66 @@
+use std::path::{Path, PathBuf};
+
+// The event log's XPath subset rejects long OR chains, so each <Select> carries at most this many terms.
+const MAX_TERMS: usize = 64;
+
+// Sorted, de-duplicated record IDs as exact inclusive ranges.
+pub fn record_ranges(ids: &[u64]) -> Vec<(u64, u64)> {
+    let mut ids = ids.to_vec();
+    ids.sort_unstable();
+    ids.dedup();
+    let mut ranges: Vec<(u64, u64)> = Vec::new();
+    for id in ids {
+        match ranges.last_mut() { Some(r) if r.1 + 1 == id => r.1 = id, _ => ranges.push((id, id)) }
+    }
+    ranges
+}
+
+fn xml_attr(s: &str) -> String { s.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;") }
+
+// Structured query for EvtExportLog; `path` is a channel name or `file://C:\logs\System.evtx`.
+// Ranges past `max_terms` go to further <Select> elements of the same query, which the service unions.
+pub fn query_xml(path: &str, ranges: &[(u64, u64)], max_terms: usize) -> String {
+    let selects = ranges.chunks(max_terms.max(1)).map(|chunk| {
+        let terms = chunk.iter().map(|(a, b)| if a == b { format!("EventRecordID={}", a) } else { format!("(EventRecordID &gt;= {} and EventRecordID &lt;= {})", a, b) }).collect::<Vec<_>>().join(" or ");
+        format!("<Select Path=\"{}\">*[System[{}]]</Select>", xml_attr(path), terms)
+    }).collect::<String>();
+    format!("<QueryList><Query Id=\"0\">{}</Query></QueryList>", selects)
+}
+
+// One source keeps the given name; several get `<stem>-<source>.evtx` next to it.
+pub fn target_path(out: &str, source: &str, many: bool) -> PathBuf {
+    if !many { return PathBuf::from(out); }
+    let p = Path::new(out);
+    let stem = p.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| "events".to_string());
+    let safe: String = source.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect();
+    p.with_file_name(format!("{}-{}.evtx", stem, safe))
+}
+
+// `records` are (source, EventRecordID) pairs from the filtered events; the source is a live channel or an EVTX file.
+pub fn export(out: &str, records: &[(String, u64)]) -> Vec<Result<PathBuf, String>> {
+    let mut sources: Vec<&str> = records.iter().map(|(s, _)| s.as_str()).collect();
+    sources.sort_unstable();
+    sources.dedup();
+    sources.iter().map(|src| {
+        let ids: Vec<u64> = records.iter().filter(|(s, _)| s == src).map(|(_, r)| *r).collect();
+        let target = target_path(out, src, sources.len() > 1);
+        // EvtExportLog refuses to overwrite.
+        let _ = std::fs::remove_file(&target);
+        crate::windows_live::export_log(&query_xml(src, &record_ranges(&ids), MAX_TERMS), &target.to_string_lossy()).map(|()| target.clone()).map_err(|code| format!("{}: EvtExportLog failed ({})", target.display(), code))
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_stay_exact_and_split_across_selects() {
+        assert_eq!(record_ranges(&[7, 3, 4, 5, 9, 4]), vec![(3, 5), (7, 7), (9, 9)]);
+        assert_eq!(record_ranges(&[10, 900000]), vec![(10, 10), (900000, 900000)]);
+        assert_eq!(query_xml("System", &[(3, 5), (9, 9)], 64), "<QueryList><Query Id=\"0\"><Select Path=\"System\">*[System[(EventRecordID &gt;= 3 and EventRecordID &lt;= 5) or EventRecordID=9]]</Select></Query></QueryList>");
+        assert_eq!(query_xml("System", &[(10, 10), (12, 12), (900000, 900000)], 2), "<QueryList><Query Id=\"0\"><Select Path=\"System\">*[System[EventRecordID=10 or EventRecordID=12]]</Select><Select Path=\"System\">*[System[EventRecordID=900000]]</Select></Query></QueryList>");
+        assert_eq!(target_path("out/support.evtx", "Microsoft-Windows-Kernel-Power/Thermal-Operational", true), PathBuf::from("out/support-Microsoft-Windows-Kernel-Power_Thermal-Operational.evtx"));
+        assert_eq!(target_path("support.evtx", "System", false), PathBuf::from("support.evtx"));
+    }
+}
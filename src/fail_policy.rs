@@ -0,0 +1,130 @@
+use crate::ReportSummary;
+
+// Exit codes: 1 = event counts, 3 = risk grade, 4 = performance score, 5 = category/provider/event ID present; `expr@N` picks N instead.
+// 2 stays reserved for invalid arguments; --exit-code-by-risk uses 6-8 so it never reads as one of these.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FailCond {
+    Count { metric: String, op: Op, value: i64 },
+    Risk { op: Op, grade: u8 },
+    PerfScore { op: Op, value: i64 },
+    Category(String),
+    Provider(String),
+    EventId(u32),
+    Coded(Box<FailCond>, i32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Op { Gt, Ge, Lt, Le, Eq, Ne }
+
+impl Op {
+    fn test(self, a: i64, b: i64) -> bool {
+        match self { Op::Gt => a > b, Op::Ge => a >= b, Op::Lt => a < b, Op::Le => a <= b, Op::Eq => a == b, Op::Ne => a != b }
+    }
+}
+
+fn risk_rank(s: &str) -> Option<u8> {
+    match s.to_lowercase().as_str() { "low" => Some(0), "medium" => Some(1), "high" => Some(2), "critical" => Some(3), _ => None }
+}
+
+fn split_op(s: &str) -> Option<(&str, Op, &str)> {
+    for (tok, op) in [(">=", Op::Ge), ("<=", Op::Le), ("!=", Op::Ne), ("==", Op::Eq), (">", Op::Gt), ("<", Op::Lt), ("=", Op::Eq)] {
+        if let Some(idx) = s.find(tok) { return Some((s[..idx].trim(), op, s[idx + tok.len()..].trim())); }
+    }
+    None
+}
+
+pub fn parse_fail_expr(s: &str) -> Result<FailCond, String> {
+    let raw = s.trim();
+    if let Some((expr, code)) = raw.rsplit_once('@') {
+        let code: i32 = code.trim().parse().map_err(|_| format!("invalid exit code '{}' in '{}'", code.trim(), raw))?;
+        if !(1..=255).contains(&code) { return Err(format!("exit code must be 1-255 in '{}'", raw)); }
+        return Ok(FailCond::Coded(Box::new(parse_fail_expr(expr)?), code));
+    }
+    if let Some((k, v)) = raw.split_once(':') {
+        let v = v.trim().to_string();
+        if v.is_empty() { return Err(format!("missing value in '{}'", raw)); }
+        return match k.trim().to_lowercase().as_str() {
+            "category" => Ok(FailCond::Category(v)),
+            "provider" => Ok(FailCond::Provider(v)),
+            "event_id" | "id" => Ok(FailCond::EventId(v.parse().map_err(|_| format!("invalid event ID '{}' in '{}'", v, raw))?)),
+            other => Err(format!("unknown selector '{}' in '{}' (use category:, provider: or event_id:)", other, raw)),
+        };
+    }
+    let (lhs, op, rhs) = split_op(raw).ok_or_else(|| format!("expected <metric><op><value> in '{}'", raw))?;
+    let metric = lhs.to_lowercase();
+    match metric.as_str() {
+        "risk" => Ok(FailCond::Risk { op, grade: risk_rank(rhs).ok_or_else(|| format!("unknown risk grade '{}' (Low, Medium, High, Critical)", rhs))? }),
+        "performance_score" | "perf" => Ok(FailCond::PerfScore { op, value: rhs.parse().map_err(|_| format!("invalid number '{}' in '{}'", rhs, raw))? }),
+        "errors" | "warnings" | "criticals" | "total" => Ok(FailCond::Count { metric, op, value: rhs.parse().map_err(|_| format!("invalid number '{}' in '{}'", rhs, raw))? }),
+        _ => Err(format!("unknown metric '{}' in '{}' (errors, criticals, warnings, total, risk, performance_score)", lhs, raw)),
+    }
+}
+
+impl FailCond {
+    pub fn exit_code(&self) -> i32 {
+        match self { FailCond::Count { .. } => 1, FailCond::Category(_) | FailCond::Provider(_) | FailCond::EventId(_) => 5, FailCond::Risk { .. } => 3, FailCond::PerfScore { .. } => 4, FailCond::Coded(_, code) => *code }
+    }
+
+    pub fn matches(&self, rep: &ReportSummary) -> bool {
+        let present = |list: &[(String, usize)], name: &str| list.iter().any(|(k, c)| *c > 0 && k.eq_ignore_ascii_case(name));
+        match self {
+            FailCond::Count { metric, op, value } => {
+                let n = match metric.as_str() { "errors" => rep.errors, "criticals" => rep.criticals, "warnings" => rep.warnings, _ => rep.total };
+                op.test(n as i64, *value)
+            }
+            FailCond::Risk { op, grade } => risk_rank(&rep.risk_grade).is_some_and(|g| op.test(g as i64, *grade as i64)),
+            FailCond::PerfScore { op, value } => op.test(rep.performance_score as i64, *value),
+            FailCond::Category(c) => present(&rep.by_category, c),
+            // The by_* lists stop at --top; the full counts are only there for freshly built (not loaded) reports.
+            FailCond::Provider(p) => present(&rep.all_providers, p) || present(&rep.by_provider, p),
+            FailCond::EventId(id) => rep.all_event_ids.iter().chain(&rep.by_event_id).any(|(k, c)| *c > 0 && k == id),
+            FailCond::Coded(c, _) => c.matches(rep),
+        }
+    }
+}
+
+// Returns the exit code of the first condition that holds.
+pub fn evaluate(conds: &[FailCond], rep: &ReportSummary) -> Option<(i32, usize)> {
+    conds.iter().enumerate().find(|(_, c)| c.matches(rep)).map(|(i, c)| (c.exit_code(), i))
+}
+
+pub fn risk_exit_code(grade: &str) -> i32 {
+    match grade { "Critical" => 8, "High" => 7, "Medium" => 6, _ => 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_expressions() {
+        assert_eq!(parse_fail_expr("errors>0").unwrap(), FailCond::Count { metric: "errors".to_string(), op: Op::Gt, value: 0 });
+        assert_eq!(parse_fail_expr("risk>=High").unwrap(), FailCond::Risk { op: Op::Ge, grade: 2 });
+        assert_eq!(parse_fail_expr("category:Storage").unwrap(), FailCond::Category("Storage".to_string()));
+        assert_eq!(parse_fail_expr("performance_score > 60").unwrap(), FailCond::PerfScore { op: Op::Gt, value: 60 });
+        assert!(parse_fail_expr("risk>=Severe").is_err());
+        assert!(parse_fail_expr("uptime>3").is_err());
+        assert_eq!(parse_fail_expr("criticals>0@10").unwrap(), FailCond::Coded(Box::new(FailCond::Count { metric: "criticals".to_string(), op: Op::Gt, value: 0 }), 10));
+        assert_eq!(parse_fail_expr("event_id:41").unwrap().exit_code(), 5);
+        assert!(parse_fail_expr("errors>0@0").is_err());
+        assert!(parse_fail_expr("errors>0@x").is_err());
+    }
+
+    #[test]
+    fn first_matching_condition_wins() {
+        let mut rep = ReportSummary { total: 3, warnings: 3, performance_score: 70, by_category: vec![("Storage".to_string(), 2)], risk_grade: "Medium".to_string(), ..crate::test_summary() };
+        let conds: Vec<FailCond> = ["errors>0", "risk>=High", "category:storage", "performance_score>60"].iter().map(|s| parse_fail_expr(s).unwrap()).collect();
+        assert_eq!(evaluate(&conds, &rep), Some((5, 2)));
+        rep.risk_grade = "Critical".to_string();
+        assert_eq!(evaluate(&conds, &rep), Some((3, 1)));
+        rep.by_category.clear();
+        rep.risk_grade = "Low".to_string();
+        rep.performance_score = 10;
+        assert_eq!(evaluate(&conds, &rep), None);
+        rep.criticals = 1;
+        rep.all_event_ids = vec![(41, 1)];
+        let conds: Vec<FailCond> = ["event_id:7", "criticals>0@12", "event_id:41"].iter().map(|s| parse_fail_expr(s).unwrap()).collect();
+        assert_eq!(evaluate(&conds, &rep), Some((12, 1)));
+        assert_eq!(["Low", "Medium", "High", "Critical"].map(risk_exit_code), [0, 6, 7, 8]);
+    }
+}
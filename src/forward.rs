@@ -0,0 +1,103 @@
+use std::io::Write;
+use std::net::{TcpStream, UdpSocket};
+
+// Where forwarded events go: `udp://host:port`, `tcp://host:port`, or a bare `host:port` using the format's default.
+pub enum Transport {
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+}
+
+pub fn parse_target(target: &str, default_tcp: bool) -> Result<(bool, String), String> {
+    let (tcp, addr) = match target.split_once("://") {
+        Some(("udp", a)) => (false, a),
+        Some(("tcp", a)) => (true, a),
+        Some((scheme, _)) => return Err(format!("unsupported scheme '{}' (use udp:// or tcp://)", scheme)),
+        None => (default_tcp, target),
+    };
+    match addr.rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => Ok((tcp, addr.to_string())),
+        _ => Err(format!("'{}' is not host:port", addr)),
+    }
+}
+
+impl Transport {
+    pub fn connect(target: &str, default_tcp: bool) -> Result<Transport, String> {
+        let (tcp, addr) = parse_target(target, default_tcp)?;
+        if tcp { return TcpStream::connect(&addr).map(Transport::Tcp).map_err(|e| format!("{}: {}", addr, e)); }
+        let sock = UdpSocket::bind(if addr.starts_with('[') { "[::]:0" } else { "0.0.0.0:0" }).map_err(|e| e.to_string())?;
+        sock.connect(&addr).map_err(|e| format!("{}: {}", addr, e))?;
+        Ok(Transport::Udp(sock))
+    }
+
+    pub fn is_tcp(&self) -> bool { matches!(self, Transport::Tcp(_)) }
+
+    // One datagram over UDP; over TCP the caller has already framed the bytes.
+    pub fn send(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        match self {
+            Transport::Udp(s) => s.send(bytes).map(|_| ()),
+            Transport::Tcp(s) => s.write_all(bytes),
+        }
+    }
+}
+
+// Errors carry how many events were lost; a batching sink can lose a whole batch at once.
+pub trait Sink {
+    fn send(&mut self, e: &crate::EventItem) -> Result<(), (usize, std::io::Error)>;
+    fn flush(&mut self) -> Result<(), (usize, std::io::Error)> { Ok(()) }
+}
+
+// HTTP sinks buffer events; a batch is due when full or once its oldest event has waited FLUSH_AFTER (follow mode trickles events in).
+const BATCH_EVENTS: usize = 500;
+const FLUSH_AFTER: std::time::Duration = std::time::Duration::from_secs(5);
+
+pub struct Batch<T> { items: Vec<T>, bytes: usize, max_bytes: usize, since: std::time::Instant }
+
+impl<T> Batch<T> {
+    pub fn new(max_bytes: usize) -> Batch<T> { Batch { items: Vec::new(), bytes: 0, max_bytes, since: std::time::Instant::now() } }
+
+    pub fn push(&mut self, item: T, bytes: usize) -> bool {
+        if self.items.is_empty() { self.since = std::time::Instant::now(); }
+        self.items.push(item);
+        self.bytes += bytes;
+        self.items.len() >= BATCH_EVENTS || self.bytes >= self.max_bytes || self.since.elapsed() >= FLUSH_AFTER
+    }
+
+    pub fn is_empty(&self) -> bool { self.items.is_empty() }
+
+    pub fn take(&mut self) -> Vec<T> { self.bytes = 0; std::mem::take(&mut self.items) }
+}
+
+// `--syslog`, `--gelf`, `--splunk-hec-url`, `--otlp-endpoint`: every matched event goes to each; the first failure per sink is logged, the rest counted.
+#[derive(Default)]
+pub struct Forwarders { sinks: Vec<(&'static str, Box<dyn Sink>, usize)> }
+
+impl Forwarders {
+    pub fn add(&mut self, name: &'static str, sink: Box<dyn Sink>) { self.sinks.push((name, sink, 0)); }
+
+    pub fn send(&mut self, e: &crate::EventItem) {
+        for (name, sink, failed) in self.sinks.iter_mut() {
+            if let Err((n, err)) = sink.send(e) { if *failed == 0 { log::error!("{} send failed: {}", name, err); } *failed += n; }
+        }
+    }
+
+    pub fn finish(&mut self, total: usize) {
+        for (name, sink, failed) in self.sinks.iter_mut() {
+            if let Err((n, err)) = sink.flush() { if *failed == 0 { log::error!("{} send failed: {}", name, err); } *failed += n; }
+            if *failed > 0 { log::warn!("{} of {} events could not be forwarded to {}", failed, total, name); }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_need_a_scheme_we_know_and_a_port() {
+        assert_eq!(parse_target("tcp://siem.local:6514", false), Ok((true, "siem.local:6514".to_string())));
+        assert_eq!(parse_target("10.0.0.5:514", false), Ok((false, "10.0.0.5:514".to_string())));
+        assert_eq!(parse_target("[::1]:514", true), Ok((true, "[::1]:514".to_string())));
+        assert!(parse_target("http://x:1", false).is_err());
+        assert!(parse_target("udp://host", false).is_err());
+    }
+}
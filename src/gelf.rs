@@ -0,0 +1,89 @@
+use crate::EventItem;
+use crate::forward::Transport;
+use serde_json::json;
+
+// Graylog's LAN datagram limit is 8192 bytes including the 12-byte chunk header; larger messages are chunked (at most 128 chunks).
+const CHUNK_HEADER: usize = 12;
+const CHUNK_SIZE: usize = 8192 - CHUNK_HEADER;
+const MAX_CHUNKS: usize = 128;
+
+// Syslog severities, as GELF expects.
+fn level(l: u8) -> u8 {
+    match l { 1 => 2, 2 => 3, 3 => 4, 4 => 6, _ => 7 }
+}
+
+pub fn message(e: &EventItem) -> serde_json::Value {
+    let mut m = json!({
+        "version": "1.1",
+        "host": if e.computer.is_empty() { "unknown" } else { e.computer.as_str() },
+        "short_message": crate::event_cause_redacted(e),
+        "full_message": crate::event_message_redacted(e),
+        "timestamp": e.time.timestamp_millis() as f64 / 1000.0,
+        "level": level(e.level),
+        "_event_id": e.event_id,
+        "_provider": e.provider,
+        "_channel": e.channel,
+        "_cause": crate::event_cause_redacted(e),
+        "_severity": crate::event_level_name(e),
+    });
+    if let Some(r) = e.record_id { m["_record_id"] = json!(r); }
+    if let Some(u) = e.user.as_ref() { m["_user"] = json!(u); }
+    m
+}
+
+// Chunk = 0x1e 0x0f, 8-byte message id, sequence number, sequence count, payload slice.
+pub fn chunks(payload: &[u8], id: u64, size: usize) -> Option<Vec<Vec<u8>>> {
+    if payload.len() <= size { return Some(vec![payload.to_vec()]); }
+    let parts: Vec<&[u8]> = payload.chunks(size).collect();
+    if parts.len() > MAX_CHUNKS { return None; }
+    Some(parts.iter().enumerate().map(|(i, p)| {
+        let mut c = vec![0x1e, 0x0f];
+        c.extend_from_slice(&id.to_be_bytes());
+        c.push(i as u8);
+        c.push(parts.len() as u8);
+        c.extend_from_slice(p);
+        c
+    }).collect())
+}
+
+pub struct Gelf { transport: Transport, next_id: u64 }
+
+impl Gelf {
+    pub fn connect(target: &str) -> Result<Gelf, String> {
+        let seed = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0) ^ ((std::process::id() as u64) << 32);
+        Transport::connect(target, false).map(|transport| Gelf { transport, next_id: seed })
+    }
+}
+
+impl crate::forward::Sink for Gelf {
+    // TCP frames end with a NUL byte; UDP messages over CHUNK_SIZE are chunked.
+    fn send(&mut self, e: &EventItem) -> Result<(), (usize, std::io::Error)> {
+        let mut payload = message(e).to_string().into_bytes();
+        if self.transport.is_tcp() { payload.push(0); return self.transport.send(&payload).map_err(|err| (1, err)); }
+        self.next_id = self.next_id.wrapping_add(1);
+        let Some(parts) = chunks(&payload, self.next_id, CHUNK_SIZE) else { return Err((1, std::io::Error::other(format!("GELF message of {} bytes exceeds {} chunks", payload.len(), MAX_CHUNKS)))) };
+        for p in parts { self.transport.send(&p).map_err(|err| (1, err))?; }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gelf_fields_and_chunking() {
+        let t = chrono::DateTime::parse_from_rfc3339("2025-11-30T12:00:00.250Z").unwrap().with_timezone(&chrono::Utc);
+        let e = EventItem { time: t, level: 3, provider: "Disk".to_string(), channel: "System".to_string(), event_id: 153, computer: "ws01".to_string(), content: "Retried IO".to_string(), record_id: Some(9), ..Default::default() };
+        let m = message(&e);
+        assert_eq!((m["version"].as_str(), m["host"].as_str(), m["level"].as_u64(), m["_event_id"].as_u64(), m["_record_id"].as_u64()), (Some("1.1"), Some("ws01"), Some(4), Some(153), Some(9)));
+        assert_eq!(m["timestamp"].as_f64(), Some(1764504000.25));
+        let c = chunks(&[7u8; 10], 0x0102030405060708, 4).unwrap();
+        assert_eq!(c.len(), 3);
+        assert_eq!(&c[2][..12], &[0x1e, 0x0f, 1, 2, 3, 4, 5, 6, 7, 8, 2, 3]);
+        assert_eq!(c[2].len(), 12 + 2);
+        assert_eq!(chunks(b"abc", 1, 4).unwrap(), vec![b"abc".to_vec()]);
+        assert!(chunks(&[0u8; 200], 1, 1).is_none());
+        assert!(chunks(&[0u8; 20000], 1, CHUNK_SIZE).unwrap().iter().all(|c| c.len() <= 8192));
+    }
+}
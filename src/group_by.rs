@@ -0,0 +1,71 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum, Serialize, Deserialize)]
+pub enum GroupKey { Provider, Channel, #[value(alias = "event_id")] EventId, Device, Domain, Level, Hour }
+
+static KEYS: OnceLock<Vec<GroupKey>> = OnceLock::new();
+
+pub fn set_keys(keys: Vec<GroupKey>) { let _ = KEYS.set(keys); }
+
+pub fn keys() -> &'static [GroupKey] { KEYS.get().map(|k| k.as_slice()).unwrap_or(&[]) }
+
+// One row per distinct key combination, most frequent first.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GroupTable {
+    pub keys: Vec<String>,
+    pub rows: Vec<(Vec<String>, usize)>,
+}
+
+fn header(k: GroupKey) -> &'static str {
+    match k { GroupKey::Provider => "Provider", GroupKey::Channel => "Channel", GroupKey::EventId => "Event ID", GroupKey::Device => "Device", GroupKey::Domain => "Domain", GroupKey::Level => "Level", GroupKey::Hour => "Hour (UTC)" }
+}
+
+fn value(k: GroupKey, e: &crate::EventItem) -> String {
+    match k {
+        GroupKey::Provider => e.provider.clone(),
+        GroupKey::Channel => e.channel.clone(),
+        GroupKey::EventId => e.event_id.to_string(),
+        GroupKey::Device => crate::event_device(e).unwrap_or_default(),
+        GroupKey::Domain => crate::classify_domain(&e.provider, &e.channel, e.event_id, &e.content),
+        GroupKey::Level => crate::event_level_name(e),
+        GroupKey::Hour => e.time.format("%Y-%m-%d %H:00").to_string(),
+    }
+}
+
+pub fn pivot(events: &[crate::EventItem], keys: &[GroupKey], top: usize) -> Option<GroupTable> {
+    if keys.is_empty() { return None; }
+    let mut counts: HashMap<Vec<String>, usize> = HashMap::new();
+    for e in events { *counts.entry(keys.iter().map(|k| value(*k, e)).collect()).or_insert(0) += 1; }
+    let mut rows: Vec<(Vec<String>, usize)> = counts.into_iter().collect();
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    rows.truncate(top);
+    Some(GroupTable { keys: keys.iter().map(|k| header(*k).to_string()).collect(), rows })
+}
+
+pub fn print(t: &GroupTable) {
+    let widths: Vec<usize> = (0..t.keys.len()).map(|i| t.rows.iter().map(|(r, _)| r[i].chars().count()).chain([t.keys[i].len()]).max().unwrap_or(0).min(48)).collect();
+    let line = |cells: &[String], count: &str| cells.iter().zip(&widths).map(|(c, w)| format!("{:<w$}  ", crate::truncate(c, *w), w = *w)).collect::<String>() + count;
+    println!("{}", line(&t.keys, "Count"));
+    for (cells, c) in &t.rows { println!("{}", line(cells, &c.to_string())); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pivots_on_key_combinations() {
+        let ev = |p: &str, id: u32, level: u8| crate::EventItem { provider: p.to_string(), event_id: id, level, ..Default::default() };
+        let events = vec![ev("Disk", 7, 2), ev("Disk", 7, 2), ev("Disk", 153, 3), ev("Ntfs", 55, 2)];
+        let t = pivot(&events, &[GroupKey::Provider, GroupKey::Level], 10).unwrap();
+        assert_eq!(t.keys, vec!["Provider", "Level"]);
+        assert_eq!(t.rows[0], (vec!["Disk".to_string(), "Error".to_string()], 2));
+        assert_eq!(t.rows.len(), 3);
+        assert_eq!(pivot(&events, &[GroupKey::EventId], 1).unwrap().rows, vec![(vec!["7".to_string()], 2)]);
+        assert!(pivot(&events, &[], 10).is_none());
+        assert_eq!(GroupKey::from_str("event_id", true), Ok(GroupKey::EventId));
+    }
+}
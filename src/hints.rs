@@ -31,10 +31,53 @@ fn push_hint(acc: &mut HashMap<(String, String, String), NoviceHint>, category:
     }
 }
 
+// Windows Update / CBS result codes seen most often: (HRESULT, what it means, what to do).
+pub const UPDATE_ERRORS: &[(&str, &str, &str)] = &[
+    ("0x80070002", "files the update needs are missing", "Reset the Windows Update cache: stop wuauserv and bits, rename C:\\Windows\\SoftwareDistribution, start both services and retry the update"),
+    ("0x800f081f", "the component store is missing source files", "Repair the component store with DISM /Online /Cleanup-Image /RestoreHealth (add /Source from matching install media if it fails), then run sfc /scannow"),
+    ("0x80073712", "the component store is damaged", "Repair the component store with DISM /Online /Cleanup-Image /RestoreHealth, then retry the update"),
+    ("0x800f0922", "the update could not reach the servers or the System Reserved partition is full", "Disconnect VPNs, check the proxy, and make sure the System Reserved partition has free space"),
+    ("0x80070070", "there is not enough disk space", "Free disk space on the system drive (Disk Cleanup, remove old restore points) and retry"),
+    ("0x8024402c", "the update server could not be reached (proxy or DNS)", "Check proxy settings (netsh winhttp show proxy) and DNS, then retry the update"),
+];
+
+// First `KB1234567` in the text.
+fn kb_number(s: &str) -> Option<String> {
+    let upper = s.to_uppercase();
+    upper.match_indices("KB").find_map(|(i, _)| {
+        let digits: String = upper[i + 2..].chars().take_while(char::is_ascii_digit).collect();
+        (digits.len() >= 6).then(|| format!("KB{}", digits))
+    })
+}
+
+// An error code as 0x + 8 lowercase hex digits; EventData sometimes carries it as a (signed) decimal.
+fn hresult(s: &str) -> Option<String> {
+    let t = s.trim();
+    if let Ok(n) = t.parse::<i64>() && n != 0 { return Some(format!("0x{:08x}", n as u32)); }
+    let lower = t.to_lowercase();
+    lower.match_indices("0x").find_map(|(i, _)| {
+        let hex: String = lower[i + 2..].chars().take_while(char::is_ascii_hexdigit).collect();
+        (hex.len() == 8).then(|| format!("0x{}", hex))
+    })
+}
+
+fn update_hint(acc: &mut HashMap<(String, String, String), NoviceHint>, severity: &str, stage: &str, code: Option<String>, kb: Option<String>, title: &str) {
+    let msg = match code.as_deref() {
+        Some(c) => match UPDATE_ERRORS.iter().find(|(k, _, _)| *k == c) {
+            Some((_, what, _)) => format!("Windows Update {} failed with {}: {}", stage, c, what),
+            None => format!("Windows Update {} failed with {}", stage, c),
+        },
+        None => format!("Windows Update {} failed", stage),
+    };
+    let ev = match (kb, title.is_empty()) { (Some(kb), false) if !title.contains(&kb) => format!("{} {}", kb, title), (Some(kb), true) => kb, _ => title.to_string() };
+    push_hint(acc, "Updates", severity, &msg, if ev.is_empty() { None } else { Some(ev) });
+}
+
 pub fn generate_hints(events: &[crate::EventItem]) -> Vec<NoviceHint> {
     let mut acc: HashMap<(String, String, String), NoviceHint> = HashMap::new();
     for e in events {
-        let m = extract_data_pairs(&e.content);
+        // Rendered or decoded messages replace content; the EventData is still in the raw XML.
+        let m = extract_data_pairs(e.raw_xml.as_deref().unwrap_or(&e.content));
         let content_lower = e.content.to_lowercase();
         match e.provider.as_str() {
             "Application Error" => {
@@ -70,6 +113,49 @@ pub fn generate_hints(events: &[crate::EventItem]) -> Vec<NoviceHint> {
                     push_hint(&mut acc, "Network", "medium", "DNS name resolution failure", if q.is_empty() { None } else { Some(q) });
                 }
             }
+            "Microsoft-Windows-Dhcp-Client" | "Dhcp" => {
+                let conflict = matches!(e.event_id, 1005 | 50013) || content_lower.contains("address conflict") || content_lower.contains("is already in use");
+                let lease = match e.event_id { 1001 => Some("DHCP lease could not be obtained"), 1002 => Some("DHCP server denied the address lease"), 1003 => Some("DHCP lease could not be renewed"), _ => None };
+                if conflict || lease.is_some() {
+                    let adapter = ["AdapterName", "InterfaceDescription", "InterfaceName", "InterfaceGuid"].iter().find_map(|k| m.get(*k)).cloned()
+                        .or_else(|| m.get("HWAddress").map(|h| format!("adapter {}", h))).unwrap_or_default();
+                    let ip = m.get("IPAddress").or_else(|| m.get("Address")).cloned().unwrap_or_default();
+                    let ev = [adapter, ip].into_iter().filter(|s| !s.is_empty()).collect::<Vec<_>>().join(" ");
+                    let (sev, msg) = if conflict { ("high", "IP address conflict detected by the DHCP client") } else { ("medium", lease.unwrap_or_default()) };
+                    push_hint(&mut acc, "Network", sev, msg, if ev.is_empty() { None } else { Some(ev) });
+                }
+            }
+            "Microsoft-Windows-Hyper-V-VMMS" | "Microsoft-Windows-Hyper-V-Worker" | "Microsoft-Windows-Hyper-V-Hypervisor" if (1..=3).contains(&e.level) => {
+                let failed = ["fail", "could not", "cannot", "unable", "error", "denied", "not found"].iter().any(|w| content_lower.contains(w));
+                let msg = if e.provider.ends_with("Hypervisor") { Some("Hyper-V hypervisor failed to launch or reported an error") }
+                    else if content_lower.contains("replica") || (32000..33000).contains(&e.event_id) { failed.then_some("Hyper-V replication failure") }
+                    else if failed && (content_lower.contains(".vhd") || content_lower.contains("virtual hard disk") || content_lower.contains("storage") || content_lower.contains("path")) { Some("Hyper-V VM storage path error") }
+                    else if content_lower.contains("failed to start") || content_lower.contains("could not initialize") || content_lower.contains("failed to power on") || content_lower.contains("failed to restore") { Some("Hyper-V virtual machine failed to start") }
+                    else { None };
+                if let Some(msg) = msg {
+                    // Hyper-V messages open with the VM name in quotes when EventData does not carry it.
+                    let vm = m.get("VmName").or_else(|| m.get("VMName")).cloned().or_else(|| e.content.strip_prefix('\'').and_then(|r| r.split_once('\'')).map(|(n, _)| n.to_string())).unwrap_or_default();
+                    push_hint(&mut acc, "Virtualization", if e.level <= 2 { "high" } else { "medium" }, msg, if vm.is_empty() { None } else { Some(vm) });
+                }
+            }
+            "Microsoft-Windows-BitLocker-API" | "Microsoft-Windows-BitLocker-Driver" => {
+                let msg = if matches!(e.event_id, 24620 | 24660) || (content_lower.contains("recovery") && ["entered", "required", "started", "used", "triggered"].iter().any(|w| content_lower.contains(w))) { Some("BitLocker recovery mode was entered") }
+                    else if content_lower.contains("unseal") || (content_lower.contains("tpm") && (content_lower.contains("fail") || content_lower.contains("could not"))) { Some("BitLocker could not unseal the volume key from the TPM") }
+                    else if matches!(e.event_id, 775 | 776) || (content_lower.contains("protector") && ["added", "created", "removed", "deleted", "changed"].iter().any(|w| content_lower.contains(w))) { Some("BitLocker key protectors were changed") }
+                    else { None };
+                if let Some(msg) = msg {
+                    let vol = ["VolumeMountPoint", "VolumeName", "Volume", "VolumeId"].iter().find_map(|k| m.get(*k)).cloned().unwrap_or_default();
+                    push_hint(&mut acc, "Security", "high", msg, if vol.is_empty() { None } else { Some(vol) });
+                }
+            }
+            "Microsoft-Windows-TPM-WMI" | "TPM" | "Microsoft-Windows-TPM" if (1..=3).contains(&e.level) => {
+                let msg = if e.event_id == 1796 || (content_lower.contains("secure boot") && content_lower.contains("variable")) { "Secure Boot variable update failed" }
+                    else if content_lower.contains("attestation") { "TPM attestation failed" }
+                    else if content_lower.contains("lockout") || content_lower.contains("dictionary attack") { "TPM is in dictionary-attack lockout" }
+                    else { "TPM reported an error" };
+                let code = m.get("ErrorCode").or_else(|| m.get("HRESULT")).and_then(|c| hresult(c)).or_else(|| hresult(&e.content)).unwrap_or_default();
+                push_hint(&mut acc, "Platform Security", if e.level <= 2 { "high" } else { "medium" }, msg, if code.is_empty() { None } else { Some(code) });
+            }
             "Microsoft-Windows-Time-Service" | "W32Time" => {
                 if content_lower.contains("failed") || content_lower.contains("no response") || content_lower.contains("synchronize") {
                     let src = m.get("SourceType").cloned().unwrap_or_default();
@@ -231,6 +317,33 @@ pub fn generate_hints(events: &[crate::EventItem]) -> Vec<NoviceHint> {
                     push_hint(&mut acc, "Network", "medium", "Domain logon or secure channel issue", if dc.is_empty() { None } else { Some(dc) });
                 }
             }
+            "Microsoft-Windows-WindowsUpdateClient" => {
+                let stage = match e.event_id { 20 => Some(("high", "installation")), 25 => Some(("medium", "scan")), 31 | 34 => Some(("medium", "download")), _ => None };
+                if let Some((sev, stage)) = stage {
+                    let title = m.get("updateTitle").cloned().unwrap_or_default();
+                    let code = m.get("errorCode").and_then(|c| hresult(c)).or_else(|| hresult(&e.content));
+                    update_hint(&mut acc, sev, stage, code, kb_number(&title).or_else(|| kb_number(&e.content)), &title);
+                }
+            }
+            "Microsoft-Windows-Servicing" if e.level <= 2 || content_lower.contains("failed") => {
+                let pkg = m.get("PackageIdentifier").cloned().unwrap_or_default();
+                let code = m.get("ErrorCode").and_then(|c| hresult(c)).or_else(|| hresult(&e.content));
+                update_hint(&mut acc, "high", "servicing", code, kb_number(&pkg).or_else(|| kb_number(&e.content)), "");
+            }
+            crate::defender::PROVIDER => {
+                if let Some(d) = crate::defender::parse(e) {
+                    let (sev, msg) = match e.event_id {
+                        1116 => ("medium", "Microsoft Defender detected malware"),
+                        1117 => ("low", "Microsoft Defender removed or quarantined a threat"),
+                        1118 => ("high", "Microsoft Defender failed to remediate a threat"),
+                        5001 => ("high", "Microsoft Defender real-time protection is disabled"),
+                        _ => ("high", "Microsoft Defender malware scanning is disabled"),
+                    };
+                    let mut ev = [d.threat, d.path].into_iter().flatten().collect::<Vec<_>>().join(" — ");
+                    if let Some(a) = d.action { ev = if ev.is_empty() { a } else { format!("{} ({})", ev, a) }; }
+                    push_hint(&mut acc, "Security", sev, msg, if ev.is_empty() { None } else { Some(ev) });
+                }
+            }
             "Microsoft-Windows-MemoryDiagnostics-Results" => {
                 let errs = m.get("TestResult").or_else(|| m.get("FailureCount")).cloned().unwrap_or_default();
                 if !errs.is_empty() && errs != "0" {
@@ -248,7 +361,8 @@ pub fn generate_hints(events: &[crate::EventItem]) -> Vec<NoviceHint> {
         if content_lower.contains("dns") || content_lower.contains("name resolution") || content_lower.contains("tcp") || content_lower.contains("connection timed out") || content_lower.contains("reset by peer") || content_lower.contains("dhcp") || content_lower.contains("media disconnected") {
             push_hint(&mut acc, "Network", "medium", "Network connectivity or name resolution issue", None);
         }
-        if content_lower.contains("windows update") || content_lower.contains("wuau") || content_lower.contains("failed to install update") || content_lower.contains("download error") {
+        let update_provider = e.provider == "Microsoft-Windows-WindowsUpdateClient" || e.provider == "Microsoft-Windows-Servicing";
+        if !update_provider && (content_lower.contains("windows update") || content_lower.contains("wuau") || content_lower.contains("failed to install update") || content_lower.contains("download error")) {
             push_hint(&mut acc, "Updates", "medium", "Windows Update reported a failure", None);
         }
         if content_lower.contains("low disk space") || content_lower.contains("not enough space") || content_lower.contains("quota exceeded") {
@@ -265,6 +379,38 @@ pub fn generate_hints(events: &[crate::EventItem]) -> Vec<NoviceHint> {
             && (e.event_id == 11 || content_lower.contains("controller error") || content_lower.contains("device not ready")) {
             push_hint(&mut acc, "Storage", "medium", "Optical drive or controller error", None);
         }
+        if e.provider.to_lowercase().contains("onedrive") || e.provider.contains("KnownFolders") {
+            let kfm = content_lower.contains("known folder") || e.provider.contains("KnownFolders");
+            push_hint(&mut acc, "Sync", "medium", if kfm { "Known Folder Move (folder redirection to OneDrive) reported an error" } else { "OneDrive sync client reported an error" }, None);
+        }
+        let bluetooth = e.provider.starts_with("Microsoft-Windows-Bluetooth") || ["BTHUSB", "BTHPORT", "BthEnum", "BthLEEnum", "bthserv"].iter().any(|p| e.provider.eq_ignore_ascii_case(p));
+        let audio_service = |s: &str| s.eq_ignore_ascii_case("Audiosrv") || s.eq_ignore_ascii_case("AudioEndpointBuilder") || s.contains("Windows Audio");
+        let audio = ["Microsoft-Windows-Audio", "Audiosrv", "AudioEndpointBuilder"].iter().any(|p| e.provider.eq_ignore_ascii_case(p))
+            || (e.provider == "Service Control Manager" && m.get("param1").is_some_and(|s| audio_service(s)) && (content_lower.contains("terminated") || content_lower.contains("failed")));
+        if (1..=3).contains(&e.level) && (bluetooth || audio) {
+            let dev = ["DeviceName", "DeviceInstanceId", "DeviceAddress", "EndpointName", "param1"].iter().find_map(|k| m.get(*k)).cloned().unwrap_or_default();
+            let msg = if audio { "Audio endpoint or audio service failure" } else if content_lower.contains("pair") || content_lower.contains("authentication") { "Bluetooth pairing or authentication failed" } else { "Bluetooth radio or device error" };
+            push_hint(&mut acc, "Peripherals/Audio", if e.level <= 2 { "medium" } else { "low" }, msg, if dev.is_empty() { None } else { Some(dev) });
+        }
+        // WSL, the Host Compute Service and Docker Desktop fail as their own providers or as services/apps in System and Application.
+        let scm_failure = e.provider == "Service Control Manager" && (content_lower.contains("terminated") || content_lower.contains("failed"));
+        let svc = if scm_failure { m.get("param1").cloned().unwrap_or_default() } else { String::new() };
+        let crashed_app = if e.provider == "Application Error" && e.event_id == 1000 { m.get("FaultingApplicationName").or_else(|| m.get("AppName")).cloned().unwrap_or_default() } else { String::new() };
+        let svc_is = |names: &[&str]| names.iter().any(|n| svc.eq_ignore_ascii_case(n));
+        let runtime = if ((e.provider == "LxssManager" || e.provider == "Microsoft-Windows-Subsystem-Linux") && (1..=2).contains(&e.level)) || svc_is(&["LxssManager", "WSLService", "WSL Service"]) {
+            Some("WSL service (LxssManager) failure")
+        } else if (e.provider == "Microsoft-Windows-Hyper-V-Compute" && (1..=2).contains(&e.level)) || svc_is(&["vmcompute", "Hyper-V Host Compute Service"]) {
+            Some("Host Compute Service (vmcompute/HCS) error")
+        } else if svc_is(&["com.docker.service", "Docker Desktop Service"]) || crashed_app.to_lowercase().starts_with("docker desktop") || crashed_app.to_lowercase().starts_with("com.docker") {
+            Some("Docker Desktop service crashed")
+        } else { None };
+        if let Some(msg) = runtime {
+            let ev = if !svc.is_empty() { svc.clone() } else if !crashed_app.is_empty() { crashed_app.clone() } else { e.provider.clone() };
+            push_hint(&mut acc, "Virtualization", "medium", msg, Some(ev));
+        }
+        if !e.provider.contains("TPM") && content_lower.contains("secure boot") && ["violat", "fail", "disabled", "not enabled", "revoked"].iter().any(|w| content_lower.contains(w)) {
+            push_hint(&mut acc, "Platform Security", "high", "Secure Boot policy violation or Secure Boot is off", None);
+        }
         if e.provider == "Microsoft-Windows-Diagnostics-Performance" {
             match e.event_id {
                 100 => push_hint(&mut acc, "Performance", "medium", "Slow startup detected (Diagnostics-Performance 100)", None),
@@ -280,10 +426,9 @@ pub fn generate_hints(events: &[crate::EventItem]) -> Vec<NoviceHint> {
             push_hint(&mut acc, "Storage", sev, msg, None);
         }
     }
-    let has_volsnap_abort = events.iter().any(|e| e.provider.eq_ignore_ascii_case("volsnap") && e.content.to_lowercase().contains("aborted"));
-    let has_ntfs_55 = events.iter().any(|e| e.provider.eq_ignore_ascii_case("Microsoft-Windows-Ntfs") && e.event_id == 55);
-    if has_volsnap_abort && has_ntfs_55 {
-        push_hint(&mut acc, "Storage", "high", "Shadow copies aborted and NTFS corruption detected (sequence)", None);
+    // Cross-event sequences (crate::correlate): one hint per sequence, one count and evidence line per chain.
+    for (seq, chains) in crate::correlate::chains(events) {
+        for c in &chains { push_hint(&mut acc, seq.category, seq.severity, seq.message, Some(crate::correlate::evidence(c, crate::display_tz()))); }
     }
     let mut out: Vec<NoviceHint> = acc.into_values().collect();
     for h in &mut out {
@@ -312,8 +457,112 @@ mod tests {
             event_id: 7,
             content: "<EventData><Data Name=\"DeviceName\">\\\\.\\PHYSICALDRIVE2</Data></EventData>".to_string(),
             raw_xml: None,
+            ..Default::default()
         };
         let out = generate_hints(&[e]);
         assert!(out.iter().any(|h| h.category == "Storage" && h.severity == "high"));
     }
+
+    #[test]
+    fn windows_update_failures_name_kb_and_error() {
+        let e = |provider: &str, id: u32, xml: &str| crate::EventItem { level: 2, channel: "System".to_string(), provider: provider.to_string(), event_id: id, content: String::new(), raw_xml: Some(xml.to_string()), ..Default::default() };
+        let out = generate_hints(&[
+            e("Microsoft-Windows-WindowsUpdateClient", 20, "<EventData><Data Name=\"errorCode\">0x80070002</Data><Data Name=\"updateTitle\">2025-11 Cumulative Update for Windows 11 (KB5068861)</Data></EventData>"),
+            e("Microsoft-Windows-Servicing", 3, "<EventData><Data Name=\"PackageIdentifier\">KB5005565</Data><Data Name=\"ErrorCode\">-2146498529</Data></EventData>"),
+        ]);
+        let h = out.iter().find(|h| h.message.starts_with("Windows Update installation")).unwrap();
+        assert_eq!((h.message.as_str(), h.severity.as_str()), ("Windows Update installation failed with 0x80070002: files the update needs are missing", "high"));
+        assert_eq!(h.evidence, vec!["2025-11 Cumulative Update for Windows 11 (KB5068861)"]);
+        let h = out.iter().find(|h| h.message.starts_with("Windows Update servicing")).unwrap();
+        assert_eq!((h.message.as_str(), h.evidence.clone()), ("Windows Update servicing failed with 0x800f081f: the component store is missing source files", vec!["KB5005565".to_string()]));
+        assert!(!out.iter().any(|h| h.message == "Windows Update reported a failure"));
+    }
+
+    #[test]
+    fn bluetooth_and_audio_failures_are_peripherals() {
+        let e = |provider: &str, id: u32, content: &str, xml: &str| crate::EventItem { level: 2, provider: provider.to_string(), event_id: id, content: content.to_string(), raw_xml: Some(xml.to_string()), ..Default::default() };
+        let out = generate_hints(&[
+            e("BTHUSB", 17, "The local Bluetooth adapter has failed in an undetermined manner", "<EventData/>"),
+            e("Microsoft-Windows-Bluetooth-BthLEPrepairing", 3, "Pairing with the device failed", "<EventData><Data Name=\"DeviceAddress\">a4c1389f0b12</Data></EventData>"),
+            e("Service Control Manager", 7031, "The Windows Audio service terminated unexpectedly.", "<EventData><Data Name=\"param1\">Windows Audio</Data></EventData>"),
+        ]);
+        let cats: Vec<(&str, &str)> = out.iter().filter(|h| h.category == "Peripherals/Audio").map(|h| (h.message.as_str(), h.evidence.first().map(String::as_str).unwrap_or(""))).collect();
+        assert_eq!(cats.len(), 3);
+        assert!(cats.contains(&("Bluetooth pairing or authentication failed", "a4c1389f0b12")));
+        assert!(cats.contains(&("Audio endpoint or audio service failure", "Windows Audio")));
+        assert!(crate::perf::generate_recommendations(&out).iter().any(|r| r.contains("Bluetooth")));
+    }
+
+    #[test]
+    fn hyperv_failures_carry_the_vm_name() {
+        let e = |provider: &str, id: u32, content: &str, xml: &str| crate::EventItem { level: 2, provider: provider.to_string(), event_id: id, content: content.to_string(), raw_xml: Some(xml.to_string()), ..Default::default() };
+        let out = generate_hints(&[
+            e("Microsoft-Windows-Hyper-V-Worker", 12030, "'pihole' failed to start. (Virtual machine ID 1B2C…)", "<EventData><Data Name=\"VmName\">pihole</Data></EventData>"),
+            e("Microsoft-Windows-Hyper-V-VMMS", 16300, "'nas' cannot open attachment 'D:\\VMs\\nas.vhdx'. Error: The system cannot find the path specified.", "<EventData/>"),
+            e("Microsoft-Windows-Hyper-V-VMMS", 32022, "Hyper-V could not replicate changes for virtual machine 'dc01'", "<EventData><Data Name=\"VmName\">dc01</Data></EventData>"),
+        ]);
+        let got: Vec<(&str, &str)> = out.iter().filter(|h| h.category == "Virtualization").map(|h| (h.message.as_str(), h.evidence[0].as_str())).collect();
+        assert!(got.contains(&("Hyper-V virtual machine failed to start", "pihole")));
+        assert!(got.contains(&("Hyper-V VM storage path error", "nas")));
+        assert!(got.contains(&("Hyper-V replication failure", "dc01")));
+    }
+
+    #[test]
+    fn wsl_hcs_and_docker_failures_are_virtualization() {
+        let e = |provider: &str, id: u32, content: &str, xml: &str| crate::EventItem { level: 2, provider: provider.to_string(), event_id: id, content: content.to_string(), raw_xml: Some(xml.to_string()), ..Default::default() };
+        let out = generate_hints(&[
+            e("Service Control Manager", 7031, "The LxssManager service terminated unexpectedly.", "<EventData><Data Name=\"param1\">LxssManager</Data></EventData>"),
+            e("Microsoft-Windows-Hyper-V-Compute", 2002, "HCS operation failed", "<EventData/>"),
+            e("Application Error", 1000, "", "<EventData><Data Name=\"AppName\">com.docker.backend.exe</Data></EventData>"),
+        ]);
+        let got: Vec<(&str, &str)> = out.iter().filter(|h| h.category == "Virtualization").map(|h| (h.message.as_str(), h.evidence[0].as_str())).collect();
+        assert_eq!(got.len(), 3);
+        assert!(got.contains(&("WSL service (LxssManager) failure", "LxssManager")));
+        assert!(got.contains(&("Docker Desktop service crashed", "com.docker.backend.exe")));
+        assert!(crate::perf::generate_recommendations(&out).iter().any(|r| r.contains("wsl --shutdown")));
+    }
+
+    #[test]
+    fn bitlocker_recovery_unseal_and_protectors() {
+        let e = |id: u32, content: &str| crate::EventItem { level: 2, provider: "Microsoft-Windows-BitLocker-API".to_string(), event_id: id, content: content.to_string(), raw_xml: Some("<EventData><Data Name=\"VolumeName\">\\\\?\\Volume{2f1a}</Data><Data Name=\"VolumeMountPoint\">C:</Data></EventData>".to_string()), ..Default::default() };
+        let out = generate_hints(&[
+            e(24620, "BitLocker recovery was triggered for volume C:."),
+            e(846, "BitLocker failed to unseal the volume master key from the TPM."),
+            e(776, "A BitLocker key protector was removed for volume C:."),
+            e(768, "BitLocker encryption was started for volume C:."),
+        ]);
+        let got: Vec<&str> = out.iter().filter(|h| h.category == "Security").map(|h| h.message.as_str()).collect();
+        assert_eq!(got.len(), 3);
+        assert!(out.iter().filter(|h| h.category == "Security").all(|h| h.severity == "high" && h.evidence == vec!["C:"]));
+        assert!(got.contains(&"BitLocker could not unseal the volume key from the TPM"));
+    }
+
+    #[test]
+    fn tpm_and_secure_boot_are_platform_security() {
+        let e = |provider: &str, id: u32, level: u8, content: &str| crate::EventItem { level, provider: provider.to_string(), event_id: id, content: content.to_string(), raw_xml: Some("<EventData/>".to_string()), ..Default::default() };
+        let out = generate_hints(&[
+            e("Microsoft-Windows-TPM-WMI", 1796, 2, "The Secure Boot update failed to update a Secure Boot variable with error 0x80070015."),
+            e("Microsoft-Windows-TPM-WMI", 1025, 3, "Device attestation failed."),
+            e("Microsoft-Windows-Kernel-Boot", 153, 2, "Secure Boot policy violation detected."),
+        ]);
+        let got: Vec<(&str, &str)> = out.iter().filter(|h| h.category == "Platform Security").map(|h| (h.message.as_str(), h.severity.as_str())).collect();
+        assert_eq!(got.len(), 3);
+        assert!(got.contains(&("TPM attestation failed", "medium")));
+        assert_eq!(out.iter().find(|h| h.message == "Secure Boot variable update failed").unwrap().evidence, vec!["0x80070015"]);
+        assert!(crate::perf::generate_recommendations(&out).iter().any(|r| r.contains("clear the TPM")));
+    }
+
+    #[test]
+    fn dhcp_failures_name_the_adapter() {
+        let e = |id: u32, xml: &str| crate::EventItem { level: 2, provider: "Microsoft-Windows-Dhcp-Client".to_string(), event_id: id, raw_xml: Some(xml.to_string()), ..Default::default() };
+        let out = generate_hints(&[
+            e(1001, "<EventData><Data Name=\"AdapterName\">Ethernet</Data><Data Name=\"StatusCode\">1460</Data></EventData>"),
+            e(50013, "<EventData><Data Name=\"InterfaceDescription\">Intel(R) Wi-Fi 6 AX201</Data><Data Name=\"IPAddress\">192.168.1.20</Data></EventData>"),
+        ]);
+        let h = out.iter().find(|h| h.message == "DHCP lease could not be obtained").unwrap();
+        assert_eq!(h.evidence, vec!["Ethernet"]);
+        let h = out.iter().find(|h| h.message.starts_with("IP address conflict")).unwrap();
+        assert_eq!((h.severity.as_str(), h.evidence.clone()), ("high", vec!["Intel(R) Wi-Fi 6 AX201 192.168.1.20".to_string()]));
+        assert!(crate::perf::generate_recommendations(&out).iter().any(|r| r.contains("ipconfig /renew")));
+    }
 }
@@ -2,7 +2,8 @@ use crate::{ReportSummary, EventItem, TimeZone, Lang};
 
 pub fn render_html(rep: &ReportSummary, theme: crate::Theme, use_emoji: bool, tz: TimeZone, tfmt: Option<&str>, lang: Lang) -> String {
     let mut s = String::new();
-    s.push_str("<html lang=\"en\"><head><meta charset=\"utf-8\"><meta name=\"viewport\" content=\"width=device-width, initial-scale=1\"><title>WinDoctor Report</title><style>");
+    s.push_str(&format!("<html lang=\"{}\">", crate::i18n::code(lang)));
+    s.push_str("<head><meta charset=\"utf-8\"><meta name=\"viewport\" content=\"width=device-width, initial-scale=1\"><title>WinDoctor Report</title><style>");
     match theme {
         crate::Theme::Dark => s.push_str(":root{--bg:#0a0e13;--fg:#ffffff;--muted:#c0c4cc;--card:#0d131a;--border:#243041;--accent:#3b82f6;--ok:#22c55e;--warn:#f59e0b;--err:#ef4444;--chip:#0f172a} body{margin:0;background:var(--bg);color:var(--fg);font-family:Segoe UI,system-ui,-apple-system,Arial,sans-serif} .container{max-width:1200px;margin:0 auto;padding:24px} .header{display:flex;align-items:center;justify-content:space-between;gap:12px;margin-bottom:16px} .title{font-size:20px;font-weight:600;letter-spacing:.2px} .sub{color:var(--muted);font-size:13px} .grid{display:grid;grid-template-columns:repeat(4,minmax(0,1fr));gap:12px} .card{background:var(--card);border:1px solid var(--border);border-radius:10px;padding:14px;box-shadow:0 1px 0 rgba(255,255,255,.03) inset} .metric{display:flex;align-items:center;justify-content:space-between} .metric .label{color:var(--muted);font-size:12px} .metric .value{font-size:22px;font-weight:700} .value.err{color:var(--err)} .value.warn{color:var(--warn)} .value.ok{color:var(--ok)} .section{margin-top:18px} .section h3{margin:0 0 10px 0;font-size:16px;font-weight:600} .table{width:100%;border-collapse:separate;border-spacing:0;background:var(--card);border:1px solid var(--border);border-radius:10px;overflow:hidden} .table th{position:sticky;top:0;background:#0c1118;color:#ffffff;text-align:left;font-weight:600;padding:10px;border-bottom:1px solid var(--border)} .table td{padding:10px;border-bottom:1px solid var(--border);vertical-align:top} .table tr:nth-child(odd) td{background:#0b0f14} .chip{display:inline-flex;align-items:center;gap:6px;background:var(--chip);color:#ffffff;border:1px solid var(--border);border-radius:999px;padding:4px 10px;font-size:12px} .sev-high{background:#3a0f12;color:#ffffff;border-color:#7f1d1d} .sev-medium{background:#3a2b0d;color:#ffffff;border-color:#854d0e} .sev-low{background:#0f1a2b;color:#ffffff;border-color:#1e3a8a} .pill{display:inline-block;background:#0c1118;color:#ffffff;padding:6px 10px;border-radius:999px;border:1px solid var(--border);font-size:12px;margin:4px 6px 0 0} .code{font-family:Consolas,Monaco,monospace;background:#091017;border:1px solid var(--border);border-radius:8px;padding:10px;margin-top:8px;white-space:pre-wrap} .ellipsis{display:block;max-width:900px;white-space:nowrap;overflow:hidden;text-overflow:ellipsis} details summary{cursor:pointer;color:var(--accent)} .split{display:grid;grid-template-columns:repeat(2,minmax(0,1fr));gap:12px} .footer{margin-top:22px;color:var(--muted);font-size:12px} @media (max-width:900px){.grid{grid-template-columns:repeat(2,minmax(0,1fr))}.split{grid-template-columns:1fr}} @media (max-width:600px){.grid{grid-template-columns:1fr}.header{flex-direction:column;align-items:flex-start}}"),
         crate::Theme::Light => s.push_str(":root{--bg:#f7fafc;--fg:#111827;--muted:#6b7280;--card:#ffffff;--border:#e5e7eb;--accent:#2563eb;--ok:#16a34a;--warn:#d97706;--err:#dc2626;--chip:#eef2f7} body{margin:0;background:var(--bg);color:var(--fg);font-family:Segoe UI,system-ui,-apple-system,Arial,sans-serif} .container{max-width:1200px;margin:0 auto;padding:24px} .header{display:flex;align-items:center;justify-content:space-between;gap:12px;margin-bottom:16px} .title{font-size:20px;font-weight:600;letter-spacing:.2px} .sub{color:var(--muted);font-size:13px} .grid{display:grid;grid-template-columns:repeat(4,minmax(0,1fr));gap:12px} .card{background:var(--card);border:1px solid var(--border);border-radius:10px;padding:14px;box-shadow:0 1px 0 rgba(0,0,0,.04)} .metric{display:flex;align-items:center;justify-content:space-between} .metric .label{color:var(--muted);font-size:12px} .metric .value{font-size:22px;font-weight:700} .value.err{color:var(--err)} .value.warn{color:var(--warn)} .value.ok{color:var(--ok)} .section{margin-top:18px} .section h3{margin:0 0 10px 0;font-size:16px;font-weight:600} .table{width:100%;border-collapse:separate;border-spacing:0;background:var(--card);border:1px solid var(--border);border-radius:10px;overflow:hidden} .table th{position:sticky;top:0;background:#f3f4f6;color:var(--fg);text-align:left;font-weight:600;padding:10px;border-bottom:1px solid var(--border)} .table td{padding:10px;border-bottom:1px solid var(--border);vertical-align:top} .table tr:nth-child(odd) td{background:#fbfdff} .chip{display:inline-flex;align-items:center;gap:6px;background:var(--chip);color:var(--fg);border:1px solid var(--border);border-radius:999px;padding:4px 10px;font-size:12px} .sev-high{background:#fee2e2;color:#7f1d1d;border-color:#fecaca} .sev-medium{background:#fde68a;color:#854d0e;border-color:#fef3c7} .sev-low{background:#dbeafe;color:#1e3a8a;border-color:#bfdbfe} .pill{display:inline-block;background:#eef2f7;color:var(--fg);padding:6px 10px;border-radius:999px;border:1px solid var(--border);font-size:12px;margin:4px 6px 0 0} .code{font-family:Consolas,Monaco,monospace;background:#f3f4f6;border:1px solid var(--border);border-radius:8px;padding:10px;margin-top:8px;white-space:pre-wrap} .ellipsis{display:block;max-width:900px;white-space:nowrap;overflow:hidden;text-overflow:ellipsis} details summary{cursor:pointer;color:var(--accent)} .split{display:grid;grid-template-columns:repeat(2,minmax(0,1fr));gap:12px} .footer{margin-top:22px;color:var(--muted);font-size:12px} @media (max-width:900px){.grid{grid-template-columns:repeat(2,minmax(0,1fr))}.split{grid-template-columns:1fr}} @media (max-width:600px){.grid{grid-template-columns:1fr}.header{flex-direction:column;align-items:flex-start}}"),
@@ -10,21 +11,27 @@ pub fn render_html(rep: &ReportSummary, theme: crate::Theme, use_emoji: bool, tz
     }
     s.push_str(" .pill:focus-visible, button:focus-visible, input:focus-visible, select:focus-visible{outline:2px solid var(--accent);outline-offset:2px} ");
     s.push_str("</style><script>(function(){const light={bg:'#f7fafc',fg:'#111827',muted:'#6b7280',card:'#ffffff',border:'#e5e7eb',accent:'#2563eb',ok:'#16a34a',warn:'#d97706',err:'#dc2626',chip:'#eef2f7'};const dark={bg:'#0f1216',fg:'#e5e7eb',muted:'#9aa0a6',card:'#141820',border:'#1f2430',accent:'#3b82f6',ok:'#22c55e',warn:'#f59e0b',err:'#ef4444',chip:'#1f2937'};const hc={bg:'#000000',fg:'#ffffff',muted:'#cccccc',card:'#0a0a0a',border:'#3a3a3a',accent:'#00b7ff',ok:'#00ff6a',warn:'#ffcc00',err:'#ff3b3b',chip:'#1a1a1a'};function apply(vars){const r=document.documentElement.style;Object.entries(vars).forEach(([k,v])=>r.setProperty('--'+k,v));document.body.style.background='var(--bg)';document.body.style.color='var(--fg)';}window.__wdTheme=window.__wdTheme||'';window.__wdLang='");
-    s.push_str(match lang { Lang::En => "en" });
+    s.push_str(crate::i18n::code(lang));
     s.push_str("';window.toggleTheme=function(){let next='light';if(window.__wdTheme==='light'){next='dark';}else if(window.__wdTheme==='dark'){next='hc';}else{next='light';}window.__wdTheme=next;apply(next==='light'?light:(next==='dark'?dark:hc));const btn=document.getElementById('themeToggle');if(btn){btn.textContent=next==='light'? 'Dark Mode' : (next==='dark'?'High Contrast':'Light Mode');}};window.copyRowMessage=function(btn){const tr=btn.closest('tr');if(!tr)return;const el=tr.querySelector('.full-msg');if(!el)return;const txt=el.textContent||'';if(navigator.clipboard){navigator.clipboard.writeText(txt).then(()=>{btn.textContent='Copied!';setTimeout(()=>btn.textContent='Copy',1500);});}};window.copyWevtutil=function(btn){const tr=btn.closest('tr');if(!tr)return;const c=tr.cells[1].innerText.trim();const id=tr.cells[4].innerText.trim();const q=`wevtutil qe ${c} /q:*[System[(EventID=${id})]]`;navigator.clipboard&&navigator.clipboard.writeText(q).then(()=>{btn.textContent='Copied!';setTimeout(()=>btn.textContent='Copy EV Query',1500);});};})();</script></head><body><div class=\"container\">");
     s.push_str("<div class=\"header\"><div class=\"title\">WinDoctor Report</div>");
     let start_s = match (tz, tfmt) { (TimeZone::Local, Some(f)) => rep.window_start.with_timezone(&chrono::Local).format(f).to_string(), (TimeZone::Utc, Some(f)) => rep.window_start.format(f).to_string(), (TimeZone::Local, None) => rep.window_start.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string(), (TimeZone::Utc, None) => rep.window_start.format("%Y-%m-%d %H:%M").to_string() };
     let end_s = match (tz, tfmt) { (TimeZone::Local, Some(f)) => rep.window_end.with_timezone(&chrono::Local).format(f).to_string(), (TimeZone::Utc, Some(f)) => rep.window_end.format(f).to_string(), (TimeZone::Local, None) => rep.window_end.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string(), (TimeZone::Utc, None) => rep.window_end.format("%Y-%m-%d %H:%M").to_string() };
     s.push_str(&format!("<div class=\"sub\">{} → {}{} <span class=\"pill\">Risk · {}</span></div>", start_s, end_s, match rep.mode.as_ref(){Some(m)=>format!(" | {}", m),None=>String::new()}, html_escape(&rep.risk_grade)));
+    if rep.partial { s.push_str(&format!("<div class=\"sub\"><span class=\"pill sev-medium\">Partial · {}</span></div>", html_escape(&crate::completion_text(&rep.completion)))); }
     s.push_str(&format!("<button id=\"themeToggle\" class=\"pill\" role=\"button\" aria-label=\"Toggle theme\" onclick=\"toggleTheme()\">{}</button>", match theme { crate::Theme::Light => "Dark Mode", crate::Theme::HighContrast => "Light Mode", _ => "High Contrast" }));
     s.push_str("</div>");
+    if let Some(si) = rep.system_info.as_ref() {
+        s.push_str("<div class=\"section\"><h3>System</h3><div class=\"card\">");
+        for (k, v) in crate::sysinfo::lines(si) { s.push_str(&format!("<div><span class=\"sub\">{}</span> {}</div>", k, html_escape(&v))); }
+        s.push_str("</div></div>");
+    }
     if !rep.by_category.is_empty() {
-        s.push_str("<div class=\"section\"><h3>Impact Assessment</h3><div class=\"card\">");
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><div class=\"card\">", crate::i18n::heading_in(lang, "Impact Assessment")));
         for (cat,cnt) in &rep.by_category { s.push_str(&format!("<span class=\"pill\">{} · {}</span>", html_escape(cat), cnt)); }
         s.push_str("</div></div>");
     }
     if !rep.likely_causes.is_empty() {
-        s.push_str("<div class=\"section\"><h3>Likely Root Causes</h3><div class=\"card\">");
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><div class=\"card\">", crate::i18n::heading_in(lang, "Likely Root Causes")));
         for c in &rep.likely_causes { s.push_str(&format!("<div class=\"pill\">{}</div>", html_escape(c))); }
         s.push_str("</div></div>");
     }
@@ -38,7 +45,7 @@ pub fn render_html(rep: &ReportSummary, theme: crate::Theme, use_emoji: bool, tz
     s.push_str(&format!("<div class=\"card metric\"><div class=\"label\">Risk</div><div class=\"{}\">{}</div></div>", risk_cls, rep.risk_grade));
     s.push_str("</div>");
     if !rep.novice_hints.is_empty() {
-        s.push_str("<div class=\"section\"><h3>Diagnostics</h3><table class=\"table\"><thead><tr><th>Category</th><th>Severity</th><th>Probability</th><th>Message</th><th>Occurrences</th><th>Examples</th></tr></thead><tbody>");
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><table class=\"table\"><thead><tr><th>Category</th><th>Severity</th><th>Probability</th><th>Message</th><th>Occurrences</th><th>Examples</th></tr></thead><tbody>", crate::i18n::heading_in(lang, "Diagnostics")));
         for h in &rep.novice_hints {
             let sev_cls = match h.severity.as_str(){"high"=>"sev-high","medium"=>"sev-medium",_=>"sev-low"}.to_string();
             let sev_emoji = if use_emoji { match h.severity.as_str(){"high"=>"⛔","medium"=>"⚠️",_=>"🛈"} } else { "" };
@@ -49,7 +56,7 @@ pub fn render_html(rep: &ReportSummary, theme: crate::Theme, use_emoji: bool, tz
         s.push_str("</tbody></table></div>");
     }
     if let Some(pc) = &rep.perf_counters {
-        s.push_str("<div class=\"section\"><h3>Live Performance</h3><div class=\"card\">");
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><div class=\"card\">", crate::i18n::heading_in(lang, "Live Performance")));
         if let Some(v) = pc.cpu_percent { s.push_str(&format!("<span class=\"pill\">CPU · {}%</span>", v)); }
         if let Some(v) = pc.avg_disk_ms_per_transfer { s.push_str(&format!("<span class=\"pill\">Avg Disk Transfer · {:.2} ms</span>", v)); }
         if let Some(v) = pc.disk_reads_per_sec { s.push_str(&format!("<span class=\"pill\">Reads/s · {}</span>", v)); }
@@ -82,8 +89,8 @@ pub fn render_html(rep: &ReportSummary, theme: crate::Theme, use_emoji: bool, tz
         s.push_str("</div></div>");
     }
     s.push_str("<div class=\"section split\">");
-    s.push_str("<div class=\"card\"><h3>Top Providers</h3><table class=\"table\"><thead><tr><th>Provider</th><th>Count</th></tr></thead><tbody>");
-    for (p,c) in &rep.by_provider { s.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>", html_escape(p), c)); }
+    s.push_str("<div class=\"card\"><h3>Top Providers</h3><table class=\"table\"><thead><tr><th>Provider</th><th>Count</th><th>Err/Warn</th><th>First Seen</th><th>Last Seen</th></tr></thead><tbody>");
+    for (p,c) in &rep.by_provider { s.push_str(&format!("<tr><td>{}</td><td>{}</td>{}</tr>", html_escape(p), c, stat_cells(crate::find_stat(&rep.provider_stats, p), tz, tfmt))); }
     s.push_str("</tbody></table></div>");
     s.push_str("<div class=\"card\"><h3>Top Domains</h3><table class=\"table\"><thead><tr><th>Domain</th><th>Count</th></tr></thead><tbody>");
     for (d,c) in &rep.by_domain { s.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>", html_escape(d), c)); }
@@ -91,27 +98,49 @@ pub fn render_html(rep: &ReportSummary, theme: crate::Theme, use_emoji: bool, tz
     s.push_str("<div class=\"card\"><h3>Top Devices</h3><table class=\"table\"><thead><tr><th>Device</th><th>Count</th></tr></thead><tbody>");
     for (d,c) in &rep.by_device { s.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>", html_escape(d), c)); }
     s.push_str("</tbody></table></div>");
-    s.push_str("<div class=\"card\"><h3>Top Event IDs</h3><table class=\"table\"><thead><tr><th>Event ID</th><th>Count</th></tr></thead><tbody>");
-    for (id,c) in &rep.by_event_id { s.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>", id, c)); }
+    if crate::multi_computer(rep) {
+        s.push_str(&format!("<div class=\"card\"><h3>{}</h3><table class=\"table\"><thead><tr><th>Computer</th><th>Events</th><th>Err/Warn</th><th>First Seen</th><th>Last Seen</th><th>Top Providers</th></tr></thead><tbody>", crate::i18n::heading_in(lang, "Computers")));
+        for (comp,c) in &rep.by_computer {
+            let top = rep.computer_top_providers.iter().find(|(k, _)| k == comp).map(|(_, p)| p.iter().map(|(p, c)| format!("{} ({})", p, c)).collect::<Vec<_>>().join(", ")).unwrap_or_default();
+            s.push_str(&format!("<tr><td>{}</td><td>{}</td>{}<td>{}</td></tr>", html_escape(comp), c, stat_cells(crate::find_stat(&rep.computer_stats, comp), tz, tfmt), html_escape(&top)));
+        }
+        s.push_str("</tbody></table></div>");
+    }
+    if !rep.by_user.is_empty() {
+        s.push_str(&format!("<div class=\"card\"><h3>{}</h3><table class=\"table\"><thead><tr><th>User</th><th>Events</th></tr></thead><tbody>", crate::i18n::heading_in(lang, "Users")));
+        for (user,c) in &rep.by_user { s.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>", html_escape(user), c)); }
+        s.push_str("</tbody></table></div>");
+    }
+    if !rep.by_source.is_empty() {
+        s.push_str(&format!("<div class=\"card\"><h3>{}</h3><table class=\"table\"><thead><tr><th>Source</th><th>Events</th><th>Records scanned</th></tr></thead><tbody>", crate::i18n::heading_in(lang, "EVTX Sources")));
+        for (src,c) in &rep.by_source {
+            let scanned = rep.scanned_by_source.iter().find(|(x, _)| x == src).map(|(_, n)| n.to_string()).unwrap_or_default();
+            s.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td></tr>", html_escape(src), c, scanned));
+        }
+        s.push_str("</tbody></table></div>");
+    }
+    s.push_str("<div class=\"card\"><h3>Top Event IDs</h3><table class=\"table\"><thead><tr><th>Event ID</th><th>Count</th><th>Err/Warn</th><th>First Seen</th><th>Last Seen</th></tr></thead><tbody>");
+    for (id,c) in &rep.by_event_id { s.push_str(&format!("<tr><td>{}</td><td>{}</td>{}</tr>", id, c, stat_cells(crate::find_stat(&rep.event_id_stats, &id.to_string()), tz, tfmt))); }
     s.push_str("</tbody></table></div>");
     s.push_str("</div>");
     if !rep.perf_metrics.is_empty() {
-        s.push_str("<div class=\"section\"><h3>Performance Metrics</h3><table class=\"table\"><thead><tr><th>Phase</th><th>Avg (ms)</th><th>Max (ms)</th><th>Count</th></tr></thead><tbody>");
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><table class=\"table\"><thead><tr><th>Phase</th><th>Avg (ms)</th><th>Max (ms)</th><th>Count</th></tr></thead><tbody>", crate::i18n::heading_in(lang, "Performance Metrics")));
         for (name, avg, max, count) in &rep.perf_metrics { s.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>", html_escape(name), avg, max, count)); }
         s.push_str("</tbody></table></div>");
     }
     if !rep.degradation_signals.is_empty() {
-        s.push_str("<div class=\"section\"><h3>Degradation Signals</h3><div class=\"card\">");
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><div class=\"card\">", crate::i18n::heading_in(lang, "Degradation Signals")));
         for (n,w) in &rep.degradation_signals { s.push_str(&format!("<span class=\"pill\">{} · weight {}</span>", html_escape(n), w)); }
         s.push_str("</div></div>");
     }
     if !rep.matched_terms.is_empty() {
-        s.push_str("<div class=\"section\"><h3>Matched Keywords</h3><div class=\"card\">");
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><div class=\"card\">", crate::i18n::heading_in(lang, "Matched Keywords")));
         for (t,c) in &rep.matched_terms { s.push_str(&format!("<span class=\"pill\">{} · {}</span>", html_escape(t), c)); }
         s.push_str("</div></div>");
     }
-    s.push_str("<div class=\"section\"><h3>Recent Samples</h3><div class=\"card\" style=\"margin-bottom:8px;display:flex;gap:8px;flex-wrap:wrap\"><label class=\"sub\">Severity <select id=\"fSev\"><option value=\"\">Any</option><option>Critical</option><option>Error</option><option>Warning</option><option>Information</option></select></label><label class=\"sub\">Provider <input id=\"fProv\" type=\"text\" placeholder=\"contains\"/></label><label class=\"sub\">Channel <input id=\"fChan\" type=\"text\" placeholder=\"contains\"/></label><button class=\"pill\" id=\"btnCsv\">Download CSV</button><button class=\"pill\" id=\"btnJson\">Download JSON</button></div><table id=\"samplesTable\" class=\"table\"><thead><tr><th onclick=\"sortSamples(0)\">Time</th><th onclick=\"sortSamples(1)\">Channel</th><th onclick=\"sortSamples(2)\">Provider</th><th onclick=\"sortSamples(3)\">Device</th><th onclick=\"sortSamples(4)\">Event ID</th><th onclick=\"sortSamples(5)\">Cause</th><th>Data</th><th onclick=\"sortSamples(7)\">Message</th><th>Actions</th></tr></thead><tbody>");
-    for e in &rep.samples {
+    s.push_str("<div class=\"section\"><h3>Recent Samples</h3><div class=\"card\" style=\"margin-bottom:8px;display:flex;gap:8px;flex-wrap:wrap\"><label class=\"sub\">Severity <select id=\"fSev\"><option value=\"\">Any</option><option>Critical</option><option>Error</option><option>Warning</option><option>Information</option></select></label><label class=\"sub\">Provider <input id=\"fProv\" type=\"text\" placeholder=\"contains\"/></label><label class=\"sub\">Channel <input id=\"fChan\" type=\"text\" placeholder=\"contains\"/></label><label class=\"sub\">Message <input id=\"fText\" type=\"text\" placeholder=\"contains\"/></label><button class=\"pill\" id=\"btnCsv\">Download CSV</button><button class=\"pill\" id=\"btnJson\">Download JSON</button><button class=\"pill\" id=\"btnReport\">Download report JSON</button></div><table id=\"samplesTable\" class=\"table\"><thead><tr><th onclick=\"sortSamples(0)\">Time</th><th onclick=\"sortSamples(1)\">Channel</th><th onclick=\"sortSamples(2)\">Provider</th><th onclick=\"sortSamples(3)\">Device</th><th onclick=\"sortSamples(4)\">Event ID</th><th onclick=\"sortSamples(5)\">Cause</th><th>Data</th><th onclick=\"sortSamples(7)\">Message</th><th>Actions</th></tr></thead><tbody>");
+    let multi = crate::multi_computer(rep);
+    for (i, e) in rep.samples.iter().enumerate() {
         let ts = match (tz, tfmt) { (TimeZone::Local, Some(f)) => e.time.with_timezone(&chrono::Local).format(f).to_string(), (TimeZone::Utc, Some(f)) => e.time.format(f).to_string(), (TimeZone::Local, None) => e.time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string(), (TimeZone::Utc, None) => e.time.format("%Y-%m-%d %H:%M").to_string() };
         let msg = &e.content;
         let truncated = truncate_chars(msg, 240);
@@ -125,15 +154,16 @@ pub fn render_html(rep: &ReportSummary, theme: crate::Theme, use_emoji: bool, tz
             for (k,v) in sel.into_iter().take(3) { data_cell.push_str(&format!("<span class=\"pill\">{} · {}</span> ", html_escape(&k), html_escape(&v))); }
         }
         let sev = match e.level { 1=>"Critical", 2=>"Error", 3=>"Warning", 4=>"Information", _=>"Other" };
+        let chan_cell = if multi && !e.computer.is_empty() { format!("{}<div class=\"sub\">{}</div>", html_escape(&e.channel), html_escape(&e.computer)) } else { html_escape(&e.channel) };
         if msg.chars().count() > 240 {
-            s.push_str(&format!("<tr data-sev=\"{}\" data-prov=\"{}\" data-chan=\"{}\"><td class=\"sub\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><span class=\"ellipsis\">{}</span><details><summary>Show full</summary><div class=\"code\">{}</div></details><span class=\"full-msg\" style=\"display:none\">{}</span></td><td><button class=\"pill\" onclick=\"copyRowMessage(this)\">Copy</button> <button class=\"pill\" onclick=\"copyWevtutil(this)\">Copy EV Query</button></td></tr>", html_escape(sev), html_escape(&e.provider), html_escape(&e.channel), ts, html_escape(&e.channel), html_escape(&e.provider), dev_disp, e.event_id, html_escape(&cause_from(e)), data_cell, html_escape(&truncated), html_escape(msg), html_escape(msg)));
+            s.push_str(&format!("<tr data-i=\"{}\" data-sev=\"{}\" data-prov=\"{}\" data-chan=\"{}\"><td class=\"sub\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><span class=\"ellipsis\">{}</span><details><summary>Show full</summary><div class=\"code\">{}</div></details><span class=\"full-msg\" style=\"display:none\">{}</span></td><td><button class=\"pill\" onclick=\"copyRowMessage(this)\">Copy</button> <button class=\"pill\" onclick=\"copyWevtutil(this)\">Copy EV Query</button></td></tr>", i, html_escape(sev), html_escape(&e.provider), html_escape(&e.channel), ts, chan_cell, html_escape(&e.provider), dev_disp, e.event_id, html_escape(&cause_from(e)), data_cell, html_escape(&truncated), html_escape(msg), html_escape(msg)));
         } else {
-            s.push_str(&format!("<tr data-sev=\"{}\" data-prov=\"{}\" data-chan=\"{}\"><td class=\"sub\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><button class=\"pill\" onclick=\"copyRowMessage(this)\">Copy</button> <button class=\"pill\" onclick=\"copyWevtutil(this)\">Copy EV Query</button><span class=\"full-msg\" style=\"display:none\">{}</span></td></tr>", html_escape(sev), html_escape(&e.provider), html_escape(&e.channel), ts, html_escape(&e.channel), html_escape(&e.provider), dev_disp, e.event_id, html_escape(&cause_from(e)), data_cell, html_escape(msg), html_escape(msg)));
+            s.push_str(&format!("<tr data-i=\"{}\" data-sev=\"{}\" data-prov=\"{}\" data-chan=\"{}\"><td class=\"sub\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><button class=\"pill\" onclick=\"copyRowMessage(this)\">Copy</button> <button class=\"pill\" onclick=\"copyWevtutil(this)\">Copy EV Query</button><span class=\"full-msg\" style=\"display:none\">{}</span></td></tr>", i, html_escape(sev), html_escape(&e.provider), html_escape(&e.channel), ts, chan_cell, html_escape(&e.provider), dev_disp, e.event_id, html_escape(&cause_from(e)), data_cell, html_escape(msg), html_escape(msg)));
         }
     }
     s.push_str("</tbody></table></div>");
     if !rep.recommendations.is_empty() {
-        s.push_str("<div class=\"section\"><h3>Recommendations</h3><div class=\"card\">");
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><div class=\"card\">", crate::i18n::heading_in(lang, "Recommendations")));
         for r in &rep.recommendations { s.push_str(&format!("<div class=\"pill\">{}</div>", html_escape(r))); }
         s.push_str("</div></div>");
     }
@@ -144,14 +174,14 @@ pub fn render_html(rep: &ReportSummary, theme: crate::Theme, use_emoji: bool, tz
     s.push_str("<a href=\"https://learn.microsoft.com/en-us/windows/win32/wmisdk/wmi-start-page\" class=\"pill\">WMI</a> ");
     s.push_str("</div></div>");
     if !rep.recommendations.is_empty() {
-        s.push_str("<div class=\"section\"><h3>Checklist</h3><div class=\"card\">");
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><div class=\"card\">", crate::i18n::heading_in(lang, "Checklist")));
         for r in &rep.recommendations { s.push_str(&format!("<div><input type=\"checkbox\"/> {}</div>", html_escape(r))); }
         s.push_str("</div></div>");
     }
     if !rep.timeline.is_empty() {
         let max_e = rep.timeline.iter().map(|(_,e,_)| *e).max().unwrap_or(1);
         let max_w = rep.timeline.iter().map(|(_,_,w)| *w).max().unwrap_or(1);
-        s.push_str("<div class=\"section\"><h3>Timeline</h3><div class=\"card\">");
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><div class=\"card\">", crate::i18n::heading_in(lang, "Timeline")));
         for (t,e,w) in &rep.timeline {
             let ew = if max_e == 0 { 0.0 } else { (*e as f64 / max_e as f64) * 100.0 };
             let ww = if max_w == 0 { 0.0 } else { (*w as f64 / max_w as f64) * 100.0 };
@@ -159,15 +189,103 @@ pub fn render_html(rep: &ReportSummary, theme: crate::Theme, use_emoji: bool, tz
         }
         s.push_str("</div></div>");
     }
+    if !rep.crashing_apps.is_empty() {
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><table class=\"table\"><thead><tr><th>Application</th><th>Crashes</th><th>Hangs</th><th>Faulting Module</th><th>Exception</th><th>First Seen</th><th>Last Seen</th></tr></thead><tbody>", crate::i18n::heading_in(lang, "Top Crashing Applications")));
+        for a in &rep.crashing_apps { s.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>", html_escape(&a.app), a.crashes, a.hangs, html_escape(a.top_module.as_deref().unwrap_or("")), html_escape(a.top_exception.as_deref().unwrap_or("")), html_escape(&crate::format_ts(a.first_seen, tz, tfmt)), html_escape(&crate::format_ts(a.last_seen, tz, tfmt)))); }
+        s.push_str("</tbody></table></div>");
+    }
+    if !rep.defender.is_empty() {
+        s.push_str("<div class=\"section\"><h3>Microsoft Defender</h3><table class=\"table\"><thead><tr><th>Time</th><th>Event</th><th>Threat</th><th>Path</th><th>Action</th></tr></thead><tbody>");
+        for d in &rep.defender { s.push_str(&format!("<tr><td>{}</td><td>{} ({})</td><td>{}</td><td>{}</td><td>{}</td></tr>", html_escape(&crate::format_ts(d.time, tz, tfmt)), html_escape(&d.kind), d.event_id, html_escape(d.threat.as_deref().unwrap_or("")), html_escape(d.path.as_deref().unwrap_or("")), html_escape(d.action.as_deref().unwrap_or("")))); }
+        s.push_str("</tbody></table></div>");
+    }
+    if let Some(t) = rep.group_by.as_ref() {
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><table class=\"table\"><thead><tr>", crate::i18n::heading_in(lang, "Grouped Counts")));
+        for k in &t.keys { s.push_str(&format!("<th>{}</th>", html_escape(k))); }
+        s.push_str("<th>Count</th></tr></thead><tbody>");
+        for (cells, c) in &t.rows { s.push_str(&format!("<tr>{}<td>{}</td></tr>", cells.iter().map(|v| format!("<td>{}</td>", html_escape(v))).collect::<String>(), c)); }
+        s.push_str("</tbody></table></div>");
+    }
+    if !rep.storms.is_empty() {
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><table class=\"table\"><thead><tr><th>Provider</th><th>Event ID</th><th>Events</th><th>Peak / min</th><th>From</th><th>To</th></tr></thead><tbody>", crate::i18n::heading_in(lang, "Event Storms")));
+        for st in &rep.storms { s.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>", html_escape(&st.provider), st.event_id, st.total, st.peak_per_minute, html_escape(&crate::format_ts(st.start, tz, tfmt)), html_escape(&crate::format_ts(st.end, tz, tfmt)))); }
+        s.push_str("</tbody></table></div>");
+    }
+    if !rep.services_down.is_empty() {
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><table class=\"table\"><thead><tr><th>Service</th><th>State</th><th>Exit Code</th><th>Last SCM Event</th></tr></thead><tbody>", crate::i18n::heading_in(lang, "Services Down Now")));
+        for sv in &rep.services_down {
+            let code = sv.exit_code.map(|c| c.to_string()).unwrap_or_default();
+            s.push_str(&format!("<tr><td>{} <span class=\"sub\">({})</span></td><td><span class=\"chip {}\">{}</span></td><td>{}</td><td>{}</td></tr>", html_escape(&sv.display_name), html_escape(&sv.name), if sv.failed() { "sev-high" } else { "sev-medium" }, html_escape(&sv.state), code, html_escape(sv.last_event.as_deref().unwrap_or(""))));
+        }
+        s.push_str("</tbody></table></div>");
+    }
+    if !rep.channel_health.is_empty() {
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><table class=\"table\"><thead><tr><th>Channel</th><th>Size</th><th>Retention</th><th>Records</th><th>Oldest Record</th><th>History</th></tr></thead><tbody>", crate::i18n::heading_in(lang, "Channel Health")));
+        for c in &rep.channel_health {
+            let mb = |b: Option<u64>| b.map(|b| format!("{:.1} MB", b as f64 / 1048576.0)).unwrap_or_else(|| "?".to_string());
+            let chip = if c.full { " <span class=\"chip sev-high\">full</span>" } else if c.near_rollover { " <span class=\"chip sev-medium\">near rollover</span>" } else { "" };
+            s.push_str(&format!("<tr><td>{}</td><td>{} / {}{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>", html_escape(&c.name), mb(c.file_size_bytes), mb(c.max_size_bytes), chip, html_escape(&c.retention), c.records.map(|r| r.to_string()).unwrap_or_default(), c.oldest_record.map(|t| html_escape(&crate::format_ts(t, tz, tfmt))).unwrap_or_default(), c.history_hours.map(crate::channel_health::format_span).unwrap_or_default()));
+        }
+        s.push_str("</tbody></table></div>");
+    }
+    if !rep.log_integrity.is_empty() {
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><ul>", crate::i18n::heading_in(lang, "Log Integrity")));
+        for c in &rep.log_integrity.clears { s.push_str(&format!("<li><span class=\"chip sev-high\">cleared</span> {} — {}</li>", html_escape(&crate::format_ts(c.time, tz, tfmt)), html_escape(&crate::integrity::clear_line(c)))); }
+        for r in &rep.log_integrity.resets { s.push_str(&format!("<li><span class=\"chip sev-medium\">reset</span> {} — {}</li>", html_escape(&crate::format_ts(r.at, tz, tfmt)), html_escape(&crate::integrity::reset_line(r)))); }
+        s.push_str("</ul></div>");
+    }
+    if let Some(pol) = rep.policy.as_ref() {
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><div class=\"card\">", crate::i18n::heading_in(lang, "Group Policy")));
+        s.push_str(&format!("<div>Last successful refresh: <b>{}</b></div>", pol.last_success.map(|t| crate::format_ts(t, tz, tfmt)).unwrap_or_else(|| "unknown".to_string())));
+        if let Some(t) = pol.last_failure { s.push_str(&format!("<div>Last failed refresh: <b>{}</b></div>", crate::format_ts(t, tz, tfmt))); }
+        for x in &pol.failed_extensions { s.push_str(&format!("<span class=\"chip sev-medium\">{} · error {}</span> ", html_escape(if x.name.is_empty() { &x.guid } else { &x.name }), x.error)); }
+        if !pol.gpos.is_empty() { s.push_str("<div class=\"sub\">Applied GPOs</div>"); for g in &pol.gpos { s.push_str(&format!("<span class=\"pill\">{}</span>", html_escape(g))); } }
+        s.push_str("</div></div>");
+    }
+    if let Some(dev) = rep.dev_env.as_ref() {
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><div class=\"card\">", crate::i18n::heading_in(lang, "WSL / Docker")));
+        s.push_str(&format!("<div>WSL: <b>{}</b></div>", html_escape(dev.wsl_status.as_deref().unwrap_or("not installed or not responding"))));
+        for (p, n) in &dev.vhdx { s.push_str(&format!("<span class=\"chip {}\">{} · {:.1} GiB</span> ", if *n >= crate::devenv::VHDX_PRESSURE_BYTES { "sev-medium" } else { "sev-low" }, html_escape(p), *n as f64 / 1073741824.0)); }
+        let lines: Vec<&str> = dev.wsl_findings.iter().chain(dev.docker_findings.iter()).map(|l| l.as_str()).collect();
+        if !lines.is_empty() { s.push_str(&format!("<div class=\"code\">{}</div>", html_escape(&lines.join("\n")))); }
+        s.push_str("</div></div>");
+    }
+    if !rep.sync.is_empty() {
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><table class=\"table\"><thead><tr><th>Account</th><th>Folder</th><th>Known Folder Move</th><th>Errors</th></tr></thead><tbody>", crate::i18n::heading_in(lang, "OneDrive")));
+        for a in &rep.sync {
+            s.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>", html_escape(&a.name), html_escape(a.user_folder.as_deref().unwrap_or("")), html_escape(&a.kfm.join(", ")), a.errors.iter().map(|e| format!("<span class=\"chip sev-medium\">{}</span>", html_escape(e))).collect::<Vec<_>>().join(" ")));
+        }
+        s.push_str("</tbody></table></div>");
+    }
+    if let Some(b) = rep.battery.as_ref() {
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3><div class=\"card\">", crate::i18n::heading_in(lang, "Battery")));
+        for l in crate::battery::lines(b) { s.push_str(&format!("<div>{}</div>", html_escape(&l))); }
+        s.push_str("</div></div>");
+    }
+    if !rep.heatmap.is_empty() {
+        let max = rep.heatmap.iter().flatten().copied().max().unwrap_or(0).max(1);
+        s.push_str("<div class=\"section\"><h3>Error Heatmap (hour × weekday)</h3><div class=\"card\"><table style=\"border-collapse:separate;border-spacing:2px;font-size:11px\"><thead><tr><th></th>");
+        for h in 0..24 { s.push_str(&format!("<th class=\"sub\" style=\"font-weight:400\">{}</th>", h)); }
+        s.push_str("</tr></thead><tbody>");
+        for (d, row) in rep.heatmap.iter().enumerate() {
+            s.push_str(&format!("<tr><td class=\"sub\">{}</td>", ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"][d % 7]));
+            for (h, v) in row.iter().enumerate() {
+                let op = if *v == 0 { 0.0 } else { 0.15 + 0.85 * (*v as f64 / max as f64) };
+                s.push_str(&format!("<td title=\"{:02}:00 · {} errors\" style=\"width:22px;height:18px;border-radius:3px;border:1px solid var(--border);background:rgba(239,68,68,{:.2})\"></td>", h, v, op));
+            }
+            s.push_str("</tr>");
+        }
+        s.push_str("</tbody></table></div></div>");
+    }
     if !rep.file_matched_terms.is_empty() || !rep.file_samples.is_empty() {
-        s.push_str("<div class=\"section\"><h3>Files</h3>");
+        s.push_str(&format!("<div class=\"section\"><h3>{}</h3>", crate::i18n::heading_in(lang, "Files")));
         if !rep.file_matched_terms.is_empty() {
-            s.push_str("<div class=\"card\"><h3>Matched Keywords</h3>");
+            s.push_str(&format!("<div class=\"card\"><h3>{}</h3>", crate::i18n::heading_in(lang, "Matched Keywords")));
             for (t,c) in &rep.file_matched_terms { s.push_str(&format!("<span class=\"pill\">{} · {} files</span>", html_escape(t), c)); }
             s.push_str("</div>");
         }
         if !rep.file_samples.is_empty() {
-            s.push_str("<div class=\"card\"><h3>Examples</h3><table class=\"table\"><thead><tr><th>Path</th><th>Pattern</th><th>Line</th><th>Content</th></tr></thead><tbody>");
+            s.push_str(&format!("<div class=\"card\"><h3>{}</h3><table class=\"table\"><thead><tr><th>Path</th><th>Pattern</th><th>Line</th><th>Content</th></tr></thead><tbody>", crate::i18n::heading_in(lang, "Examples")));
             for s2 in &rep.file_samples {
                 let msg = s2.line.replace('\n', " ");
                 let truncated = truncate_chars(&msg, 160);
@@ -181,12 +299,18 @@ pub fn render_html(rep: &ReportSummary, theme: crate::Theme, use_emoji: bool, tz
         }
         s.push_str("</div>");
     }
-    s.push_str("<div class=\"footer\">Generated by WinDoctor</div></div><script>(function(){var init=");
+    let errs = if rep.collection_errors.is_empty() { String::new() } else { format!(" · <span style=\"color:var(--warn)\">Collection errors — data may be incomplete: {}</span>", html_escape(&rep.collection_errors.iter().map(crate::collection_error_line).collect::<Vec<_>>().join("; "))) };
+    s.push_str(&format!("<div class=\"footer\">Generated by WinDoctor{}</div></div><script type=\"application/json\" id=\"wdData\">{}</script><script>(function(){{var init=", errs, data_blob(rep)));
     s.push_str(match theme { crate::Theme::Light => "'light'", _ => "'dark'" });
-    s.push_str("; window.__wdTheme=init; toggleTheme();\n  const fSev=document.getElementById('fSev');\n  const fProv=document.getElementById('fProv');\n  const fChan=document.getElementById('fChan');\n  const tbl=document.getElementById('samplesTable');\n  function matches(txt, q){return !q || (txt.toLowerCase().indexOf(q.toLowerCase())>=0);}\n  function filter(){const qSev=fSev.value;const qProv=fProv.value;const qChan=fChan.value;const rows=tbl.tBodies[0].rows;for(let i=0;i<rows.length;i++){const r=rows[i];const sev=r.getAttribute('data-sev')||'';const prov=r.getAttribute('data-prov')||'';const chan=r.getAttribute('data-chan')||'';const ok=(!qSev||sev===qSev)&&matches(prov,qProv)&&matches(chan,qChan);r.style.display=ok?'':'none';}}\n  fSev.onchange=filter; fProv.oninput=filter; fChan.oninput=filter;\n  window.sortSamples=function(idx){const tbody=tbl.tBodies[0];const arr=[...tbody.rows];const asc=tbl.getAttribute('data-sort')!=='asc';arr.sort((a,b)=>{const ta=a.cells[idx].innerText.trim();const tb=b.cells[idx].innerText.trim();if(!isNaN(Number(ta)) && !isNaN(Number(tb))){return asc?Number(ta)-Number(tb):Number(tb)-Number(ta);}return asc?ta.localeCompare(tb):tb.localeCompare(ta);});tbody.innerHTML='';arr.forEach(r=>tbody.appendChild(r));tbl.setAttribute('data-sort',asc?'asc':'desc');};\n  function visibleRows(){return [...tbl.tBodies[0].rows].filter(r=>r.style.display!=='none');}\n  document.getElementById('btnCsv').onclick=function(){const rows=visibleRows();let csv='time,channel,provider,device,event_id,cause,message\n';rows.forEach(r=>{const cells=[...r.cells];csv+=[0,1,2,3,4,5,7].map(i=>cells[i].innerText.replace(/\n/g,' ')).join(',')+'\n';});const blob=new Blob([csv],{type:'text/csv'});const a=document.createElement('a');a.href=URL.createObjectURL(blob);a.download='samples.csv';a.click();};\n  document.getElementById('btnJson').onclick=function(){const rows=visibleRows();const out=rows.map(r=>{const c=[...r.cells];return {time:c[0].innerText, channel:c[1].innerText, provider:c[2].innerText, device:c[3].innerText, event_id:c[4].innerText, cause:c[5].innerText, message:c[7].innerText};});const blob=new Blob([JSON.stringify(out,null,2)],{type:'application/json'});const a=document.createElement('a');a.href=URL.createObjectURL(blob);a.download='samples.json';a.click();};\n})();</script></body></html>");
+    s.push_str("; window.__wdTheme=init; toggleTheme();\n  const fSev=document.getElementById('fSev');\n  const fProv=document.getElementById('fProv');\n  const fChan=document.getElementById('fChan');\n  const fText=document.getElementById('fText');\n  const tbl=document.getElementById('samplesTable');\n  const blob=document.getElementById('wdData').textContent;\n  const data=JSON.parse(blob);\n  const sevName=['Other','Critical','Error','Warning','Information'];\n  function ev(r){return data.samples[Number(r.getAttribute('data-i'))]||{};}\n  function matches(txt, q){return !q || ((txt||'').toLowerCase().indexOf(q.toLowerCase())>=0);}\n  function filter(){const qSev=fSev.value;const qProv=fProv.value;const qChan=fChan.value;const qText=fText.value;const rows=tbl.tBodies[0].rows;for(let i=0;i<rows.length;i++){const r=rows[i];const e=ev(r);const ok=(!qSev||sevName[e.level]===qSev)&&matches(e.provider,qProv)&&matches(e.channel,qChan)&&matches(e.content,qText);r.style.display=ok?'':'none';}}\n  fSev.onchange=filter; fProv.oninput=filter; fChan.oninput=filter; fText.oninput=filter;\n  window.sortSamples=function(idx){const tbody=tbl.tBodies[0];const arr=[...tbody.rows];const asc=tbl.getAttribute('data-sort')!=='asc';arr.sort((a,b)=>{const ta=a.cells[idx].innerText.trim();const tb=b.cells[idx].innerText.trim();if(!isNaN(Number(ta)) && !isNaN(Number(tb))){return asc?Number(ta)-Number(tb):Number(tb)-Number(ta);}return asc?ta.localeCompare(tb):tb.localeCompare(ta);});tbody.innerHTML='';arr.forEach(r=>tbody.appendChild(r));tbl.setAttribute('data-sort',asc?'asc':'desc');};\n  function visibleRows(){return [...tbl.tBodies[0].rows].filter(r=>r.style.display!=='none');}\n  function save(text,type,name){const a=document.createElement('a');a.href=URL.createObjectURL(new Blob([text],{type:type}));a.download=name;a.click();}\n  function csvCell(v){const t=String(v==null?'':v);return /[\",\\n\\r]/.test(t)?'\"'+t.replace(/\"/g,'\"\"')+'\"':t;}\n  document.getElementById('btnCsv').onclick=function(){let csv='time,computer,channel,provider,event_id,severity,record_id,message\\n';visibleRows().map(ev).forEach(e=>{csv+=[e.time,e.computer,e.channel,e.provider,e.event_id,sevName[e.level]||'',e.record_id,e.content].map(csvCell).join(',')+'\\n';});save(csv,'text/csv','samples.csv');};\n  document.getElementById('btnJson').onclick=function(){save(JSON.stringify(visibleRows().map(ev),null,2),'application/json','samples.json');};\n  document.getElementById('btnReport').onclick=function(){save(blob,'application/json','report.json');};\n})();</script></body></html>");
     s
 }
 
+// The whole report as JSON for the page's filters and downloads; `<` is escaped so no string can close the script element.
+fn data_blob(rep: &ReportSummary) -> String {
+    serde_json::to_string(rep).unwrap_or_else(|_| "{}".to_string()).replace('<', "\\u003c")
+}
+
 fn selected_data_from(e: &EventItem) -> Vec<(String,String)> {
     let pairs = crate::event_xml::event_data_pairs_or_fallback(&e.content);
     let keys = [
@@ -205,6 +329,10 @@ fn html_escape(s: &str) -> String {
     s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
 }
 
+fn stat_cells(st: Option<&crate::AggStat>, tz: TimeZone, tfmt: Option<&str>) -> String {
+    match st { Some(x) => format!("<td>{}/{}</td><td>{}</td><td>{}</td>", x.errors, x.warnings, crate::format_ts(x.first_seen, tz, tfmt), crate::format_ts(x.last_seen, tz, tfmt)), None => "<td></td><td></td><td></td>".to_string() }
+}
+
 fn truncate_chars(s: &str, n: usize) -> String { s.chars().take(n).collect() }
 
 fn cause_from(e: &EventItem) -> String {
@@ -220,3 +348,24 @@ fn device_from(e: &EventItem) -> Option<String> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_json_is_embedded_and_cannot_close_the_script() {
+        let mut rep = ReportSummary { total: 1, errors: 1, ..crate::test_summary() };
+        let long = format!("{}</script><b>", "x".repeat(300));
+        rep.samples = vec![EventItem { provider: "Disk".to_string(), channel: "System".to_string(), level: 2, content: long.clone(), ..Default::default() }];
+        let html = render_html(&rep, crate::Theme::Dark, false, TimeZone::Utc, None, Lang::En);
+        let start = html.find("<script type=\"application/json\" id=\"wdData\">").unwrap() + 44;
+        let blob = &html[start..start + html[start..].find("</script>").unwrap()];
+        let back: ReportSummary = serde_json::from_str(blob).unwrap();
+        assert_eq!(back.samples[0].content, long);
+        assert!(html.contains("<tr data-i=\"0\" data-sev=\"Error\""));
+        rep.recommendations = vec!["Run chkdsk".to_string()];
+        let html = render_html(&rep, crate::Theme::Dark, false, TimeZone::Utc, None, Lang::De);
+        assert!(html.starts_with("<html lang=\"de\"><head>") && html.contains("<h3>Empfehlungen</h3>"));
+    }
+}
@@ -0,0 +1,116 @@
+use std::time::Duration as StdDuration;
+
+const USER_AGENT: &str = concat!("WinDoctor/", env!("CARGO_PKG_VERSION"));
+const TIMEOUT_MS: i32 = 15_000;
+
+pub struct Response { pub status: u16, pub body: String }
+
+#[derive(Debug, PartialEq)]
+pub struct Url { pub https: bool, pub host: String, pub port: u16, pub path: String }
+
+pub fn parse_url(url: &str) -> Result<Url, String> {
+    let (https, rest) = if let Some(r) = url.strip_prefix("https://") { (true, r) } else if let Some(r) = url.strip_prefix("http://") { (false, r) } else { return Err(format!("'{}' is not an http:// or https:// URL", url)) };
+    let (authority, path) = match rest.find('/') { Some(i) => (&rest[..i], &rest[i..]), None => (rest, "/") };
+    // `[v6]:port` keeps its colons inside the brackets.
+    let split = match authority.strip_prefix('[') { Some(v6) => v6.split_once(']').map(|(h, p)| (h, p.strip_prefix(':'))), None => Some(match authority.rsplit_once(':') { Some((h, p)) => (h, Some(p)), None => (authority, None) }) };
+    let Some((host, port)) = split else { return Err(format!("bad host in '{}'", url)) };
+    let port = match port { Some(p) => p.parse::<u16>().map_err(|_| format!("bad port in '{}'", url))?, None => if https { 443 } else { 80 } };
+    if host.is_empty() { return Err(format!("no host in '{}'", url)); }
+    Ok(Url { https, host: host.to_string(), port, path: path.to_string() })
+}
+
+// Adds `path` when the URL names only the server.
+pub fn default_path(url: &str, path: &str) -> Result<String, String> {
+    Ok(if parse_url(url)?.path == "/" { format!("{}{}", url.trim_end_matches('/'), path) } else { url.to_string() })
+}
+
+// 408/429 and 5xx gateway/availability answers are worth retrying; anything else is the request's fault.
+pub fn is_transient(status: u16) -> bool { matches!(status, 408 | 429 | 500 | 502 | 503 | 504) }
+
+// Retries connection failures and transient statuses with exponential backoff (1 s, 2 s, 4 s, ...).
+pub fn post_with_retry(url: &str, headers: &[(&str, String)], body: &[u8], attempts: u32) -> Result<Response, String> {
+    let mut last = String::new();
+    for attempt in 0..attempts.max(1) {
+        if attempt > 0 { std::thread::sleep(StdDuration::from_secs(1 << (attempt - 1).min(5))); }
+        match post(url, headers, body) {
+            Ok(r) if is_transient(r.status) => { last = format!("HTTP {}: {}", r.status, r.body.trim()); }
+            Ok(r) => return Ok(r),
+            Err(e) => { last = e; }
+        }
+        log::debug!("POST {} attempt {} failed: {}", url, attempt + 1, last);
+    }
+    Err(last)
+}
+
+// WinHTTP brings TLS, the system proxy configuration and certificate store for free.
+#[cfg(target_os = "windows")]
+pub fn post(url: &str, headers: &[(&str, String)], body: &[u8]) -> Result<Response, String> {
+    use std::ptr;
+    use windows_sys::Win32::Foundation::GetLastError;
+    use windows_sys::Win32::Networking::WinHttp::*;
+    struct H(*mut core::ffi::c_void);
+    impl Drop for H { fn drop(&mut self) { if !self.0.is_null() { unsafe { WinHttpCloseHandle(self.0); } } } }
+    let w = |s: &str| s.encode_utf16().chain(Some(0)).collect::<Vec<u16>>();
+    let fail = |op: &str| format!("{} failed: error {}", op, unsafe { GetLastError() });
+    let u = parse_url(url)?;
+    unsafe {
+        let session = H(WinHttpOpen(w(USER_AGENT).as_ptr(), WINHTTP_ACCESS_TYPE_AUTOMATIC_PROXY, ptr::null(), ptr::null(), 0));
+        if session.0.is_null() { return Err(fail("WinHttpOpen")); }
+        WinHttpSetTimeouts(session.0, TIMEOUT_MS, TIMEOUT_MS, TIMEOUT_MS, TIMEOUT_MS);
+        let connect = H(WinHttpConnect(session.0, w(&u.host).as_ptr(), u.port, 0));
+        if connect.0.is_null() { return Err(fail("WinHttpConnect")); }
+        let request = H(WinHttpOpenRequest(connect.0, w("POST").as_ptr(), w(&u.path).as_ptr(), ptr::null(), ptr::null(), ptr::null(), if u.https { WINHTTP_FLAG_SECURE } else { 0 }));
+        if request.0.is_null() { return Err(fail("WinHttpOpenRequest")); }
+        let extra = w(&headers.iter().map(|(k, v)| format!("{}: {}\r\n", k, v)).collect::<String>());
+        // Header length u32::MAX (-1L) means "NUL-terminated".
+        if WinHttpSendRequest(request.0, extra.as_ptr(), u32::MAX, body.as_ptr() as *const core::ffi::c_void, body.len() as u32, body.len() as u32, 0) == 0 { return Err(fail("WinHttpSendRequest")); }
+        if WinHttpReceiveResponse(request.0, ptr::null_mut()) == 0 { return Err(fail("WinHttpReceiveResponse")); }
+        let mut status = 0u32;
+        let mut len = std::mem::size_of::<u32>() as u32;
+        WinHttpQueryHeaders(request.0, WINHTTP_QUERY_STATUS_CODE | WINHTTP_QUERY_FLAG_NUMBER, ptr::null(), &mut status as *mut u32 as *mut core::ffi::c_void, &mut len, ptr::null_mut());
+        let mut out = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let mut n = 0u32;
+            if WinHttpReadData(request.0, buf.as_mut_ptr() as *mut core::ffi::c_void, buf.len() as u32, &mut n) == 0 || n == 0 { break; }
+            out.extend_from_slice(&buf[..n as usize]);
+        }
+        Ok(Response { status: status as u16, body: String::from_utf8_lossy(&out).into_owned() })
+    }
+}
+
+// Plain HTTP/1.1 only; there is no TLS stack outside Windows.
+#[cfg(not(target_os = "windows"))]
+pub fn post(url: &str, headers: &[(&str, String)], body: &[u8]) -> Result<Response, String> {
+    use std::io::{Read, Write};
+    let u = parse_url(url)?;
+    if u.https { return Err("https:// endpoints need Windows (WinHTTP)".to_string()); }
+    let timeout = Some(StdDuration::from_millis(TIMEOUT_MS as u64));
+    let mut s = std::net::TcpStream::connect((u.host.as_str(), u.port)).map_err(|e| format!("{}:{}: {}", u.host, u.port, e))?;
+    let _ = s.set_read_timeout(timeout);
+    let _ = s.set_write_timeout(timeout);
+    let mut req = format!("POST {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nContent-Length: {}\r\nConnection: close\r\n", u.path, u.host, USER_AGENT, body.len());
+    for (k, v) in headers { req.push_str(&format!("{}: {}\r\n", k, v)); }
+    req.push_str("\r\n");
+    s.write_all(req.as_bytes()).and_then(|_| s.write_all(body)).map_err(|e| e.to_string())?;
+    let mut raw = Vec::new();
+    s.read_to_end(&mut raw).map_err(|e| e.to_string())?;
+    let text = String::from_utf8_lossy(&raw);
+    let status = text.split(' ').nth(1).and_then(|c| c.parse::<u16>().ok()).ok_or_else(|| format!("malformed HTTP response from {}", u.host))?;
+    Ok(Response { status, body: text.split_once("\r\n\r\n").map(|(_, b)| b.to_string()).unwrap_or_default() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls_split_into_scheme_host_port_and_path() {
+        assert_eq!(parse_url("https://splunk.local:8088/services/collector/event"), Ok(Url { https: true, host: "splunk.local".to_string(), port: 8088, path: "/services/collector/event".to_string() }));
+        assert_eq!(parse_url("http://[::1]/v1/logs").map(|u| (u.host, u.port)), Ok(("::1".to_string(), 80)));
+        assert!(parse_url("ftp://x").is_err());
+        assert_eq!(default_path("https://splunk:8088/", "/services/collector/event").unwrap(), "https://splunk:8088/services/collector/event");
+        assert_eq!(default_path("http://otel:4318/custom", "/v1/logs").unwrap(), "http://otel:4318/custom");
+        assert!(is_transient(503) && !is_transient(401));
+    }
+}
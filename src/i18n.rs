@@ -0,0 +1,88 @@
+use crate::Lang;
+use std::sync::OnceLock;
+
+static UI_LANG: OnceLock<Lang> = OnceLock::new();
+
+pub fn set_lang(lang: Lang) { let _ = UI_LANG.set(lang); }
+
+// (English label, de, es, fr); the English label is the lookup key.
+const LABELS: &[(&str, &str, &str, &str)] = &[
+    ("Status: No errors or warnings detected.", "Status: Keine Fehler oder Warnungen gefunden.", "Estado: No se detectaron errores ni advertencias.", "État : aucune erreur ni avertissement détecté."),
+    ("Risk:", "Risiko:", "Riesgo:", "Risque :"),
+    ("Likely Root Causes:", "Wahrscheinliche Ursachen:", "Causas raíz probables:", "Causes probables :"),
+    ("Impact Assessment:", "Auswirkungsanalyse:", "Evaluación de impacto:", "Évaluation de l'impact :"),
+    ("Diagnostics:", "Diagnose:", "Diagnóstico:", "Diagnostics :"),
+    ("None", "Keine", "Ninguno", "Aucun"),
+    ("Performance Score:", "Leistungsbewertung:", "Puntuación de rendimiento:", "Score de performance :"),
+    ("Live Performance:", "Live-Leistung:", "Rendimiento en vivo:", "Performances en direct :"),
+    ("SMART: Predicts failure on one or more drives", "SMART: Ausfall eines oder mehrerer Laufwerke vorhergesagt", "SMART: Se predice un fallo en una o más unidades", "SMART : défaillance prévue sur un ou plusieurs disques"),
+    ("Degradation Signals:", "Verschlechterungssignale:", "Señales de degradación:", "Signaux de dégradation :"),
+    ("Recommendations:", "Empfehlungen:", "Recomendaciones:", "Recommandations :"),
+    ("Checklist:", "Checkliste:", "Lista de comprobación:", "Liste de contrôle :"),
+    ("Timeline:", "Zeitverlauf:", "Cronología:", "Chronologie :"),
+    ("Performance Metrics:", "Leistungsmetriken:", "Métricas de rendimiento:", "Métriques de performance :"),
+    ("Events:", "Ereignisse:", "Eventos:", "Événements :"),
+    ("Key Sources:", "Wichtigste Quellen:", "Fuentes principales:", "Sources principales :"),
+    ("EVTX Sources:", "EVTX-Quellen:", "Fuentes EVTX:", "Sources EVTX :"),
+    ("Highest Record IDs:", "Höchste Datensatz-IDs:", "ID de registro más altos:", "ID d'enregistrement les plus élevés :"),
+    ("Services Down Now:", "Derzeit ausgefallene Dienste:", "Servicios detenidos ahora:", "Services arrêtés actuellement :"),
+    ("Channel Health:", "Zustand der Protokolle:", "Estado de los registros:", "État des journaux :"),
+    ("Log Integrity:", "Protokollintegrität:", "Integridad de los registros:", "Intégrité des journaux :"),
+    ("Group Policy:", "Gruppenrichtlinie:", "Directiva de grupo:", "Stratégie de groupe :"),
+    ("WSL / Docker:", "WSL / Docker:", "WSL / Docker:", "WSL / Docker :"),
+    ("OneDrive:", "OneDrive:", "OneDrive:", "OneDrive :"),
+    ("Battery:", "Akku:", "Batería:", "Batterie :"),
+    ("Computers:", "Computer:", "Equipos:", "Ordinateurs :"),
+    ("Users:", "Benutzer:", "Usuarios:", "Utilisateurs :"),
+    ("Key Domains:", "Wichtigste Bereiche:", "Dominios principales:", "Domaines principaux :"),
+    ("Key Devices:", "Wichtigste Geräte:", "Dispositivos principales:", "Périphériques principaux :"),
+    ("Common Event Codes:", "Häufige Ereignis-IDs:", "Códigos de evento comunes:", "Codes d'événement fréquents :"),
+    ("Matched Keywords:", "Gefundene Schlüsselwörter:", "Palabras clave encontradas:", "Mots-clés trouvés :"),
+    ("Recent Activity:", "Letzte Aktivität:", "Actividad reciente:", "Activité récente :"),
+    ("Files:", "Dateien:", "Archivos:", "Fichiers :"),
+    ("Examples:", "Beispiele:", "Ejemplos:", "Exemples :"),
+    ("(Analysis-only mode — samples hidden)", "(Nur-Analyse-Modus — Beispiele ausgeblendet)", "(Modo solo análisis — muestras ocultas)", "(Mode analyse seule — échantillons masqués)"),
+    ("Error Heatmap (hour 0-23):", "Fehler-Heatmap (Stunde 0-23):", "Mapa de calor de errores (hora 0-23):", "Carte thermique des erreurs (heure 0-23) :"),
+    ("Event Storms:", "Ereignisstürme:", "Tormentas de eventos:", "Tempêtes d'événements :"),
+    ("Top Crashing Applications:", "Häufigste abstürzende Anwendungen:", "Aplicaciones que más fallan:", "Applications plantant le plus :"),
+    ("Noise Report:", "Rauschanalyse:", "Informe de ruido:", "Rapport de bruit :"),
+    ("Grouped Counts:", "Gruppierte Anzahlen:", "Recuentos agrupados:", "Comptes groupés :"),
+    ("Comparison (NDJSON):", "Vergleich (NDJSON):", "Comparación (NDJSON):", "Comparaison (NDJSON) :"),
+];
+
+fn pick(row: &(&'static str, &'static str, &'static str, &'static str), lang: Lang) -> &'static str {
+    match lang { Lang::En => row.0, Lang::De => row.1, Lang::Es => row.2, Lang::Fr => row.3 }
+}
+
+pub fn tr_in(lang: Lang, s: &'static str) -> &'static str {
+    LABELS.iter().find(|r| r.0 == s).map(|r| pick(r, lang)).unwrap_or(s)
+}
+
+// Translates a CLI label for the --lang locale; unknown labels pass through unchanged.
+pub fn tr(s: &'static str) -> &'static str { tr_in(UI_LANG.get().copied().unwrap_or(Lang::En), s) }
+
+// HTML section headings are the CLI labels without their colon.
+pub fn heading_in(lang: Lang, s: &str) -> String {
+    LABELS.iter().find(|r| r.0.strip_suffix(':') == Some(s)).map(|r| pick(r, lang).trim_end_matches(':').trim_end().to_string()).unwrap_or_else(|| s.to_string())
+}
+
+pub fn code(lang: Lang) -> &'static str {
+    match lang { Lang::En => "en", Lang::De => "de", Lang::Es => "es", Lang::Fr => "fr" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_translate_and_fall_back() {
+        assert_eq!(tr_in(Lang::De, "Recommendations:"), "Empfehlungen:");
+        assert_eq!(tr_in(Lang::Fr, "Likely Root Causes:"), "Causes probables :");
+        assert_eq!(tr_in(Lang::Es, "Not a label"), "Not a label");
+        assert_eq!(heading_in(Lang::Fr, "Recommendations"), "Recommandations");
+        assert_eq!(heading_in(Lang::De, "Top Providers"), "Top Providers");
+        assert_eq!(tr_in(Lang::En, "Risk:"), "Risk:");
+        // A translation keeps its label's trailing colon (French spaces it), so a row cannot pair a heading with a non-heading.
+        for r in LABELS { assert!([r.1, r.2, r.3].iter().all(|t| !t.is_empty() && t.ends_with(':') == r.0.ends_with(':')), "{}", r.0); }
+    }
+}
@@ -0,0 +1,99 @@
+use std::io::{BufRead, IsTerminal, Write};
+
+pub struct InitAnswers {
+    pub channels: Vec<String>,
+    pub exclude_providers: Vec<String>,
+    pub hours: i64,
+    pub html: String,
+}
+
+impl Default for InitAnswers {
+    fn default() -> Self {
+        InitAnswers { channels: vec!["System".to_string(), "Application".to_string()], exclude_providers: vec![], hours: 24, html: "report.html".to_string() }
+    }
+}
+
+pub fn prompt(label: &str, default: &str) -> String {
+    print!("{} [{}]: ", label, default);
+    let _ = std::io::stdout().flush();
+    let mut line = String::new();
+    if std::io::stdin().lock().read_line(&mut line).is_err() { return default.to_string(); }
+    let v = line.trim();
+    if v.is_empty() { default.to_string() } else { v.to_string() }
+}
+
+fn split_list(s: &str) -> Vec<String> { s.split(',').map(|x| x.trim().to_string()).filter(|x| !x.is_empty()).collect() }
+
+pub fn ask(mut a: InitAnswers) -> InitAnswers {
+    if !std::io::stdin().is_terminal() { return a; }
+    a.channels = split_list(&prompt("Channels to scan (comma-separated)", &a.channels.join(",")));
+    a.exclude_providers = split_list(&prompt("Providers to ignore (comma-separated, empty for none)", &a.exclude_providers.join(",")));
+    a.hours = prompt("Default time window in hours", &a.hours.to_string()).parse().unwrap_or(a.hours);
+    a.html = prompt("HTML report path (empty to skip)", &a.html);
+    a
+}
+
+fn toml_list(v: &[String]) -> String { format!("[{}]", v.iter().map(|s| format!("{:?}", s)).collect::<Vec<_>>().join(", ")) }
+
+pub fn render_config(a: &InitAnswers) -> String {
+    let mut s = String::new();
+    s.push_str("# WinDoctor configuration. Command-line flags override these values.\n");
+    s.push_str("# Generated by `WinDoctor init`; see ADVANCED.md for every option.\n\n");
+    s.push_str("# Event log channels to query (live) or EVTX file stems to read.\n");
+    s.push_str(&format!("channels = {}\n", toml_list(&a.channels)));
+    s.push_str("# Only keep these providers (empty = all).\nproviders = []\n");
+    s.push_str("# Drop these providers.\n");
+    s.push_str(&format!("exclude_providers = {}\n\n", toml_list(&a.exclude_providers)));
+    s.push_str("# Time window: days/hours/minutes are added together; 0 everywhere means\n# \"last N criticals + last N errors\" mode.\n");
+    s.push_str(&format!("days = 0\nhours = {}\nminutes = 0\n", a.hours));
+    s.push_str("# Absolute or relative bounds, e.g. \"2025-11-29 13:00\", \"yesterday 14:00\", \"2h ago\".\n# since = \"\"\n# until = \"\"\n");
+    s.push_str("last_errors = 50\nlast_criticals = 50\n\n");
+    s.push_str("# Severity: 1 critical, 2 error, 3 warning, 4 information.\nmin_level = 1\nmax_level = 3\ninclude_info = false\n");
+    s.push_str("# Stop after this many matching events.\nmax_events = 5000\n\n");
+    s.push_str("# Output: Text or Json; text_format Lines or Table; theme Dark, Light or HighContrast.\n");
+    s.push_str("output = \"Text\"\ntext_format = \"Lines\"\ntheme = \"Dark\"\ntime_zone = \"Local\" # Local|Utc\ntime_format = \"%Y-%m-%d %H:%M\"\n");
+    s.push_str("sample_count = 20\n\n");
+    s.push_str("# Report files. Use \"-\" to write a format to stdout.\n");
+    if a.html.is_empty() { s.push_str("# html = \"report.html\"\n"); } else { s.push_str(&format!("html = {:?}\n", a.html)); }
+    s.push_str("# json_path = \"report.json\"\n# csv_path = \"report.csv\"\n# ndjson_path = \"events.ndjson\"\n# md_fix_path = \"report.md\"\n\n");
+    s.push_str("# CI behaviour, e.g. [\"errors>0\", \"risk>=High\", \"category:Storage\"].\nfail_on = []\n\n");
+    s.push_str("# Regex patterns, hint rules and level overrides live in rules.json (override with --rules).\n");
+    s
+}
+
+pub fn render_rules(patterns: &[&str]) -> String {
+    let rules = serde_json::json!({
+        "event_patterns": patterns,
+        "file_patterns": ["(?i)error", "(?i)exception", "(?i)fail"],
+        "hint_rules": [
+            { "provider": "Service Control Manager", "contains_any": ["failed to start", "terminated unexpectedly"], "category": "Services", "severity": "medium", "message": "Service reported a start/termination failure" },
+            { "provider": "Microsoft-Windows-DNS-Client", "event_id": 1014, "contains_any": ["timed out"], "category": "Network", "severity": "medium", "message": "DNS name resolution failure" }
+        ],
+        "level_overrides": [
+            { "provider": "DistributedCOM", "event_id": 10016, "to": "info" }
+        ]
+    });
+    serde_json::to_string_pretty(&rules).unwrap_or_default() + "\n"
+}
+
+pub fn write_file(path: &str, data: &str, force: bool) -> Result<(), String> {
+    if !force && std::path::Path::new(path).exists() { return Err(format!("{} already exists (use --force to overwrite)", path)); }
+    crate::atomic::write_atomic(path, data.as_bytes()).map_err(|e| format!("{}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_files_parse() {
+        let a = InitAnswers { exclude_providers: vec!["DistributedCOM".to_string()], hours: 6, ..Default::default() };
+        let cfg: crate::AppConfig = toml::from_str(&render_config(&a)).unwrap();
+        assert_eq!(cfg.hours, Some(6));
+        assert_eq!(cfg.exclude_providers, Some(vec!["DistributedCOM".to_string()]));
+        let rules: crate::rules::RulesConfig = serde_json::from_str(&render_rules(&crate::DEFAULT_PATTERNS)).unwrap();
+        assert_eq!(rules.event_patterns.map(|p| p.len()), Some(crate::DEFAULT_PATTERNS.len()));
+        // Every generated hint rule needs contains_any or regex to fire at all.
+        assert_eq!(crate::rules::check(&render_rules(&crate::DEFAULT_PATTERNS)), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LogClear {
+    pub time: DateTime<Utc>,
+    pub channel: String,
+    pub account: Option<String>,
+    pub computer: String,
+}
+
+// Record IDs only grow within a channel, so a later event with a lower ID means the log was cleared or replaced in between.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordReset {
+    pub channel: String,
+    pub computer: String,
+    pub before: u64,
+    pub after: u64,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LogIntegrity {
+    pub clears: Vec<LogClear>,
+    pub resets: Vec<RecordReset>,
+}
+
+impl LogIntegrity {
+    pub fn is_empty(&self) -> bool { self.clears.is_empty() && self.resets.is_empty() }
+}
+
+fn tag<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let open = format!("<{}>", name);
+    let from = xml.rfind(&open)? + open.len();
+    xml[from..].find(&format!("</{}>", name)).map(|end| xml[from..from + end].trim()).filter(|v| !v.is_empty() && *v != "-")
+}
+
+pub fn is_log_clear(provider: &str, event_id: u32) -> bool { matches!(event_id, 104 | 1102) && provider.to_lowercase().contains("eventlog") }
+
+// Eventlog 1102 (Security log cleared) and 104 (any other log cleared) carry the account in <UserData><LogFileCleared>.
+fn clear_of(e: &crate::EventItem) -> Option<LogClear> {
+    if !is_log_clear(&e.provider, e.event_id) { return None; }
+    let xml = e.raw_xml.as_deref().filter(|x| x.contains("LogFileCleared")).unwrap_or(&e.content);
+    let account = tag(xml, "SubjectUserName").map(|u| match tag(xml, "SubjectDomainName") { Some(d) => format!("{}\\{}", d, u), None => u.to_string() });
+    // 104's UserData names the cleared channel; the System <Channel> comes first, so the last one wins.
+    let channel = if e.event_id == 1102 { "Security".to_string() } else if xml.matches("<Channel>").count() > 1 { tag(xml, "Channel").unwrap_or("unknown").to_string() } else { "unknown".to_string() };
+    Some(LogClear { time: e.time, channel, account, computer: e.computer.clone() })
+}
+
+pub fn analyze(events: &[crate::EventItem]) -> LogIntegrity {
+    let mut clears: Vec<LogClear> = events.iter().filter_map(clear_of).collect();
+    clears.sort_by_key(|c| c.time);
+    // (channel, computer) → (time, record ID)
+    let mut by_channel: HashMap<(&str, &str), Vec<_>> = HashMap::new();
+    for e in events { if let Some(r) = e.record_id { by_channel.entry((e.channel.as_str(), e.computer.as_str())).or_default().push((e.time, r)); } }
+    let mut resets = Vec::new();
+    for ((channel, computer), mut recs) in by_channel {
+        recs.sort();
+        for w in recs.windows(2) {
+            if w[1].1 < w[0].1 && w[1].0 > w[0].0 { resets.push(RecordReset { channel: channel.to_string(), computer: computer.to_string(), before: w[0].1, after: w[1].1, at: w[1].0 }); }
+        }
+    }
+    resets.sort_by(|a, b| a.at.cmp(&b.at).then_with(|| a.channel.cmp(&b.channel)));
+    LogIntegrity { clears, resets }
+}
+
+pub fn clear_line(c: &LogClear) -> String {
+    format!("{} log cleared by {}{}", c.channel, c.account.as_deref().unwrap_or("an unknown account"), if c.computer.is_empty() { String::new() } else { format!(" on {}", c.computer) })
+}
+
+pub fn reset_line(r: &RecordReset) -> String {
+    format!("{} record IDs restarted ({} → {}){}", r.channel, r.before, r.after, if r.computer.is_empty() { String::new() } else { format!(" on {}", r.computer) })
+}
+
+pub fn hints(li: &LogIntegrity) -> Vec<crate::hints::NoviceHint> {
+    let mut out = Vec::new();
+    let mut by_channel: Vec<(&str, Vec<&LogClear>)> = Vec::new();
+    for c in &li.clears {
+        match by_channel.iter_mut().find(|(ch, _)| *ch == c.channel) { Some((_, v)) => v.push(c), None => by_channel.push((c.channel.as_str(), vec![c])) }
+    }
+    for (ch, cs) in by_channel {
+        let mut accounts: Vec<&str> = cs.iter().filter_map(|c| c.account.as_deref()).collect();
+        accounts.dedup();
+        let who = if accounts.is_empty() { String::new() } else { format!(" by {}", accounts.join(", ")) };
+        out.push(crate::hints::NoviceHint { category: "Logging".to_string(), severity: "high".to_string(), message: format!("{} log was cleared{}; events before the clear are gone — confirm this was authorized", ch, who), evidence: cs.iter().map(|c| format!("{} {}", c.time.format("%Y-%m-%d %H:%M:%S"), clear_line(c))).collect(), count: cs.len(), probability: 90 });
+    }
+    // A reset without a matching clear event points at a replaced or deleted log file.
+    let unexplained: Vec<&RecordReset> = li.resets.iter().filter(|r| !li.clears.iter().any(|c| c.channel.eq_ignore_ascii_case(&r.channel) && c.time <= r.at)).collect();
+    if !unexplained.is_empty() {
+        out.push(crate::hints::NoviceHint { category: "Logging".to_string(), severity: "medium".to_string(), message: "Event record IDs went backwards without a log-clear event; the log file may have been replaced or deleted".to_string(), evidence: unexplained.iter().map(|r| format!("{} {}", r.at.format("%Y-%m-%d %H:%M:%S"), reset_line(r))).collect(), count: unexplained.len(), probability: 60 });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clears_and_resets_are_reported() {
+        let t0 = DateTime::parse_from_rfc3339("2025-11-30T12:00:00Z").unwrap().with_timezone(&Utc);
+        let ev = |p: &str, id: u32, ch: &str, rec: u64, min: i64, xml: &str| crate::EventItem { provider: p.to_string(), event_id: id, channel: ch.to_string(), record_id: Some(rec), time: t0 + chrono::Duration::minutes(min), raw_xml: Some(xml.to_string()), computer: "ws01".to_string(), ..Default::default() };
+        let sec = "<Event><System><Channel>Security</Channel></System><UserData><LogFileCleared><SubjectUserName>alice</SubjectUserName><SubjectDomainName>CONTOSO</SubjectDomainName></LogFileCleared></UserData></Event>";
+        let sys = "<Event><System><Channel>System</Channel></System><UserData><LogFileCleared><SubjectUserName>bob</SubjectUserName><SubjectDomainName>-</SubjectDomainName><Channel>Application</Channel></LogFileCleared></UserData></Event>";
+        let events = vec![
+            ev("Microsoft-Windows-Eventlog", 1102, "Security", 90, 5, sec),
+            ev("Microsoft-Windows-Eventlog", 104, "System", 500, 6, sys),
+            ev("Disk", 7, "System", 480, 1, ""),
+            ev("Service Control Manager", 7000, "Application", 2000, 2, ""),
+            ev("Service Control Manager", 7000, "Application", 3, 10, ""),
+            ev("Disk", 7, "System", 100, 20, ""),
+        ];
+        let li = analyze(&events);
+        assert_eq!(li.clears.iter().map(clear_line).collect::<Vec<_>>(), vec!["Security log cleared by CONTOSO\\alice on ws01", "Application log cleared by bob on ws01"]);
+        assert_eq!(li.resets.len(), 2);
+        let h = hints(&li);
+        assert_eq!(h.len(), 3);
+        assert!(h[0].message.starts_with("Security log was cleared by CONTOSO\\alice"));
+        assert_eq!(h[2].evidence, vec!["2025-11-30 12:20:00 System record IDs restarted (500 → 100) on ws01"]);
+    }
+}
@@ -0,0 +1,87 @@
+// Standard keyword bits shared by all providers; the low 48 bits are provider-defined.
+pub const KNOWN: [(&str, &str, u64); 8] = [
+    ("response-time", "Response Time", 0x0001_0000_0000_0000),
+    ("wdi-context", "WDI Context", 0x0002_0000_0000_0000),
+    ("wdi-diagnostic", "WDI Diagnostic", 0x0004_0000_0000_0000),
+    ("sqm", "SQM", 0x0008_0000_0000_0000),
+    ("audit-failure", "Audit Failure", 0x0010_0000_0000_0000),
+    ("audit-success", "Audit Success", 0x0020_0000_0000_0000),
+    ("correlation-hint", "Correlation Hint", 0x0040_0000_0000_0000),
+    ("classic", "Classic", 0x0080_0000_0000_0000),
+];
+
+// Bits a provider may define for itself in its manifest.
+pub const PROVIDER_BITS: u64 = 0x0000_FFFF_FFFF_FFFF;
+
+fn parse_hex(s: &str) -> Option<u64> {
+    let t = s.trim();
+    let h = t.strip_prefix("0x").or_else(|| t.strip_prefix("0X"))?;
+    u64::from_str_radix(h, 16).ok()
+}
+
+// `<Keywords>0x8010000000000000</Keywords>`
+pub fn parse_xml_value(s: &str) -> u64 { parse_hex(s).unwrap_or(0) }
+
+fn token_mask(tok: &str) -> Option<u64> {
+    let norm = tok.trim().to_lowercase().replace([' ', '_'], "-");
+    KNOWN.iter().find(|(k, d, _)| *k == norm || d.eq_ignore_ascii_case(tok.trim())).map(|(_, _, m)| *m).or_else(|| parse_hex(tok))
+}
+
+// `--keywords audit-failure,0x4` → one mask; an event passes when it has any of the bits.
+pub fn parse_filter(items: &[String]) -> Result<u64, String> {
+    items.iter().try_fold(0u64, |acc, t| token_mask(t).map(|m| acc | m).ok_or_else(|| format!("unknown keyword '{}' (use {} or a 0x hex mask)", t, KNOWN.iter().map(|(k, _, _)| *k).collect::<Vec<_>>().join(", "))))
+}
+
+// Names for known bits plus any remaining bits as hex; parse_list reverses it for NDJSON round trips.
+pub fn display(mask: u64) -> String {
+    let mut parts: Vec<String> = KNOWN.iter().filter(|(_, _, m)| mask & m != 0).map(|(_, d, _)| d.to_string()).collect();
+    let rest = KNOWN.iter().fold(mask, |acc, (_, _, m)| acc & !m);
+    if rest != 0 { parts.push(format!("0x{:x}", rest)); }
+    parts.join(", ")
+}
+
+// display() with the provider's own keyword names in place of hex where its metadata declares them.
+pub fn label(provider: &str, mask: u64) -> String {
+    let names = crate::tasks::keyword_names(provider, mask);
+    if names.is_empty() { return display(mask); }
+    let mut parts: Vec<String> = KNOWN.iter().filter(|(_, _, m)| mask & m != 0).map(|(_, d, _)| d.to_string()).collect();
+    let named = names.iter().fold(0, |a, (v, _)| a | v);
+    parts.extend(names.into_iter().map(|(_, n)| n));
+    let rest = KNOWN.iter().fold(mask & !named, |acc, (_, _, m)| acc & !m);
+    if rest != 0 { parts.push(format!("0x{:x}", rest)); }
+    parts.join(", ")
+}
+
+// `--keyword-names "Audit Failure,Microsoft-Windows-Kernel-Power/Diagnostic"`: matches the decoded names, case-insensitively.
+pub fn names_match(filters: &[String], provider: &str, mask: u64) -> bool {
+    filters.is_empty() || label(provider, mask).split(", ").any(|n| filters.iter().any(|f| f.trim().eq_ignore_ascii_case(n)))
+}
+
+pub fn parse_list(s: &str) -> u64 {
+    s.split(',').filter(|t| !t.trim().is_empty()).filter_map(token_mask).fold(0, |a, m| a | m)
+}
+
+// Security audit events are logged at Level 0 (LogAlways); the outcome lives in the keywords instead.
+// Failures count as warnings and successes as information, so the default filters keep only failures.
+pub fn audit_level(level: u8, keywords: u64) -> u8 {
+    if level != 0 { return level; }
+    if keywords & 0x0010_0000_0000_0000 != 0 { 3 } else if keywords & 0x0020_0000_0000_0000 != 0 { 4 } else { 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyword_masks_round_trip() {
+        let m = parse_xml_value("0x8010000000000000");
+        assert_eq!(display(m), "Audit Failure, 0x8000000000000000");
+        assert_eq!(parse_list(&display(m)), m);
+        assert_eq!(parse_filter(&["audit-success".to_string(), "Audit Failure".to_string()]).unwrap(), 0x0030_0000_0000_0000);
+        assert!(parse_filter(&["bogus".to_string()]).is_err());
+        assert_eq!(display(0), "");
+        assert_eq!(label("Microsoft-Windows-NoSuchProvider", m), display(m));
+        assert!(names_match(&["audit failure".to_string()], "x", m) && !names_match(&["Classic".to_string()], "x", m) && names_match(&[], "x", 0));
+        assert_eq!((audit_level(0, m), audit_level(0, 0x0020_0000_0000_0000), audit_level(2, m), audit_level(0, 0)), (3, 4, 2, 0));
+    }
+}
@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 use std::sync::OnceLock;
 use chrono::{DateTime, Duration, Utc, Local};
-use clap::{Parser, ValueEnum, ColorChoice, ArgAction, CommandFactory};
+use clap::{Parser, Subcommand, ValueEnum, ColorChoice, ArgAction, CommandFactory};
 use clap_complete::Shell;
 use comfy_table::{Table, ContentArrangement};
 use evtx::EvtxParser;
@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 use quick_xml::Reader;
 use quick_xml::events::Event as XmlEvent;
 use is_terminal::IsTerminal;
+use std::io::Write;
 mod windows_live;
 mod decoder;
 mod html;
@@ -20,12 +21,71 @@ mod rules;
 mod event_xml;
 mod markdown;
 mod perf;
-
+mod time_expr;
+mod fail_policy;
+mod init;
+mod services;
+mod policy;
+mod devenv;
+mod sync;
+mod i18n;
+mod bench;
+mod atomic;
+mod checkpoint;
+mod wizard;
+mod query;
+mod counts;
+mod accounts;
+mod keywords;
+mod tasks;
+mod storms;
+mod noise;
+mod group_by;
+mod crashes;
+mod sysinfo;
+mod channel_health;
+mod preflight;
+mod evtx_out;
+mod integrity;
+mod dashboard;
+mod sarif;
+mod siem;
+mod elastic;
+mod forward;
+mod syslog;
+mod gelf;
+mod http;
+mod splunk;
+mod otlp;
+mod bundle;
+mod template;
+mod merge;
+mod rule_test;
+mod msgfile;
+mod defender;
+mod battery;
+mod correlate;
+use crate::i18n::tr;
+
+const DEFAULT_PATTERNS: [&str; 23] = ["(?i)error", "(?i)fail", "(?i)exception", "(?i)timeout", "(?i)bugcheck", "(?i)crash", "(?i)access denied", "(?i)disk", "(?i)io error", "(?i)network", "(?i)service", "(?i)reset", "(?i)retry", "(?i)corrupt", "(?i)degraded", "(?i)unexpected", "(?i)dcom", "(?i)dns", "(?i)w32time", "(?i)group policy", "(?i)usb", "(?i)cdrom", "(?i)netlogon"];
 static ENABLE_COLOR: OnceLock<bool> = OnceLock::new();
 static REDACT_KEYS: OnceLock<Vec<String>> = OnceLock::new();
+static SCAN_DEADLINE: OnceLock<std::time::Instant> = OnceLock::new();
+static QUERY: OnceLock<crate::query::Query> = OnceLock::new();
+static GREP: OnceLock<Vec<Regex>> = OnceLock::new();
+static KEYWORD_MASK: OnceLock<u64> = OnceLock::new();
+static KEYWORD_NAMES: OnceLock<Vec<String>> = OnceLock::new();
+static EXCLUDE_GREP: OnceLock<Vec<Regex>> = OnceLock::new();
+static COMPRESS: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+// `--time-zone` for text built into the report itself (hint evidence, heatmap buckets).
+static DISPLAY_TZ: OnceLock<TimeZone> = OnceLock::new();
+
+fn display_tz() -> TimeZone { DISPLAY_TZ.get().copied().unwrap_or(TimeZone::Local) }
+
+fn budget_exhausted() -> bool { SCAN_DEADLINE.get().is_some_and(|d| std::time::Instant::now() >= *d) }
 
 #[derive(Clone, Copy, Debug, ValueEnum, Serialize, Deserialize)]
-enum OutputFmt { Text, Json }
+enum OutputFmt { Text, Json, Ndjson }
 
 #[derive(Clone, Copy, Debug, ValueEnum, Serialize, Deserialize)]
 enum TimeZone { Local, Utc }
@@ -37,7 +97,7 @@ enum SortBy { Time, Severity, Provider, Channel, EventId }
 enum SortOrder { Desc, Asc }
 
 #[derive(Clone, Copy, Debug, ValueEnum, Serialize, Deserialize)]
-enum Column { Time, Severity, Channel, Provider, EventId, Cause, Message }
+enum Column { Time, Severity, Channel, Provider, EventId, Cause, Message, Computer, User, Keywords }
 
 #[derive(Clone, Copy, Debug, ValueEnum, Serialize, Deserialize)]
 enum Theme { Dark, Light, HighContrast }
@@ -45,12 +105,184 @@ enum Theme { Dark, Light, HighContrast }
 #[derive(Clone, Copy, Debug, ValueEnum, Serialize, Deserialize)]
 enum LogLevel { Error, Warn, Info, Debug, Trace }
 #[derive(Clone, Copy, Debug, ValueEnum, Serialize, Deserialize)]
-enum Lang { En }
+enum Lang { En, De, Es, Fr }
 #[derive(Clone, Copy, Debug, ValueEnum, Serialize, Deserialize)]
 enum Preset { Triage, Deep }
 #[derive(Clone, Copy, Debug, ValueEnum, Serialize, Deserialize)]
 enum ColumnsPreset { Minimal, Detailed }
 
+#[derive(Subcommand, Debug, Clone)]
+enum Command {
+    /// Write a commented WinDoctor.toml and an example rules.json
+    Init {
+        #[arg(long, default_value = "WinDoctor.toml")]
+        config_out: String,
+        #[arg(long, default_value = "rules.json")]
+        rules_out: String,
+        #[arg(long, help = "Skip prompts and use defaults plus any flags given")]
+        yes: bool,
+        #[arg(long, help = "Overwrite existing files")]
+        force: bool,
+        #[arg(long, num_args = 0.., value_delimiter = ',')]
+        channels: Vec<String>,
+        #[arg(long)]
+        hours: Option<i64>,
+    },
+    /// Time EVTX reading, XML parsing (quick-xml vs fallback), message decoding and regex matching on a file
+    Bench {
+        file: String,
+        #[arg(long, default_value_t = 3)]
+        iterations: u32,
+        #[arg(long, help = "Only use the first N records")]
+        max_records: Option<usize>,
+        #[arg(long, help = "Print the timing report as JSON")]
+        json: bool,
+    },
+    /// Scan EVTX files or directories
+    Scan {
+        #[arg(required = true, help = "EVTX files or directories; .etl trace files are read as --etl-path")]
+        paths: Vec<String>,
+        #[arg(long)]
+        glob: Option<String>,
+        #[arg(long)]
+        recursive: bool,
+        #[command(flatten)]
+        window: WindowOpts,
+        #[command(flatten)]
+        report: ReportOpts,
+    },
+    /// Query the live event log API
+    Live {
+        #[arg(long, num_args = 0.., value_delimiter = ',')]
+        channels: Vec<String>,
+        #[arg(long, help = "Keep listening for new events for N minutes")]
+        subscribe_minutes: Option<u64>,
+        #[command(flatten)]
+        window: WindowOpts,
+        #[command(flatten)]
+        report: ReportOpts,
+    },
+    /// Compare two NDJSON exports (base, current)
+    Compare {
+        base: String,
+        current: String,
+        #[arg(long, help = "Write the comparison as JSON")]
+        out: Option<String>,
+    },
+    /// Dump every event a provider declares (IDs, levels, message templates, EventData fields) as JSON or Markdown
+    Catalog {
+        provider: String,
+        #[arg(long, help = "Write to this file instead of stdout")]
+        out: Option<String>,
+        #[arg(long, help = "Markdown table instead of JSON (implied by an .md --out)")]
+        md: bool,
+    },
+    /// Merge report JSONs from several machines or time windows into one report
+    Merge {
+        #[arg(required = true, num_args = 2.., help = "Report JSON files written with --json")]
+        inputs: Vec<String>,
+        #[arg(long, help = "Write the merged report here (.html renders HTML, anything else JSON); stdout if omitted")]
+        out: Option<String>,
+    },
+    /// Work with rules.json
+    Rules {
+        #[command(subcommand)]
+        action: RulesAction,
+    },
+    /// Render reports from an NDJSON export without collecting events
+    Report {
+        #[arg(value_name = "NDJSON")]
+        input: String,
+        #[arg(long)]
+        check_schema: bool,
+        #[command(flatten)]
+        report: ReportOpts,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum RulesAction {
+    /// Run the rules against sample event XML or an NDJSON export and show which fired and why
+    Test {
+        #[arg(long, help = "Rules file (default: WINDOCTOR_RULES_PATH or rules.json)")]
+        rules: Option<String>,
+        #[arg(long, num_args = 1.., required_unless_present = "ndjson", conflicts_with = "ndjson", help = "Event XML files or directories of *.xml (wevtutil qe /f:xml output works)")]
+        xml: Vec<String>,
+        #[arg(long, help = "An NDJSON export (--ndjson-path) instead of XML")]
+        ndjson: Option<String>,
+        #[arg(long, help = "Print the result as JSON")]
+        json: bool,
+    },
+}
+
+#[derive(clap::Args, Debug, Clone, Default)]
+struct WindowOpts {
+    #[arg(long)]
+    minutes: Option<i64>,
+    #[arg(long)]
+    hours: Option<i64>,
+    #[arg(long)]
+    days: Option<i64>,
+    #[arg(long, conflicts_with_all = ["minutes", "hours", "days"])]
+    since: Option<String>,
+    #[arg(long, conflicts_with_all = ["minutes", "hours", "days"])]
+    until: Option<String>,
+}
+
+#[derive(clap::Args, Debug, Clone, Default)]
+struct ReportOpts {
+    #[arg(long, short = 'o', value_enum)]
+    output: Option<OutputFmt>,
+    #[arg(long)]
+    html: Option<String>,
+    #[arg(long)]
+    json: Option<String>,
+    #[arg(long)]
+    csv: Option<String>,
+    #[arg(long)]
+    ndjson: Option<String>,
+    #[arg(long)]
+    md: Option<String>,
+}
+
+// Subcommands are front-ends over the flat flag set: they fill the matching top-level fields, and any
+// top-level flag given before the subcommand (filters, presets, ...) still applies.
+fn apply_command(args: &mut Args) {
+    let (window, report) = match args.command.clone() {
+        Some(Command::Scan { paths, glob, recursive, window, report }) => {
+            let (etl, evtx): (Vec<String>, Vec<String>) = paths.into_iter().partition(|p| p.to_lowercase().ends_with(".etl"));
+            args.evtx_path = evtx;
+            args.etl_path.extend(etl);
+            if glob.is_some() { args.evtx_glob = glob; }
+            args.evtx_recursive |= recursive;
+            (window, report)
+        }
+        Some(Command::Live { channels, subscribe_minutes, window, report }) => {
+            args.live = true;
+            if !channels.is_empty() { args.channels = channels; }
+            if let Some(m) = subscribe_minutes { args.subscribe_minutes = m; }
+            (window, report)
+        }
+        Some(Command::Report { input, check_schema, report }) => {
+            args.from_ndjson = Some(input);
+            args.check_ndjson_schema |= check_schema;
+            (WindowOpts::default(), report)
+        }
+        _ => return,
+    };
+    if let Some(v) = window.minutes { args.minutes = v; }
+    if let Some(v) = window.hours { args.hours = v; }
+    if let Some(v) = window.days { args.days = v; }
+    if window.since.is_some() { args.since = window.since; }
+    if window.until.is_some() { args.until = window.until; }
+    if let Some(v) = report.output { args.output = v; }
+    if report.html.is_some() { args.html = report.html; }
+    if report.json.is_some() { args.json_path = report.json; }
+    if report.csv.is_some() { args.csv_path = report.csv; }
+    if report.ndjson.is_some() { args.ndjson_path = report.ndjson; }
+    if report.md.is_some() { args.md_path = report.md; }
+}
+
 #[derive(Parser, Debug)]
 #[command(
     name = "WinDoctor",
@@ -60,14 +292,24 @@ enum ColumnsPreset { Minimal, Detailed }
     color = ColorChoice::Auto
 )]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
     #[arg(long, short = 'm', default_value_t = 0)]
     minutes: i64,
     #[arg(long, default_value_t = 0)]
     hours: i64,
+    #[arg(long, default_value_t = 0, help = "Window length in days; combines with --hours/--minutes")]
+    days: i64,
     #[arg(long, num_args = 0.., value_delimiter = ',')]
     channels: Vec<String>,
+    #[arg(long, default_value_t = false, requires = "live", conflicts_with = "channels", help = "Scan every enabled Admin/Operational channel that has events in the window")]
+    all_channels: bool,
     #[arg(long, num_args = 0.., value_delimiter = ',')]
     patterns: Vec<String>,
+    #[arg(long, action = ArgAction::Append, help = "Keep only events whose message matches this regex (repeatable; any match keeps); does not affect --patterns statistics")]
+    grep: Vec<String>,
+    #[arg(long, action = ArgAction::Append, help = "Drop events whose message matches this regex (repeatable), e.g. known-benign noise, before counting and scoring")]
+    exclude_grep: Vec<String>,
     #[arg(long, short = 'n', default_value_t = 20)]
     top: usize,
     #[arg(long, short = 'o', value_enum, default_value = "text")]
@@ -80,13 +322,19 @@ struct Args {
     live: bool,
     #[arg(long, default_value_t = 0)]
     subscribe_minutes: u64,
-    #[arg(long, default_value_t = false, help = "Shortcut: last 10 minutes", conflicts_with_all = ["minutes", "hours", "since", "until"])]
+    #[arg(long, default_value_t = false, requires = "live", help = "Stream new events to stdout as they arrive until Ctrl-C (NDJSON with --output json)")]
+    follow: bool,
+    #[arg(long, value_name = "INTERVAL", requires = "live", conflicts_with = "follow", help = "Re-run the live scan every INTERVAL (e.g. 30s, 5m) and redraw a compact dashboard until Ctrl-C")]
+    watch: Option<String>,
+    #[arg(long, default_value_t = 0, help = "With --subscribe-minutes, print running totals to stderr every N seconds")]
+    subscribe_progress: u64,
+    #[arg(long, default_value_t = false, help = "Shortcut: last 10 minutes", conflicts_with_all = ["minutes", "hours", "days", "since", "until"])]
     last10m: bool,
-    #[arg(long, default_value_t = false, help = "Shortcut: last day (24 hours)", conflicts_with_all = ["minutes", "hours", "since", "until"])]
+    #[arg(long, default_value_t = false, help = "Shortcut: last day (24 hours)", conflicts_with_all = ["minutes", "hours", "days", "since", "until"])]
     last_day: bool,
-    #[arg(long, default_value_t = false, help = "Shortcut: last hour", conflicts_with_all = ["minutes", "hours", "since", "until"])]
+    #[arg(long, default_value_t = false, help = "Shortcut: last hour", conflicts_with_all = ["minutes", "hours", "days", "since", "until"])]
     last_hour: bool,
-    #[arg(long, default_value_t = false, help = "Shortcut: last week (7 days)", conflicts_with_all = ["minutes", "hours", "since", "until"])]
+    #[arg(long, default_value_t = false, help = "Shortcut: last week (7 days)", conflicts_with_all = ["minutes", "hours", "days", "since", "until"])]
     last_week: bool,
     #[arg(long, default_value_t = false, help = "Include information level (4)")]
     include_info: bool,
@@ -100,15 +348,17 @@ struct Args {
     file_glob: Option<String>,
     #[arg(long, default_value_t = 20)]
     max_file_samples: usize,
-    #[arg(long, short = 'e')]
-    evtx_path: Option<String>,
+    #[arg(long, short = 'e', value_delimiter = ',', help = "EVTX file or directory; repeat or comma-separate to merge several sources (e.g. archived logs)")]
+    evtx_path: Vec<String>,
+    #[arg(long, value_delimiter = ',', help = "Event trace log (.etl) to read through the event log API, e.g. WindowsUpdate or WPR captures; repeat or comma-separate for several")]
+    etl_path: Vec<String>,
     #[arg(long)]
     evtx_glob: Option<String>,
     #[arg(long, default_value_t = false)]
     evtx_recursive: bool,
-    #[arg(long, conflicts_with_all = ["last10m", "last_hour", "last_day", "last_week", "minutes", "hours"])]
+    #[arg(long, conflicts_with_all = ["last10m", "last_hour", "last_day", "last_week", "minutes", "hours", "days"])]
     since: Option<String>,
-    #[arg(long, conflicts_with_all = ["last10m", "last_hour", "last_day", "last_week", "minutes", "hours"])]
+    #[arg(long, conflicts_with_all = ["last10m", "last_hour", "last_day", "last_week", "minutes", "hours", "days"])]
     until: Option<String>,
     /// Fetch last N error events (default 50; ignored if any time window flag is provided)
     #[arg(long, default_value_t = 50)]
@@ -119,6 +369,8 @@ struct Args {
     /// Path to JSON rules registry (default ./rules.json)
     #[arg(long)]
     rules: Option<String>,
+    #[arg(long, help = "Validate the rules file (syntax, unknown fields, regexes, values), list every problem and exit non-zero if there is any")]
+    rules_check: bool,
     #[arg(long, short = 'C', default_value_t = false)]
     no_color: bool,
     #[arg(long, default_value_t = false)]
@@ -137,24 +389,86 @@ struct Args {
     json_path: Option<String>,
     #[arg(long)]
     csv_path: Option<String>,
+    #[arg(long, value_name = "PATH", help = "Write every filtered event as NDJSON while it is read, not capped by sampling or --max-events")]
+    stream_ndjson: Option<String>,
+    #[arg(long, value_name = "PATH", help = "Save the filtered events as a new .evtx (one file per channel when several are read) for vendor support")]
+    evtx_out: Option<String>,
     #[arg(long)]
     ndjson_path: Option<String>,
+    #[arg(long, num_args = 0.., value_delimiter = ',', help = "CSV/TSV/NDJSON fields: time,severity,level,channel,provider,event_id,cause,message,computer,user,keywords,task,opcode,record_id or any EventData name")]
+    export_fields: Vec<String>,
+    #[arg(long, num_args = 0.., value_delimiter = ',', help = "CSV/TSV columns (overrides --export-fields there); eventdata:<Name> adds an EventData value")]
+    csv_columns: Vec<String>,
     #[arg(long, default_value_t = false)]
     emit_eventdata: bool,
     #[arg(long, default_value_t = false)]
     emit_xml: bool,
+    #[arg(long, default_value_t = false, help = "EVTX input: render classic providers' messages from their registered EventMessageFile, as Event Viewer does")]
+    resolve_messages: bool,
+    #[arg(long, default_value_t = false, help = "CSV, TSV, NDJSON, CEF, LEEF and bulk exports contain every filtered event, not just the report's samples")]
+    export_all_events: bool,
+    #[arg(long, default_value_t = false, help = "Gzip JSON, NDJSON, CSV and TSV exports while writing them (adds .gz)")]
+    compress: bool,
     #[arg(long)]
     md_path: Option<String>,
     #[arg(long)]
     md_fix_path: Option<String>,
+    #[arg(long, value_name = "FILE", help = "Render the report through a Handlebars-style template (fields as in --json-path)")]
+    template: Option<String>,
+    #[arg(long, value_name = "PATH", requires = "template", help = "Where the --template output goes (default: stdout)")]
+    template_out: Option<String>,
+    #[arg(long, help = "Write hints and sampled events as SARIF 2.1.0 results for compliance pipelines")]
+    sarif_path: Option<String>,
+    #[arg(long, value_name = "udp|tcp://HOST:PORT", help = "Forward every matched event as an RFC 5424 syslog message (UDP unless tcp:// is given)")]
+    syslog: Option<String>,
+    #[arg(long, value_name = "HOST:PORT", help = "Send every matched event to Graylog as GELF (chunked UDP, or tcp://host:port)")]
+    gelf: Option<String>,
+    #[arg(long, value_name = "URL", help = "POST every matched event to a Splunk HTTP Event Collector (batched, gzip, retried)")]
+    splunk_hec_url: Option<String>,
+    #[arg(long, value_name = "TOKEN", requires = "splunk_hec_url", help = "HEC token (default: SPLUNK_HEC_TOKEN environment variable)")]
+    splunk_hec_token: Option<String>,
+    #[arg(long, value_name = "URL", help = "Export every matched event as OpenTelemetry LogRecords over OTLP/HTTP JSON (e.g. http://collector:4318)")]
+    otlp_endpoint: Option<String>,
+    #[arg(long, help = "Write sampled events as ArcSight CEF lines for SIEM ingestion")]
+    cef_path: Option<String>,
+    #[arg(long, help = "Write sampled events as IBM QRadar LEEF 1.0 lines")]
+    leef_path: Option<String>,
+    #[arg(long, help = "Write sampled events as Elasticsearch _bulk NDJSON with ECS field names")]
+    elastic_bulk_path: Option<String>,
     #[arg(long)]
     tsv_path: Option<String>,
     #[arg(long, short = 'p', num_args = 0.., value_delimiter = ',')]
     providers: Vec<String>,
     #[arg(long, short = 'x', num_args = 0.., value_delimiter = ',')]
     exclude_providers: Vec<String>,
+    #[arg(long, num_args = 0.., value_delimiter = ',', help = "Only include events from these computers (System/Computer)")]
+    computers: Vec<String>,
+    #[arg(long, num_args = 0.., value_delimiter = ',', help = "Drop events from these computers (System/Computer)")]
+    exclude_computers: Vec<String>,
+    #[arg(long, num_args = 0.., value_delimiter = ',', help = "Only include events with these user SIDs (System/Security UserID)")]
+    users: Vec<String>,
+    #[arg(long, help = "Only include events with EventRecordID >= N")]
+    from_record: Option<u64>,
+    #[arg(long, help = "Only include events with EventRecordID <= N")]
+    to_record: Option<u64>,
+    #[arg(long, default_value_t = 60, help = "Report an event storm when one provider/event ID logs more than N events in a minute")]
+    storm_threshold: usize,
+    #[arg(long, help = "Rank providers/event IDs by volume against severity and print rules.json suppression entries for chronic benign events")]
+    noise_report: bool,
+    #[arg(long, help = "Skip the OS/CPU/RAM/disk section (collected over WMI)")]
+    no_sysinfo: bool,
+    #[arg(long, help = "Only include events with EventRecordID > N; records up to N are skipped before parsing")]
+    after_record_id: Option<u64>,
+    #[arg(long, help = "Stop collecting after this wall-clock budget (e.g. 60s, 5m) and mark the report partial")]
+    max_duration: Option<String>,
+    #[arg(long, value_name = "SECS", conflicts_with = "max_duration", help = "Overall scan budget in seconds; same as --max-duration <SECS>s")]
+    timeout: Option<u64>,
     #[arg(long, short = 'E', default_value_t = 5000)]
     max_events: usize,
+    #[arg(long, default_value_t = 64, help = "Event handles fetched per EvtNext call (1-1024)")]
+    query_batch_size: usize,
+    #[arg(long, default_value_t = 100, help = "How long one EvtNext batch may wait before the channel read is abandoned")]
+    query_timeout_ms: u32,
     #[arg(long, value_parser = clap::value_parser!(u8).range(0..=4))]
     min_level: Option<u8>,
     #[arg(long, value_parser = clap::value_parser!(u8).range(0..=4))]
@@ -171,12 +485,20 @@ struct Args {
     quiet: bool,
     #[arg(long, default_value_t = false)]
     progress: bool,
-    #[arg(long, default_value_t = false)]
+    #[arg(long, default_value_t = false, help = "Print one stable key=value summary line (errors, warnings, risk, score, top_domain)")]
+    porcelain: bool,
+    #[arg(long, default_value_t = false, help = "Deprecated: same as --fail-on errors>0,warnings>0")]
     warnings_as_errors: bool,
+    #[arg(long, num_args = 0.., value_delimiter = ',', help = "Exit non-zero when a condition holds: errors>0, risk>=High, category:Storage, provider:Disk, performance_score>60")]
+    fail_on: Vec<String>,
     #[arg(long, value_enum)]
     completions: Option<Shell>,
     #[arg(long)]
     completions_out: Option<String>,
+    #[arg(long, default_value_t = false, help = "List registered event channels with enabled state, size and last write time, then exit")]
+    list_channels: bool,
+    #[arg(long, num_args = 0..=1, value_name = "CHANNEL", help = "List event providers (optionally only those writing to CHANNEL; --providers narrows further) with the event IDs, levels and descriptions they declare, then exit")]
+    list_providers: Option<Option<String>>,
     #[arg(long)]
     config: Option<String>,
     #[arg(long)]
@@ -202,6 +524,14 @@ struct Args {
     include_event_ids: Vec<u32>,
     #[arg(long, num_args = 0.., value_delimiter = ',')]
     exclude_event_ids: Vec<u32>,
+    #[arg(long, num_args = 0.., value_delimiter = ',', help = "Keep events with any of these keywords: audit-failure, audit-success, classic, wdi-context, ... or a 0x hex mask")]
+    keywords: Vec<String>,
+    #[arg(long, num_args = 0.., value_delimiter = ',', help = "Keep events carrying any of these keyword names, standard or declared by the provider's manifest")]
+    keyword_names: Vec<String>,
+    #[arg(long, num_args = 0.., value_delimiter = ',', help = "Keep events whose task category matches one of these numbers or names (e.g. 12544 or Logon)")]
+    tasks: Vec<String>,
+    #[arg(long, help = "Filter expression, e.g. 'provider=Disk AND (event_id IN (7,11) OR message ~ \"bad block\") AND level<=2'")]
+    query: Option<String>,
     #[arg(long, default_value_t = false)]
     force_color: bool,
     #[arg(long, value_enum, default_value = "local")]
@@ -212,22 +542,52 @@ struct Args {
     sort_order: SortOrder,
     #[arg(long, num_args = 0.., value_delimiter = ',')]
     columns: Vec<Column>,
+    #[arg(long, value_enum, num_args = 0.., value_delimiter = ',', help = "Pivot event counts by any combination of provider,channel,event_id,device,domain,level,hour")]
+    group_by: Vec<crate::group_by::GroupKey>,
     #[arg(long, value_enum)]
     columns_preset: Option<ColumnsPreset>,
     #[arg(long, default_value_t = false)]
     no_truncate: bool,
     #[arg(long)]
     time_format: Option<String>,
-    #[arg(long, value_enum, default_value = "en")]
+    #[arg(long, value_enum, default_value = "en", help = "Language for CLI section labels: en, de, es, fr (report files stay English)")]
     lang: Lang,
     #[arg(long)]
     per_channel_sample_limit: Option<usize>,
     #[arg(long)]
     per_provider_sample_limit: Option<usize>,
+    #[arg(long)]
+    per_computer_sample_limit: Option<usize>,
+    #[arg(long, default_value_t = false, help = "Continue an interrupted EVTX scan from its checkpoint")]
+    resume: bool,
+    #[arg(long, help = "Checkpoint file for long EVTX scans (default: <temp>/windoctor-checkpoint.json)")]
+    checkpoint_path: Option<String>,
+    #[arg(long, default_value_t = false, help = "Only count matching records per level/channel/provider/event ID from EVTX files; no samples, decoding, hints or reports")]
+    count_only: bool,
+    #[arg(long, default_value_t = false, help = "Ask a few questions (time window, problem type), then scan and open an HTML report")]
+    wizard: bool,
     #[arg(long, default_value_t = false)]
     collect_perf: bool,
     #[arg(long, default_value_t = false)]
     smart_check: bool,
+    #[arg(long, default_value_t = false, help = "List auto-start services that are stopped or failed right now (WMI)")]
+    service_health: bool,
+    #[arg(long, default_value_t = false, help = "Check elevation and read access to the channels, print what is missing and how to fix it, then exit (non-zero if a channel is unreadable)")]
+    preflight: bool,
+    #[arg(long, default_value_t = false, help = "Relaunch elevated through the UAC prompt when not running as Administrator")]
+    elevate: bool,
+    #[arg(long, default_value_t = false, requires = "live", help = "Report size, retention and history span of the queried channels and flag logs about to wrap")]
+    channel_health: bool,
+    #[arg(long, default_value_t = false, help = "Summarize Group Policy RSoP status: failed extensions, last successful refresh (WMI)")]
+    policy_status: bool,
+    #[arg(long, default_value_t = false, help = "Collect WSL status/dmesg and Docker Desktop logs and check WSL/Docker virtual disk sizes")]
+    dev_env: bool,
+    #[arg(long, default_value_t = false, help = "Read OneDrive account registry state and SyncDiagnostics.log for sync/Known Folder Move errors")]
+    onedrive: bool,
+    #[arg(long, default_value_t = false, help = "Read battery design and full-charge capacity (WMI) and flag worn batteries")]
+    battery: bool,
+    #[arg(long, default_value_t = 70, value_parser = clap::value_parser!(u8).range(1..=100), help = "With --battery: batteries below this percent of design capacity are reported as worn")]
+    battery_min_health: u8,
     #[arg(long, num_args = 2, value_delimiter = ',', help = "Two NDJSON paths: base,current")]
     compare_ndjson: Option<Vec<String>>,
     #[arg(long, help = "Write comparison summary to JSON path")]
@@ -236,21 +596,33 @@ struct Args {
     export_dir: Option<String>,
     #[arg(long, default_value_t = false)]
     print_effective_config: bool,
-    #[arg(long, num_args = 0.., value_delimiter = ',', help = "Fail CI if categories present")]
+    #[arg(long, num_args = 0.., value_delimiter = ',', help = "Fail CI if categories present (same as --fail-on category:<name>)")]
     fail_on_categories: Vec<String>,
-    #[arg(long, num_args = 0.., value_delimiter = ',', help = "Fail CI if providers present")]
+    #[arg(long, num_args = 0.., value_delimiter = ',', help = "Fail CI if providers present (same as --fail-on provider:<name>)")]
     fail_on_providers: Vec<String>,
-    #[arg(long, help = "Render report from NDJSON file (offline)")]
+    #[arg(long, visible_alias = "ndjson-in", help = "Render report from an events NDJSON export instead of collecting (offline); '-' reads stdin")]
     from_ndjson: Option<String>,
     #[arg(long, default_value_t = false, help = "Disable WMI metrics collection")]
     no_wmi: bool,
+    #[arg(long, help = "Keep raw EventData for live events instead of the provider's formatted message (faster)")]
+    no_render_messages: bool,
+    #[arg(long, value_name = "HOST", help = "Read live events from a remote computer over the Event Log RPC interface")]
+    computer: Option<String>,
+    #[arg(long, requires = "computer", help = "Account for --computer (DOMAIN\\user or user@domain); defaults to the current logon")]
+    username: Option<String>,
+    #[arg(long, requires = "username", help = "Password for --username; WINDOCTOR_PASSWORD is read when omitted")]
+    password: Option<String>,
     #[arg(long, default_value_t = false, help = "Validate NDJSON schema before reading")]
     check_ndjson_schema: bool,
-    #[arg(long, default_value_t = false, help = "Compress export_dir into a ZIP archive")]
-    export_zip: bool,
+    #[arg(long, value_name = "PATH", num_args = 0..=1, requires = "export_dir", help = "Package this run's --export-dir files with a manifest.json into one ZIP (default: <export_dir>/bundle-<time>.zip)")]
+    export_zip: Option<Option<String>>,
+    #[arg(long, value_name = "PATH", num_args = 0..=1, help = "Write a SHA-256 manifest of every file this run wrote (default: windoctor-manifest.json, in --export-dir when set)")]
+    sign_reports: Option<Option<String>>,
+    #[arg(long, value_name = "KEY", requires = "sign_reports", help = "Sign the manifest with this minisign secret key (needs minisign on PATH; writes <manifest>.minisig)")]
+    sign_key: Option<String>,
     #[arg(long, num_args = 0.., value_delimiter = ',', help = "Redact keys (e.g., SID, QueryName, paths)")]
     redact: Vec<String>,
-    #[arg(long, default_value_t = false, help = "Exit with code based on risk grade")]
+    #[arg(long, default_value_t = false, help = "Exit with code based on risk grade (6 Medium, 7 High, 8 Critical)")]
     exit_code_by_risk: bool,
     #[arg(long, help = "Subscribe and write incremental HTML snapshots for N minutes")]
     live_html: Option<u64>,
@@ -261,14 +633,20 @@ impl Default for Args {
         Self {
             minutes: 0,
             hours: 0,
+            days: 0,
             channels: vec![],
             patterns: vec![],
+            grep: vec![],
+            exclude_grep: vec![],
             top: 20,
             output: OutputFmt::Text,
             text_format: TextFormat::Lines,
             theme: Theme::Dark,
             live: false,
             subscribe_minutes: 0,
+            follow: false,
+            watch: None,
+            subscribe_progress: 0,
             last10m: false,
             last_day: false,
             last_hour: false,
@@ -279,7 +657,7 @@ impl Default for Args {
             scan_path: None,
             file_glob: None,
             max_file_samples: 20,
-            evtx_path: None,
+            evtx_path: vec![],
             evtx_glob: None,
             evtx_recursive: false,
             since: None,
@@ -287,6 +665,7 @@ impl Default for Args {
             last_errors: 50,
             last_criticals: 50,
             rules: None,
+            rules_check: false,
             no_color: false,
             no_emoji: false,
             log_level: None,
@@ -296,15 +675,47 @@ impl Default for Args {
             no_open: false,
             json_path: None,
             csv_path: None,
+            evtx_out: None,
+            stream_ndjson: None,
             ndjson_path: None,
+            etl_path: vec![],
+            export_fields: vec![],
+            csv_columns: vec![],
             emit_eventdata: false,
+            resolve_messages: false,
             emit_xml: false,
+            compress: false,
+            export_all_events: false,
             md_path: None,
+            sarif_path: None,
+            template: None,
+            template_out: None,
+            syslog: None,
+            gelf: None,
+            splunk_hec_url: None,
+            splunk_hec_token: None,
+            otlp_endpoint: None,
+            cef_path: None,
+            leef_path: None,
+            elastic_bulk_path: None,
             md_fix_path: None,
             tsv_path: None,
             providers: vec![],
             exclude_providers: vec![],
+            computers: vec![],
+            exclude_computers: vec![],
+            users: vec![],
+            from_record: None,
+            to_record: None,
+            storm_threshold: 60,
+            noise_report: false,
+            no_sysinfo: false,
+            after_record_id: None,
+            max_duration: None,
+            timeout: None,
             max_events: 5000,
+            query_batch_size: 64,
+            query_timeout_ms: 100,
             min_level: None,
             max_level: None,
             only_critical: false,
@@ -314,10 +725,16 @@ impl Default for Args {
             quiet: false,
             progress: false,
             warnings_as_errors: false,
+            porcelain: false,
+            fail_on: vec![],
             completions: None,
             completions_out: None,
+            list_channels: false,
+            all_channels: false,
+            list_providers: None,
             config: None,
             load_config: None,
+            command: None,
             save_config: None,
             only_matched: false,
             msg_width: None,
@@ -327,23 +744,44 @@ impl Default for Args {
             analysis_only: false,
             sample_count: None,
             include_event_ids: vec![],
+            keywords: vec![],
+            keyword_names: vec![],
+            tasks: vec![],
+            query: None,
             exclude_event_ids: vec![],
             force_color: false,
             time_zone: TimeZone::Local,
             sort_by: SortBy::Time,
             sort_order: SortOrder::Desc,
             columns: vec![],
+            group_by: vec![],
             columns_preset: None,
             no_truncate: false,
             time_format: None,
             per_channel_sample_limit: None,
             per_provider_sample_limit: None,
+            per_computer_sample_limit: None,
+            resume: false,
+            checkpoint_path: None,
+            count_only: false,
+            wizard: false,
             collect_perf: false,
             smart_check: false,
+            service_health: false,
+            channel_health: false,
+            preflight: false,
+            elevate: false,
+            policy_status: false,
+            dev_env: false,
+            onedrive: false,
+            battery: false,
+            battery_min_health: 70,
             compare_ndjson: None,
             compare_out: None,
             export_dir: None,
-            export_zip: false,
+            export_zip: None,
+            sign_reports: None,
+            sign_key: None,
             redact: vec![],
             exit_code_by_risk: false,
             live_html: None,
@@ -352,13 +790,17 @@ impl Default for Args {
             fail_on_providers: vec![],
             from_ndjson: None,
             no_wmi: false,
+            no_render_messages: false,
+            computer: None,
+            username: None,
+            password: None,
             check_ndjson_schema: false,
             lang: Lang::En,
         }
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 struct EventItem {
     time: DateTime<Utc>,
     level: u8,
@@ -367,13 +809,38 @@ struct EventItem {
     event_id: u32,
     content: String,
     raw_xml: Option<String>,
+    #[serde(default)]
+    computer: String,
+    #[serde(default)]
+    user: Option<String>,
+    #[serde(default)]
+    record_id: Option<u64>,
+    #[serde(default)]
+    keywords: u64,
+    #[serde(default)]
+    task: u16,
+    #[serde(default)]
+    opcode: u8,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct AggStat {
+    key: String,
+    errors: usize,
+    warnings: usize,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct ReportSummary {
     window_start: DateTime<Utc>,
     window_end: DateTime<Utc>,
+    #[serde(default)]
+    system_info: Option<crate::sysinfo::SystemInfo>,
     total: usize,
+    #[serde(default)]
+    criticals: usize,
     errors: usize,
     warnings: usize,
     by_provider: Vec<(String, usize)>,
@@ -400,14 +867,82 @@ struct ReportSummary {
     smart_failure_predicted: Option<bool>,
     risk_grade: String,
     compare: Option<ComparisonResult>,
+    #[serde(default)]
+    by_source: Vec<(String, usize)>,
+    #[serde(default)]
+    scanned_by_source: Vec<(String, usize)>,
+    #[serde(default)]
+    provider_stats: Vec<AggStat>,
+    #[serde(default)]
+    event_id_stats: Vec<AggStat>,
+    #[serde(default)]
+    heatmap: Vec<Vec<usize>>,
+    #[serde(default)]
+    by_computer: Vec<(String, usize)>,
+    #[serde(default)]
+    computer_stats: Vec<AggStat>,
+    // Per source machine, its most frequent providers; for Windows Event Forwarding collectors.
+    #[serde(default)]
+    computer_top_providers: Vec<(String, Vec<(String, usize)>)>,
+    #[serde(default)]
+    by_user: Vec<(String, usize)>,
+    #[serde(default)]
+    max_record_ids: Vec<(String, u64)>,
+    #[serde(default)]
+    partial: bool,
+    #[serde(default)]
+    completion: Vec<(String, u8)>,
+    #[serde(default)]
+    services_down: Vec<crate::services::ServiceState>,
+    #[serde(default)]
+    policy: Option<crate::policy::PolicyStatus>,
+    #[serde(default)]
+    dev_env: Option<crate::devenv::DevEnvStatus>,
+    #[serde(default)]
+    sync: Vec<crate::sync::SyncAccount>,
+    #[serde(default)]
+    storms: Vec<crate::storms::Storm>,
+    #[serde(default)]
+    noise: Vec<crate::noise::NoiseEntry>,
+    #[serde(default)]
+    group_by: Option<crate::group_by::GroupTable>,
+    #[serde(default)]
+    crashing_apps: Vec<crate::crashes::AppCrash>,
+    #[serde(default)]
+    defender: Vec<crate::defender::DefenderEvent>,
+    #[serde(default)]
+    battery: Option<crate::battery::BatteryReport>,
+    #[serde(default)]
+    channel_health: Vec<crate::channel_health::ChannelHealth>,
+    #[serde(default)]
+    log_integrity: crate::integrity::LogIntegrity,
+    #[serde(default)]
+    collection_errors: Vec<crate::windows_live::CollectionError>,
+    // Untruncated counts behind by_provider / by_event_id, for `--fail-on provider:` and `event_id:`.
+    #[serde(skip)]
+    all_providers: Vec<(String, usize)>,
+    #[serde(skip)]
+    all_event_ids: Vec<(u32, usize)>,
+}
+
+// An empty one-hour report; tests fill in what they check with struct-update syntax.
+#[cfg(test)]
+fn test_summary() -> ReportSummary {
+    serde_json::from_str(r#"{"window_start":"2025-01-01T00:00:00Z","window_end":"2025-01-01T01:00:00Z","total":0,"errors":0,"warnings":0,"by_provider":[],"by_channel":[],"by_event_id":[],"by_device":[],"by_domain":[],"matched_terms":[],"samples":[],"file_matched_terms":[],"file_samples":[],"scanned_records":0,"parsed_events":0,"novice_hints":[],"mode":null,"performance_score":0,"degradation_signals":[],"recommendations":[],"likely_causes":[],"timeline":[],"by_category":[],"perf_metrics":[],"perf_counters":null,"smart_failure_predicted":null,"risk_grade":"Low","compare":null}"#).unwrap()
 }
 
 #[derive(Deserialize, Serialize)]
 struct AppConfig {
     channels: Option<Vec<String>>,
     patterns: Option<Vec<String>>,
+    grep: Option<Vec<String>>,
+    exclude_grep: Option<Vec<String>>,
     providers: Option<Vec<String>>,
     exclude_providers: Option<Vec<String>>,
+    computers: Option<Vec<String>>,
+    exclude_computers: Option<Vec<String>>,
+    users: Option<Vec<String>>,
+    max_duration: Option<String>,
     output: Option<OutputFmt>,
     text_format: Option<TextFormat>,
     theme: Option<Theme>,
@@ -419,32 +954,50 @@ struct AppConfig {
     scan_path: Option<String>,
     file_glob: Option<String>,
     evtx_path: Option<String>,
+    evtx_paths: Option<Vec<String>>,
     evtx_glob: Option<String>,
     html: Option<String>,
     json_path: Option<String>,
     csv_path: Option<String>,
     ndjson_path: Option<String>,
     md_path: Option<String>,
+    sarif_path: Option<String>,
+    template: Option<String>,
+    cef_path: Option<String>,
+    leef_path: Option<String>,
+    elastic_bulk_path: Option<String>,
     md_fix_path: Option<String>,
     warnings_as_errors: Option<bool>,
+    fail_on: Option<Vec<String>>,
     progress: Option<bool>,
     last_errors: Option<usize>,
     last_criticals: Option<usize>,
     hours: Option<i64>,
     minutes: Option<i64>,
+    days: Option<i64>,
     since: Option<String>,
     until: Option<String>,
     summary_only: Option<bool>,
     analysis_only: Option<bool>,
     sample_count: Option<usize>,
     include_event_ids: Option<Vec<u32>>,
+    keywords: Option<Vec<String>>,
+    keyword_names: Option<Vec<String>>,
+    tasks: Option<Vec<String>>,
+    query: Option<String>,
     exclude_event_ids: Option<Vec<u32>>,
+    export_fields: Option<Vec<String>>,
+    csv_columns: Option<Vec<String>>,
     emit_eventdata: Option<bool>,
     emit_xml: Option<bool>,
+    resolve_messages: Option<bool>,
+    compress: Option<bool>,
+    export_all_events: Option<bool>,
     force_color: Option<bool>,
     time_zone: Option<TimeZone>,
     columns: Option<Vec<Column>>, 
     columns_preset: Option<ColumnsPreset>,
+    group_by: Option<Vec<crate::group_by::GroupKey>>,
     no_truncate: Option<bool>,
     time_format: Option<String>,
     log_format: Option<LogFormat>,
@@ -453,6 +1006,8 @@ struct AppConfig {
     preset: Option<Preset>,
     // duplicate removed
     export_zip: Option<bool>,
+    sign_reports: Option<bool>,
+    sign_key: Option<String>,
     redact: Option<Vec<String>>, 
     exit_code_by_risk: Option<bool>,
     print_effective_config: Option<bool>,
@@ -467,6 +1022,78 @@ struct AppConfig {
 
 fn main() {
     let mut args = Args::parse();
+    if let Some(Command::Init { config_out, rules_out, yes, force, channels, hours }) = args.command.clone() {
+        let mut a = crate::init::InitAnswers::default();
+        if !channels.is_empty() { a.channels = channels; }
+        if let Some(h) = hours { a.hours = h; }
+        if !yes { a = crate::init::ask(a); }
+        for (path, data) in [(config_out, crate::init::render_config(&a)), (rules_out, crate::init::render_rules(&DEFAULT_PATTERNS))] {
+            match crate::init::write_file(&path, &data, force) {
+                Ok(_) => println!("Written: {}", path),
+                Err(e) => { eprintln!("init: {}", e); std::process::exit(2); }
+            }
+        }
+        return;
+    }
+    if let Some(Command::Bench { file, iterations, max_records, json }) = args.command.clone() {
+        match crate::bench::run(std::path::Path::new(&file), iterations.max(1), max_records) {
+            Ok(rep) if json => println!("{}", serde_json::to_string_pretty(&rep).unwrap()),
+            Ok(rep) => crate::bench::print(&rep),
+            Err(e) => { eprintln!("bench: {}: {}", file, e); std::process::exit(2); }
+        }
+        return;
+    }
+    if let Some(Command::Compare { base, current, out }) = args.command.clone() {
+        let _ = ENABLE_COLOR.set(std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none());
+        let Some(cmp) = compare_ndjson(&base, &current) else { eprintln!("compare: failed to read {} or {}", base, current); std::process::exit(2) };
+        print_comparison(&cmp);
+        if let Some(p) = out && let Err(e) = write_compare_json(&p, &cmp) { eprintln!("compare: {}: {}", p, e); std::process::exit(2); }
+        return;
+    }
+    if let Some(Command::Merge { inputs, out }) = args.command.clone() {
+        let mut reports = Vec::new();
+        for p in &inputs {
+            match std::fs::read_to_string(p).map_err(|e| e.to_string()).and_then(|s| serde_json::from_str::<ReportSummary>(&s).map_err(|e| e.to_string())) {
+                Ok(rep) => reports.push((crate::merge::label(&rep, p), rep)),
+                Err(e) => { eprintln!("merge: {}: {}", p, e); std::process::exit(2); }
+            }
+        }
+        let Some(rep) = crate::merge::merge(&reports) else { return };
+        let data = if out.as_deref().is_some_and(|o| o.to_lowercase().ends_with(".html")) {
+            crate::html::render_html(&rep, args.theme, !args.no_emoji, args.time_zone, args.time_format.as_deref(), args.lang)
+        } else { serde_json::to_string_pretty(&rep).unwrap() };
+        match out {
+            Some(path) => if let Err(e) = write_output(&path, data.as_bytes()) { eprintln!("merge: {}: {}", path, e); std::process::exit(2); } else { println!("Written: {}", path); },
+            None => println!("{}", data),
+        }
+        return;
+    }
+    if let Some(Command::Rules { action: RulesAction::Test { rules, xml, ndjson, json } }) = args.command.clone() {
+        let Some(cfg) = crate::rules::load_rules(rules.as_deref()) else { eprintln!("rules test: no usable rules file"); std::process::exit(2) };
+        crate::rules::set_decoders(&cfg);
+        let events = match ndjson {
+            Some(p) => ndjson_events(&p).map(|v| v.into_iter().enumerate().map(|(i, e)| (format!("{}#{}", p, i + 1), e)).collect()).ok_or_else(|| format!("{}: cannot read", p)),
+            None => crate::rule_test::load_xml(&xml),
+        };
+        let events = events.unwrap_or_else(|e| { eprintln!("rules test: {}", e); std::process::exit(2) });
+        let rep = crate::rule_test::run(&cfg, events);
+        if json { println!("{}", serde_json::to_string_pretty(&rep).unwrap()); } else { crate::rule_test::print(&rep); }
+        return;
+    }
+    if let Some(Command::Catalog { provider, out, md }) = args.command.clone() {
+        let p = match crate::windows_live::provider_catalog(&provider) {
+            Ok(p) => p,
+            Err(code) => { eprintln!("catalog: cannot open publisher metadata for {} (error {})", provider, code); std::process::exit(2); }
+        };
+        let md = md || out.as_deref().is_some_and(|o| o.to_lowercase().ends_with(".md"));
+        let data = if md { crate::markdown::render_catalog(&p) } else { serde_json::to_string_pretty(&p).unwrap() };
+        match out {
+            Some(path) => if let Err(e) = write_output(&path, data.as_bytes()) { eprintln!("catalog: {}: {}", path, e); std::process::exit(2); } else { println!("Written: {}", path); },
+            None => println!("{}", data),
+        }
+        return;
+    }
+    apply_command(&mut args);
     if let Some(lc) = args.load_config.as_ref() { args.config = Some(lc.clone()); }
     if let Some(sh) = args.completions {
         let mut cmd = Args::command();
@@ -532,7 +1159,38 @@ fn main() {
         }
         builder.init();
     }
+    if args.rules_check { std::process::exit(crate::rules::check_file(args.rules.as_deref())); }
+    if args.elevate && args.computer.is_none() && crate::preflight::is_elevated() == Some(false) {
+        // The elevated copy gets its own console window, so its stdout is not seen here.
+        match crate::preflight::relaunch_elevated() {
+            Ok(()) => { log::info!("Relaunched elevated"); return; }
+            Err(e) => { log::error!("{}", e); std::process::exit(2); }
+        }
+    }
+    crate::windows_live::set_render_messages(!args.no_render_messages);
+    crate::windows_live::set_query_tuning(args.query_batch_size, args.query_timeout_ms);
+    COMPRESS.store(args.compress, std::sync::atomic::Ordering::Relaxed);
+    if let Some(host) = args.computer.as_deref() {
+        let password = args.password.clone().or_else(|| args.username.as_ref().and_then(|_| std::env::var("WINDOCTOR_PASSWORD").ok()));
+        if let Err(code) = crate::windows_live::open_remote_session(host, args.username.as_deref(), password.as_deref()) { log::error!("Cannot open an event log session on {}: error {}", host, code); std::process::exit(2); }
+        // WMI collectors would describe this machine, not the remote one.
+        args.no_wmi = true;
+    }
+    if args.list_channels {
+        let chans = crate::windows_live::list_channels();
+        if matches!(args.output, OutputFmt::Json) { println!("{}", serde_json::to_string_pretty(&chans).unwrap()); } else { print_channel_list(&chans, args.time_zone, args.time_format.as_deref()); }
+        return;
+    }
+    if let Some(ch) = args.list_providers.as_ref() {
+        let provs = crate::windows_live::list_providers(ch.as_deref(), &args.providers);
+        if matches!(args.output, OutputFmt::Json) { println!("{}", serde_json::to_string_pretty(&provs).unwrap()); } else { print_provider_list(&provs, args.msg_width.unwrap_or(96)); }
+        return;
+    }
     let _ = REDACT_KEYS.set(args.redact.clone());
+    crate::storms::set_threshold(args.storm_threshold);
+    if args.noise_report { crate::noise::enable(); }
+    crate::group_by::set_keys(args.group_by.clone());
+    if args.wizard { crate::wizard::apply(&mut args, &crate::wizard::ask()); }
     if let Some(p) = args.preset {
         match p {
             Preset::Triage => {
@@ -545,6 +1203,8 @@ fn main() {
                 if args.hours == 0 { args.hours = 24; }
                 if !args.collect_perf { args.collect_perf = true; }
                 if !args.smart_check { args.smart_check = true; }
+                if !args.service_health { args.service_health = true; }
+                if !args.policy_status { args.policy_status = true; }
             }
         }
     }
@@ -553,190 +1213,282 @@ fn main() {
     let color_default = std::io::stdout().is_terminal() && !no_color_env && term != "dumb";
     let enable_color = if args.force_color { true } else { color_default && !args.no_color };
     let _ = ENABLE_COLOR.set(enable_color);
-    let since = compute_since(&args);
-    let until = compute_until(&args);
-    let channels = if args.channels.is_empty() {
+    let _ = DISPLAY_TZ.set(args.time_zone);
+    crate::i18n::set_lang(args.lang);
+    if !args.live && !args.evtx_path.is_empty() && [args.since.as_deref(), args.until.as_deref()].into_iter().flatten().any(crate::time_expr::mentions_boot) {
+        crate::time_expr::set_last_boot(evtx_last_boot(&args.evtx_path));
+    }
+    for (flag, val) in [("--since", args.since.as_ref()), ("--until", args.until.as_ref())] {
+        if let Some(v) = val && let Err(e) = crate::time_expr::parse_time_expr(v, Utc::now(), args.time_zone) { log::error!("Invalid {} value: {}", flag, e); std::process::exit(2); }
+    }
+    if (args.days > 0 || args.hours > 0 || args.minutes > 0) && window_span(&args).and_then(|d| Utc::now().checked_sub_signed(d)).is_none() { log::error!("Invalid --days/--hours/--minutes: the window reaches too far back"); std::process::exit(2); }
+    if !args.keywords.is_empty() {
+        match crate::keywords::parse_filter(&args.keywords) {
+            Ok(m) => { let _ = KEYWORD_MASK.set(m); }
+            Err(e) => { log::error!("Invalid --keywords: {}", e); std::process::exit(2); }
+        }
+        // Audit Success counts as Information, which the default level filter drops.
+        if !args.only_critical && !args.only_errors && !args.only_warnings && args.min_level.is_none() && args.max_level.is_none() { args.no_level_filter = true; }
+    }
+    if !args.keyword_names.is_empty() { let _ = KEYWORD_NAMES.set(args.keyword_names.clone()); }
+    if args.resolve_messages { crate::msgfile::enable(); }
+    if let Some(q) = args.query.as_ref() {
+        match crate::query::parse_query(q) {
+            Ok(q) => { let _ = QUERY.set(q); }
+            Err(e) => { log::error!("Invalid --query: {}", e); std::process::exit(2); }
+        }
+    }
+    for (flag, list, cell) in [("--grep", &args.grep, &GREP), ("--exclude-grep", &args.exclude_grep, &EXCLUDE_GREP)] {
+        let res: Vec<Regex> = list.iter().map(|g| Regex::new(g).unwrap_or_else(|e| { log::error!("Invalid {} regex '{}': {}", flag, g, e); std::process::exit(2); })).collect();
+        if !res.is_empty() { let _ = cell.set(res); }
+    }
+    let evtx_glob = evtx_glob_set(&args).unwrap_or_else(|e| { log::error!("Invalid --evtx-glob: {}", e); std::process::exit(2); });
+    let mut fail_exprs = args.fail_on.clone();
+    if args.warnings_as_errors { fail_exprs.extend(["errors>0".to_string(), "warnings>0".to_string()]); }
+    fail_exprs.extend(args.fail_on_categories.iter().map(|c| format!("category:{}", c)).chain(args.fail_on_providers.iter().map(|p| format!("provider:{}", p))));
+    let fail_conds: Vec<crate::fail_policy::FailCond> = fail_exprs.iter().map(|s| crate::fail_policy::parse_fail_expr(s).unwrap_or_else(|e| { log::error!("Invalid --fail-on value: {}", e); std::process::exit(2); })).collect();
+    if let Some(t) = args.timeout { args.max_duration = Some(format!("{}s", t)); }
+    if let Some(v) = args.max_duration.as_ref() {
+        match crate::time_expr::parse_duration(v).and_then(|d| d.to_std().map_err(|e| e.to_string())) {
+            Ok(d) => { let _ = SCAN_DEADLINE.set(std::time::Instant::now() + d); }
+            Err(e) => { log::error!("Invalid --max-duration value: {}", e); std::process::exit(2); }
+        }
+    }
+    let ckpt_path = args.checkpoint_path.as_ref().map(PathBuf::from).unwrap_or_else(crate::checkpoint::default_path);
+    let ckpt_key = crate::checkpoint::key_for(&build_config_from_args(&args));
+    let resumed = if args.resume { crate::checkpoint::load(&ckpt_path, &ckpt_key) } else { None };
+    if args.resume && resumed.is_none() { log::warn!("No matching checkpoint at {}; starting a fresh scan", ckpt_path.display()); }
+    let (since, until) = match resumed.as_ref() { Some(cp) => (cp.since, cp.until), None => (compute_since(&args), compute_until(&args)) };
+    let channels = if args.all_channels {
+        let found: Vec<String> = crate::windows_live::list_channels().into_iter().filter(|c| scannable_channel(c, since)).map(|c| c.name).collect();
+        log::info!("--all-channels: {} channels with events since {}", found.len(), since);
+        found
+    } else if args.channels.is_empty() {
         vec!["System".to_string(), "Application".to_string()]
     } else {
         args.channels.clone()
     };
+    // Local Security reads need SeSecurityPrivilege enabled on the token; remote sessions authenticate separately.
+    if args.computer.is_none() && channels.iter().any(|c| c.eq_ignore_ascii_case("Security")) && !crate::windows_live::enable_security_privilege() {
+        log::warn!("SeSecurityPrivilege is not available; the Security channel will be skipped unless this process runs elevated");
+    }
+    // Channel reads only; EVTX/ETL files and NDJSON input have their own open errors.
+    if args.preflight || (args.evtx_path.is_empty() && args.etl_path.is_empty() && args.from_ndjson.is_none()) {
+        let pf = crate::preflight::check(&channels);
+        if args.preflight {
+            if matches!(args.output, OutputFmt::Json) { println!("{}", serde_json::to_string_pretty(&pf).unwrap()); } else { crate::preflight::print(&pf); }
+            std::process::exit(if pf.ok() { 0 } else { 2 });
+        }
+        for c in pf.channels.iter().filter(|c| !c.readable) { log::warn!("Cannot read {} (error {}){}", c.channel, c.error.unwrap_or(0), c.guidance.as_ref().map(|g| format!(": {}", g)).unwrap_or_default()); }
+    }
+    if args.count_only {
+        let keep = |level: u8, provider: &str, id: u32| pass_level(&args, level) && pass_provider(&args, provider) && pass_event_id(&args, id);
+        let mut counter = crate::counts::Counter::default();
+        let (files, tmp) = count_only_files(&args, &channels, evtx_glob.as_ref());
+        for f in &files {
+            if let Err(e) = counter.scan_file(f, since, until, &keep) { log::error!("EVTX open failed: {} ({})", f.to_string_lossy(), e); }
+        }
+        drop(tmp);
+        let rep = counter.finish(Some(since), Some(until));
+        if matches!(args.output, OutputFmt::Json) { println!("{}", serde_json::to_string_pretty(&rep).unwrap()); } else { crate::counts::print(&rep, args.top); }
+        return;
+    }
     let rules_cfg = crate::rules::load_rules(args.rules.as_deref());
+    if let Some(cfg) = rules_cfg.as_ref() { crate::rules::set_level_names(cfg); crate::rules::set_level_overrides(cfg); crate::rules::set_uses_event_data(cfg); crate::rules::set_decoders(cfg); }
     let patterns = if args.patterns.is_empty() {
-        if let Some(cfg) = rules_cfg.as_ref() {
-            if let Some(p) = cfg.event_patterns.as_ref() { p.clone() } else {
-                vec![
-                    "(?i)error",
-                    "(?i)fail",
-                    "(?i)exception",
-                    "(?i)timeout",
-                    "(?i)bugcheck",
-                    "(?i)crash",
-                    "(?i)access denied",
-                    "(?i)disk",
-                    "(?i)io error",
-                    "(?i)network",
-                    "(?i)service",
-                    "(?i)reset",
-                    "(?i)retry",
-                    "(?i)corrupt",
-                    "(?i)degraded",
-                    "(?i)unexpected",
-                    "(?i)dcom",
-                    "(?i)dns",
-                    "(?i)w32time",
-                    "(?i)group policy",
-                    "(?i)usb",
-                    "(?i)cdrom",
-                    "(?i)netlogon",
-                ]
-                .into_iter()
-                .map(|s| s.to_string())
-                .collect()
-            }
-        } else {
-            vec![
-                "(?i)error",
-                "(?i)fail",
-                "(?i)exception",
-                "(?i)timeout",
-                "(?i)bugcheck",
-                "(?i)crash",
-                "(?i)access denied",
-                "(?i)disk",
-                "(?i)io error",
-                "(?i)network",
-                "(?i)service",
-                "(?i)reset",
-                "(?i)retry",
-                "(?i)corrupt",
-                "(?i)degraded",
-                "(?i)unexpected",
-                "(?i)dcom",
-                "(?i)dns",
-                "(?i)w32time",
-                "(?i)group policy",
-                "(?i)usb",
-                "(?i)cdrom",
-                "(?i)netlogon",
-            ]
-            .into_iter()
-            .map(|s| s.to_string())
-            .collect()
+        match rules_cfg.as_ref().and_then(|cfg| cfg.event_patterns.clone()) {
+            Some(p) => p,
+            None => DEFAULT_PATTERNS.iter().map(|s| s.to_string()).collect(),
         }
     } else {
         args.patterns.clone()
     };
     let compiled_patterns: Vec<Regex> = if args.only_matched { patterns.iter().filter_map(|p| Regex::new(p).ok()).collect() } else { Vec::new() };
+    let mut forwarders = crate::forward::Forwarders::default();
+    if let Some(t) = args.syslog.as_ref() { forwarders.add("syslog", Box::new(crate::syslog::Syslog::connect(t).unwrap_or_else(|e| { log::error!("Invalid --syslog target: {}", e); std::process::exit(2); }))); }
+    if let Some(t) = args.gelf.as_ref() { forwarders.add("GELF", Box::new(crate::gelf::Gelf::connect(t).unwrap_or_else(|e| { log::error!("Invalid --gelf target: {}", e); std::process::exit(2); }))); }
+    if let Some(url) = args.splunk_hec_url.as_ref() {
+        let Some(token) = args.splunk_hec_token.clone().or_else(|| std::env::var("SPLUNK_HEC_TOKEN").ok()).filter(|t| !t.is_empty()) else { log::error!("--splunk-hec-url needs --splunk-hec-token or SPLUNK_HEC_TOKEN"); std::process::exit(2); };
+        forwarders.add("Splunk HEC", Box::new(crate::splunk::Hec::new(url, &token).unwrap_or_else(|e| { log::error!("Invalid --splunk-hec-url: {}", e); std::process::exit(2); })));
+    }
+    if let Some(url) = args.otlp_endpoint.as_ref() {
+        // os.version only describes this machine, not a --computer or file source.
+        let local = args.computer.is_none() && args.evtx_path.is_empty() && args.etl_path.is_empty() && args.from_ndjson.is_none();
+        let os_version = if local && !args.no_wmi { crate::sysinfo::collect_system_info().and_then(|si| si.version) } else { None };
+        forwarders.add("OTLP", Box::new(crate::otlp::Otlp::new(url, os_version).unwrap_or_else(|e| { log::error!("Invalid --otlp-endpoint: {}", e); std::process::exit(2); })));
+    }
+    if args.follow {
+        let json = matches!(args.output, OutputFmt::Json | OutputFmt::Ndjson) || args.ndjson_path.as_deref() == Some("-");
+        let names = export_field_names(&args.export_fields);
+        let want_data = needs_event_data(&names);
+        let widths = PrintWidths { msg: args.msg_width.unwrap_or(96), cause: args.cause_width.unwrap_or(24) };
+        let cols = text_columns(&args);
+        // The window only bounds the start; events keep arriving after `until` was computed.
+        let ok = crate::windows_live::follow_events(&channels, |e| {
+            if !pass_event(&args, &e, since, DateTime::<Utc>::MAX_UTC, &compiled_patterns) { return; }
+            forwarders.send(&e);
+            if json {
+                let ts = format_ts(e.time, args.time_zone, args.time_format.as_deref());
+                let data = if want_data { crate::event_xml::event_data_pairs_or_fallback(e.raw_xml.as_deref().unwrap_or(&e.content)) } else { std::collections::HashMap::new() };
+                let row = names.iter().map(|f| export_field_value(&e, f, &ts, &data, true)).collect();
+                println!("{}", ndjson_object(&e, &names, row, args.emit_eventdata, args.emit_xml));
+            } else {
+                println!("{}", sample_line(&e, &widths, args.time_zone, &cols, args.no_truncate, args.time_format.as_deref(), !args.no_emoji));
+            }
+        });
+        if !ok { log::error!("Follow failed: no channel could be subscribed"); std::process::exit(2); }
+        return;
+    }
+    if let Some(iv) = args.watch.as_ref() {
+        let interval = crate::time_expr::parse_duration(iv).and_then(|d| d.to_std().map_err(|e| e.to_string())).unwrap_or_else(|e| { log::error!("Invalid --watch interval: {}", e); std::process::exit(2); });
+        let span = until - since;
+        let window = if span.num_minutes() < 60 { format!("{} min", span.num_minutes()) } else { crate::channel_health::format_span(span.num_hours()) };
+        let widths = PrintWidths { msg: args.msg_width.unwrap_or(96), cause: args.cause_width.unwrap_or(24) };
+        let cols = text_columns(&args);
+        let mut last_seen: Option<DateTime<Utc>> = None;
+        // The window slides with each refresh; only events newer than the previous pass count as new.
+        loop {
+            let now = Utc::now();
+            let mut evs = crate::windows_live::query_live_events(&channels, now - span, None);
+            evs.retain(|e| pass_event(&args, e, now - span, now, &compiled_patterns));
+            let fresh = match last_seen { Some(t) => evs.iter().filter(|e| e.time > t && (1..=2).contains(&e.level)).map(|e| sample_line(e, &widths, args.time_zone, &cols, args.no_truncate, args.time_format.as_deref(), !args.no_emoji)).collect(), None => vec![] };
+            last_seen = evs.iter().map(|e| e.time).max().or(last_seen);
+            let n = evs.len();
+            let rep = build_summary_with_files(evs, patterns.clone(), args.top, 0, args.sort_by, args.sort_order, now - span, now, vec![], vec![], n, n, None, rules_cfg.clone(), None, None, None, None, None);
+            let snap = crate::dashboard::Snapshot {
+                refreshed: format_ts(now, args.time_zone, Some("%H:%M:%S")), window: window.clone(), channels: channels.clone(),
+                total: rep.total, criticals: rep.criticals, errors: rep.errors, warnings: rep.warnings, risk: rep.risk_grade.clone(),
+                top_providers: rep.by_provider.iter().take(5).cloned().collect(), top_hints: rep.novice_hints.iter().take(3).map(|h| h.message.clone()).collect(), fresh,
+            };
+            print!("{}{}", crate::dashboard::CLEAR, crate::dashboard::render(&snap));
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+            std::thread::sleep(interval);
+        }
+    }
     let mut events: Vec<EventItem> = vec![];
     let mut scanned_records: usize = 0;
     let mut parsed_events: usize = 0;
-    if args.live {
-        let live_events = crate::windows_live::query_live_events(&channels, since);
+    let mut by_source: Vec<(String, usize)> = Vec::new();
+    let mut scanned_by_source: Vec<(String, usize)> = Vec::new();
+    let mut completion: Vec<(String, u8)> = Vec::new();
+    // `-o ndjson` keeps stdout to the event records alone; everything else is quiet or goes to stderr.
+    if matches!(args.output, OutputFmt::Ndjson) && args.stream_ndjson.is_none() { args.stream_ndjson = Some("-".to_string()); }
+    let mut stream = args.stream_ndjson.as_ref().map(|p| EventStream::open(p, &args).unwrap_or_else(|e| { log::error!("NDJSON stream open failed for {}: {}", p, e); std::process::exit(2); }));
+    let cp = match resumed {
+        Some(mut cp) => {
+            log::info!("Resuming from {} ({} events, {} files)", ckpt_path.display(), cp.events.len(), cp.files.len());
+            events = std::mem::take(&mut cp.events);
+            scanned_records = cp.scanned_records;
+            parsed_events = cp.parsed_events;
+            cp
+        }
+        None => crate::checkpoint::Checkpoint { key: ckpt_key, since, until, scanned_records: 0, parsed_events: 0, files: vec![], events: vec![] },
+    };
+    crate::checkpoint::start(ckpt_path, cp);
+    if args.from_ndjson.is_some() {
+        // Offline: the report is rebuilt from the NDJSON file below, so nothing is collected.
+    } else if args.live {
+        let live_events = crate::windows_live::query_live_events(&channels, since, args.after_record_id);
+        if args.max_duration.is_some() { completion = live_completion(&live_events, &channels, since, until); }
         scanned_records += live_events.len();
         parsed_events += live_events.len();
         events = live_events;
+        events.retain(|e| e.time >= since && e.time <= until && pass_level(&args, crate::rules::effective_level(e)) && pass_provider(&args, &e.provider) && pass_identity(&args, e) && pass_query(e) && pass_grep(e) && pass_keywords(e) && pass_tasks(&args, e) && pass_record(&args, e) && (!args.only_matched || compiled_patterns.iter().any(|re| re.is_match(&e.content))));
+        if let Some(st) = stream.as_mut() { for e in &events { st.write(e); } }
         if args.subscribe_minutes > 0 {
-            let more = crate::windows_live::subscribe_events(&channels, args.subscribe_minutes * 60);
-            scanned_records += more.len();
-            parsed_events += more.len();
-            events.extend(more);
-        }
-        events.retain(|e| e.time >= since && e.time <= until && pass_level(&args, e.level) && pass_provider(&args, &e.provider) && (!args.only_matched || compiled_patterns.iter().any(|re| re.is_match(&e.content))));
-    } else if let Some(evtx) = args.evtx_path.as_ref() {
-        let p = PathBuf::from(evtx);
-        if !p.exists() { log::warn!("Missing EVTX: {}", p.to_string_lossy()); }
-        if p.is_file() {
-            if let Ok(mut parser) = EvtxParser::from_path(&p) {
-                let ch = p.file_stem().and_then(|s| s.to_str()).unwrap_or("").to_string();
-                let pb = if args.progress { Some(indicatif::ProgressBar::new_spinner()) } else { None };
-                if let Some(ref pb) = pb { pb.set_message(format!("Scanning {}", ch)); }
-                for r in parser.records() {
+            // Filtered as they arrive, so only matching events are held; they postdate `until` by design.
+            let mut tally = crate::counts::Counter::default();
+            let started = std::time::Instant::now();
+            let tick = (args.subscribe_progress > 0).then(|| std::time::Duration::from_secs(args.subscribe_progress));
+            crate::windows_live::subscribe_events(&channels, std::time::Duration::from_secs(args.subscribe_minutes * 60), tick, |feed| match feed {
+                crate::windows_live::Feed::Event(e) => {
                     scanned_records += 1;
-                    if let Some(ref pb) = pb { if scanned_records % 500 == 0 { pb.tick(); pb.set_message(format!("Scanned {} records", scanned_records)); } }
-                    if r.is_err() { continue; }
-                    let r = r.unwrap();
-                    let xml = r.data;
-                    if let Some(mut item) = parse_event_xml(&xml, &ch) {
-                        parsed_events += 1;
-                        if let Some(msg) = crate::decoder::decode_event(&item.provider, item.event_id, &xml) { item.content = msg; }
-                        if args.emit_xml || args.emit_eventdata { item.raw_xml = Some(xml.clone()); }
-                        if item.time >= since && item.time <= until && pass_level(&args, item.level) && pass_provider(&args, &item.provider) && pass_event_id(&args, item.event_id) && (!args.only_matched || compiled_patterns.iter().any(|re| re.is_match(&item.content))) { events.push(item); }
+                    parsed_events += 1;
+                    let kept = pass_event(&args, &e, since, DateTime::<Utc>::MAX_UTC, &compiled_patterns);
+                    tally.add_event(&e, kept);
+                    if kept { if let Some(st) = stream.as_mut() { st.write(&e); } events.push(e); }
+                }
+                crate::windows_live::Feed::Tick => if !args.quiet { eprintln!("[+{}s] {}", started.elapsed().as_secs(), tally.progress_line()); },
+            });
+        }
+    } else if !args.evtx_path.is_empty() {
+        for evtx in args.evtx_path.iter().filter(|s| !s.is_empty()) {
+            if evtx == "-" {
+                match buffer_stdin_to_temp() {
+                    Ok(tmp) => {
+                        let before = scanned_records;
+                        match scan_evtx_file(&tmp, "stdin", &args, since, until, &compiled_patterns, &mut events, &mut stream, &mut scanned_records, &mut parsed_events, &mut completion) {
+                            Ok(n) => { by_source.push(("stdin".to_string(), n)); scanned_by_source.push(("stdin".to_string(), scanned_records - before)); }
+                            Err(e) => log::error!("EVTX parse failed for stdin: {}", e),
+                        }
                     }
-                    if events.len() >= args.max_events { break; }
+                    Err(e) => log::error!("Failed to buffer EVTX from stdin: {}", e),
                 }
-                if let Some(pb) = pb { pb.finish_and_clear(); }
-            } else { log::error!("EVTX open failed: {}. Reading .evtx may require Administrator privileges.", p.to_string_lossy()); }
-        } else if p.is_dir() {
-            let mut set_opt = None;
-            if let Some(g) = args.evtx_glob.as_ref() {
-                let mut gb = globset::GlobSetBuilder::new();
-                let glob = globset::GlobBuilder::new(g).case_insensitive(true).build().unwrap();
-                gb.add(glob);
-                set_opt = Some(gb.build().unwrap());
+                continue;
             }
-            let wd = if args.evtx_recursive { walkdir::WalkDir::new(&p) } else { walkdir::WalkDir::new(&p).max_depth(1) };
-            for de in wd.into_iter().filter_map(Result::ok) {
-                let fp = de.path();
-                if !fp.is_file() { continue; }
-                if let Some(set) = &set_opt { if !set.is_match(fp) { continue; } }
-                if fp.extension().and_then(|e| e.to_str()).map(|s| s.eq_ignore_ascii_case("evtx")).unwrap_or(false) {
-                    let mut parser = match EvtxParser::from_path(fp) { Ok(x) => x, Err(e) => { log::error!("EVTX open failed for {}: {}", fp.to_string_lossy(), e); continue } };
+            let p = PathBuf::from(evtx);
+            if !p.exists() { log::warn!("Missing EVTX: {}", p.to_string_lossy()); continue; }
+            if p.is_file() {
+                let ch = p.file_stem().and_then(|s| s.to_str()).unwrap_or("").to_string();
+                let before = scanned_records;
+                match scan_evtx_file(&p, &ch, &args, since, until, &compiled_patterns, &mut events, &mut stream, &mut scanned_records, &mut parsed_events, &mut completion) {
+                    Ok(n) => { by_source.push((p.to_string_lossy().into_owned(), n)); scanned_by_source.push((p.to_string_lossy().into_owned(), scanned_records - before)); }
+                    Err(e) => log::error!("EVTX open failed: {} ({}). Reading .evtx may require Administrator privileges.", p.to_string_lossy(), e),
+                }
+            } else if p.is_dir() {
+                for fp in evtx_dir_files(&p, args.evtx_recursive, evtx_glob.as_ref()) {
                     let ch = fp.file_stem().and_then(|s| s.to_str()).unwrap_or("").to_string();
-                    let pb = if args.progress { Some(indicatif::ProgressBar::new_spinner()) } else { None };
-                    if let Some(ref pb) = pb { pb.set_message(format!("Scanning {}", ch)); }
-                    for r in parser.records() {
-                        scanned_records += 1;
-                        if let Some(ref pb) = pb { if scanned_records % 500 == 0 { pb.tick(); pb.set_message(format!("Scanned {} records", scanned_records)); } }
-                        if r.is_err() { continue; }
-                        let r = r.unwrap();
-                        let xml = r.data;
-                        if let Some(mut item) = parse_event_xml(&xml, &ch) {
-                            parsed_events += 1;
-                            if let Some(msg) = crate::decoder::decode_event(&item.provider, item.event_id, &xml) { item.content = msg; }
-                            if args.emit_xml || args.emit_eventdata { item.raw_xml = Some(xml.clone()); }
-                            if item.time >= since && pass_level(&args, item.level) && pass_provider(&args, &item.provider) && pass_event_id(&args, item.event_id) && (!args.only_matched || compiled_patterns.iter().any(|re| re.is_match(&item.content))) { events.push(item); }
-                        }
-                        if events.len() >= args.max_events { break; }
+                    let before = scanned_records;
+                    match scan_evtx_file(&fp, &ch, &args, since, until, &compiled_patterns, &mut events, &mut stream, &mut scanned_records, &mut parsed_events, &mut completion) {
+                        Ok(n) => { by_source.push((fp.to_string_lossy().into_owned(), n)); scanned_by_source.push((fp.to_string_lossy().into_owned(), scanned_records - before)); }
+                        Err(e) => log::error!("EVTX open failed for {}: {}", fp.to_string_lossy(), e),
                     }
-                    if let Some(pb) = pb { pb.finish_and_clear(); }
+                    if events.len() >= args.max_events && stream.is_none() { break; }
                 }
+            } else {
+                log::warn!("EVTX path is neither file nor directory: {}", p.to_string_lossy());
             }
-        } else {
-            log::warn!("EVTX path is neither file nor directory: {}", p.to_string_lossy());
+            if events.len() >= args.max_events && stream.is_none() { break; }
         }
-    } else {
-        let mut live_events = crate::windows_live::query_live_events(&channels, since);
+    } else if args.etl_path.is_empty() {
+        let mut live_events = crate::windows_live::query_live_events(&channels, since, args.after_record_id);
+        if args.max_duration.is_some() { completion = live_completion(&live_events, &channels, since, until); }
         scanned_records += live_events.len();
         parsed_events += live_events.len();
-        live_events.retain(|e| e.time >= since && e.time <= until && pass_level(&args, e.level) && pass_provider(&args, &e.provider) && pass_event_id(&args, e.event_id));
+        live_events.retain(|e| e.time >= since && e.time <= until && pass_level(&args, crate::rules::effective_level(e)) && pass_provider(&args, &e.provider) && pass_event_id(&args, e.event_id) && pass_identity(&args, e) && pass_query(e) && pass_grep(e) && pass_keywords(e) && pass_tasks(&args, e) && pass_record(&args, e));
+        if let Some(st) = stream.as_mut() { for e in &live_events { st.write(e); } }
         if !live_events.is_empty() {
             events = live_events;
         } else {
+            completion.clear();
             for ch in channels.clone() {
                 let path = PathBuf::from(r"C:\Windows\System32\winevt\Logs").join(format!("{}.evtx", ch));
                 if !path.exists() { log::warn!("Missing EVTX: {}", path.to_string_lossy()); continue; }
-                let mut parser = match EvtxParser::from_path(&path) { Ok(p) => p, Err(e) => { log::error!("EVTX open failed for {}: {}. Reading .evtx may require Administrator privileges.", ch, e); continue } };
-                let pb = if args.progress { Some(indicatif::ProgressBar::new_spinner()) } else { None };
-                if let Some(ref pb) = pb { pb.set_message(format!("Scanning {}", ch)); }
-                for r in parser.records() {
-                    scanned_records += 1;
-                    if let Some(ref pb) = pb { if scanned_records % 500 == 0 { pb.tick(); pb.set_message(format!("Scanned {} records", scanned_records)); } }
-                    if r.is_err() { continue; }
-                    let r = r.unwrap();
-                    let xml = r.data;
-                    if let Some(mut item) = parse_event_xml(&xml, &ch) {
-                        parsed_events += 1;
-                        if let Some(msg) = crate::decoder::decode_event(&item.provider, item.event_id, &xml) { item.content = msg; }
-                        if args.emit_xml || args.emit_eventdata { item.raw_xml = Some(xml.clone()); }
-                        if item.time >= since && item.time <= until && pass_level(&args, item.level) && pass_provider(&args, &item.provider) && pass_event_id(&args, item.event_id) && (!args.only_matched || compiled_patterns.iter().any(|re| re.is_match(&item.content))) { events.push(item); }
-                    }
-                    if events.len() >= args.max_events { break; }
-                }
-                if let Some(pb) = pb { pb.finish_and_clear(); }
+                if let Err(e) = scan_evtx_file(&path, &ch, &args, since, until, &compiled_patterns, &mut events, &mut stream, &mut scanned_records, &mut parsed_events, &mut completion) { log::error!("EVTX open failed for {}: {}. Reading .evtx may require Administrator privileges.", ch, e); }
+                if events.len() >= args.max_events && stream.is_none() { break; }
+            }
+        }
+    }
+    for p in args.etl_path.iter().filter(|s| !s.is_empty()) {
+        let label = std::path::Path::new(p).file_stem().and_then(|s| s.to_str()).unwrap_or("ETL").to_string();
+        match crate::windows_live::query_trace_file(p, &label, args.max_events) {
+            Ok(items) => {
+                scanned_records += items.len();
+                parsed_events += items.len();
+                let before = events.len();
+                events.extend(items.into_iter().filter(|e| pass_event(&args, e, since, until, &compiled_patterns)));
+                if let Some(st) = stream.as_mut() { for e in &events[before..] { st.write(e); } }
+                by_source.push((p.clone(), events.len() - before));
             }
+            Err(code) => log::error!("ETL open failed for {}: error {}", p, code),
         }
     }
-    if events.len() > args.max_events { events.sort_by(|a, b| b.time.cmp(&a.time)); events.truncate(args.max_events); }
+    crate::checkpoint::finish();
+    if by_source.len() > 1 {
+        let dups = merge_sources(&mut events);
+        if dups > 0 { log::info!("Merged {} EVTX sources; dropped {} duplicate records present in more than one file", by_source.len(), dups); }
+    }
+    if events.len() > args.max_events { events.sort_by_key(|e| std::cmp::Reverse(e.time)); events.truncate(args.max_events); }
     let mut file_terms: Vec<(String, usize)> = vec![];
     let mut file_samples: Vec<crate::file_scan::FileSample> = vec![];
     if let Some(root) = args.scan_path.as_ref() {
@@ -746,7 +1498,7 @@ fn main() {
         file_samples = fs.samples;
     }
     {
-        let any_time_flag = args.last10m || args.last_hour || args.last_day || args.last_week || args.hours > 0 || args.minutes > 0;
+        let any_time_flag = any_time_flag(&args);
         if !any_time_flag {
             let mut crit: Vec<EventItem> = events.iter().filter(|e| e.level == 1).cloned().collect();
             crit.sort_by(|a, b| b.time.cmp(&a.time));
@@ -760,130 +1512,231 @@ fn main() {
             events = combined;
         }
     }
-    let any_time_flag = args.last10m || args.last_hour || args.last_day || args.last_week || args.hours > 0 || args.minutes > 0;
+    let any_time_flag = any_time_flag(&args);
     let mode = if !any_time_flag { Some(format!("Last {} critical + last {} errors", args.last_criticals, args.last_errors)) } else { None };
     let sample_n = args.sample_count.unwrap_or(args.top);
     let perf_counters = if args.collect_perf && !args.no_wmi { Some(crate::perf::collect_perf_counters()) } else { None };
     let smart_pred = if args.smart_check && !args.no_wmi { crate::perf::smart_predict_failure() } else { None };
-    let mut summary = build_summary_with_files(events, patterns.clone(), args.top, sample_n, args.sort_by, args.sort_order, since, until, file_terms.clone(), file_samples.clone(), scanned_records, parsed_events, mode, rules_cfg.clone(), perf_counters.clone(), smart_pred, args.per_channel_sample_limit, args.per_provider_sample_limit);
+    let mut services_down = if args.service_health && !args.no_wmi { crate::services::collect_stopped_auto_services() } else { vec![] };
+    crate::services::attach_last_scm_events(&mut services_down, &events);
+    let policy = if args.policy_status && !args.no_wmi {
+        let mut st = crate::policy::collect_rsop();
+        crate::policy::merge_events(&mut st, &events);
+        Some(st)
+    } else { None };
+    let dev_env = if args.dev_env { Some(crate::devenv::collect_dev_env()) } else { None };
+    let sync_accounts = if args.onedrive { crate::sync::collect_onedrive() } else { vec![] };
+    let channel_health = if args.channel_health && args.live { crate::channel_health::collect(&channels) } else { vec![] };
+    // Only describes this machine, so reports built from EVTX or NDJSON input leave it out.
+    let system_info = if !args.no_wmi && !args.no_sysinfo && args.evtx_path.is_empty() && args.etl_path.is_empty() && args.from_ndjson.is_none() { crate::sysinfo::collect_system_info() } else { None };
+    let dev_hints = dev_env.as_ref().map(|st| crate::devenv::dev_env_hints(st, &events)).unwrap_or_default();
+    for e in &events { forwarders.send(e); }
+    forwarders.finish(events.len());
+    // Record IDs only identify an event within one channel or one EVTX file.
+    let evtx_out_records: Vec<(String, u64)> = match (args.evtx_out.as_ref(), args.evtx_path.as_slice()) {
+        (None, _) => vec![],
+        (Some(_), []) if args.etl_path.is_empty() && args.from_ndjson.is_none() => events.iter().filter_map(|e| Some((e.channel.clone(), e.record_id?))).collect(),
+        (Some(_), [one]) if PathBuf::from(one).is_file() => { let src = format!("file://{}", std::fs::canonicalize(one).map(|p| p.to_string_lossy().trim_start_matches(r"\\?\").to_string()).unwrap_or_else(|_| one.clone())); events.iter().filter_map(|e| Some((src.clone(), e.record_id?))).collect() }
+        (Some(_), _) => { log::warn!("--evtx-out needs live channels or a single .evtx input; skipped"); vec![] }
+    };
+    // `--export-all-events`: file exports get every filtered event rather than the deduplicated, capped samples.
+    let mut all_events = if args.export_all_events { let mut v = events.clone(); sort_events(&mut v, args.sort_by, args.sort_order); v } else { vec![] };
+    let mut summary = build_summary_with_files(events, patterns.clone(), args.top, sample_n, args.sort_by, args.sort_order, since, until, file_terms.clone(), file_samples.clone(), scanned_records, parsed_events, mode, rules_cfg.clone(), perf_counters.clone(), smart_pred, args.per_channel_sample_limit, args.per_provider_sample_limit, args.per_computer_sample_limit);
+    summary.by_source = by_source;
+    summary.scanned_by_source = scanned_by_source;
+    summary.partial = completion.iter().any(|(_, p)| *p < 100);
+    if summary.partial { log::warn!("Time budget exhausted; report is partial"); }
+    summary.completion = completion;
+    merge_extra_hints(&mut summary, crate::services::service_hints(&services_down));
+    summary.services_down = services_down;
+    if let Some(st) = policy.as_ref() { merge_extra_hints(&mut summary, crate::policy::policy_hints(st)); }
+    summary.policy = policy;
+    merge_extra_hints(&mut summary, dev_hints);
+    summary.dev_env = dev_env;
+    merge_extra_hints(&mut summary, crate::sync::sync_hints(&sync_accounts));
+    summary.sync = sync_accounts;
+    merge_extra_hints(&mut summary, crate::channel_health::hints(&channel_health));
+    summary.channel_health = channel_health;
+    summary.system_info = system_info;
+    if args.battery && !args.no_wmi {
+        let mut b = summary.battery.take().unwrap_or_default();
+        b.batteries = crate::battery::collect();
+        merge_extra_hints(&mut summary, crate::battery::wear_hints(&b.batteries, args.battery_min_health));
+        summary.battery = Some(b);
+    }
     if let Some(path) = args.from_ndjson.as_ref() {
         if args.check_ndjson_schema && !check_ndjson_schema(path) { log::error!("NDJSON schema check failed for {}", path); std::process::exit(2); }
-        if let Some(ev) = read_ndjson_full(path) {
-            let mut items: Vec<EventItem> = Vec::new();
-            for r in ev {
-                if let Some(sv) = r.schema_version { if sv != 1 { log::warn!("Skipping NDJSON record with unsupported schema_version: {}", sv); continue; } }
-                let time = parse_system_time(&r.time.unwrap_or_else(|| Utc::now().to_rfc3339())).unwrap_or(Utc::now());
-                let severity = match r.severity.as_deref() { Some("Critical")=>1, Some("Error")=>2, Some("Warning")=>3, Some("Information")=>4, _=>0 };
-                items.push(EventItem { time, level: severity, channel: r.channel.unwrap_or_else(|| "".to_string()), provider: r.provider.unwrap_or_else(|| "".to_string()), event_id: r.event_id.unwrap_or(0), content: r.message.or(r.cause).unwrap_or_default(), raw_xml: None });
-            }
-            summary = build_summary_with_files(items, patterns.clone(), args.top, sample_n, args.sort_by, args.sort_order, since, until, vec![], vec![], scanned_records, parsed_events, Some("Offline NDJSON".to_string()), rules_cfg.clone(), None, None, args.per_channel_sample_limit, args.per_provider_sample_limit);
+        if let Some(mut items) = ndjson_events(path) {
+            let read = items.len();
+            // Without an explicit window the export's own time span is the report window.
+            let (since, until) = if args.since.is_some() || args.until.is_some() || any_time_flag { (since, until) } else {
+                (items.iter().map(|e| e.time).min().unwrap_or(since), items.iter().map(|e| e.time).max().unwrap_or(until))
+            };
+            items.retain(|e| e.time >= since && e.time <= until && pass_level(&args, crate::rules::effective_level(e)) && pass_provider(&args, &e.provider) && pass_event_id(&args, e.event_id) && pass_identity(&args, e) && pass_query(e) && pass_grep(e) && pass_keywords(e) && pass_tasks(&args, e) && pass_record(&args, e));
+            if let Some(st) = stream.as_mut() { for e in &items { st.write(e); } }
+            if args.export_all_events { all_events = items.clone(); sort_events(&mut all_events, args.sort_by, args.sort_order); }
+            summary = build_summary_with_files(items, patterns.clone(), args.top, sample_n, args.sort_by, args.sort_order, since, until, vec![], vec![], read, read, Some("Offline NDJSON".to_string()), rules_cfg.clone(), None, None, args.per_channel_sample_limit, args.per_provider_sample_limit, args.per_computer_sample_limit);
         }
     }
+    if let (Some(st), Some(p)) = (stream.take(), args.stream_ndjson.as_ref()) {
+        match st.finish() {
+            Ok(n) => if !args.quiet && p != "-" { println!("{}", paint(&format!("NDJSON stream written: {} ({} events)", export_path(p), n), "1;36")); },
+            Err(e) => log::error!("NDJSON stream write failed for {}: {}", p, e),
+        }
+    }
+    let stdout_export = [&args.json_path, &args.csv_path, &args.ndjson_path, &args.md_path, &args.tsv_path, &args.sarif_path, &args.cef_path, &args.leef_path, &args.elastic_bulk_path, &args.stream_ndjson].iter().any(|p| p.as_deref() == Some("-")) || (args.template.is_some() && args.template_out.as_deref().unwrap_or("-") == "-");
+    let quiet = args.quiet || stdout_export || args.porcelain;
+    let export_events: &[EventItem] = if args.export_all_events { &all_events } else { &summary.samples };
     if let Some(path) = args.html.as_ref() {
         let html = crate::html::render_html(&summary, args.theme, !args.no_emoji, args.time_zone, args.time_format.as_deref(), args.lang);
-        match std::fs::write(path, html) {
+        match crate::atomic::write_atomic(path, html.as_bytes()) {
             Ok(_) => {
                 if !args.no_open { open_file_default(PathBuf::from(path)); }
-                if !args.quiet { println!("{}", paint(&format!("HTML generated: {}", path), "1;36")); }
+                if !quiet { println!("{}", paint(&format!("HTML generated: {}", path), "1;36")); }
             }
             Err(e) => { log::error!("HTML write failed for {}: {}", path, e); }
         }
-    } else if summary.mode.is_some() {
+    } else if summary.mode.is_some() && !matches!(args.output, OutputFmt::Ndjson) {
         let def = PathBuf::from("report.html");
         let html = crate::html::render_html(&summary, args.theme, !args.no_emoji, args.time_zone, args.time_format.as_deref(), args.lang);
-        match std::fs::write(&def, html) {
+        match crate::atomic::write_atomic(&def, html.as_bytes()) {
             Ok(_) => {
                 let s = def.to_string_lossy().into_owned();
                 if !args.no_open { open_file_default(def.clone()); }
-                if !args.quiet { println!("{}", paint(&format!("HTML generated: {}", s), "1;36")); }
+                if !quiet { println!("{}", paint(&format!("HTML generated: {}", s), "1;36")); }
             }
             Err(e) => { log::error!("HTML write failed for {}: {}", def.to_string_lossy(), e); }
         }
     }
     match args.output {
+        _ if args.porcelain => println!("{}", porcelain_line(&summary)),
+        OutputFmt::Text if stdout_export => {
+            if args.json_path.as_deref() == Some("-") && let Err(e) = write_output("-", &serde_json::to_vec_pretty(&summary).unwrap()) { log::error!("JSON write failed for stdout: {}", e); }
+        }
         OutputFmt::Text => {
             let widths = PrintWidths { msg: args.msg_width.unwrap_or(96), cause: args.cause_width.unwrap_or(24) };
-            let cols = if args.columns.is_empty() {
-                match args.columns_preset.unwrap_or(ColumnsPreset::Detailed) {
-                    ColumnsPreset::Minimal => vec![Column::Time, Column::Severity, Column::Provider, Column::EventId, Column::Message],
-                    ColumnsPreset::Detailed => vec![Column::Time, Column::Severity, Column::Channel, Column::Provider, Column::EventId, Column::Cause, Column::Message],
-                }
-            } else { args.columns.clone() };
+            let cols = text_columns(&args);
+            let cols = if args.columns.is_empty() && multi_computer(&summary) { let mut c = cols; c.insert(2, Column::Computer); c } else { cols };
             match args.text_format {
                 TextFormat::Lines => print_text(&summary, widths, args.no_header, args.summary_only, args.analysis_only, args.time_zone, &cols, args.no_truncate, args.time_format.as_deref(), !args.no_emoji),
                 TextFormat::Table => print_text_table(&summary, widths, args.no_header, args.summary_only, args.analysis_only, args.time_zone, &cols, args.no_truncate, args.time_format.as_deref(), !args.no_emoji),
             }
         },
+        OutputFmt::Ndjson => {}
         OutputFmt::Json => {
             if let Some(p) = args.json_path.as_ref() {
-                match std::fs::write(p, serde_json::to_vec_pretty(&summary).unwrap()) {
-                    Ok(_) => { if !args.quiet { println!("{}", paint(&format!("JSON written: {}", p), "1;36")); } },
+                match write_export(p, &serde_json::to_vec_pretty(&summary).unwrap()) {
+                    Ok(_) => { if !quiet { println!("{}", paint(&format!("JSON written: {}", export_path(p)), "1;36")); } },
                     Err(e) => log::error!("JSON write failed for {}: {}", p, e),
                 }
-            } else if !args.quiet { println!("{}", serde_json::to_string_pretty(&summary).unwrap()); }
+            } else if !quiet { println!("{}", serde_json::to_string_pretty(&summary).unwrap()); }
+        }
+    }
+    if let Some(out) = args.evtx_out.as_ref() {
+        if evtx_out_records.is_empty() { log::warn!("No events with record IDs to write to {}", out); }
+        for r in crate::evtx_out::export(out, &evtx_out_records) {
+            match r { Ok(p) => if !quiet { println!("{}", paint(&format!("EVTX written: {}", p.display()), "1;36")); }, Err(e) => log::error!("EVTX export failed: {}", e) }
         }
     }
     if let Some(p) = args.csv_path.as_ref() {
-        if let Err(e) = write_csv(p, &summary, args.time_zone, args.time_format.as_deref()) { log::error!("CSV write failed for {}: {}", p, e); } else if !args.quiet { println!("{}", paint(&format!("CSV written: {}", p), "1;36")); }
+        if let Err(e) = write_csv(p, &summary, export_events, args.time_zone, args.time_format.as_deref(), csv_fields(&args)) { log::error!("CSV write failed for {}: {}", p, e); } else if !quiet { println!("{}", paint(&format!("CSV written: {}", export_path(p)), "1;36")); }
     }
     if let Some(p) = args.ndjson_path.as_ref() {
-        if let Err(e) = write_ndjson(p, &summary, args.time_zone, args.time_format.as_deref(), args.emit_eventdata, args.emit_xml) { log::error!("NDJSON write failed for {}: {}", p, e); } else if !args.quiet { println!("{}", paint(&format!("NDJSON written: {}", p), "1;36")); }
+        if let Err(e) = write_ndjson(p, &summary, export_events, args.time_zone, args.time_format.as_deref(), args.emit_eventdata, args.emit_xml, &args.export_fields) { log::error!("NDJSON write failed for {}: {}", p, e); } else if !quiet { println!("{}", paint(&format!("NDJSON written: {}", export_path(p)), "1;36")); }
     }
     if let Some(p) = args.md_path.as_ref() {
         let md = crate::markdown::render_markdown(&summary, args.time_zone, args.time_format.as_deref());
-        match std::fs::write(p, md) {
-            Ok(_) => { if !args.quiet { println!("{}", paint(&format!("Markdown written: {}", p), "1;36")); } }
+        match write_output(p, md.as_bytes()) {
+            Ok(_) => { if !quiet { println!("{}", paint(&format!("Markdown written: {}", p), "1;36")); } }
             Err(e) => { log::error!("Markdown write failed for {}: {}", p, e); }
         }
     }
+    if let Some(p) = args.sarif_path.as_ref() {
+        match write_output(p, &serde_json::to_vec_pretty(&crate::sarif::render(&summary)).unwrap()) {
+            Ok(_) => { if !quiet { println!("{}", paint(&format!("SARIF written: {}", p), "1;36")); } }
+            Err(e) => { log::error!("SARIF write failed for {}: {}", p, e); }
+        }
+    }
+    if let Some(t) = args.template.as_ref() {
+        let out = args.template_out.as_deref().unwrap_or("-");
+        let rendered = std::fs::read_to_string(t).map_err(|e| e.to_string()).and_then(|src| crate::template::render(&src, &serde_json::to_value(&summary).unwrap()));
+        match rendered.and_then(|text| write_output(out, text.as_bytes()).map_err(|e| e.to_string())) {
+            Ok(_) => if !quiet { println!("{}", paint(&format!("Template output written: {}", out), "1;36")); },
+            Err(e) => log::error!("Template {} failed: {}", t, e),
+        }
+    }
+    for (p, name, line) in [(&args.cef_path, "CEF", crate::siem::cef_line as fn(&EventItem) -> String), (&args.leef_path, "LEEF", crate::siem::leef_line), (&args.elastic_bulk_path, "Elasticsearch bulk", crate::elastic::bulk_lines)] {
+        let Some(p) = p.as_ref() else { continue };
+        if let Err(e) = write_lines(p, export_events, line) { log::error!("{} write failed for {}: {}", name, p, e); } else if !quiet { println!("{}", paint(&format!("{} written: {}", name, p), "1;36")); }
+    }
     if let Some(p) = args.tsv_path.as_ref() {
-        if let Err(e) = write_tsv(p, &summary, args.time_zone, args.time_format.as_deref()) { log::error!("TSV write failed for {}: {}", p, e); } else if !args.quiet { println!("{}", paint(&format!("TSV written: {}", p), "1;36")); }
+        if let Err(e) = write_tsv(p, &summary, export_events, args.time_zone, args.time_format.as_deref(), csv_fields(&args)) { log::error!("TSV write failed for {}: {}", p, e); } else if !quiet { println!("{}", paint(&format!("TSV written: {}", export_path(p)), "1;36")); }
     }
     
     if let Some(p) = args.md_fix_path.as_ref() {
         let md = crate::markdown::render_fix_markdown(&summary, args.time_zone, args.time_format.as_deref());
-        match std::fs::write(p, md.as_bytes()) {
-            Ok(_) => { if !args.quiet { println!("{}", paint(&format!("Fix-It Markdown written: {}", p), "1;36")); } }
+        match crate::atomic::write_atomic(p, md.as_bytes()) {
+            Ok(_) => { if !quiet { println!("{}", paint(&format!("Fix-It Markdown written: {}", p), "1;36")); } }
             Err(e) => log::error!("Fix-It Markdown write failed for {}: {}", p, e),
         }
     }
+    let host = std::env::var("COMPUTERNAME").ok().or_else(|| summary.samples.first().map(|e| e.computer.clone())).unwrap_or_default();
+    let argv: Vec<String> = std::env::args().skip(1).collect();
+    let mut dir_written: Vec<PathBuf> = Vec::new();
     if let Some(dir) = args.export_dir.as_ref() {
         let _ = std::fs::create_dir_all(dir);
         let ts = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
         let base = std::path::PathBuf::from(dir);
         let html_path = base.join(format!("report-{}.html", ts));
         let html = crate::html::render_html(&summary, args.theme, !args.no_emoji, args.time_zone, args.time_format.as_deref(), args.lang);
-        match std::fs::write(&html_path, html) {
+        match crate::atomic::write_atomic(&html_path, html.as_bytes()) {
             Ok(_) => {
                 if !args.no_open { open_file_default(html_path.clone()); }
-                if !args.quiet {
+                if !quiet {
                     println!("{}", paint(&format!("HTML generated: {}", html_path.to_string_lossy()), "1;36"));
                 }
             }
             Err(e) => { log::error!("HTML write failed for {}: {}", html_path.to_string_lossy(), e); }
         }
         let json_path = base.join(format!("report-{}.json", ts));
-        match std::fs::write(&json_path, serde_json::to_vec_pretty(&summary).unwrap()) {
-            Ok(_) => { if !args.quiet { println!("{}", paint(&format!("JSON written: {}", json_path.to_string_lossy()), "1;36")); } }
+        match write_export(&json_path.to_string_lossy(), &serde_json::to_vec_pretty(&summary).unwrap()) {
+            Ok(_) => { if !quiet { println!("{}", paint(&format!("JSON written: {}", export_path(&json_path.to_string_lossy())), "1;36")); } }
             Err(e) => log::error!("JSON write failed for {}: {}", json_path.to_string_lossy(), e),
         }
         let ndjson_path = base.join(format!("events-{}.ndjson", ts));
-        if let Err(e) = write_ndjson(&ndjson_path.to_string_lossy(), &summary, args.time_zone, args.time_format.as_deref(), args.emit_eventdata, args.emit_xml) {
+        if let Err(e) = write_ndjson(&ndjson_path.to_string_lossy(), &summary, export_events, args.time_zone, args.time_format.as_deref(), args.emit_eventdata, args.emit_xml, &args.export_fields) {
             log::error!("NDJSON write failed for {}: {}", ndjson_path.to_string_lossy(), e);
-        } else if !args.quiet { println!("{}", paint(&format!("NDJSON written: {}", ndjson_path.to_string_lossy()), "1;36")); }
+        } else if !quiet { println!("{}", paint(&format!("NDJSON written: {}", export_path(&ndjson_path.to_string_lossy())), "1;36")); }
         let csv_path = base.join(format!("events-{}.csv", ts));
-        if let Err(e) = write_csv(&csv_path.to_string_lossy(), &summary, args.time_zone, args.time_format.as_deref()) { log::error!("CSV write failed for {}: {}", csv_path.to_string_lossy(), e); } else if !args.quiet { println!("{}", paint(&format!("CSV written: {}", csv_path.to_string_lossy()), "1;36")); }
+        if let Err(e) = write_csv(&csv_path.to_string_lossy(), &summary, export_events, args.time_zone, args.time_format.as_deref(), csv_fields(&args)) { log::error!("CSV write failed for {}: {}", csv_path.to_string_lossy(), e); } else if !quiet { println!("{}", paint(&format!("CSV written: {}", export_path(&csv_path.to_string_lossy())), "1;36")); }
         let tsv_path = base.join(format!("events-{}.tsv", ts));
-        if let Err(e) = write_tsv(&tsv_path.to_string_lossy(), &summary, args.time_zone, args.time_format.as_deref()) { log::error!("TSV write failed for {}: {}", tsv_path.to_string_lossy(), e); } else if !args.quiet { println!("{}", paint(&format!("TSV written: {}", tsv_path.to_string_lossy()), "1;36")); }
+        if let Err(e) = write_tsv(&tsv_path.to_string_lossy(), &summary, export_events, args.time_zone, args.time_format.as_deref(), csv_fields(&args)) { log::error!("TSV write failed for {}: {}", tsv_path.to_string_lossy(), e); } else if !quiet { println!("{}", paint(&format!("TSV written: {}", export_path(&tsv_path.to_string_lossy())), "1;36")); }
         let fix_md_path = base.join(format!("fix-{}.md", ts));
         let fix_md = crate::markdown::render_fix_markdown(&summary, args.time_zone, args.time_format.as_deref());
-        match std::fs::write(&fix_md_path, fix_md.as_bytes()) {
-            Ok(_) => { if !args.quiet { println!("{}", paint(&format!("Fix-It Markdown written: {}", fix_md_path.to_string_lossy()), "1;36")); } }
+        match crate::atomic::write_atomic(&fix_md_path, fix_md.as_bytes()) {
+            Ok(_) => { if !quiet { println!("{}", paint(&format!("Fix-It Markdown written: {}", fix_md_path.to_string_lossy()), "1;36")); } }
             Err(e) => log::error!("Fix-It Markdown write failed for {}: {}", fix_md_path.to_string_lossy(), e),
         }
-        if args.export_zip {
-            let zip_path = base.join(format!("bundle-{}.zip", ts));
-            if let Err(e) = zip_directory(dir, &zip_path.to_string_lossy()) { log::error!("ZIP export failed for {}: {}", zip_path.to_string_lossy(), e); } else if !args.quiet { println!("{}", paint(&format!("ZIP written: {}", zip_path.to_string_lossy()), "1;36")); }
+        // Only what this run wrote; earlier reports in the same directory stay out of the bundle and the manifest.
+        dir_written = [html_path, json_path, ndjson_path, csv_path, tsv_path].into_iter().map(|p| std::path::PathBuf::from(export_path(&p.to_string_lossy()))).chain(std::iter::once(fix_md_path)).filter(|p| p.is_file()).collect();
+        if let Some(zip_arg) = args.export_zip.as_ref() {
+            let zip_path = zip_arg.as_ref().map(std::path::PathBuf::from).unwrap_or_else(|| base.join(format!("bundle-{}.zip", ts)));
+            match crate::bundle::write(&zip_path.to_string_lossy(), &dir_written, &argv, &host) {
+                Ok(_) => { if !quiet { println!("{}", paint(&format!("ZIP written: {}", zip_path.to_string_lossy()), "1;36")); } dir_written.push(zip_path); }
+                Err(e) => log::error!("ZIP export failed for {}: {}", zip_path.to_string_lossy(), e),
+            }
+        }
+    }
+    if let Some(sign_arg) = args.sign_reports.as_ref() {
+        let manifest_path = sign_arg.clone().unwrap_or_else(|| args.export_dir.as_ref().map(|d| PathBuf::from(d).join("windoctor-manifest.json")).unwrap_or_else(|| PathBuf::from("windoctor-manifest.json")).to_string_lossy().into_owned());
+        let html = args.html.clone().or_else(|| summary.mode.is_some().then(|| "report.html".to_string()));
+        let gzipped = [&args.json_path, &args.csv_path, &args.ndjson_path, &args.tsv_path, &args.stream_ndjson].into_iter().flatten().map(|p| export_path(p));
+        let plain = [&html, &args.md_path, &args.md_fix_path, &args.sarif_path, &args.cef_path, &args.leef_path, &args.elastic_bulk_path, &args.template_out].into_iter().flatten().cloned();
+        let mut files: Vec<PathBuf> = gzipped.chain(plain).filter(|p| p != "-").map(PathBuf::from).filter(|p| p.is_file()).collect();
+        files.extend(dir_written);
+        match crate::bundle::sign(&manifest_path, &files, &argv, &host, args.sign_key.as_deref()) {
+            Ok(_) => if !quiet { println!("{}", paint(&format!("Manifest written: {} ({} files{})", manifest_path, files.len(), if args.sign_key.is_some() { ", signed" } else { "" }), "1;36")); },
+            Err(e) => log::error!("Report manifest failed: {}", e),
         }
     }
     if let Some(paths) = args.compare_ndjson.as_ref()
@@ -892,19 +1745,11 @@ fn main() {
         print_comparison(&cmp);
         if let Some(p) = args.compare_out.as_ref() { let _ = write_compare_json(p, &cmp); }
     }
-    if args.warnings_as_errors && (summary.errors > 0 || summary.warnings > 0) { std::process::exit(1); }
-    if args.exit_code_by_risk {
-        let code = match summary.risk_grade.as_str() { "Critical" => 4, "High" => 3, "Medium" => 2, _ => 0 };
+    if let Some((code, idx)) = crate::fail_policy::evaluate(&fail_conds, &summary) {
+        log::warn!("Failure condition met: {}", fail_exprs[idx]);
         std::process::exit(code);
     }
-    if !args.fail_on_categories.is_empty() {
-        let set: Vec<String> = args.fail_on_categories.iter().map(|s| s.to_lowercase()).collect();
-        for (cat, cnt) in &summary.by_category { if *cnt > 0 && set.contains(&cat.to_lowercase()) { std::process::exit(2); } }
-    }
-    if !args.fail_on_providers.is_empty() {
-        let set: Vec<String> = args.fail_on_providers.iter().map(|s| s.to_lowercase()).collect();
-        for (prov, cnt) in &summary.by_provider { if *cnt > 0 && set.contains(&prov.to_lowercase()) { std::process::exit(2); } }
-    }
+    if args.exit_code_by_risk { std::process::exit(crate::fail_policy::risk_exit_code(&summary.risk_grade)); }
     if args.print_effective_config {
         let cfg = build_config_from_args(&args);
         if let Ok(txt) = toml::to_string(&cfg) { println!("{}", txt); }
@@ -912,31 +1757,38 @@ fn main() {
     if let Some(path) = args.save_config.as_ref() {
         let cfg = build_config_from_args(&args);
         if let Ok(txt) = toml::to_string(&cfg) {
-            if let Err(e) = std::fs::write(path, txt) { log::error!("Save config failed for {}: {}", path, e); } else if !args.quiet { println!("{}", paint(&format!("Config saved: {}", path), "1;36")); }
+            if let Err(e) = crate::atomic::write_atomic(path, txt.as_bytes()) { log::error!("Save config failed for {}: {}", path, e); } else if !quiet { println!("{}", paint(&format!("Config saved: {}", path), "1;36")); }
         }
     }
     if let Some(mins) = args.live_html {
         let target_dir = args.export_dir.clone().unwrap_or_else(|| ".".to_string());
         let _ = std::fs::create_dir_all(&target_dir);
         let mut acc_events: Vec<EventItem> = Vec::new();
-        for _i in 0..mins {
-            let more = crate::windows_live::subscribe_events(&channels, 60);
-            acc_events.extend(more);
-            acc_events.retain(|e| e.time >= since && e.time <= Utc::now() && pass_level(&args, e.level) && pass_provider(&args, &e.provider) && pass_event_id(&args, e.event_id));
-            let snap = build_summary_with_files(acc_events.clone(), patterns.clone(), args.top, sample_n, args.sort_by, args.sort_order, since, Utc::now(), file_terms.clone(), file_samples.clone(), scanned_records, parsed_events, Some("Live HTML".to_string()), rules_cfg.clone(), perf_counters.clone(), smart_pred, args.per_channel_sample_limit, args.per_provider_sample_limit);
-            let html = crate::html::render_html(&snap, args.theme, !args.no_emoji, args.time_zone, args.time_format.as_deref(), args.lang);
-            let ts = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
-            let path = std::path::PathBuf::from(&target_dir).join(format!("report-live-{}.html", ts));
-            let _ = std::fs::write(path, html);
-        }
+        // One subscription for the whole run; each minute's tick renders a snapshot of what has arrived so far.
+        crate::windows_live::subscribe_events(&channels, std::time::Duration::from_secs(mins * 60), Some(std::time::Duration::from_secs(60)), |feed| match feed {
+            crate::windows_live::Feed::Event(e) => if pass_event(&args, &e, since, DateTime::<Utc>::MAX_UTC, &compiled_patterns) { acc_events.push(e); },
+            crate::windows_live::Feed::Tick => {
+                let snap = build_summary_with_files(acc_events.clone(), patterns.clone(), args.top, sample_n, args.sort_by, args.sort_order, since, Utc::now(), file_terms.clone(), file_samples.clone(), scanned_records, parsed_events, Some("Live HTML".to_string()), rules_cfg.clone(), perf_counters.clone(), smart_pred, args.per_channel_sample_limit, args.per_provider_sample_limit, args.per_computer_sample_limit);
+                let html = crate::html::render_html(&snap, args.theme, !args.no_emoji, args.time_zone, args.time_format.as_deref(), args.lang);
+                let ts = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+                let path = std::path::PathBuf::from(&target_dir).join(format!("report-live-{}.html", ts));
+                let _ = crate::atomic::write_atomic(path, html.as_bytes());
+            }
+        });
     }
 }
 
 fn apply_config(args: &mut Args, cfg: AppConfig) {
     if args.channels.is_empty() && let Some(v) = cfg.channels { args.channels = v; }
     if args.patterns.is_empty() && let Some(v) = cfg.patterns { args.patterns = v; }
+    if args.grep.is_empty() && let Some(v) = cfg.grep { args.grep = v; }
+    if args.exclude_grep.is_empty() && let Some(v) = cfg.exclude_grep { args.exclude_grep = v; }
     if args.providers.is_empty() && let Some(v) = cfg.providers { args.providers = v; }
     if args.exclude_providers.is_empty() && let Some(v) = cfg.exclude_providers { args.exclude_providers = v; }
+    if args.computers.is_empty() && let Some(v) = cfg.computers { args.computers = v; }
+    if args.exclude_computers.is_empty() && let Some(v) = cfg.exclude_computers { args.exclude_computers = v; }
+    if args.users.is_empty() && let Some(v) = cfg.users { args.users = v; }
+    if args.max_duration.is_none() { args.max_duration = cfg.max_duration; }
     if let Some(v) = cfg.output { args.output = v; }
     if let Some(v) = cfg.text_format { args.text_format = v; }
     if let Some(v) = cfg.theme { args.theme = v; }
@@ -947,26 +1799,43 @@ fn apply_config(args: &mut Args, cfg: AppConfig) {
     if args.max_level.is_none() && let Some(v) = cfg.max_level { args.max_level = Some(v); }
     if args.scan_path.is_none() && let Some(v) = cfg.scan_path { args.scan_path = Some(v); }
     if args.file_glob.is_none() && let Some(v) = cfg.file_glob { args.file_glob = Some(v); }
-    if args.evtx_path.is_none() && let Some(v) = cfg.evtx_path { args.evtx_path = Some(v); }
+    if args.evtx_path.is_empty() && let Some(v) = cfg.evtx_paths { args.evtx_path = v; }
+    if args.evtx_path.is_empty() && let Some(v) = cfg.evtx_path { args.evtx_path = vec![v]; }
     if args.evtx_glob.is_none() && let Some(v) = cfg.evtx_glob { args.evtx_glob = Some(v); }
     if args.html.is_none() && let Some(v) = cfg.html { args.html = Some(v); }
     if args.json_path.is_none() && let Some(v) = cfg.json_path { args.json_path = Some(v); }
     if args.csv_path.is_none() && let Some(v) = cfg.csv_path { args.csv_path = Some(v); }
     if args.ndjson_path.is_none() && let Some(v) = cfg.ndjson_path { args.ndjson_path = Some(v); }
     if args.md_path.is_none() && let Some(v) = cfg.md_path { args.md_path = Some(v); }
+    if args.sarif_path.is_none() && let Some(v) = cfg.sarif_path { args.sarif_path = Some(v); }
+    if args.template.is_none() && let Some(v) = cfg.template { args.template = Some(v); }
+    if args.cef_path.is_none() && let Some(v) = cfg.cef_path { args.cef_path = Some(v); }
+    if args.leef_path.is_none() && let Some(v) = cfg.leef_path { args.leef_path = Some(v); }
+    if args.elastic_bulk_path.is_none() && let Some(v) = cfg.elastic_bulk_path { args.elastic_bulk_path = Some(v); }
     if args.md_fix_path.is_none() && let Some(v) = cfg.md_fix_path { args.md_fix_path = Some(v); }
     if let Some(v) = cfg.warnings_as_errors { args.warnings_as_errors = v; }
+    if args.fail_on.is_empty() && let Some(v) = cfg.fail_on { args.fail_on = v; }
     if let Some(v) = cfg.progress { args.progress = v; }
     if let Some(v) = cfg.summary_only { args.summary_only = v; }
     if let Some(v) = cfg.analysis_only { args.analysis_only = v; }
     if args.sample_count.is_none() && let Some(v) = cfg.sample_count { args.sample_count = Some(v); }
     if args.include_event_ids.is_empty() && let Some(v) = cfg.include_event_ids { args.include_event_ids = v; }
+    if args.keywords.is_empty() && let Some(v) = cfg.keywords { args.keywords = v; }
+    if args.keyword_names.is_empty() && let Some(v) = cfg.keyword_names { args.keyword_names = v; }
+    if args.tasks.is_empty() && let Some(v) = cfg.tasks { args.tasks = v; }
+    if args.query.is_none() && let Some(v) = cfg.query { args.query = Some(v); }
     if args.exclude_event_ids.is_empty() && let Some(v) = cfg.exclude_event_ids { args.exclude_event_ids = v; }
+    if args.export_fields.is_empty() && let Some(v) = cfg.export_fields { args.export_fields = v; }
+    if args.csv_columns.is_empty() && let Some(v) = cfg.csv_columns { args.csv_columns = v; }
     if let Some(v) = cfg.emit_eventdata { args.emit_eventdata = v; }
     if let Some(v) = cfg.emit_xml { args.emit_xml = v; }
+    if let Some(v) = cfg.resolve_messages { args.resolve_messages = v; }
+    if let Some(v) = cfg.compress { args.compress = v; }
+    if let Some(v) = cfg.export_all_events { args.export_all_events = v; }
     if let Some(v) = cfg.force_color { args.force_color = v; }
     if let Some(v) = cfg.time_zone { args.time_zone = v; }
     if args.columns.is_empty() && let Some(v) = cfg.columns { args.columns = v; }
+    if args.group_by.is_empty() && let Some(v) = cfg.group_by { args.group_by = v; }
     if args.columns_preset.is_none() && let Some(v) = cfg.columns_preset { args.columns_preset = Some(v); }
     if let Some(v) = cfg.no_truncate { args.no_truncate = v; }
     if args.time_format.is_none() && let Some(v) = cfg.time_format { args.time_format = Some(v); }
@@ -979,39 +1848,201 @@ fn apply_config(args: &mut Args, cfg: AppConfig) {
     if args.from_ndjson.is_none() && let Some(v) = cfg.from_ndjson { args.from_ndjson = Some(v); }
     if let Some(v) = cfg.no_wmi { args.no_wmi = v; }
     if let Some(v) = cfg.check_ndjson_schema { args.check_ndjson_schema = v; }
+    if args.export_zip.is_none() && cfg.export_zip == Some(true) { args.export_zip = Some(None); }
+    if args.sign_reports.is_none() && cfg.sign_reports == Some(true) { args.sign_reports = Some(None); }
+    if args.sign_key.is_none() && let Some(v) = cfg.sign_key { args.sign_key = Some(v); }
     if let Some(v) = cfg.lang { args.lang = v; }
-    let any_time_flag = args.last10m || args.last_hour || args.last_day || args.last_week || args.hours > 0 || args.minutes > 0 || args.since.is_some() || args.until.is_some();
+    let any_time_flag = any_time_flag(args) || args.since.is_some() || args.until.is_some();
     if !any_time_flag {
         if let Some(v) = cfg.last_errors { args.last_errors = v; }
         if let Some(v) = cfg.last_criticals { args.last_criticals = v; }
         if let Some(v) = cfg.hours { args.hours = v; }
         if let Some(v) = cfg.minutes { args.minutes = v; }
-        if args.since.is_none() && let Some(v) = cfg.since { args.since = Some(v); }
-        if args.until.is_none() && let Some(v) = cfg.until { args.until = Some(v); }
+        if let Some(v) = cfg.days { args.days = v; }
+        // The shipped WinDoctor.toml writes `since = ""`; a blank bound is no bound.
+        if args.since.is_none() && let Some(v) = cfg.since.filter(|v| !v.trim().is_empty()) { args.since = Some(v); }
+        if args.until.is_none() && let Some(v) = cfg.until.filter(|v| !v.trim().is_empty()) { args.until = Some(v); }
     }
 }
 
 fn compute_since(args: &Args) -> DateTime<Utc> {
     let now = Utc::now();
-    if let Some(s) = args.since.as_ref() && let Some(dt) = parse_system_time(s) { return dt; }
-    let any_time_flag = args.last10m || args.last_hour || args.last_day || args.last_week || args.hours > 0 || args.minutes > 0;
-    if !any_time_flag && (args.last_errors > 0 || args.last_criticals > 0) { return now - Duration::days(36500); }
+    if let Some(s) = args.since.as_ref() && let Ok(dt) = crate::time_expr::parse_time_expr(s, now, args.time_zone) { return dt; }
+    if !any_time_flag(args) && (args.last_errors > 0 || args.last_criticals > 0) { return now - Duration::days(36500); }
     if args.last10m { return now - Duration::minutes(10); }
     if args.last_hour { return now - Duration::hours(1); }
     if args.last_day { return now - Duration::hours(24); }
     if args.last_week { return now - Duration::days(7); }
-    if args.hours > 0 { return now - Duration::hours(args.hours); }
-    if args.minutes > 0 { return now - Duration::minutes(args.minutes); }
+    if (args.days > 0 || args.hours > 0 || args.minutes > 0) && let Some(t) = window_span(args).and_then(|d| now.checked_sub_signed(d)) { return t; }
     now - Duration::hours(1)
 }
 
+// `--days`, `--hours` and `--minutes` add up; None when the sum does not fit.
+fn window_span(args: &Args) -> Option<Duration> {
+    Duration::try_days(args.days.max(0))?.checked_add(&Duration::try_hours(args.hours.max(0))?)?.checked_add(&Duration::try_minutes(args.minutes.max(0))?)
+}
+
+fn any_time_flag(args: &Args) -> bool {
+    args.last10m || args.last_hour || args.last_day || args.last_week || args.days > 0 || args.hours > 0 || args.minutes > 0
+}
+
 fn compute_until(args: &Args) -> DateTime<Utc> {
-    if let Some(s) = args.until.as_ref() && let Some(dt) = parse_system_time(s) { return dt; }
-    Utc::now()
+    let now = Utc::now();
+    if let Some(s) = args.until.as_ref() && let Ok(dt) = crate::time_expr::parse_time_expr(s, now, args.time_zone) { return dt; }
+    now
+}
+
+#[allow(clippy::too_many_arguments)]
+fn scan_evtx_file(path: &std::path::Path, ch: &str, args: &Args, since: DateTime<Utc>, until: DateTime<Utc>, compiled_patterns: &[Regex], events: &mut Vec<EventItem>, stream: &mut Option<EventStream>, scanned_records: &mut usize, parsed_events: &mut usize, completion: &mut Vec<(String, u8)>) -> Result<usize, String> {
+    if budget_exhausted() { completion.push((ch.to_string(), 0)); return Ok(0); }
+    let file_key = path.to_string_lossy().into_owned();
+    let resume = crate::checkpoint::progress(&file_key).unwrap_or_default();
+    if resume.done {
+        if args.max_duration.is_some() { completion.push((ch.to_string(), 100)); }
+        return Ok(resume.matched);
+    }
+    let mut parser = EvtxParser::from_path(path).map_err(|e| e.to_string())?;
+    let mut first_rid: Option<u64> = None;
+    let mut last_rid = 0u64;
+    let mut timed_out = false;
+    let pb = if args.progress { Some(indicatif::ProgressBar::new_spinner()) } else { None };
+    if let Some(ref pb) = pb { pb.set_message(format!("Scanning {}", ch)); }
+    let mut matched = resume.matched;
+    for r in parser.records() {
+        if budget_exhausted() { timed_out = true; break; }
+        if let Ok(r) = r.as_ref() && (r.event_record_id <= resume.last_record_id || args.after_record_id.is_some_and(|n| r.event_record_id <= n)) { continue; }
+        *scanned_records += 1;
+        if let Some(ref pb) = pb && *scanned_records % 500 == 0 { pb.tick(); pb.set_message(format!("Scanned {} records", scanned_records)); }
+        if *scanned_records % 1000 == 0 { crate::checkpoint::record(&file_key, last_rid, matched, false, events, *scanned_records, *parsed_events, false); }
+        let Ok(r) = r else { continue };
+        first_rid.get_or_insert(r.event_record_id);
+        last_rid = r.event_record_id;
+        let xml = r.data;
+        if let Some(mut item) = parse_event_xml(&xml, ch) {
+            *parsed_events += 1;
+            if let Some(msg) = crate::decoder::decode_event(&item.provider, item.event_id, &xml).or_else(|| crate::msgfile::resolve(&item, &xml)) { item.content = msg; }
+            if args.emit_xml || args.emit_eventdata || needs_event_data(&args.export_fields) || needs_event_data(&args.csv_columns) || QUERY.get().is_some_and(|q| q.uses_event_data()) || crate::rules::uses_event_data() { item.raw_xml = Some(xml.clone()); }
+            if pass_event(args, &item, since, until, compiled_patterns) {
+                if let Some(st) = stream.as_mut() { st.write(&item); }
+                // With a stream the scan goes on past --max-events; only the report's copy is capped.
+                if events.len() < args.max_events { events.push(item); }
+                matched += 1;
+            }
+        }
+        if events.len() >= args.max_events && stream.is_none() { break; }
+    }
+    if let Some(pb) = pb { pb.finish_and_clear(); }
+    crate::checkpoint::record(&file_key, last_rid.max(resume.last_record_id), matched, !timed_out, events, *scanned_records, *parsed_events, false);
+    if args.max_duration.is_some() {
+        let pct = match (timed_out, first_rid, evtx_next_record_id(path)) {
+            (false, _, _) => 100,
+            (true, Some(f), Some(next)) if next > f => (((last_rid + 1).saturating_sub(f)) * 100 / (next - f)).min(99) as u8,
+            _ => 0,
+        };
+        completion.push((ch.to_string(), pct));
+    }
+    Ok(matched)
+}
+
+// Archived logs overlap the live file they were rolled from; the same record can appear in both.
+fn merge_sources(events: &mut Vec<EventItem>) -> usize {
+    events.sort_by_key(|e| e.time);
+    let before = events.len();
+    let mut seen = std::collections::HashSet::new();
+    events.retain(|e| match e.record_id { Some(rid) => seen.insert((e.computer.clone(), e.channel.clone(), rid, e.time)), None => true });
+    before - events.len()
+}
+
+fn source_scanned(rep: &ReportSummary, src: &str) -> String {
+    rep.scanned_by_source.iter().find(|(s, _)| s == src).map(|(_, n)| format!(" of {} records", n)).unwrap_or_default()
+}
+
+// "last boot" for offline input is the newest boot marker in the given files (directories: their top-level .evtx files).
+fn evtx_last_boot(paths: &[String]) -> Option<DateTime<Utc>> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for p in paths.iter().filter(|p| !p.is_empty() && *p != "-").map(PathBuf::from) {
+        if p.is_file() { files.push(p); }
+        else if let Ok(rd) = std::fs::read_dir(&p) { files.extend(rd.filter_map(Result::ok).map(|d| d.path()).filter(|f| f.extension().is_some_and(|e| e.eq_ignore_ascii_case("evtx")))); }
+    }
+    let mut best: Option<DateTime<Utc>> = None;
+    for f in files {
+        let Ok(mut parser) = EvtxParser::from_path(&f) else { continue };
+        for r in parser.records().filter_map(Result::ok) {
+            if !(r.data.contains("6005") || r.data.contains("Kernel-General")) { continue; }
+            if let Some(e) = parse_event_xml(&r.data, "System") && crate::time_expr::is_boot_event(&e.provider, e.event_id) && best.is_none_or(|b| e.time > b) { best = Some(e.time); }
+        }
+    }
+    best
+}
+
+// The file header stores the next record id at offset 24, after the "ElfFile\0" magic and chunk numbers.
+fn evtx_next_record_id(path: &std::path::Path) -> Option<u64> {
+    use std::io::Read;
+    let mut buf = [0u8; 32];
+    std::fs::File::open(path).ok()?.read_exact(&mut buf).ok()?;
+    if &buf[..8] != b"ElfFile\0" { return None; }
+    Some(u64::from_le_bytes(buf[24..32].try_into().ok()?))
+}
+
+// Live queries return events oldest-first, so coverage of the time window approximates progress.
+fn live_completion(events: &[EventItem], channels: &[String], since: DateTime<Utc>, until: DateTime<Utc>) -> Vec<(String, u8)> {
+    let span = (until - since).num_seconds().max(1);
+    channels.iter().map(|ch| {
+        let last = events.iter().filter(|e| e.channel.eq_ignore_ascii_case(ch)).map(|e| e.time).max();
+        let pct = if !budget_exhausted() { 100 } else { last.map(|t| ((t - since).num_seconds().clamp(0, span) * 100 / span).min(99) as u8).unwrap_or(0) };
+        (ch.clone(), pct)
+    }).collect()
+}
+
+// EVTX files for --count-only: the --evtx-path sources (stdin spooled to the returned temp file), else the live channels' log files.
+fn count_only_files(args: &Args, channels: &[String], set: Option<&globset::GlobSet>) -> (Vec<PathBuf>, Option<tempfile::TempPath>) {
+    if args.evtx_path.is_empty() { return (channels.iter().map(|ch| PathBuf::from(r"C:\Windows\System32\winevt\Logs").join(format!("{}.evtx", ch.replace('/', "%4")))).filter(|p| p.exists()).collect(), None); }
+    let (mut files, mut tmp) = (Vec::new(), None);
+    for evtx in args.evtx_path.iter().filter(|s| !s.is_empty()) {
+        if evtx == "-" {
+            match buffer_stdin_to_temp() { Ok(t) => { files.push(t.to_path_buf()); tmp = Some(t); } Err(e) => log::error!("Failed to buffer EVTX from stdin: {}", e) }
+            continue;
+        }
+        let p = PathBuf::from(evtx);
+        if p.is_file() { files.push(p); continue; }
+        files.extend(evtx_dir_files(&p, args.evtx_recursive, set));
+    }
+    (files, tmp)
+}
+
+fn evtx_glob_set(args: &Args) -> Result<Option<globset::GlobSet>, globset::Error> {
+    let Some(g) = args.evtx_glob.as_ref() else { return Ok(None) };
+    let mut gb = globset::GlobSetBuilder::new();
+    gb.add(globset::GlobBuilder::new(g).case_insensitive(true).build()?);
+    gb.build().map(Some)
+}
+
+// The `.evtx` files under an --evtx-path directory (its subdirectories only with --evtx-recursive) that pass --evtx-glob.
+fn evtx_dir_files<'a>(dir: &std::path::Path, recursive: bool, set: Option<&'a globset::GlobSet>) -> impl Iterator<Item = PathBuf> + 'a {
+    let wd = if recursive { walkdir::WalkDir::new(dir) } else { walkdir::WalkDir::new(dir).max_depth(1) };
+    wd.into_iter().filter_map(Result::ok).map(|d| d.into_path()).filter(move |f| f.is_file() && set.is_none_or(|s| s.is_match(f)) && f.extension().is_some_and(|e| e.eq_ignore_ascii_case("evtx")))
+}
+
+// EvtxParser needs Read + Seek, so the piped stream is spooled to a fresh, randomly named file; it is deleted when dropped.
+fn buffer_stdin_to_temp() -> std::io::Result<tempfile::TempPath> {
+    let mut f = tempfile::Builder::new().prefix("windoctor-stdin-").suffix(".evtx").tempfile()?;
+    std::io::copy(&mut std::io::stdin().lock(), f.as_file_mut())?;
+    Ok(f.into_temp_path())
+}
+
+fn pass_event(args: &Args, e: &EventItem, since: DateTime<Utc>, until: DateTime<Utc>, compiled_patterns: &[Regex]) -> bool {
+    e.time >= since && e.time <= until && pass_level(args, crate::rules::effective_level(e)) && pass_provider(args, &e.provider) && pass_event_id(args, e.event_id) && pass_identity(args, e) && pass_query(e) && pass_grep(e) && pass_keywords(e) && pass_tasks(args, e) && pass_record(args, e) && (!args.only_matched || compiled_patterns.iter().any(|re| re.is_match(&e.content)))
 }
 
 fn parse_event_xml(xml: &str, channel: &str) -> Option<EventItem> {
-    if let Some(item) = parse_event_xml_qx(xml, channel) { return Some(item); }
+    let mut e = parse_event_xml_qx(xml, channel).or_else(|| parse_event_xml_fallback(xml, channel))?;
+    e.level = crate::keywords::audit_level(e.level, e.keywords);
+    // Log clears are logged as Information; they are kept by the default filter so integrity checks see them.
+    if crate::integrity::is_log_clear(&e.provider, e.event_id) && e.level != 1 && e.level != 2 { e.level = 3; }
+    Some(e)
+}
+
+fn parse_event_xml_fallback(xml: &str, channel: &str) -> Option<EventItem> {
     let t = extract_attr(xml, "TimeCreated", "SystemTime").and_then(|s| parse_system_time(&s))
         .or_else(|| extract_between(xml, "<TimeCreated SystemTime=\"", "\"").and_then(|s| parse_system_time(&s)));
     let time = t?;
@@ -1023,7 +2054,12 @@ fn parse_event_xml(xml: &str, channel: &str) -> Option<EventItem> {
     }).unwrap_or(0);
     let content = extract_between(xml, "<EventData>", "</EventData>").unwrap_or_else(|| xml.to_string());
     let ch_xml = extract_between(xml, "<Channel>", "</Channel>").unwrap_or_else(|| channel.to_string());
-    Some(EventItem { time, level, channel: ch_xml, provider, event_id, content, raw_xml: None })
+    let (computer, user) = system_identity(xml);
+    let record_id = extract_between(xml, "<EventRecordID>", "</EventRecordID>").and_then(|s| s.trim().parse::<u64>().ok());
+    let keywords = extract_between(xml, "<Keywords>", "</Keywords>").map(|s| crate::keywords::parse_xml_value(&s)).unwrap_or(0);
+    let task = extract_between(xml, "<Task>", "</Task>").and_then(|s| s.trim().parse().ok()).unwrap_or(0);
+    let opcode = extract_between(xml, "<Opcode>", "</Opcode>").and_then(|s| s.trim().parse().ok()).unwrap_or(0);
+    Some(EventItem { time, level, channel: ch_xml, provider, event_id, content, raw_xml: None, computer, user, record_id, keywords, task, opcode })
 }
 
 fn parse_event_xml_qx(xml: &str, channel: &str) -> Option<EventItem> {
@@ -1035,6 +2071,8 @@ fn parse_event_xml_qx(xml: &str, channel: &str) -> Option<EventItem> {
     let mut provider = String::new();
     let mut event_id_opt: Option<u32> = None;
     let mut channel_s = String::new();
+    let mut keywords = 0u64;
+    let (mut task, mut opcode) = (0u16, 0u8);
     let mut cur = String::new();
     loop {
         match reader.read_event_into(&mut buf) {
@@ -1060,6 +2098,10 @@ fn parse_event_xml_qx(xml: &str, channel: &str) -> Option<EventItem> {
                 if cur == "Level" { if let Ok(n) = v.parse::<u8>() { level_opt = Some(n); } }
                 else if cur == "EventID" { if let Ok(n) = v.trim().parse::<u32>() { event_id_opt = Some(n); } }
                 else if cur == "Channel" { channel_s = v; }
+                else if cur == "Keywords" { keywords = crate::keywords::parse_xml_value(&v); }
+                // RenderingInfo repeats Task/Opcode as display names; only the numeric System values are kept.
+                else if cur == "Task" && let Ok(t) = v.trim().parse() { task = t; }
+                else if cur == "Opcode" && let Ok(o) = v.trim().parse() { opcode = o; }
             }
             Ok(XmlEvent::Eof) => break,
             Err(_) => return None,
@@ -1072,7 +2114,15 @@ fn parse_event_xml_qx(xml: &str, channel: &str) -> Option<EventItem> {
     let event_id = event_id_opt.unwrap_or(0);
     let content = extract_between(xml, "<EventData>", "</EventData>").unwrap_or_else(|| xml.to_string());
     let ch_xml = if channel_s.is_empty() { channel.to_string() } else { channel_s };
-    Some(EventItem { time, level, channel: ch_xml, provider, event_id, content, raw_xml: None })
+    let (computer, user) = system_identity(xml);
+    let record_id = extract_between(xml, "<EventRecordID>", "</EventRecordID>").and_then(|s| s.trim().parse::<u64>().ok());
+    Some(EventItem { time, level, channel: ch_xml, provider, event_id, content, raw_xml: None, computer, user, record_id, keywords, task, opcode })
+}
+
+fn system_identity(xml: &str) -> (String, Option<String>) {
+    let computer = extract_between(xml, "<Computer>", "</Computer>").map(|s| s.trim().to_string()).unwrap_or_default();
+    let user = extract_attr(xml, "Security", "UserID").filter(|s| !s.is_empty());
+    (computer, user)
 }
 
 fn parse_system_time(s: &str) -> Option<DateTime<Utc>> {
@@ -1101,20 +2151,29 @@ fn extract_attr(xml: &str, tag: &str, attr: &str) -> Option<String> {
     Some(after[..ke].to_string())
 }
 
+fn event_device(e: &EventItem) -> Option<String> {
+    let pairs = crate::event_xml::event_data_pairs_or_fallback(e.raw_xml.as_deref().unwrap_or(&e.content));
+    ["DeviceName", "TargetDevice", "Device", "InstancePath", "PhysicalDeviceObjectName"].iter().find_map(|k| pairs.get(*k).filter(|v| !v.is_empty()).cloned())
+}
+
 #[allow(clippy::too_many_arguments)]
-fn build_summary_with_files(events: Vec<EventItem>, patterns: Vec<String>, top: usize, sample_count: usize, sort_by: SortBy, sort_order: SortOrder, since: DateTime<Utc>, until: DateTime<Utc>, file_terms: Vec<(String, usize)>, file_samples: Vec<crate::file_scan::FileSample>, scanned_records: usize, parsed_events: usize, mode: Option<String>, rules_cfg: Option<crate::rules::RulesConfig>, perf_counters: Option<crate::perf::PerfCounters>, smart_pred: Option<bool>, per_channel_sample_limit: Option<usize>, per_provider_sample_limit: Option<usize>) -> ReportSummary {
+fn build_summary_with_files(events: Vec<EventItem>, patterns: Vec<String>, top: usize, sample_count: usize, sort_by: SortBy, sort_order: SortOrder, since: DateTime<Utc>, until: DateTime<Utc>, file_terms: Vec<(String, usize)>, file_samples: Vec<crate::file_scan::FileSample>, scanned_records: usize, parsed_events: usize, mode: Option<String>, rules_cfg: Option<crate::rules::RulesConfig>, perf_counters: Option<crate::perf::PerfCounters>, smart_pred: Option<bool>, per_channel_sample_limit: Option<usize>, per_provider_sample_limit: Option<usize>, per_computer_sample_limit: Option<usize>) -> ReportSummary {
+    let mut events = events;
+    if let Some(cfg) = rules_cfg.as_ref() { crate::rules::apply_suppressions(&mut events, cfg); crate::rules::apply_level_overrides(&mut events, cfg); }
+    let mut criticals = 0usize;
     let mut errors = 0usize;
     let mut warnings = 0usize;
     for e in &events {
-        if e.level == 2 { errors += 1; } else if e.level == 3 { warnings += 1; }
+        if e.level == 1 { criticals += 1; } else if e.level == 2 { errors += 1; } else if e.level == 3 { warnings += 1; }
     }
-    let by_provider: Vec<(String, usize)> = {
+    let all_providers: Vec<(String, usize)> = {
         let mut pc: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
         for e in &events { *pc.entry(e.provider.clone()).or_insert(0) += 1; }
         let mut pv: Vec<(String, usize)> = pc.into_iter().collect();
         pv.sort_by(|a, b| b.1.cmp(&a.1));
-        pv.into_iter().take(top).collect()
+        pv
     };
+    let by_provider: Vec<(String, usize)> = all_providers.iter().take(top).cloned().collect();
     let by_channel: Vec<(String, usize)> = {
         let mut cc: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
         for e in &events { *cc.entry(e.channel.clone()).or_insert(0) += 1; }
@@ -1122,21 +2181,49 @@ fn build_summary_with_files(events: Vec<EventItem>, patterns: Vec<String>, top:
         cv.sort_by(|a, b| b.1.cmp(&a.1));
         cv.into_iter().take(top).collect()
     };
-    let by_event_id: Vec<(u32, usize)> = {
+    let all_event_ids: Vec<(u32, usize)> = {
         let mut ec: std::collections::HashMap<u32, usize> = std::collections::HashMap::new();
         for e in &events { *ec.entry(e.event_id).or_insert(0) += 1; }
         let mut ev: Vec<(u32, usize)> = ec.into_iter().collect();
         ev.sort_by(|a, b| b.1.cmp(&a.1));
-        ev.into_iter().take(top).collect()
+        ev
+    };
+    let by_event_id: Vec<(u32, usize)> = all_event_ids.iter().take(top).cloned().collect();
+    let provider_stats = build_agg_stats(&events, &by_provider.iter().map(|(p, _)| p.clone()).collect::<Vec<_>>(), |e| e.provider.clone());
+    let event_id_stats = build_agg_stats(&events, &by_event_id.iter().map(|(id, _)| id.to_string()).collect::<Vec<_>>(), |e| e.event_id.to_string());
+    let by_computer: Vec<(String, usize)> = {
+        let mut cc: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        for e in events.iter().filter(|e| !e.computer.is_empty()) { *cc.entry(e.computer.clone()).or_insert(0) += 1; }
+        let mut cv: Vec<(String, usize)> = cc.into_iter().collect();
+        cv.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        cv.into_iter().take(top).collect()
+    };
+    let computer_stats = build_agg_stats(&events, &by_computer.iter().map(|(c, _)| c.clone()).collect::<Vec<_>>(), |e| e.computer.clone());
+    let computer_top_providers: Vec<(String, Vec<(String, usize)>)> = if by_computer.len() > 1 {
+        by_computer.iter().map(|(comp, _)| {
+            let mut pc: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+            for e in events.iter().filter(|e| &e.computer == comp) { *pc.entry(e.provider.as_str()).or_insert(0) += 1; }
+            let mut pv: Vec<(String, usize)> = pc.into_iter().map(|(p, c)| (p.to_string(), c)).collect();
+            pv.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            pv.truncate(3);
+            (comp.clone(), pv)
+        }).collect()
+    } else { vec![] };
+    let by_user: Vec<(String, usize)> = {
+        let mut uc: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+        for e in &events { if let Some(u) = e.user.as_deref() { *uc.entry(u).or_insert(0) += 1; } }
+        let mut uv: Vec<(&str, usize)> = uc.into_iter().collect();
+        uv.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        uv.into_iter().take(top).map(|(sid, c)| (crate::accounts::display(sid), c)).collect()
+    };
+    let max_record_ids: Vec<(String, u64)> = {
+        let mut mr: std::collections::BTreeMap<String, u64> = std::collections::BTreeMap::new();
+        for e in &events { if let Some(r) = e.record_id { let m = mr.entry(e.channel.clone()).or_insert(r); if r > *m { *m = r; } } }
+        mr.into_iter().collect()
     };
     let by_device: Vec<(String, usize)> = {
         let mut dc: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
-        for e in &events {
-            let pairs = crate::event_xml::event_data_pairs_or_fallback(&e.content);
-            for k in ["DeviceName", "TargetDevice", "Device", "InstancePath", "PhysicalDeviceObjectName"].iter() {
-                if let Some(v) = pairs.get(*k) && !v.is_empty() { *dc.entry(v.clone()).or_insert(0) += 1; break; }
-            }
-        }
+        for d in events.iter().filter_map(event_device) { *dc.entry(d).or_insert(0) += 1; }
         let mut dv: Vec<(String, usize)> = dc.into_iter().collect();
         dv.sort_by(|a, b| b.1.cmp(&a.1));
         dv.into_iter().take(top).collect()
@@ -1167,47 +2254,29 @@ fn build_summary_with_files(events: Vec<EventItem>, patterns: Vec<String>, top:
         tv
     };
     let mut samples = events.clone();
-    match (sort_by, sort_order) {
-        (SortBy::Time, SortOrder::Desc) => samples.sort_by(|a, b| b.time.cmp(&a.time)),
-        (SortBy::Time, SortOrder::Asc) => samples.sort_by(|a, b| a.time.cmp(&b.time)),
-        (SortBy::Severity, SortOrder::Desc) => samples.sort_by(|a, b| b.level.cmp(&a.level)),
-        (SortBy::Severity, SortOrder::Asc) => samples.sort_by(|a, b| a.level.cmp(&b.level)),
-        (SortBy::Provider, SortOrder::Desc) => samples.sort_by(|a, b| b.provider.cmp(&a.provider)),
-        (SortBy::Provider, SortOrder::Asc) => samples.sort_by(|a, b| a.provider.cmp(&b.provider)),
-        (SortBy::Channel, SortOrder::Desc) => samples.sort_by(|a, b| b.channel.cmp(&a.channel)),
-        (SortBy::Channel, SortOrder::Asc) => samples.sort_by(|a, b| a.channel.cmp(&b.channel)),
-        (SortBy::EventId, SortOrder::Desc) => samples.sort_by(|a, b| b.event_id.cmp(&a.event_id)),
-        (SortBy::EventId, SortOrder::Asc) => samples.sort_by(|a, b| a.event_id.cmp(&b.event_id)),
-    }
-    if per_channel_sample_limit.is_some() || per_provider_sample_limit.is_some() {
+    sort_events(&mut samples, sort_by, sort_order);
+    if per_channel_sample_limit.is_some() || per_provider_sample_limit.is_some() || per_computer_sample_limit.is_some() {
         let cl = per_channel_sample_limit.unwrap_or(usize::MAX);
         let pl = per_provider_sample_limit.unwrap_or(usize::MAX);
+        let ml = per_computer_sample_limit.unwrap_or(usize::MAX);
         let mut ch_cnt: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
         let mut pr_cnt: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+        let mut co_cnt: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
         let mut limited: Vec<EventItem> = Vec::new();
         for e in samples.iter() {
             let cc = *ch_cnt.get(&e.channel).unwrap_or(&0);
             let pc = *pr_cnt.get(&e.provider).unwrap_or(&0);
-            if cc < cl && pc < pl {
+            let mc = *co_cnt.get(&e.computer).unwrap_or(&0);
+            if cc < cl && pc < pl && mc < ml {
                 ch_cnt.insert(e.channel.clone(), cc + 1);
                 pr_cnt.insert(e.provider.clone(), pc + 1);
+                co_cnt.insert(e.computer.clone(), mc + 1);
                 limited.push(e.clone());
             }
         }
         samples = limited;
     }
-    match (sort_by, sort_order) {
-        (SortBy::Time, SortOrder::Desc) => samples.sort_by(|a, b| b.time.cmp(&a.time)),
-        (SortBy::Time, SortOrder::Asc) => samples.sort_by(|a, b| a.time.cmp(&b.time)),
-        (SortBy::Severity, SortOrder::Desc) => samples.sort_by(|a, b| b.level.cmp(&a.level)),
-        (SortBy::Severity, SortOrder::Asc) => samples.sort_by(|a, b| a.level.cmp(&b.level)),
-        (SortBy::Provider, SortOrder::Desc) => samples.sort_by(|a, b| b.provider.cmp(&a.provider)),
-        (SortBy::Provider, SortOrder::Asc) => samples.sort_by(|a, b| a.provider.cmp(&b.provider)),
-        (SortBy::Channel, SortOrder::Desc) => samples.sort_by(|a, b| b.channel.cmp(&a.channel)),
-        (SortBy::Channel, SortOrder::Asc) => samples.sort_by(|a, b| a.channel.cmp(&b.channel)),
-        (SortBy::EventId, SortOrder::Desc) => samples.sort_by(|a, b| b.event_id.cmp(&a.event_id)),
-        (SortBy::EventId, SortOrder::Asc) => samples.sort_by(|a, b| a.event_id.cmp(&b.event_id)),
-    }
+    sort_events(&mut samples, sort_by, sort_order);
     samples.truncate(sample_count);
     {
         use std::collections::HashMap;
@@ -1229,26 +2298,32 @@ fn build_summary_with_files(events: Vec<EventItem>, patterns: Vec<String>, top:
         samples = deduped;
     }
     
-    let mut novice_hints = crate::hints::generate_hints(&events);
-    if let Some(cfg) = rules_cfg.as_ref() {
-        let extra = crate::rules::apply_hint_rules(&events, cfg);
-        if !extra.is_empty() { novice_hints.extend(extra); }
-    }
+    let mut novice_hints = match rules_cfg.as_ref() { Some(cfg) => crate::rules::hints_with_rules(&events, cfg), None => crate::hints::generate_hints(&events) };
+    let storms = crate::storms::detect(&events, crate::storms::threshold());
+    let log_integrity = crate::integrity::analyze(&events);
+    let crashing_apps = crate::crashes::top_crashing_apps(&events, top);
+    let defender = crate::defender::analyze(&events);
+    let battery = crate::battery::analyze(&events);
+    let group_by = crate::group_by::pivot(&events, crate::group_by::keys(), top);
+    let noise = if crate::noise::enabled() { crate::noise::analyze(&events, top.max(20)) } else { vec![] };
+    novice_hints.extend(crate::storms::hints(&storms));
+    novice_hints.extend(crate::integrity::hints(&log_integrity));
+    novice_hints.extend(battery.as_ref().map(crate::battery::event_hints).unwrap_or_default());
     let (perf_score, perf_signals) = perf::compute_performance_metrics(&events);
-    let perf_metrics = perf::compute_perf_details(&events);
+    let mut perf_metrics = perf::compute_perf_details(&events);
+    // Live channel reads this run: wall time per channel, with the events read as the sample count.
+    perf_metrics.extend(crate::windows_live::query_timings().into_iter().map(|(ch, ms, n)| (format!("Query {}", ch), ms, ms, n)));
     let recs = perf::generate_recommendations(&novice_hints);
     let causes = perf::compute_root_causes(&novice_hints);
     let timeline = perf::compute_timeline(&events, since, until);
+    let heatmap = perf::compute_heatmap(&events, display_tz());
     let by_category = perf::compute_by_category(&novice_hints);
-    let risk_grade = {
-        let mut grade = if perf_score >= 80 { "Critical" } else if perf_score >= 60 { "High" } else if perf_score >= 40 { "Medium" } else { "Low" };
-        if novice_hints.iter().any(|h| h.category == "Storage" && h.severity == "high") && perf_score >= 40 { grade = "High"; }
-        grade.to_string()
-    };
+    let risk_grade = risk_grade(perf_score, &novice_hints);
     ReportSummary {
         window_start: since,
         window_end: until,
         total: events.len(),
+        criticals,
         errors,
         warnings,
         by_provider,
@@ -1275,11 +2350,116 @@ fn build_summary_with_files(events: Vec<EventItem>, patterns: Vec<String>, top:
         smart_failure_predicted: smart_pred,
         risk_grade,
         compare: None,
+        by_source: vec![],
+        scanned_by_source: vec![],
+        provider_stats,
+        event_id_stats,
+        heatmap,
+        by_computer,
+        computer_stats,
+        computer_top_providers,
+        by_user,
+        max_record_ids,
+        partial: false,
+        completion: vec![],
+        services_down: vec![],
+        policy: None,
+        dev_env: None,
+        sync: vec![],
+        storms,
+        noise,
+        group_by,
+        crashing_apps,
+        defender,
+        battery,
+        channel_health: vec![],
+        log_integrity,
+        collection_errors: crate::windows_live::collection_errors(),
+        system_info: None,
+        all_providers,
+        all_event_ids,
     }
 }
 
+fn build_agg_stats(events: &[EventItem], keys: &[String], key_of: impl Fn(&EventItem) -> String) -> Vec<AggStat> {
+    let mut map: std::collections::HashMap<String, AggStat> = std::collections::HashMap::new();
+    for e in events {
+        let k = key_of(e);
+        if !keys.contains(&k) { continue; }
+        let st = map.entry(k.clone()).or_insert(AggStat { key: k, errors: 0, warnings: 0, first_seen: e.time, last_seen: e.time });
+        if e.level == 1 || e.level == 2 { st.errors += 1; } else if e.level == 3 { st.warnings += 1; }
+        if e.time < st.first_seen { st.first_seen = e.time; }
+        if e.time > st.last_seen { st.last_seen = e.time; }
+    }
+    keys.iter().filter_map(|k| map.remove(k)).collect()
+}
+
+// Folds hints from collectors that run outside build_summary_with_files into the derived sections.
+fn risk_grade(perf_score: u8, hints: &[crate::hints::NoviceHint]) -> String {
+    let mut grade = if perf_score >= 80 { "Critical" } else if perf_score >= 60 { "High" } else if perf_score >= 40 { "Medium" } else { "Low" };
+    if hints.iter().any(|h| h.category == "Storage" && h.severity == "high") && perf_score >= 40 { grade = "High"; }
+    // Security findings (malware not removed, protection off, BitLocker recovery) are serious however healthy the machine otherwise looks.
+    if hints.iter().any(|h| h.category == "Security") && grade == "Low" { grade = "Medium"; }
+    if hints.iter().any(|h| h.category == "Security" && h.severity == "high") && grade == "Medium" { grade = "High"; }
+    grade.to_string()
+}
+
+fn merge_extra_hints(rep: &mut ReportSummary, extra: Vec<crate::hints::NoviceHint>) {
+    if extra.is_empty() { return; }
+    rep.novice_hints.extend(extra);
+    rep.recommendations = perf::generate_recommendations(&rep.novice_hints);
+    rep.likely_causes = perf::compute_root_causes(&rep.novice_hints);
+    rep.by_category = perf::compute_by_category(&rep.novice_hints);
+    rep.risk_grade = risk_grade(rep.performance_score, &rep.novice_hints);
+}
+
+fn completion_text(completion: &[(String, u8)]) -> String {
+    completion.iter().map(|(ch, p)| format!("{} {}%", ch, p)).collect::<Vec<_>>().join(", ")
+}
+
+fn find_stat<'a>(stats: &'a [AggStat], key: &str) -> Option<&'a AggStat> { stats.iter().find(|s| s.key == key) }
+
+fn stat_suffix(st: Option<&AggStat>, tz: TimeZone, tfmt: Option<&str>) -> String {
+    match st { Some(s) => format!(" · {}E/{}W · first {} · last {}", s.errors, s.warnings, format_ts(s.first_seen, tz, tfmt), format_ts(s.last_seen, tz, tfmt)), None => String::new() }
+}
+
 struct PrintWidths { msg: usize, cause: usize }
 
+// One colored row in `lines` format; shared by the report and --follow.
+#[allow(clippy::too_many_arguments)]
+fn sample_line(e: &EventItem, widths: &PrintWidths, tz: TimeZone, cols: &Vec<Column>, no_trunc: bool, tfmt: Option<&str>, emoji: bool) -> String {
+    let ts = match (tz, tfmt) {
+        (TimeZone::Local, Some(f)) => format!("{}", e.time.with_timezone(&Local).format(f)),
+        (TimeZone::Utc, Some(f)) => format!("{}", e.time.format(f)),
+        (TimeZone::Local, None) => format!("{}", e.time.with_timezone(&Local).format("%Y-%m-%d %H:%M")),
+        (TimeZone::Utc, None) => format!("{}", e.time.format("%Y-%m-%d %H:%M")),
+    };
+    let sev_name = event_level_name(e);
+    let sev = sev_name.as_str();
+    let sev_disp = if emoji { match sev { "Critical"=>"⛔ Critical", "Error"=>"⛔ Error", "Warning"=>"⚠️ Warning", "Information"=>"🛈 Information", _=>sev } } else { sev };
+    let sev_s = paint(sev_disp, sev_code(e.level));
+    let ch = if no_trunc { e.channel.clone() } else { truncate(&e.channel, 14) };
+    let pr = if no_trunc { e.provider.clone() } else { truncate(&e.provider, 18) };
+    let eid = e.event_id.to_string();
+    let cause_r = event_cause_redacted(e);
+    let msg_r = event_message_redacted(e);
+    let cause = if no_trunc { cause_r } else { truncate(&cause_r, widths.cause) };
+    let msg = if no_trunc { msg_r } else { truncate(&msg_r, widths.msg) };
+    let comp = if no_trunc { e.computer.clone() } else { truncate(&e.computer, 16) };
+    let user = if no_trunc { e.user.clone().unwrap_or_default() } else { truncate(e.user.as_deref().unwrap_or(""), 14) };
+    let kw = crate::keywords::label(&e.provider, e.keywords);
+    let kw = if no_trunc { kw } else { truncate(&kw, 20) };
+    build_line(cols, &ts, &sev_s, &ch, &pr, Some(&eid), &cause, &msg, &comp, &user, &kw, 16, 10, 14, 18, 8, 24, 96)
+}
+
+fn text_columns(args: &Args) -> Vec<Column> {
+    if !args.columns.is_empty() { return args.columns.clone(); }
+    match args.columns_preset.unwrap_or(ColumnsPreset::Detailed) {
+        ColumnsPreset::Minimal => vec![Column::Time, Column::Severity, Column::Provider, Column::EventId, Column::Message],
+        ColumnsPreset::Detailed => vec![Column::Time, Column::Severity, Column::Channel, Column::Provider, Column::EventId, Column::Cause, Column::Message],
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn print_text(rep: &ReportSummary, widths: PrintWidths, no_header: bool, summary_only: bool, analysis_only: bool, tz: TimeZone, cols: &Vec<Column>, no_trunc: bool, tfmt: Option<&str>, emoji: bool) {
     let start_local = rep.window_start.with_timezone(&Local);
@@ -1288,42 +2468,43 @@ fn print_text(rep: &ReportSummary, widths: PrintWidths, no_header: bool, summary
     let end_s = format!("{}", end_local.format("%Y-%m-%d %H:%M"));
     if !no_header { println!("{}", paint(&format!("Time Window: {} to {} (local time)", start_s, end_s), "1;36")); }
     if !no_header && let Some(m) = rep.mode.as_ref() { println!("{}", paint(&format!("Mode: {}", m), "1;36")); }
+    if !no_header && rep.partial { println!("{}", paint(&format!("Partial report (time budget exhausted): {}", completion_text(&rep.completion)), "1;33")); }
     if rep.errors == 0 && rep.warnings == 0 {
-        if !no_header { println!("{}", paint("Status: No errors or warnings detected.", "1;32")); }
+        if !no_header { println!("{}", paint(tr("Status: No errors or warnings detected."), "1;32")); }
     } else if !no_header { println!("{}", paint(&format!("Status: {} errors and {} warnings detected.", rep.errors, rep.warnings), "1;33")); }
-    if !no_header { println!("{} {}", paint("Risk:", "1"), rep.risk_grade); }
+    if !no_header { println!("{} {}", paint(tr("Risk:"), "1"), rep.risk_grade); }
     if !rep.likely_causes.is_empty() {
-        if !no_header { println!("{}", paint("Likely Root Causes:", "1")); }
+        if !no_header { println!("{}", paint(tr("Likely Root Causes:"), "1")); }
         for c in &rep.likely_causes { println!("- {}", c); }
     }
     if !rep.by_category.is_empty() {
-        if !no_header { println!("{}", paint("Impact Assessment:", "1")); }
+        if !no_header { println!("{}", paint(tr("Impact Assessment:"), "1")); }
         for (cat, cnt) in &rep.by_category { println!("• {} ({})", cat, cnt); }
     }
     if analysis_only || rep.mode.is_some() {
-        if !no_header { println!("{}", paint("Diagnostics:", "1")); }
+        if !no_header { println!("{}", paint(tr("Diagnostics:"), "1")); }
         if rep.novice_hints.is_empty() {
-            if !no_header { println!("{}", paint("None", "2")); }
+            if !no_header { println!("{}", paint(tr("None"), "2")); }
         } else {
             for h in &rep.novice_hints {
                 let ev = if h.evidence.is_empty() { String::new() } else { format!(" | Examples: {}", h.evidence.join(", ")) };
                 println!("[{} {}%] {} ({} occurrences){}", h.severity, h.probability, h.message, h.count, ev);
             }
         }
-        println!("{} {}", paint("Performance Score:", "1"), rep.performance_score);
+        println!("{} {}", paint(tr("Performance Score:"), "1"), rep.performance_score);
         if let Some(pc) = &rep.perf_counters {
-            println!("{}", paint("Live Performance:", "1"));
+            println!("{}", paint(tr("Live Performance:"), "1"));
             if let Some(v) = pc.cpu_percent { println!("• CPU: {}%", v); }
             if let Some(v) = pc.avg_disk_ms_per_transfer { println!("• Avg Disk Transfer: {:.2} ms", v); }
             if let Some(v) = pc.disk_reads_per_sec { println!("• Reads/s: {}", v); }
             if let Some(v) = pc.disk_writes_per_sec { println!("• Writes/s: {}", v); }
         }
-        if let Some(pred) = rep.smart_failure_predicted && pred { println!("{}", paint("SMART: Predicts failure on one or more drives", "1;31")); }
-        if !rep.degradation_signals.is_empty() { println!("{}", paint("Degradation Signals:", "1")); for (n,w) in &rep.degradation_signals { println!("• {} (weight {})", n, w); } }
-        if !rep.recommendations.is_empty() { println!("{}", paint("Recommendations:", "1")); for r in &rep.recommendations { println!("- {}", r); } }
-        if !rep.recommendations.is_empty() { println!("{}", paint("Checklist:", "1")); for r in &rep.recommendations { println!("[ ] {}", r); } }
+        if let Some(pred) = rep.smart_failure_predicted && pred { println!("{}", paint(tr("SMART: Predicts failure on one or more drives"), "1;31")); }
+        if !rep.degradation_signals.is_empty() { println!("{}", paint(tr("Degradation Signals:"), "1")); for (n,w) in &rep.degradation_signals { println!("• {} (weight {})", n, w); } }
+        if !rep.recommendations.is_empty() { println!("{}", paint(tr("Recommendations:"), "1")); for r in &rep.recommendations { println!("- {}", r); } }
+        if !rep.recommendations.is_empty() { println!("{}", paint(tr("Checklist:"), "1")); for r in &rep.recommendations { println!("[ ] {}", r); } }
         if !rep.timeline.is_empty() {
-            println!("{}", paint("Timeline:", "1"));
+            println!("{}", paint(tr("Timeline:"), "1"));
             let max_e = rep.timeline.iter().map(|(_,e,_)| *e).max().unwrap_or(1);
             let max_w = rep.timeline.iter().map(|(_,_,w)| *w).max().unwrap_or(1);
             for (t,e,w) in &rep.timeline {
@@ -1332,59 +2513,94 @@ fn print_text(rep: &ReportSummary, widths: PrintWidths, no_header: bool, summary
                 println!("{}  E:{:<3} {}  W:{:<3} {}", t, e, eb, w, wb);
             }
         }
+        print_heatmap(&rep.heatmap);
+    print_storms(&rep.storms, tz, tfmt);
+    print_crashing_apps(&rep.crashing_apps, tz, tfmt);
+    print_noise(&rep.noise);
+    if let Some(t) = rep.group_by.as_ref() { println!("{}", paint(tr("Grouped Counts:"), "1")); crate::group_by::print(t); }
+        print_storms(&rep.storms, tz, tfmt);
+        print_crashing_apps(&rep.crashing_apps, tz, tfmt);
+        print_noise(&rep.noise);
+        if let Some(t) = rep.group_by.as_ref() { println!("{}", paint(tr("Grouped Counts:"), "1")); crate::group_by::print(t); }
         if !rep.perf_metrics.is_empty() {
-            println!("{}", paint("Performance Metrics:", "1"));
+            println!("{}", paint(tr("Performance Metrics:"), "1"));
             for (name, avg, max, count) in &rep.perf_metrics {
                 println!("{}: avg {} ms, max {} ms ({} samples)", name, avg, max, count);
             }
         }
         return;
     }
-    if !no_header { println!("{} {}", paint("Events:", "1"), rep.total); }
-    if !no_header { println!("{}", paint("Key Sources:", "1")); }
-    if !no_header { if rep.by_provider.is_empty() { println!("{}", paint("None", "2")); } else { for (p, c) in &rep.by_provider { println!("• {} ({})", p, c); } } }
-    if !no_header { println!("{}", paint("Key Domains:", "1")); }
-    if !no_header { if rep.by_domain.is_empty() { println!("{}", paint("None", "2")); } else { for (d, c) in &rep.by_domain { println!("• {} ({})", d, c); } } }
-    if !no_header { println!("{}", paint("Key Devices:", "1")); }
-    if !no_header { if rep.by_device.is_empty() { println!("{}", paint("None", "2")); } else { for (d, c) in &rep.by_device { println!("• {} ({})", d, c); } } }
-    if !no_header { println!("{}", paint("Common Event Codes:", "1")); }
-    if !no_header { if rep.by_event_id.is_empty() { println!("{}", paint("None", "2")); } else { for (id, c) in &rep.by_event_id { println!("• {} ({})", id, c); } } }
-    if !no_header { println!("{}", paint("Matched Keywords:", "1")); }
-    if !no_header { if rep.matched_terms.is_empty() { println!("{}", paint("None", "2")); } else { for (t, c) in &rep.matched_terms { println!("• {} ({})", t, c); } } }
-    if !no_header { println!("{}", paint("Recent Activity:", "1;36")); }
+    if !no_header { println!("{} {}", paint(tr("Events:"), "1"), rep.total); }
+    if !no_header { println!("{}", paint(tr("Key Sources:"), "1")); }
+    if !no_header { if rep.by_provider.is_empty() { println!("{}", paint(tr("None"), "2")); } else { for (p, c) in &rep.by_provider { println!("• {} ({}){}", p, c, stat_suffix(find_stat(&rep.provider_stats, p), tz, tfmt)); } } }
+    if !no_header && !rep.by_source.is_empty() { println!("{}", paint(tr("EVTX Sources:"), "1")); for (src, c) in &rep.by_source { println!("• {} ({}{})", src, c, source_scanned(rep, src)); } }
+    if !no_header && !rep.max_record_ids.is_empty() { println!("{} {}", paint(tr("Highest Record IDs:"), "1"), rep.max_record_ids.iter().map(|(ch, r)| format!("{}={}", ch, r)).collect::<Vec<_>>().join(", ")); }
+    if !no_header && !rep.services_down.is_empty() {
+        println!("{}", paint(tr("Services Down Now:"), "1"));
+        for sv in &rep.services_down {
+            let code = if sv.failed() { format!(", exit {}", sv.exit_code.unwrap_or(0)) } else { String::new() };
+            println!("• {} [{}{}]{}", sv.display_name, sv.state, code, sv.last_event.as_ref().map(|e| format!(" — last SCM event: {}", e)).unwrap_or_default());
+        }
+    }
+    if !no_header && !rep.channel_health.is_empty() {
+        println!("{}", paint(tr("Channel Health:"), "1"));
+        for c in &rep.channel_health { println!("• {} — {}", c.name, crate::channel_health::detail(c)); }
+    }
+    if !no_header && !rep.collection_errors.is_empty() { println!("{}", paint(&format!("Collection errors — data may be incomplete: {}", rep.collection_errors.iter().map(collection_error_line).collect::<Vec<_>>().join("; ")), "1;33")); }
+    if !no_header && !rep.log_integrity.is_empty() {
+        println!("{}", paint(tr("Log Integrity:"), "1"));
+        for c in &rep.log_integrity.clears { println!("• {} — {}", format_ts(c.time, tz, tfmt), crate::integrity::clear_line(c)); }
+        for r in &rep.log_integrity.resets { println!("• {} — {}", format_ts(r.at, tz, tfmt), crate::integrity::reset_line(r)); }
+    }
+    if !no_header && let Some(pol) = rep.policy.as_ref() {
+        println!("{}", paint(tr("Group Policy:"), "1"));
+        println!("• Last successful refresh: {}", pol.last_success.map(|t| format_ts(t, tz, tfmt)).unwrap_or_else(|| "unknown".to_string()));
+        if let Some(t) = pol.last_failure { println!("• Last failed refresh: {}", format_ts(t, tz, tfmt)); }
+        for x in &pol.failed_extensions { println!("• Failed extension: {} (error {})", if x.name.is_empty() { &x.guid } else { &x.name }, x.error); }
+        if !pol.gpos.is_empty() { println!("• Applied GPOs: {}", pol.gpos.join(", ")); }
+    }
+    if !no_header && let Some(dev) = rep.dev_env.as_ref() {
+        println!("{}", paint(tr("WSL / Docker:"), "1"));
+        println!("• WSL: {}", dev.wsl_status.as_deref().unwrap_or("not installed or not responding"));
+        for (p, n) in &dev.vhdx { println!("• {} ({:.1} GiB)", p, *n as f64 / 1073741824.0); }
+        for l in dev.wsl_findings.iter().chain(dev.docker_findings.iter()) { println!("  {}", l); }
+    }
+    if !no_header && !rep.sync.is_empty() {
+        println!("{}", paint(tr("OneDrive:"), "1"));
+        for a in &rep.sync {
+            println!("• {}{}{}", a.name, a.user_folder.as_ref().map(|f| format!(" → {}", f)).unwrap_or_default(), if a.kfm.is_empty() { String::new() } else { format!(" [{}]", a.kfm.join(", ")) });
+            for e in &a.errors { println!("  {}", e); }
+        }
+    }
+    if !no_header && let Some(b) = rep.battery.as_ref() {
+        println!("{}", paint(tr("Battery:"), "1"));
+        for l in crate::battery::lines(b) { println!("• {}", l); }
+    }
+    if !no_header && multi_computer(rep) { println!("{}", paint(tr("Computers:"), "1")); for (comp, c) in &rep.by_computer { println!("• {} ({}){}{}", comp, c, stat_suffix(find_stat(&rep.computer_stats, comp), tz, tfmt), computer_top_suffix(rep, comp)); } }
+    if !no_header && !rep.by_user.is_empty() { println!("{}", paint(tr("Users:"), "1")); for (user, c) in &rep.by_user { println!("• {} ({})", user, c); } }
+    if !no_header { println!("{}", paint(tr("Key Domains:"), "1")); }
+    if !no_header { if rep.by_domain.is_empty() { println!("{}", paint(tr("None"), "2")); } else { for (d, c) in &rep.by_domain { println!("• {} ({})", d, c); } } }
+    if !no_header { println!("{}", paint(tr("Key Devices:"), "1")); }
+    if !no_header { if rep.by_device.is_empty() { println!("{}", paint(tr("None"), "2")); } else { for (d, c) in &rep.by_device { println!("• {} ({})", d, c); } } }
+    if !no_header { println!("{}", paint(tr("Common Event Codes:"), "1")); }
+    if !no_header { if rep.by_event_id.is_empty() { println!("{}", paint(tr("None"), "2")); } else { for (id, c) in &rep.by_event_id { println!("• {} ({}){}", id, c, stat_suffix(find_stat(&rep.event_id_stats, &id.to_string()), tz, tfmt)); } } }
+    if !no_header { println!("{}", paint(tr("Matched Keywords:"), "1")); }
+    if !no_header { if rep.matched_terms.is_empty() { println!("{}", paint(tr("None"), "2")); } else { for (t, c) in &rep.matched_terms { println!("• {} ({})", t, c); } } }
+    if !no_header { println!("{}", paint(tr("Recent Activity:"), "1;36")); }
     if !no_header {
-        let header = build_line(cols, "Time", "Severity", "Channel", "Provider", Some("EventId"), "Cause", "Message", 16, 10, 14, 18, 8, 24, 96);
+        let header = build_line(cols, "Time", "Severity", "Channel", "Provider", Some("EventId"), "Cause", "Message", "Computer", "User", "Keywords", 16, 10, 14, 18, 8, 24, 96);
         println!("{}", paint(&header, "1"));
     }
     if summary_only { return; }
-    for e in &rep.samples {
-        let ts = match (tz, tfmt) {
-            (TimeZone::Local, Some(f)) => format!("{}", e.time.with_timezone(&Local).format(f)),
-            (TimeZone::Utc, Some(f)) => format!("{}", e.time.format(f)),
-            (TimeZone::Local, None) => format!("{}", e.time.with_timezone(&Local).format("%Y-%m-%d %H:%M")),
-            (TimeZone::Utc, None) => format!("{}", e.time.format("%Y-%m-%d %H:%M")),
-        };
-        let sev = level_name(e.level);
-        let sev_disp = if emoji { match sev { "Critical"=>"⛔ Critical", "Error"=>"⛔ Error", "Warning"=>"⚠️ Warning", "Information"=>"🛈 Information", _=>sev } } else { sev };
-        let sev_s = paint(sev_disp, sev_code(e.level));
-        let ch = if no_trunc { e.channel.clone() } else { truncate(&e.channel, 14) };
-        let pr = if no_trunc { e.provider.clone() } else { truncate(&e.provider, 18) };
-        let eid = e.event_id.to_string();
-        let cause_r = event_cause_redacted(e);
-        let msg_r = event_message_redacted(e);
-        let cause = if no_trunc { cause_r } else { truncate(&cause_r, widths.cause) };
-        let msg = if no_trunc { msg_r } else { truncate(&msg_r, widths.msg) };
-        let line = build_line(cols, &ts, &sev_s, &ch, &pr, Some(&eid), &cause, &msg, 16, 10, 14, 18, 8, 24, 96);
-        println!("{}", line);
-    }
+    for e in &rep.samples { println!("{}", sample_line(e, &widths, tz, cols, no_trunc, tfmt, emoji)); }
     if !rep.file_samples.is_empty() || !rep.file_matched_terms.is_empty() {
-        println!("{}", paint("Files:", "1;36"));
+        println!("{}", paint(tr("Files:"), "1;36"));
         if !rep.file_matched_terms.is_empty() {
-            println!("{}", paint("Matched Keywords:", "1"));
+            println!("{}", paint(tr("Matched Keywords:"), "1"));
             for (t, c) in &rep.file_matched_terms { println!("• {} ({} files)", t, c); }
         }
         if !rep.file_samples.is_empty() {
-            println!("{}", paint("Examples:", "1"));
+            println!("{}", paint(tr("Examples:"), "1"));
             for s in &rep.file_samples {
                 let msg = truncate(&s.line.replace('\n', " "), 120);
                 println!("{} [{}] line {}: {}", s.path, s.pattern, s.line_no, msg);
@@ -1401,16 +2617,17 @@ fn print_text_table(rep: &ReportSummary, widths: PrintWidths, no_header: bool, s
     let end_s = format!("{}", end_local.format("%Y-%m-%d %H:%M"));
     if !no_header { println!("{}", paint(&format!("Time Window: {} to {} (local time)", start_s, end_s), "1;36")); }
     if !no_header && let Some(m) = rep.mode.as_ref() { println!("{}", paint(&format!("Mode: {}", m), "1;36")); }
+    if !no_header && rep.partial { println!("{}", paint(&format!("Partial report (time budget exhausted): {}", completion_text(&rep.completion)), "1;33")); }
     let mut table = Table::new();
     table.set_content_arrangement(ContentArrangement::Dynamic);
     let mut hdr: Vec<String> = Vec::new();
     for c in cols {
-        let h = match c { Column::Time => "Time", Column::Severity => "Severity", Column::Channel => "Channel", Column::Provider => "Provider", Column::EventId => "EventId", Column::Cause => "Cause", Column::Message => "Message" };
+        let h = match c { Column::Time => "Time", Column::Severity => "Severity", Column::Channel => "Channel", Column::Provider => "Provider", Column::EventId => "EventId", Column::Cause => "Cause", Column::Message => "Message", Column::Computer => "Computer", Column::User => "User", Column::Keywords => "Keywords" };
         hdr.push(paint(h, "1"));
     }
         table.set_header(hdr);
     if summary_only { println!("{}", table); return; }
-    if analysis_only { println!("{}", paint("(Analysis-only mode — samples hidden)", "2")); return; }
+    if analysis_only { println!("{}", paint(tr("(Analysis-only mode — samples hidden)"), "2")); return; }
     for e in &rep.samples {
         let ts = match (tz, tfmt) {
             (TimeZone::Local, Some(f)) => format!("{}", e.time.with_timezone(&Local).format(f)),
@@ -1418,7 +2635,8 @@ fn print_text_table(rep: &ReportSummary, widths: PrintWidths, no_header: bool, s
             (TimeZone::Local, None) => format!("{}", e.time.with_timezone(&Local).format("%Y-%m-%d %H:%M")),
             (TimeZone::Utc, None) => format!("{}", e.time.format("%Y-%m-%d %H:%M")),
         };
-        let sev = level_name(e.level);
+        let sev_name = event_level_name(e);
+        let sev = sev_name.as_str();
         let sev_disp = if emoji { match sev { "Critical"=>"⛔ Critical", "Error"=>"⛔ Error", "Warning"=>"⚠️ Warning", "Information"=>"🛈 Information", _=>sev } } else { sev };
         let sev_s = paint(sev_disp, sev_code(e.level));
         let ch = if no_trunc { e.channel.clone() } else { truncate(&e.channel, 14) };
@@ -1438,25 +2656,28 @@ fn print_text_table(rep: &ReportSummary, widths: PrintWidths, no_header: bool, s
                 Column::EventId => row.push(eid.clone()),
                 Column::Cause => row.push(cause.clone()),
                 Column::Message => row.push(msg.clone()),
+                Column::Computer => row.push(e.computer.clone()),
+                Column::User => row.push(e.user.clone().unwrap_or_default()),
+                Column::Keywords => row.push(crate::keywords::label(&e.provider, e.keywords)),
             }
         }
         table.add_row(row);
     }
     println!("{}", table);
-    println!("{} {}", paint("Performance Score:", "1"), rep.performance_score);
+    println!("{} {}", paint(tr("Performance Score:"), "1"), rep.performance_score);
     if let Some(pc) = &rep.perf_counters {
-        println!("{}", paint("Live Performance:", "1"));
+        println!("{}", paint(tr("Live Performance:"), "1"));
         if let Some(v) = pc.cpu_percent { println!("• CPU: {}%", v); }
         if let Some(v) = pc.avg_disk_ms_per_transfer { println!("• Avg Disk Transfer: {:.2} ms", v); }
         if let Some(v) = pc.disk_reads_per_sec { println!("• Reads/s: {}", v); }
         if let Some(v) = pc.disk_writes_per_sec { println!("• Writes/s: {}", v); }
     }
-    if let Some(pred) = rep.smart_failure_predicted && pred { println!("{}", paint("SMART: Predicts failure on one or more drives", "1;31")); }
-    if !rep.degradation_signals.is_empty() { println!("{}", paint("Degradation Signals:", "1")); for (n,w) in &rep.degradation_signals { println!("• {} (weight {})", n, w); } }
-    if !rep.recommendations.is_empty() { println!("{}", paint("Recommendations:", "1")); for r in &rep.recommendations { println!("- {}", r); } }
-    if !rep.recommendations.is_empty() { println!("{}", paint("Checklist:", "1")); for r in &rep.recommendations { println!("[ ] {}", r); } }
+    if let Some(pred) = rep.smart_failure_predicted && pred { println!("{}", paint(tr("SMART: Predicts failure on one or more drives"), "1;31")); }
+    if !rep.degradation_signals.is_empty() { println!("{}", paint(tr("Degradation Signals:"), "1")); for (n,w) in &rep.degradation_signals { println!("• {} (weight {})", n, w); } }
+    if !rep.recommendations.is_empty() { println!("{}", paint(tr("Recommendations:"), "1")); for r in &rep.recommendations { println!("- {}", r); } }
+    if !rep.recommendations.is_empty() { println!("{}", paint(tr("Checklist:"), "1")); for r in &rep.recommendations { println!("[ ] {}", r); } }
     if !rep.timeline.is_empty() {
-        println!("{}", paint("Timeline:", "1"));
+        println!("{}", paint(tr("Timeline:"), "1"));
         let max_e = rep.timeline.iter().map(|(_,e,_)| *e).max().unwrap_or(1);
         let max_w = rep.timeline.iter().map(|(_,_,w)| *w).max().unwrap_or(1);
         for (t,e,w) in &rep.timeline {
@@ -1465,8 +2686,45 @@ fn print_text_table(rep: &ReportSummary, widths: PrintWidths, no_header: bool, s
             println!("{}  E:{:<3} {}  W:{:<3} {}", t, e, eb, w, wb);
         }
     }
+    print_heatmap(&rep.heatmap);
+}
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+fn heatmap_row(row: &[usize], max: usize) -> String {
+    const SHADES: [char; 5] = ['·', '░', '▒', '▓', '█'];
+    row.iter().map(|&v| if v == 0 || max == 0 { SHADES[0] } else { SHADES[(1 + (v * 3) / max).min(4)] }).collect()
+}
+
+fn print_heatmap(heatmap: &[Vec<usize>]) {
+    if heatmap.is_empty() { return; }
+    let max = heatmap.iter().flatten().copied().max().unwrap_or(0);
+    println!("{}", paint(tr("Error Heatmap (hour 0-23):"), "1"));
+    println!("    {}", (0..24).map(|h| if h % 6 == 0 { format!("{:<6}", h) } else { String::new() }).collect::<String>());
+    for (d, row) in heatmap.iter().enumerate() { println!("{} {}  {}", WEEKDAYS[d], heatmap_row(row, max), row.iter().sum::<usize>()); }
+}
+
+fn print_storms(storms: &[crate::storms::Storm], tz: TimeZone, tfmt: Option<&str>) {
+    if storms.is_empty() { return; }
+    println!("{}", paint(tr("Event Storms:"), "1"));
+    for st in storms { println!("• {} {} — {} events, peak {}/min ({} → {})", st.provider, st.event_id, st.total, st.peak_per_minute, format_ts(st.start, tz, tfmt), format_ts(st.end, tz, tfmt)); }
+}
+
+fn print_crashing_apps(apps: &[crate::crashes::AppCrash], tz: TimeZone, tfmt: Option<&str>) {
+    if apps.is_empty() { return; }
+    println!("{}", paint(tr("Top Crashing Applications:"), "1"));
+    for a in apps {
+        let detail = [a.top_module.as_ref().map(|m| format!("module {}", m)), a.top_exception.as_ref().map(|x| format!("exception {}", x))].into_iter().flatten().collect::<Vec<_>>().join(", ");
+        println!("• {} — {} crashes, {} hangs ({} → {}){}", a.app, a.crashes, a.hangs, format_ts(a.first_seen, tz, tfmt), format_ts(a.last_seen, tz, tfmt), if detail.is_empty() { String::new() } else { format!(" · {}", detail) });
+    }
 }
 
+fn print_noise(noise: &[crate::noise::NoiseEntry]) {
+    if noise.is_empty() { return; }
+    println!("{}", paint(tr("Noise Report:"), "1"));
+    for n in noise { println!("• {} {} — {} events over {} h, worst {} (score {:.1}){}", n.provider, n.event_id, n.count, n.active_hours, level_name(n.level), n.score, if n.chronic { " · chronic" } else { "" }); }
+    if noise.iter().any(|n| n.chronic) { println!("Suppression entries for rules.json:\n{}", crate::noise::suppress_snippet(noise)); }
+}
 
 fn bar(v: usize, max: usize, width: usize) -> String {
     if max == 0 { return String::new(); }
@@ -1478,7 +2736,7 @@ fn bar(v: usize, max: usize, width: usize) -> String {
 
 
 #[allow(clippy::too_many_arguments)]
-fn build_line(cols: &Vec<Column>, time: &str, sev: &str, ch: &str, pr: &str, eid: Option<&str>, cause: &str, msg: &str, tw: usize, sw: usize, chw: usize, prw: usize, ew: usize, cw: usize, mw: usize) -> String {
+fn build_line(cols: &Vec<Column>, time: &str, sev: &str, ch: &str, pr: &str, eid: Option<&str>, cause: &str, msg: &str, comp: &str, user: &str, kw: &str, tw: usize, sw: usize, chw: usize, prw: usize, ew: usize, cw: usize, mw: usize) -> String {
     let mut parts: Vec<String> = Vec::new();
     for c in cols {
         match c {
@@ -1489,79 +2747,269 @@ fn build_line(cols: &Vec<Column>, time: &str, sev: &str, ch: &str, pr: &str, eid
             Column::EventId => parts.push(format!("{:<ew$}", eid.unwrap_or("") , ew=ew)),
             Column::Cause => parts.push(format!("{:<cw$}", cause, cw=cw)),
             Column::Message => parts.push(format!("{:<mw$}", msg, mw=mw)),
+            Column::Computer => parts.push(format!("{:<16}", comp)),
+            Column::User => parts.push(format!("{:<14}", user)),
+            Column::Keywords => parts.push(format!("{:<20}", kw)),
         }
     }
     parts.join(" ")
 }
 
-fn write_csv(path: &str, rep: &ReportSummary, tz: TimeZone, tfmt: Option<&str>) -> Result<(), std::io::Error> {
-    let mut wtr = csv::Writer::from_path(path)?;
-    wtr.write_record(["time", "severity", "channel", "provider", "event_id", "cause", "message"])?;
-    for e in &rep.samples {
-        let ts = match (tz, tfmt) { (TimeZone::Local, Some(f)) => format!("{}", e.time.with_timezone(&Local).format(f)), (TimeZone::Utc, Some(f)) => format!("{}", e.time.format(f)), (TimeZone::Local, None) => format!("{}", e.time.with_timezone(&Local).format("%Y-%m-%d %H:%M")), (TimeZone::Utc, None) => format!("{}", e.time.format("%Y-%m-%d %H:%M")) };
-        let sev = level_name(e.level);
-        let cause = event_cause(e);
-        let msg = event_message(e);
-        wtr.write_record([ts, sev.to_string(), e.channel.clone(), e.provider.clone(), e.event_id.to_string(), cause, msg])?;
+// Files are written to a sibling temp file and renamed into place by `finish`, so readers never see a truncated report.
+enum Output { Stdout(std::io::StdoutLock<'static>), File(crate::atomic::AtomicFile), Gzip(flate2::write::GzEncoder<crate::atomic::AtomicFile>) }
+
+impl std::io::Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> { match self { Output::Stdout(w) => w.write(buf), Output::File(w) => w.write(buf), Output::Gzip(w) => w.write(buf) } }
+    fn flush(&mut self) -> std::io::Result<()> { match self { Output::Stdout(w) => w.flush(), Output::File(w) => w.flush(), Output::Gzip(w) => w.flush() } }
+}
+
+impl Output {
+    fn finish(self) -> std::io::Result<()> { match self { Output::Stdout(mut w) => w.flush(), Output::File(w) => w.commit(), Output::Gzip(w) => w.finish()?.commit() } }
+}
+
+fn open_output(path: &str) -> Result<Output, std::io::Error> {
+    if path == "-" { Ok(Output::Stdout(std::io::stdout().lock())) } else { Ok(Output::File(crate::atomic::AtomicFile::create(path)?)) }
+}
+
+fn write_output(path: &str, data: &[u8]) -> Result<(), std::io::Error> {
+    let mut w = open_output(path)?;
+    w.write_all(data)?;
+    w.finish()
+}
+
+// `--compress`: JSON/NDJSON/CSV/TSV files are gzipped as they are written; stdout stays plain.
+fn export_path(path: &str) -> String {
+    if COMPRESS.load(std::sync::atomic::Ordering::Relaxed) && path != "-" && !path.ends_with(".gz") { format!("{}.gz", path) } else { path.to_string() }
+}
+
+fn open_export(path: &str) -> Result<Output, std::io::Error> {
+    if !COMPRESS.load(std::sync::atomic::Ordering::Relaxed) || path == "-" { return open_output(path); }
+    Ok(Output::Gzip(flate2::write::GzEncoder::new(crate::atomic::AtomicFile::create(export_path(path))?, flate2::Compression::default())))
+}
+
+fn write_export(path: &str, data: &[u8]) -> Result<(), std::io::Error> {
+    let mut w = open_export(path)?;
+    w.write_all(data)?;
+    w.finish()
+}
+
+const DEFAULT_EXPORT_FIELDS: [&str; 8] = ["time", "severity", "channel", "provider", "event_id", "cause", "message", "record_id"];
+const BUILTIN_EXPORT_FIELDS: [&str; 14] = ["time", "severity", "level", "channel", "provider", "event_id", "cause", "message", "computer", "user", "keywords", "task", "opcode", "record_id"];
+
+fn needs_event_data(fields: &[String]) -> bool {
+    fields.iter().any(|f| !BUILTIN_EXPORT_FIELDS.contains(&f.to_lowercase().as_str()))
+}
+
+fn porcelain_line(rep: &ReportSummary) -> String {
+    let tok = |s: &str| if s.is_empty() { "none".to_string() } else { s.split_whitespace().collect::<Vec<_>>().join("_") };
+    let top_domain = rep.by_domain.first().map(|(d, _)| tok(d)).unwrap_or_else(|| "none".to_string());
+    format!("errors={} warnings={} risk={} score={} top_domain={}", rep.errors, rep.warnings, tok(&rep.risk_grade), rep.performance_score, top_domain)
+}
+
+// Analytic and Debug channels are trace sessions that cannot be queried while enabled; a log last written before
+// the window cannot contain matching events.
+// "System: EvtRender error 15027 (3 events)"
+fn collection_error_line(e: &crate::windows_live::CollectionError) -> String {
+    let code = if e.code == 0 { String::new() } else { format!(" error {}", e.code) };
+    let lost = if e.records == 0 { String::new() } else { format!(" ({} event{})", e.records, if e.records == 1 { "" } else { "s" }) };
+    format!("{}: {}{}{}", e.channel, if e.operation == "parse" { "unparsable event XML" } else { &e.operation }, code, lost)
+}
+
+fn scannable_channel(c: &crate::windows_live::ChannelInfo, since: DateTime<Utc>) -> bool {
+    c.enabled == Some(true) && c.records.unwrap_or(0) > 0 && matches!(c.kind, None | Some(0) | Some(1)) && c.last_write.is_none_or(|t| t >= since)
+}
+
+fn print_channel_list(chans: &[crate::windows_live::ChannelInfo], tz: TimeZone, tfmt: Option<&str>) {
+    let mb = |b: Option<u64>| b.map(|b| format!("{:.1}", b as f64 / 1048576.0)).unwrap_or_else(|| "-".to_string());
+    println!("{:<64} {:<8} {:>9} {:>9} {:>10}  Last write", "Channel", "Enabled", "Max MB", "Size MB", "Records");
+    for c in chans {
+        let enabled = match c.enabled { Some(true) => "yes", Some(false) => "no", None => "?" };
+        let last = c.last_write.map(|t| format_ts(t, tz, tfmt)).unwrap_or_else(|| "-".to_string());
+        println!("{:<64} {:<8} {:>9} {:>9} {:>10}  {}", c.name, enabled, mb(c.max_size_bytes), mb(c.file_size_bytes), c.records.map(|r| r.to_string()).unwrap_or_else(|| "-".to_string()), last);
     }
-    wtr.flush()?;
-    Ok(())
+    println!("{} channels ({} enabled)", chans.len(), chans.iter().filter(|c| c.enabled == Some(true)).count());
 }
 
-fn write_tsv(path: &str, rep: &ReportSummary, tz: TimeZone, tfmt: Option<&str>) -> Result<(), std::io::Error> {
-    let mut wtr = csv::WriterBuilder::new().delimiter(b'\t').from_path(path)?;
-    wtr.write_record(["time", "severity", "channel", "provider", "event_id", "cause", "message"])?;
-    for e in &rep.samples {
-        let ts = match (tz, tfmt) { (TimeZone::Local, Some(f)) => format!("{}", e.time.with_timezone(&Local).format(f)), (TimeZone::Utc, Some(f)) => format!("{}", e.time.format(f)), (TimeZone::Local, None) => format!("{}", e.time.with_timezone(&Local).format("%Y-%m-%d %H:%M")), (TimeZone::Utc, None) => format!("{}", e.time.format("%Y-%m-%d %H:%M")) };
-        let sev = level_name(e.level);
-        let cause = event_cause(e);
-        let msg = event_message(e);
-        wtr.write_record([ts, sev.to_string(), e.channel.clone(), e.provider.clone(), e.event_id.to_string(), cause, msg])?;
+fn print_provider_list(provs: &[crate::windows_live::ProviderInfo], width: usize) {
+    for p in provs {
+        println!("{}", paint(&p.name, "1"));
+        if !p.channels.is_empty() { println!("  Channels: {}", p.channels.join(", ")); }
+        for e in &p.events {
+            let desc = e.description.lines().next().unwrap_or("");
+            println!("  {:>6}  v{:<2} {:<11} {:<40} {}", e.id, e.version, level_name(e.level), e.channel.as_deref().unwrap_or("-"), truncate(desc, width));
+        }
     }
-    wtr.flush()?;
-    Ok(())
+    println!("{} providers, {} events", provs.len(), provs.iter().map(|p| p.events.len()).sum::<usize>());
 }
 
-fn write_ndjson(path: &str, rep: &ReportSummary, tz: TimeZone, tfmt: Option<&str>, emit_eventdata: bool, emit_xml: bool) -> Result<(), std::io::Error> {
-    use std::io::Write;
-    let mut file = std::fs::File::create(path)?;
-    for e in &rep.samples {
-        let ts = match (tz, tfmt) { (TimeZone::Local, Some(f)) => format!("{}", e.time.with_timezone(&Local).format(f)), (TimeZone::Utc, Some(f)) => format!("{}", e.time.format(f)), (TimeZone::Local, None) => format!("{}", e.time.with_timezone(&Local).format("%Y-%m-%d %H:%M")), (TimeZone::Utc, None) => format!("{}", e.time.format("%Y-%m-%d %H:%M")) };
-        let mut obj = serde_json::json!({
-            "schema_version": 1,
-            "time": ts,
-            "severity": level_name(e.level),
-            "channel": e.channel,
-            "provider": e.provider,
-            "event_id": e.event_id,
-            "cause": event_cause_redacted(e),
-            "message": event_message_redacted(e)
-        });
-        if emit_eventdata && let Some(xml) = e.raw_xml.as_ref()
-            && let Some(map) = obj.as_object_mut() {
-            let mut pairs = crate::event_xml::event_data_pairs_or_fallback(xml);
-            let keys = REDACT_KEYS.get().cloned().unwrap_or_default();
-            if !keys.is_empty() {
-                let lower: Vec<String> = keys.iter().map(|k| k.to_lowercase()).collect();
-                pairs.retain(|k,_| !lower.contains(&k.to_lowercase()));
-            }
-            map.insert("event_data".to_string(), serde_json::to_value(pairs).unwrap());
+fn format_ts(t: DateTime<Utc>, tz: TimeZone, tfmt: Option<&str>) -> String {
+    match (tz, tfmt) { (TimeZone::Local, Some(f)) => format!("{}", t.with_timezone(&Local).format(f)), (TimeZone::Utc, Some(f)) => format!("{}", t.format(f)), (TimeZone::Local, None) => format!("{}", t.with_timezone(&Local).format("%Y-%m-%d %H:%M")), (TimeZone::Utc, None) => format!("{}", t.format("%Y-%m-%d %H:%M")) }
+}
+
+// Forwarded (WEF) or mixed-origin reports carry the source computer through to samples and exports.
+fn computer_top_suffix(rep: &ReportSummary, comp: &str) -> String {
+    rep.computer_top_providers.iter().find(|(c, _)| c == comp).filter(|(_, p)| !p.is_empty()).map(|(_, p)| format!(" · top {}", p.iter().map(|(p, c)| format!("{} ({})", p, c)).collect::<Vec<_>>().join(", "))).unwrap_or_default()
+}
+
+fn sort_events(events: &mut [EventItem], sort_by: SortBy, sort_order: SortOrder) {
+    match (sort_by, sort_order) {
+        (SortBy::Time, SortOrder::Desc) => events.sort_by_key(|e| std::cmp::Reverse(e.time)),
+        (SortBy::Time, SortOrder::Asc) => events.sort_by_key(|e| e.time),
+        (SortBy::Severity, SortOrder::Desc) => events.sort_by_key(|e| std::cmp::Reverse(e.level)),
+        (SortBy::Severity, SortOrder::Asc) => events.sort_by_key(|e| e.level),
+        (SortBy::Provider, SortOrder::Desc) => events.sort_by(|a, b| b.provider.cmp(&a.provider)),
+        (SortBy::Provider, SortOrder::Asc) => events.sort_by(|a, b| a.provider.cmp(&b.provider)),
+        (SortBy::Channel, SortOrder::Desc) => events.sort_by(|a, b| b.channel.cmp(&a.channel)),
+        (SortBy::Channel, SortOrder::Asc) => events.sort_by(|a, b| a.channel.cmp(&b.channel)),
+        (SortBy::EventId, SortOrder::Desc) => events.sort_by_key(|e| std::cmp::Reverse(e.event_id)),
+        (SortBy::EventId, SortOrder::Asc) => events.sort_by_key(|e| e.event_id),
+    }
+}
+
+fn multi_computer(rep: &ReportSummary) -> bool {
+    rep.by_computer.len() > 1 || rep.by_channel.iter().any(|(c, _)| c.eq_ignore_ascii_case("ForwardedEvents"))
+}
+
+fn export_field_names(fields: &[String]) -> Vec<String> {
+    if fields.is_empty() { DEFAULT_EXPORT_FIELDS.iter().map(|s| s.to_string()).collect() } else { fields.to_vec() }
+}
+
+fn csv_fields(args: &Args) -> &[String] {
+    if args.csv_columns.is_empty() { &args.export_fields } else { &args.csv_columns }
+}
+
+// `eventdata:FaultingModuleName` -> `FaultingModuleName`; the prefix also reaches EventData names that clash with a built-in field.
+fn event_data_key(field: &str) -> &str {
+    match field.get(..10) { Some(p) if p.eq_ignore_ascii_case("eventdata:") => &field[10..], _ => field }
+}
+
+// Unknown field names are looked up as named <EventData> values.
+fn export_field_value(e: &EventItem, field: &str, ts: &str, data: &std::collections::HashMap<String, String>, redact: bool) -> serde_json::Value {
+    let txt = |v: String| serde_json::Value::String(if redact { redact_text(&v) } else { v });
+    match field.to_lowercase().as_str() {
+        "time" => serde_json::Value::String(ts.to_string()),
+        "severity" => serde_json::Value::String(event_level_name(e)),
+        "level" => serde_json::Value::from(e.level),
+        "channel" => serde_json::Value::String(e.channel.clone()),
+        "provider" => serde_json::Value::String(e.provider.clone()),
+        "event_id" => serde_json::Value::from(e.event_id),
+        "cause" => txt(event_cause(e)),
+        "message" => txt(event_message(e)),
+        "computer" => serde_json::Value::String(e.computer.clone()),
+        "user" => e.user.clone().map(&txt).unwrap_or(serde_json::Value::Null),
+        "keywords" => serde_json::Value::String(crate::keywords::display(e.keywords)),
+        "record_id" => e.record_id.map(serde_json::Value::from).unwrap_or(serde_json::Value::Null),
+        "task" => serde_json::Value::String(crate::tasks::task_label(&e.provider, e.task)),
+        "opcode" => serde_json::Value::String(crate::tasks::opcode_label(&e.provider, e.task, e.opcode)),
+        _ => txt(data.get(event_data_key(field)).cloned().unwrap_or_default()),
+    }
+}
+
+fn export_rows(rep: &ReportSummary, events: &[EventItem], tz: TimeZone, tfmt: Option<&str>, fields: &[String], redact: bool) -> (Vec<String>, Vec<Vec<serde_json::Value>>) {
+    let mut names = export_field_names(fields);
+    if fields.is_empty() && multi_computer(rep) { names.insert(3, "computer".to_string()); }
+    let want_data = needs_event_data(&names);
+    let mut rows = Vec::with_capacity(events.len());
+    for e in events {
+        let ts = format_ts(e.time, tz, tfmt);
+        let data = if want_data { crate::event_xml::event_data_pairs_or_fallback(e.raw_xml.as_deref().unwrap_or(&e.content)) } else { std::collections::HashMap::new() };
+        rows.push(names.iter().map(|f| export_field_value(e, f, &ts, &data, redact)).collect());
+    }
+    (names, rows)
+}
+
+fn value_cell(v: serde_json::Value) -> String {
+    match v { serde_json::Value::String(s) => s, other => other.to_string() }
+}
+
+fn write_delimited(path: &str, rep: &ReportSummary, events: &[EventItem], tz: TimeZone, tfmt: Option<&str>, fields: &[String], delimiter: u8) -> Result<(), std::io::Error> {
+    let mut wtr = csv::WriterBuilder::new().delimiter(delimiter).from_writer(open_export(path)?);
+    let (names, rows) = export_rows(rep, events, tz, tfmt, fields, false);
+    wtr.write_record(names.iter().map(|n| event_data_key(n)))?;
+    for row in rows { wtr.write_record(row.into_iter().map(value_cell))?; }
+    wtr.into_inner().map_err(|e| e.into_error())?.finish()
+}
+
+fn write_csv(path: &str, rep: &ReportSummary, events: &[EventItem], tz: TimeZone, tfmt: Option<&str>, fields: &[String]) -> Result<(), std::io::Error> {
+    write_delimited(path, rep, events, tz, tfmt, fields, b',')
+}
+
+fn write_tsv(path: &str, rep: &ReportSummary, events: &[EventItem], tz: TimeZone, tfmt: Option<&str>, fields: &[String]) -> Result<(), std::io::Error> {
+    write_delimited(path, rep, events, tz, tfmt, fields, b'\t')
+}
+
+#[allow(clippy::too_many_arguments)]
+fn write_ndjson(path: &str, rep: &ReportSummary, events: &[EventItem], tz: TimeZone, tfmt: Option<&str>, emit_eventdata: bool, emit_xml: bool, fields: &[String]) -> Result<(), std::io::Error> {
+    let mut file = open_export(path)?;
+    let (names, rows) = export_rows(rep, events, tz, tfmt, fields, true);
+    for (e, row) in events.iter().zip(rows) { writeln!(file, "{}", ndjson_object(e, &names, row, emit_eventdata, emit_xml))?; }
+    file.finish()
+}
+
+// `--stream-ndjson`: one NDJSON line per filtered event, written as events are read rather than from the report's samples.
+// The first write error stops the stream and is reported by `finish`.
+struct EventStream { out: Output, names: Vec<String>, want_data: bool, tz: TimeZone, tfmt: Option<String>, emit_eventdata: bool, emit_xml: bool, written: usize, error: Option<std::io::Error> }
+
+impl EventStream {
+    fn open(path: &str, args: &Args) -> Result<EventStream, std::io::Error> {
+        let names = export_field_names(&args.export_fields);
+        Ok(EventStream { out: open_export(path)?, want_data: needs_event_data(&names), names, tz: args.time_zone, tfmt: args.time_format.clone(), emit_eventdata: args.emit_eventdata, emit_xml: args.emit_xml, written: 0, error: None })
+    }
+
+    fn write(&mut self, e: &EventItem) {
+        if self.error.is_some() { return; }
+        let ts = format_ts(e.time, self.tz, self.tfmt.as_deref());
+        let data = if self.want_data { crate::event_xml::event_data_pairs_or_fallback(e.raw_xml.as_deref().unwrap_or(&e.content)) } else { std::collections::HashMap::new() };
+        let row = self.names.iter().map(|f| export_field_value(e, f, &ts, &data, true)).collect();
+        match writeln!(self.out, "{}", ndjson_object(e, &self.names, row, self.emit_eventdata, self.emit_xml)) { Ok(_) => self.written += 1, Err(err) => self.error = Some(err) }
+    }
+
+    fn finish(self) -> Result<usize, std::io::Error> {
+        if let Some(e) = self.error { return Err(e); }
+        self.out.finish().map(|_| self.written)
+    }
+}
+
+fn write_lines(path: &str, events: &[EventItem], line: fn(&EventItem) -> String) -> Result<(), std::io::Error> {
+    let mut file = open_output(path)?;
+    for e in events { writeln!(file, "{}", line(e))?; }
+    file.finish()
+}
+
+fn ndjson_object(e: &EventItem, names: &[String], row: Vec<serde_json::Value>, emit_eventdata: bool, emit_xml: bool) -> serde_json::Value {
+    let mut obj = serde_json::json!({ "schema_version": 1 });
+    if let Some(map) = obj.as_object_mut() { for (k, v) in names.iter().zip(row) { map.insert(event_data_key(k).to_string(), v); } }
+    if emit_eventdata && let Some(xml) = e.raw_xml.as_ref()
+        && let Some(map) = obj.as_object_mut() {
+        let mut pairs = crate::event_xml::event_data_pairs_or_fallback(xml);
+        let keys = REDACT_KEYS.get().cloned().unwrap_or_default();
+        if !keys.is_empty() {
+            let lower: Vec<String> = keys.iter().map(|k| k.to_lowercase()).collect();
+            pairs.retain(|k,_| !lower.contains(&k.to_lowercase()));
         }
-        if emit_xml && let Some(xml) = e.raw_xml.as_ref()
-            && let Some(map) = obj.as_object_mut() { map.insert("xml".to_string(), serde_json::Value::String(xml.clone())); }
-        writeln!(file, "{}", obj)?;
+        map.insert("event_data".to_string(), serde_json::to_value(pairs).unwrap());
     }
-    Ok(())
+    if emit_xml && let Some(xml) = e.raw_xml.as_ref()
+        && let Some(map) = obj.as_object_mut() { map.insert("xml".to_string(), serde_json::Value::String(xml.clone())); }
+    obj
 }
 
 #[derive(Clone, Debug)]
 struct NdRecord { severity: String, provider: String, event_id: u32 }
 
 #[derive(Clone, Debug)]
-struct NdRecordFull { schema_version: Option<u32>, time: Option<String>, severity: Option<String>, channel: Option<String>, provider: Option<String>, event_id: Option<u32>, cause: Option<String>, message: Option<String> }
+struct NdRecordFull { schema_version: Option<u32>, time: Option<String>, severity: Option<String>, channel: Option<String>, provider: Option<String>, event_id: Option<u32>, cause: Option<String>, message: Option<String>, computer: Option<String>, user: Option<String>, xml: Option<String>, keywords: Option<String>, task: Option<u16>, opcode: Option<u8>, record_id: Option<u64> }
+
+static STDIN_NDJSON: OnceLock<Option<String>> = OnceLock::new();
+
+// `-` is stdin, read once so the schema check and the reader see the same data.
+fn read_ndjson_text(path: &str) -> Option<String> {
+    if path == "-" { return STDIN_NDJSON.get_or_init(|| std::io::read_to_string(std::io::stdin()).ok()).clone(); }
+    std::fs::read_to_string(path).ok()
+}
 
 fn read_ndjson(path: &str) -> Option<Vec<NdRecord>> {
-    if let Ok(data) = std::fs::read_to_string(path) {
+    if let Some(data) = read_ndjson_text(path) {
         let mut out = Vec::new();
         for line in data.lines() {
             if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
@@ -1576,8 +3024,20 @@ fn read_ndjson(path: &str) -> Option<Vec<NdRecord>> {
     None
 }
 
+// Events of an NDJSON export as written by --ndjson-path; records of another schema version are skipped.
+fn ndjson_events(path: &str) -> Option<Vec<EventItem>> {
+    let mut items: Vec<EventItem> = Vec::new();
+    for r in read_ndjson_full(path)? {
+        if let Some(sv) = r.schema_version { if sv != 1 { log::warn!("Skipping NDJSON record with unsupported schema_version: {}", sv); continue; } }
+        let time = parse_system_time(&r.time.unwrap_or_else(|| Utc::now().to_rfc3339())).unwrap_or(Utc::now());
+        let severity = match r.severity.as_deref() { Some("Critical")=>1, Some("Error")=>2, Some("Warning")=>3, Some("Information")=>4, Some("Verbose")=>5, _=>0 };
+        items.push(EventItem { time, level: severity, channel: r.channel.unwrap_or_else(|| "".to_string()), provider: r.provider.unwrap_or_else(|| "".to_string()), event_id: r.event_id.unwrap_or(0), content: r.message.or(r.cause).unwrap_or_default(), raw_xml: r.xml, computer: r.computer.unwrap_or_default(), user: r.user, record_id: r.record_id, keywords: r.keywords.as_deref().map(crate::keywords::parse_list).unwrap_or(0), task: r.task.unwrap_or(0), opcode: r.opcode.unwrap_or(0) });
+    }
+    Some(items)
+}
+
 fn read_ndjson_full(path: &str) -> Option<Vec<NdRecordFull>> {
-    if let Ok(data) = std::fs::read_to_string(path) {
+    if let Some(data) = read_ndjson_text(path) {
         let mut out = Vec::new();
         for line in data.lines() {
             if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
@@ -1589,7 +3049,15 @@ fn read_ndjson_full(path: &str) -> Option<Vec<NdRecordFull>> {
                 let eid = v.get("event_id").and_then(|x| x.as_u64()).map(|x| x as u32);
                 let cause = v.get("cause").and_then(|x| x.as_str()).map(|s| s.to_string());
                 let msg = v.get("message").and_then(|x| x.as_str()).map(|s| s.to_string());
-                out.push(NdRecordFull { schema_version: sv, time, severity: sev, channel: ch, provider: prv, event_id: eid, cause, message: msg });
+                let computer = v.get("computer").and_then(|x| x.as_str()).map(|s| s.to_string());
+                let user = v.get("user").and_then(|x| x.as_str()).map(|s| s.to_string());
+                let xml = v.get("xml").and_then(|x| x.as_str()).map(|s| s.to_string());
+                let keywords = v.get("keywords").and_then(|x| x.as_str()).map(|s| s.to_string());
+                // Exported as a name when the publisher declares one; only numbers can be read back.
+                let num = |k: &str| v.get(k).and_then(|x| x.as_u64().or_else(|| x.as_str().and_then(|s| s.parse().ok())));
+                let (task, opcode) = (num("task").map(|n| n as u16), num("opcode").map(|n| n as u8));
+                let record_id = num("record_id");
+                out.push(NdRecordFull { schema_version: sv, time, severity: sev, channel: ch, provider: prv, event_id: eid, cause, message: msg, computer, user, xml, keywords, task, opcode, record_id });
             }
         }
         return Some(out);
@@ -1598,7 +3066,7 @@ fn read_ndjson_full(path: &str) -> Option<Vec<NdRecordFull>> {
 }
 
 fn check_ndjson_schema(path: &str) -> bool {
-    if let Ok(data) = std::fs::read_to_string(path) {
+    if let Some(data) = read_ndjson_text(path) {
         for line in data.lines() {
             if let Ok(v) = serde_json::from_str::<serde_json::Value>(line) {
                 if let Some(sv) = v.get("schema_version").and_then(|x| x.as_u64()) { return sv == 1; }
@@ -1652,7 +3120,7 @@ fn compare_ndjson(base: &str, current: &str) -> Option<ComparisonResult> {
 }
 
 fn print_comparison(cmp: &ComparisonResult) {
-    println!("{}", paint("Comparison (NDJSON):", "1"));
+    println!("{}", paint(tr("Comparison (NDJSON):"), "1"));
     println!("Δ Errors: {} | Δ Warnings: {}", cmp.delta_errors, cmp.delta_warnings);
     if !cmp.new_providers.is_empty() { println!("New Providers:"); for p in &cmp.new_providers { println!("• {}", p); } }
     if !cmp.removed_providers.is_empty() { println!("Removed Providers:"); for p in &cmp.removed_providers { println!("• {}", p); } }
@@ -1673,27 +3141,7 @@ fn write_compare_json(path: &str, cmp: &ComparisonResult) -> Result<(), std::io:
         "decreased_event_ids": cmp.decreased_event_ids,
         "new_event_ids": cmp.new_event_ids,
     });
-    std::fs::write(path, serde_json::to_string_pretty(&obj).unwrap())
-}
-
-fn zip_directory(dir: &str, zip_path: &str) -> Result<(), std::io::Error> {
-    use std::fs::File;
-    use std::io::Write;
-    let file = File::create(zip_path)?;
-    let mut zip = zip::ZipWriter::new(file);
-    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
-    for de in walkdir::WalkDir::new(dir).into_iter().filter_map(Result::ok) {
-        let p = de.path();
-        if p.is_file() {
-            let rel = p.strip_prefix(dir).unwrap_or(p);
-            let rel_s = rel.to_string_lossy().replace('\\', "/");
-            zip.start_file(rel_s, options)?;
-            let data = std::fs::read(p)?;
-            zip.write_all(&data)?;
-        }
-    }
-    zip.finish()?;
-    Ok(())
+    crate::atomic::write_atomic(path, serde_json::to_string_pretty(&obj).unwrap().as_bytes())
 }
 
 #[cfg(test)]
@@ -1721,6 +3169,40 @@ mod tests_ndjson_compare {
         let _ = std::fs::remove_file(&c);
     }
     #[test]
+    fn full_reader_keeps_exported_xml() {
+        let p = std::env::temp_dir().join(format!("windoctor-ndin-{}.ndjson", std::process::id()));
+        std::fs::write(&p, "{\"schema_version\":1,\"severity\":\"Error\",\"provider\":\"Disk\",\"event_id\":7,\"xml\":\"<Event/>\"}\nnot json\n").unwrap();
+        let recs = read_ndjson_full(&p.to_string_lossy()).unwrap();
+        assert_eq!(recs.len(), 1);
+        assert_eq!(recs[0].xml.as_deref(), Some("<Event/>"));
+        assert!(check_ndjson_schema(&p.to_string_lossy()));
+        let _ = std::fs::remove_file(&p);
+    }
+    #[test]
+    fn event_stream_writes_every_event_it_is_given() {
+        let p = std::env::temp_dir().join(format!("windoctor-stream-{}.ndjson", std::process::id()));
+        let args = Args { export_fields: vec!["event_id".to_string(), "provider".to_string()], ..Default::default() };
+        let mut st = EventStream::open(&p.to_string_lossy(), &args).unwrap();
+        for id in 0..3 { st.write(&EventItem { event_id: id, provider: "Disk".to_string(), ..Default::default() }); }
+        assert_eq!(st.finish().unwrap(), 3);
+        let text = std::fs::read_to_string(&p).unwrap();
+        let rows: Vec<serde_json::Value> = text.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!((rows.len(), rows[2]["event_id"].as_u64(), rows[0]["provider"].as_str()), (3, Some(2), Some("Disk")));
+        let _ = std::fs::remove_file(&p);
+    }
+    #[test]
+    fn gzip_output_streams_into_the_final_file() {
+        let p = std::env::temp_dir().join(format!("windoctor-gz-{}.ndjson.gz", std::process::id()));
+        let mut out = Output::Gzip(flate2::write::GzEncoder::new(crate::atomic::AtomicFile::create(&p).unwrap(), flate2::Compression::default()));
+        for i in 0..1000 { writeln!(out, "{{\"event_id\":{}}}", i).unwrap(); }
+        out.finish().unwrap();
+        let mut text = String::new();
+        std::io::Read::read_to_string(&mut flate2::read::GzDecoder::new(std::fs::File::open(&p).unwrap()), &mut text).unwrap();
+        assert_eq!(text.lines().count(), 1000);
+        assert!(std::fs::metadata(&p).unwrap().len() < text.len() as u64 / 4);
+        let _ = std::fs::remove_file(&p);
+    }
+    #[test]
     fn compare_out_writes_json() {
         let dir = std::env::temp_dir();
         let b = dir.join("base2.ndjson");
@@ -1741,7 +3223,12 @@ mod tests_ndjson_compare {
     }
 }
 
-fn level_name(l: u8) -> &'static str { match l { 1 => "Critical", 2 => "Error", 3 => "Warning", 4 => "Information", _ => "Other" } }
+fn level_name(l: u8) -> &'static str { match l { 1 => "Critical", 2 => "Error", 3 => "Warning", 4 => "Information", 5 => "Verbose", _ => "Other" } }
+
+// rules.json `level_names`, then the publisher's own names for custom levels, then the standard names.
+fn event_level_name(e: &EventItem) -> String {
+    crate::rules::level_name_for(&e.provider, e.level).or_else(|| crate::tasks::level_name(&e.provider, e.level)).unwrap_or_else(|| level_name(e.level).to_string())
+}
 
 fn truncate(s: &str, n: usize) -> String {
     let mut out: String = s.chars().take(n).collect();
@@ -1788,6 +3275,10 @@ fn classify_domain(provider: &str, channel: &str, event_id: u32, content: &str)
     };
     let ch = channel.to_lowercase();
     let ct = content.to_lowercase();
+    // OneDrive / Known Folder Move
+    if p.contains("onedrive") || p.contains("knownfolders") {
+        return "Sync".to_string();
+    }
     // Storage / Filesystem
     if p.contains("disk") || p.contains("ntfs") || p.contains("storport") || p.contains("volmgr") || p.contains("volsnap") || ch.contains("storage") || [7u32,11,51,55,57,129,140,153,157].contains(&event_id) {
         return "Storage".to_string();
@@ -1878,6 +3369,31 @@ fn pass_provider(args: &Args, provider: &str) -> bool {
     } else { true }
 }
 
+fn pass_identity(args: &Args, e: &EventItem) -> bool {
+    let host = |c: &String| c.eq_ignore_ascii_case(&e.computer) || e.computer.split('.').next().is_some_and(|h| c.eq_ignore_ascii_case(h));
+    (args.computers.is_empty() || args.computers.iter().any(host))
+        && !args.exclude_computers.iter().any(host)
+        && (args.users.is_empty() || e.user.as_ref().is_some_and(|u| args.users.iter().any(|x| x.eq_ignore_ascii_case(u))))
+}
+
+fn pass_keywords(e: &EventItem) -> bool { KEYWORD_MASK.get().is_none_or(|m| e.keywords & m != 0) && KEYWORD_NAMES.get().is_none_or(|n| crate::keywords::names_match(n, &e.provider, e.keywords)) }
+
+fn pass_tasks(args: &Args, e: &EventItem) -> bool { crate::tasks::task_matches(&args.tasks, &e.provider, e.task) }
+
+fn pass_query(e: &EventItem) -> bool { QUERY.get().is_none_or(|q| q.matches(e)) }
+
+fn pass_grep(e: &EventItem) -> bool { grep_keeps(GREP.get().map_or(&[], |v| v.as_slice()), EXCLUDE_GREP.get().map_or(&[], |v| v.as_slice()), &e.content) }
+
+// Exclusions win over inclusions.
+fn grep_keeps(include: &[Regex], exclude: &[Regex], content: &str) -> bool {
+    (include.is_empty() || include.iter().any(|re| re.is_match(content))) && !exclude.iter().any(|re| re.is_match(content))
+}
+
+fn pass_record(args: &Args, e: &EventItem) -> bool {
+    if args.from_record.is_none() && args.to_record.is_none() && args.after_record_id.is_none() { return true; }
+    e.record_id.is_some_and(|r| args.from_record.is_none_or(|f| r >= f) && args.to_record.is_none_or(|t| r <= t) && args.after_record_id.is_none_or(|n| r > n))
+}
+
 fn pass_event_id(args: &Args, id: u32) -> bool {
     if !args.include_event_ids.is_empty() {
         args.include_event_ids.contains(&id)
@@ -1900,6 +3416,7 @@ mod tests {
             window_start: Utc::now(),
             window_end: Utc::now(),
             total: 1,
+            criticals: 0,
             errors: 1,
             warnings: 0,
             by_provider: vec![("Disk".to_string(), 1)],
@@ -1908,7 +3425,7 @@ mod tests {
             by_device: vec![],
             by_domain: vec![],
             matched_terms: vec![],
-            samples: vec![EventItem { time: Utc::now(), level: 2, channel: "System".to_string(), provider: "Disk".to_string(), event_id: 7, content: "Bad block".to_string(), raw_xml: None }],
+            samples: vec![EventItem { time: Utc::now(), level: 2, channel: "System".to_string(), provider: "Disk".to_string(), event_id: 7, content: "Bad block".to_string(), raw_xml: None, ..Default::default() }],
             file_matched_terms: vec![],
             file_samples: vec![],
             scanned_records: 1,
@@ -1926,14 +3443,139 @@ mod tests {
             smart_failure_predicted: None,
             risk_grade: "Unknown".to_string(),
             compare: None,
+            by_source: vec![],
+            scanned_by_source: vec![],
+            provider_stats: vec![],
+            event_id_stats: vec![],
+            heatmap: vec![],
+            by_computer: vec![],
+            computer_stats: vec![],
+            computer_top_providers: vec![],
+            by_user: vec![],
+            max_record_ids: vec![],
+            partial: false,
+            completion: vec![],
+            services_down: vec![],
+            policy: None,
+            dev_env: None,
+            sync: vec![],
+            storms: vec![],
+            noise: vec![],
+            group_by: None,
+            crashing_apps: vec![],
+            defender: vec![],
+            battery: None,
+            channel_health: vec![],
+            log_integrity: Default::default(),
+            collection_errors: vec![],
+            system_info: None,
+            all_providers: vec![],
+            all_event_ids: vec![],
         };
         let p = std::env::temp_dir().join("windoctor_test.ndjson");
-        write_ndjson(&p.to_string_lossy(), &rep, TimeZone::Utc, None, false, false).unwrap();
+        write_ndjson(&p.to_string_lossy(), &rep, &rep.samples, TimeZone::Utc, None, false, false, &[]).unwrap();
         let data = std::fs::read_to_string(&p).unwrap();
         assert!(data.lines().count() >= 1);
         let _ = std::fs::remove_file(&p);
     }
 
+    #[test]
+    fn porcelain_line_is_one_stable_record() {
+        assert_eq!(porcelain_line(&test_summary()), "errors=0 warnings=0 risk=Low score=0 top_domain=none");
+        let rep = ReportSummary { errors: 12, warnings: 30, risk_grade: "High".to_string(), performance_score: 64, by_domain: vec![("Windows Update".to_string(), 7)], ..test_summary() };
+        assert_eq!(porcelain_line(&rep), "errors=12 warnings=30 risk=High score=64 top_domain=Windows_Update");
+    }
+
+    #[test]
+    fn export_fields_include_event_data() {
+        let e = EventItem { time: Utc::now(), level: 2, channel: "Application".to_string(), provider: "Application Error".to_string(), event_id: 1000, content: "crash".to_string(), raw_xml: Some("<Event><EventData><Data Name=\"FaultingModuleName\">ntdll.dll</Data></EventData></Event>".to_string()), ..Default::default() };
+        let fields = vec!["provider".to_string(), "event_id".to_string(), "FaultingModuleName".to_string()];
+        assert!(needs_event_data(&fields));
+        let data = crate::event_xml::event_data_pairs_or_fallback(e.raw_xml.as_deref().unwrap());
+        let row: Vec<serde_json::Value> = fields.iter().map(|f| export_field_value(&e, f, "t", &data, false)).collect();
+        assert_eq!(row[0], serde_json::json!("Application Error"));
+        assert_eq!(row[1], serde_json::json!(1000));
+        assert_eq!(row[2], serde_json::json!("ntdll.dll"));
+        assert_eq!(export_field_value(&e, "eventdata:FaultingModuleName", "t", &data, false), serde_json::json!("ntdll.dll"));
+        assert_eq!(event_data_key("EventData:User"), "User");
+    }
+
+    #[test]
+    fn agg_stats_track_first_last_and_split() {
+        let t0 = Utc::now() - chrono::Duration::hours(3);
+        let mk = |h: i64, level: u8, provider: &str| EventItem { time: t0 + chrono::Duration::hours(h), level, channel: "System".to_string(), provider: provider.to_string(), event_id: 7, content: String::new(), raw_xml: None, ..Default::default() };
+        let events = vec![mk(2, 2, "Disk"), mk(0, 3, "Disk"), mk(1, 2, "Disk"), mk(1, 2, "Ntfs")];
+        let stats = build_agg_stats(&events, &["Disk".to_string()], |e| e.provider.clone());
+        assert_eq!(stats.len(), 1);
+        assert_eq!((stats[0].errors, stats[0].warnings), (2, 1));
+        assert_eq!(stats[0].first_seen, t0);
+        assert_eq!(stats[0].last_seen, t0 + chrono::Duration::hours(2));
+    }
+
+    #[test]
+    fn heatmap_shades_scale_to_max() {
+        let mut row = vec![0usize; 24];
+        row[2] = 8;
+        row[3] = 1;
+        let line = heatmap_row(&row, 8);
+        assert_eq!(line.chars().count(), 24);
+        assert_eq!(line.chars().nth(2), Some('█'));
+        assert_eq!(line.chars().nth(3), Some('░'));
+        assert_eq!(line.chars().next(), Some('·'));
+        // 2025-11-30 is a Sunday; UTC buckets do not depend on the machine's zone.
+        let e = EventItem { level: 2, time: DateTime::parse_from_rfc3339("2025-11-30T23:30:00Z").unwrap().with_timezone(&Utc), ..Default::default() };
+        assert_eq!(crate::perf::compute_heatmap(&[e], TimeZone::Utc)[6][23], 1);
+    }
+
+    #[test]
+    fn identity_parsed_and_filtered() {
+        let xml = "<Event><System><Provider Name=\"Disk\"/><EventID>7</EventID><Level>2</Level><TimeCreated SystemTime=\"2025-11-30T12:00:00.000Z\"/><Channel>ForwardedEvents</Channel><Computer>ws01.corp.local</Computer><Security UserID=\"S-1-5-18\"/></System></Event>";
+        let e = parse_event_xml(xml, "ForwardedEvents").unwrap();
+        assert_eq!(e.computer, "ws01.corp.local");
+        assert_eq!(e.user.as_deref(), Some("S-1-5-18"));
+        let mut a = base_args();
+        a.computers = vec!["WS01".to_string()];
+        assert!(pass_identity(&a, &e));
+        a.users = vec!["S-1-5-19".to_string()];
+        assert!(!pass_identity(&a, &e));
+        let mut a = base_args();
+        a.exclude_computers = vec!["ws01".to_string()];
+        assert!(!pass_identity(&a, &e));
+        a.exclude_computers = vec!["ws02.corp.local".to_string()];
+        assert!(pass_identity(&a, &e));
+    }
+
+    #[test]
+    fn record_range_filter() {
+        let xml = "<Event><System><Provider Name=\"Disk\"/><EventID>7</EventID><Level>2</Level><TimeCreated SystemTime=\"2025-11-30T12:00:00.000Z\"/><EventRecordID>1500</EventRecordID><Channel>System</Channel></System></Event>";
+        let e = parse_event_xml(xml, "System").unwrap();
+        assert_eq!(e.record_id, Some(1500));
+        let mut a = base_args();
+        assert!(pass_record(&a, &e));
+        a.from_record = Some(1501);
+        assert!(!pass_record(&a, &e));
+        a.from_record = Some(1000);
+        a.to_record = Some(1500);
+        assert!(pass_record(&a, &e));
+        a.after_record_id = Some(1500);
+        assert!(!pass_record(&a, &e));
+        a.after_record_id = Some(1499);
+        assert!(pass_record(&a, &e));
+    }
+
+    #[test]
+    fn evtx_header_next_record_id() {
+        let p = std::env::temp_dir().join(format!("windoctor-hdr-{}.evtx", std::process::id()));
+        let mut hdr = b"ElfFile\0".to_vec();
+        hdr.extend_from_slice(&0u64.to_le_bytes());
+        hdr.extend_from_slice(&3u64.to_le_bytes());
+        hdr.extend_from_slice(&4242u64.to_le_bytes());
+        std::fs::write(&p, &hdr).unwrap();
+        assert_eq!(evtx_next_record_id(&p), Some(4242));
+        let _ = std::fs::remove_file(&p);
+        assert_eq!(completion_text(&[("System".to_string(), 40), ("Application".to_string(), 100)]), "System 40%, Application 100%");
+    }
+
     #[test]
     fn since_last10m() {
         let mut a = base_args();
@@ -1954,6 +3596,29 @@ mod tests {
         assert!(now - s <= Duration::hours(6));
     }
 
+    #[test]
+    fn since_combines_days_hours_minutes() {
+        let mut a = base_args();
+        a.days = 1;
+        a.hours = 6;
+        a.minutes = 30;
+        let s = compute_since(&a);
+        let now = Utc::now();
+        let want = Duration::days(1) + Duration::hours(6) + Duration::minutes(30);
+        assert!(now - s >= want - Duration::minutes(1));
+        assert!(now - s <= want + Duration::minutes(1));
+    }
+
+    #[test]
+    fn since_relative_expression() {
+        let mut a = base_args();
+        a.since = Some("2h ago".to_string());
+        let s = compute_since(&a);
+        let now = Utc::now();
+        assert!(now - s >= Duration::minutes(119));
+        assert!(now - s <= Duration::minutes(121));
+    }
+
     #[test]
     fn since_last_mode_far_past() {
         let mut a = base_args();
@@ -2002,6 +3667,38 @@ mod tests {
         assert!(pass_level(&a, 1));
         assert!(!pass_level(&a, 2));
     }
+
+    #[test]
+    fn count_only_files_match_the_scanned_directory_walk() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("old")).unwrap();
+        for f in ["System.evtx", "Setup.EVTX", "notes.txt", "old/Application.evtx"] { std::fs::write(dir.path().join(f), b"").unwrap(); }
+        let mut a = base_args();
+        a.evtx_path = vec![dir.path().to_string_lossy().into_owned()];
+        let names = |a: &Args| { let mut v: Vec<String> = count_only_files(a, &[], evtx_glob_set(a).unwrap().as_ref()).0.iter().map(|p| p.file_name().unwrap().to_string_lossy().into_owned()).collect(); v.sort(); v };
+        assert_eq!(names(&a), vec!["Setup.EVTX", "System.evtx"]);
+        a.evtx_recursive = true;
+        a.evtx_glob = Some("**/s*.evtx".to_string());
+        assert_eq!(names(&a), vec!["Setup.EVTX", "System.evtx"]);
+        a.evtx_glob = None;
+        assert_eq!(names(&a), vec!["Application.evtx", "Setup.EVTX", "System.evtx"]);
+        a.evtx_glob = Some("[abc".to_string());
+        assert!(evtx_glob_set(&a).is_err());
+    }
+
+    #[test]
+    fn shipped_config_leaves_since_and_until_unset() {
+        let mut a = base_args();
+        apply_config(&mut a, toml::from_str(include_str!("../WinDoctor.toml")).unwrap());
+        assert_eq!((a.since, a.until), (None, None));
+    }
+
+    #[test]
+    fn extra_hints_raise_the_risk_grade() {
+        let mut rep = test_summary();
+        merge_extra_hints(&mut rep, vec![crate::hints::NoviceHint { category: "Security".to_string(), severity: "high".to_string(), message: "Defender real-time protection is off".to_string(), evidence: vec![], count: 1, probability: 80 }]);
+        assert_eq!(rep.risk_grade, "High");
+    }
 }
 
 #[cfg(test)]
@@ -2028,12 +3725,111 @@ mod tests_parse {
         assert_eq!(item.channel, "System");
     }
 
+    #[test]
+    fn parse_event_xml_reads_keywords() {
+        let xml = "<Event><System><Provider Name=\"Microsoft-Windows-Security-Auditing\"/><EventID>4625</EventID><Level>0</Level><Keywords>0x8010000000000000</Keywords><TimeCreated SystemTime=\"2025-11-30T12:00:00Z\"/><Channel>Security</Channel></System></Event>";
+        let item = parse_event_xml(xml, "Security").unwrap();
+        assert_eq!(item.keywords & 0x0010_0000_0000_0000, 0x0010_0000_0000_0000);
+        assert_eq!(parse_event_xml_fallback(xml, "Security").unwrap().keywords, item.keywords);
+        assert_eq!(item.level, 3);
+    }
+
+    #[test]
+    fn parse_event_xml_reads_task_and_opcode() {
+        let xml = "<Event><System><Provider Name=\"Microsoft-Windows-TaskScheduler\"/><EventID>101</EventID><Level>2</Level><Task>101</Task><Opcode>2</Opcode><TimeCreated SystemTime=\"2025-11-30T12:00:00Z\"/><Channel>Microsoft-Windows-TaskScheduler/Operational</Channel></System><RenderingInfo Culture=\"en-US\"><Task>Task Start Failed</Task><Opcode>Stop</Opcode></RenderingInfo></Event>";
+        let item = parse_event_xml(xml, "x").unwrap();
+        assert_eq!((item.task, item.opcode), (101, 2));
+        let fb = parse_event_xml_fallback(xml, "x").unwrap();
+        assert_eq!((fb.task, fb.opcode), (101, 2));
+    }
+
     #[test]
     fn decoder_maps_disk_event_7() {
         let xml = "<Event><EventData><Data Name=\"DeviceName\">\\\\.\\PHYSICALDRIVE1</Data></EventData></Event>";
         let msg = crate::decoder::decode_event("Disk", 7, xml).unwrap();
         assert!(msg.contains("Bad block"));
     }
+
+    #[test]
+    fn subcommands_fill_flat_args() {
+        Args::command().debug_assert();
+        let mut a = Args::try_parse_from(["WinDoctor", "--providers=Disk", "scan", "System.evtx", "logs", "WindowsUpdate.ETL", "--hours", "6", "--html", "r.html"]).unwrap();
+        apply_command(&mut a);
+        assert_eq!(a.evtx_path, vec!["System.evtx".to_string(), "logs".to_string()]);
+        assert_eq!(a.etl_path, vec!["WindowsUpdate.ETL".to_string()]);
+        assert_eq!((a.hours, a.html.as_deref()), (6, Some("r.html")));
+        assert_eq!(a.providers, vec!["Disk".to_string()]);
+        let mut a = Args::try_parse_from(["WinDoctor", "report", "events.ndjson", "--md", "r.md", "-o", "ndjson"]).unwrap();
+        apply_command(&mut a);
+        assert_eq!((a.from_ndjson.as_deref(), a.md_path.as_deref()), (Some("events.ndjson"), Some("r.md")));
+        assert!(matches!(a.output, OutputFmt::Ndjson));
+        assert!(Args::try_parse_from(["WinDoctor", "live", "--since", "2h ago", "--hours", "3"]).is_err());
+        assert!(Args::try_parse_from(["WinDoctor", "--timeout", "30", "--max-duration", "1m"]).is_err());
+    }
+
+    #[test]
+    fn collection_errors_read_as_one_line() {
+        let e = |op: &str, code: u32, records: usize| crate::windows_live::CollectionError { channel: "System".to_string(), operation: op.to_string(), code, records };
+        assert_eq!(collection_error_line(&e("EvtRender", 15027, 3)), "System: EvtRender error 15027 (3 events)");
+        assert_eq!(collection_error_line(&e("EvtNext", 1460, 0)), "System: EvtNext error 1460");
+        assert_eq!(collection_error_line(&e("parse", 0, 1)), "System: unparsable event XML (1 event)");
+    }
+
+    #[test]
+    fn all_channels_skips_empty_trace_and_stale_logs() {
+        let since = DateTime::parse_from_rfc3339("2025-11-30T12:00:00Z").unwrap().with_timezone(&Utc);
+        let ch = |kind: u32, records: u64, min: i64| crate::windows_live::ChannelInfo { name: "x".to_string(), enabled: Some(true), records: Some(records), kind: Some(kind), last_write: Some(since + chrono::Duration::minutes(min)), ..Default::default() };
+        assert!(scannable_channel(&ch(1, 10, 5), since));
+        assert!(!scannable_channel(&ch(1, 0, 5), since));
+        assert!(!scannable_channel(&ch(2, 10, 5), since));
+        assert!(!scannable_channel(&ch(0, 10, -5), since));
+        assert!(!scannable_channel(&crate::windows_live::ChannelInfo { enabled: Some(false), ..ch(0, 10, 5) }, since));
+    }
+
+    #[test]
+    fn grep_is_repeatable_and_keeps_commas() {
+        let a = Args::try_parse_from(["WinDoctor", "--grep", "(?i)bad block", "--grep", "x{1,3}"]).unwrap();
+        assert_eq!(a.grep, vec!["(?i)bad block".to_string(), "x{1,3}".to_string()]);
+        assert!(a.patterns.is_empty());
+    }
+
+    #[test]
+    fn exclude_grep_drops_noise_even_when_included() {
+        let inc = vec![Regex::new("(?i)dcom|disk").unwrap()];
+        let exc = vec![Regex::new("10016").unwrap()];
+        assert!(grep_keeps(&inc, &exc, "Disk reset"));
+        assert!(!grep_keeps(&inc, &exc, "DCOM 10016 permission"));
+        assert!(!grep_keeps(&[], &exc, "event 10016"));
+        assert!(grep_keeps(&[], &[], "anything"));
+    }
+
+    #[test]
+    fn list_providers_channel_is_optional() {
+        assert_eq!(Args::try_parse_from(["WinDoctor", "--list-providers"]).unwrap().list_providers, Some(None));
+        assert_eq!(Args::try_parse_from(["WinDoctor", "--list-providers", "System"]).unwrap().list_providers, Some(Some("System".to_string())));
+        assert_eq!(Args::try_parse_from(["WinDoctor"]).unwrap().list_providers, None);
+    }
+
+    #[test]
+    fn remote_credentials_need_a_computer() {
+        let a = Args::try_parse_from(["WinDoctor", "--computer", "srv01", "--username", "CORP\\admin"]).unwrap();
+        assert_eq!((a.computer.as_deref(), a.username.as_deref(), a.password), (Some("srv01"), Some("CORP\\admin"), None));
+        assert!(Args::try_parse_from(["WinDoctor", "--username", "admin"]).is_err());
+        assert!(Args::try_parse_from(["WinDoctor", "--computer", "srv01", "--password", "x"]).is_err());
+    }
+
+    #[test]
+    fn follow_rows_match_report_lines() {
+        assert!(Args::try_parse_from(["WinDoctor", "--follow"]).is_err());
+        let a = Args::try_parse_from(["WinDoctor", "--live", "--follow", "--columns-preset", "minimal"]).unwrap();
+        assert_eq!(text_columns(&a).len(), 5);
+        let e = EventItem { provider: "Disk".to_string(), event_id: 7, level: 2, raw_xml: Some("<EventData><Data Name=\"DeviceName\">\\Device\\Harddisk1</Data></EventData>".to_string()), ..Default::default() };
+        let row = vec![serde_json::Value::from(7)];
+        let obj = ndjson_object(&e, &["event_id".to_string()], row, true, false);
+        assert_eq!(obj["event_id"], 7);
+        assert_eq!(obj["event_data"]["DeviceName"], "\\Device\\Harddisk1");
+        assert!(obj.get("xml").is_none());
+    }
 }
 
 #[cfg(test)]
@@ -2069,10 +3865,10 @@ mod tests_sampling_limits {
         let now = Utc::now();
         let mut events: Vec<EventItem> = Vec::new();
         for i in 0..10 {
-            events.push(EventItem { time: now - Duration::minutes(i as i64), level: 2, channel: "System".to_string(), provider: "Disk".to_string(), event_id: 7, content: format!("E{}", i), raw_xml: None });
+            events.push(EventItem { time: now - Duration::minutes(i as i64), level: 2, channel: "System".to_string(), provider: "Disk".to_string(), event_id: 7, content: format!("E{}", i), raw_xml: None, ..Default::default() });
         }
         for i in 0..10 {
-            events.push(EventItem { time: now - Duration::minutes(20 + i as i64), level: 3, channel: "Application".to_string(), provider: "DistributedCOM".to_string(), event_id: 10016, content: format!("A{}", i), raw_xml: None });
+            events.push(EventItem { time: now - Duration::minutes(20 + i as i64), level: 3, channel: "Application".to_string(), provider: "DistributedCOM".to_string(), event_id: 10016, content: format!("A{}", i), raw_xml: None, ..Default::default() });
         }
         let rep = build_summary_with_files(
             events,
@@ -2093,6 +3889,7 @@ mod tests_sampling_limits {
             None,
             Some(5),
             Some(5),
+            None,
         );
         let sys = rep.samples.iter().filter(|e| e.channel == "System").count();
         let app = rep.samples.iter().filter(|e| e.channel == "Application").count();
@@ -2103,6 +3900,40 @@ mod tests_sampling_limits {
         assert!(disk <= 5);
         assert!(dcom <= 5);
     }
+
+    #[test]
+    fn merge_sources_orders_and_drops_overlap() {
+        let now = Utc::now();
+        let ev = |rid: Option<u64>, m: i64| EventItem { time: now - Duration::minutes(m), channel: "System".to_string(), record_id: rid, ..Default::default() };
+        let mut events = vec![ev(Some(5), 1), ev(Some(3), 3), ev(Some(5), 1), ev(None, 2), ev(None, 2)];
+        assert_eq!(merge_sources(&mut events), 1);
+        assert_eq!(events.iter().map(|e| e.record_id).collect::<Vec<_>>(), [Some(3), None, None, Some(5)]);
+    }
+
+    #[test]
+    fn forwarded_events_limit_per_computer_and_export_origin() {
+        let now = Utc::now();
+        let events: Vec<EventItem> = (0..12).map(|i| EventItem { time: now - Duration::minutes(i), level: 2, channel: "ForwardedEvents".to_string(), provider: "Disk".to_string(), event_id: 7, computer: if i % 3 == 0 { "ws01".to_string() } else { "ws02".to_string() }, ..Default::default() }).collect();
+        let rep = build_summary_with_files(events, vec![], 50, 50, SortBy::Time, SortOrder::Desc, now - Duration::hours(1), now, vec![], vec![], 0, 12, None, None, None, None, None, None, Some(2));
+        assert_eq!(rep.samples.len(), 4);
+        assert!(multi_computer(&rep));
+        let (names, rows) = export_rows(&rep, &rep.samples, TimeZone::Utc, None, &[], false);
+        assert_eq!(names[3], "computer");
+        assert!(rows.iter().all(|r| r[3].as_str().is_some_and(|c| c.starts_with("ws0"))));
+        assert_eq!(find_stat(&rep.computer_stats, "ws02").map(|s| s.errors), Some(8));
+        assert_eq!(computer_top_suffix(&rep, "ws01"), " · top Disk (4)");
+    }
+
+    #[test]
+    fn export_all_events_is_not_capped_by_samples() {
+        let now = Utc::now();
+        let mut events: Vec<EventItem> = (0..12).map(|i| EventItem { time: now - Duration::minutes(i), level: 2, channel: "System".to_string(), provider: "Disk".to_string(), event_id: 7 + i as u32, ..Default::default() }).collect();
+        let rep = build_summary_with_files(events.clone(), vec![], 3, 3, SortBy::Time, SortOrder::Desc, now - Duration::hours(1), now, vec![], vec![], 0, 12, None, None, None, None, None, None, None);
+        sort_events(&mut events, SortBy::EventId, SortOrder::Asc);
+        let (_, rows) = export_rows(&rep, &events, TimeZone::Utc, None, &["event_id".to_string()], false);
+        assert!(rep.samples.len() <= 3);
+        assert_eq!((rows.len(), rows[0][0].as_u64(), rows[11][0].as_u64()), (12, Some(7), Some(18)));
+    }
 }
 
 #[cfg(test)]
@@ -2113,7 +3944,7 @@ mod tests_dedup_app_error {
         let now = Utc::now();
         let mut events: Vec<EventItem> = Vec::new();
         for i in 0..10 {
-            events.push(EventItem { time: now - Duration::minutes(i as i64), level: 2, channel: "Application".to_string(), provider: "Application Error".to_string(), event_id: 1000, content: "Faulting app crash X".to_string(), raw_xml: None });
+            events.push(EventItem { time: now - Duration::minutes(i as i64), level: 2, channel: "Application".to_string(), provider: "Application Error".to_string(), event_id: 1000, content: "Faulting app crash X".to_string(), raw_xml: None, ..Default::default() });
         }
         let rep = build_summary_with_files(
             events,
@@ -2134,6 +3965,7 @@ mod tests_dedup_app_error {
             None,
             None,
             None,
+            None,
         );
         let cnt = rep.samples.iter().filter(|e| e.provider == "Application Error" && event_message(e) == "Faulting app crash X" && event_cause(e) == "Application Error 1000").count();
         assert!(cnt <= 3);
@@ -2167,8 +3999,14 @@ fn build_config_from_args(a: &Args) -> AppConfig {
     AppConfig {
         channels: if a.channels.is_empty() { None } else { Some(a.channels.clone()) },
         patterns: if a.patterns.is_empty() { None } else { Some(a.patterns.clone()) },
+        grep: if a.grep.is_empty() { None } else { Some(a.grep.clone()) },
+        exclude_grep: if a.exclude_grep.is_empty() { None } else { Some(a.exclude_grep.clone()) },
         providers: if a.providers.is_empty() { None } else { Some(a.providers.clone()) },
         exclude_providers: if a.exclude_providers.is_empty() { None } else { Some(a.exclude_providers.clone()) },
+        computers: if a.computers.is_empty() { None } else { Some(a.computers.clone()) },
+        exclude_computers: if a.exclude_computers.is_empty() { None } else { Some(a.exclude_computers.clone()) },
+        users: if a.users.is_empty() { None } else { Some(a.users.clone()) },
+        max_duration: a.max_duration.clone(),
         output: Some(a.output),
         text_format: Some(a.text_format),
         theme: Some(a.theme),
@@ -2179,32 +4017,50 @@ fn build_config_from_args(a: &Args) -> AppConfig {
         max_level: a.max_level,
         scan_path: a.scan_path.clone(),
         file_glob: a.file_glob.clone(),
-        evtx_path: a.evtx_path.clone(),
+        evtx_path: None,
+        evtx_paths: if a.evtx_path.is_empty() { None } else { Some(a.evtx_path.clone()) },
         evtx_glob: a.evtx_glob.clone(),
         html: a.html.clone(),
         json_path: a.json_path.clone(),
         csv_path: a.csv_path.clone(),
         ndjson_path: a.ndjson_path.clone(),
         md_path: a.md_path.clone(),
+        sarif_path: a.sarif_path.clone(),
+        template: a.template.clone(),
+        cef_path: a.cef_path.clone(),
+        leef_path: a.leef_path.clone(),
+        elastic_bulk_path: a.elastic_bulk_path.clone(),
         md_fix_path: a.md_fix_path.clone(),
         warnings_as_errors: Some(a.warnings_as_errors),
+        fail_on: if a.fail_on.is_empty() { None } else { Some(a.fail_on.clone()) },
         progress: Some(a.progress),
         last_errors: Some(a.last_errors),
         last_criticals: Some(a.last_criticals),
         hours: Some(a.hours),
         minutes: Some(a.minutes),
+        days: Some(a.days),
         since: a.since.clone(),
         until: a.until.clone(),
         summary_only: Some(a.summary_only),
         analysis_only: Some(a.analysis_only),
         sample_count: a.sample_count,
         include_event_ids: if a.include_event_ids.is_empty() { None } else { Some(a.include_event_ids.clone()) },
+        keywords: if a.keywords.is_empty() { None } else { Some(a.keywords.clone()) },
+        keyword_names: if a.keyword_names.is_empty() { None } else { Some(a.keyword_names.clone()) },
+        tasks: if a.tasks.is_empty() { None } else { Some(a.tasks.clone()) },
+        query: a.query.clone(),
         exclude_event_ids: if a.exclude_event_ids.is_empty() { None } else { Some(a.exclude_event_ids.clone()) },
+        export_fields: if a.export_fields.is_empty() { None } else { Some(a.export_fields.clone()) },
+        csv_columns: if a.csv_columns.is_empty() { None } else { Some(a.csv_columns.clone()) },
         emit_eventdata: Some(a.emit_eventdata),
+        resolve_messages: Some(a.resolve_messages),
         emit_xml: Some(a.emit_xml),
+        compress: Some(a.compress),
+        export_all_events: Some(a.export_all_events),
         force_color: Some(a.force_color),
         time_zone: Some(a.time_zone),
         columns: if a.columns.is_empty() { None } else { Some(a.columns.clone()) },
+        group_by: if a.group_by.is_empty() { None } else { Some(a.group_by.clone()) },
         no_truncate: Some(a.no_truncate),
         time_format: a.time_format.clone(),
         log_format: a.log_format,
@@ -2212,7 +4068,9 @@ fn build_config_from_args(a: &Args) -> AppConfig {
         export_dir: a.export_dir.clone(),
         preset: a.preset,
         columns_preset: a.columns_preset,
-        export_zip: Some(a.export_zip),
+        export_zip: Some(a.export_zip.is_some()),
+        sign_reports: Some(a.sign_reports.is_some()),
+        sign_key: a.sign_key.clone(),
         redact: if a.redact.is_empty() { None } else { Some(a.redact.clone()) },
         exit_code_by_risk: Some(a.exit_code_by_risk),
         print_effective_config: Some(a.print_effective_config),
@@ -24,6 +24,12 @@ pub fn render_markdown(rep: &ReportSummary, tz: TimeZone, tfmt: Option<&str>) ->
     s.push_str("# WinDoctor Report\n\n");
     s.push_str(&format!("Time Window: {} → {}\n\n", start_s, end_s));
     if let Some(m) = rep.mode.as_ref() { s.push_str(&format!("Mode: {}\n\n", m)); }
+    if let Some(si) = rep.system_info.as_ref() {
+        s.push_str("## System\n");
+        for (k, v) in crate::sysinfo::lines(si) { s.push_str(&format!("- {}: {}\n", k, v)); }
+        s.push('\n');
+    }
+    if rep.partial { s.push_str(&format!("**Partial report** (time budget exhausted): {}\n\n", crate::completion_text(&rep.completion))); }
     s.push_str(&format!("Risk: {}\n", rep.risk_grade));
     s.push_str(&format!("Performance Score: {}\n\n", rep.performance_score));
 
@@ -33,9 +39,62 @@ pub fn render_markdown(rep: &ReportSummary, tz: TimeZone, tfmt: Option<&str>) ->
     s.push_str(&format!("- Warnings: {}\n\n", rep.warnings));
 
     s.push_str("## Key Sources\n");
-    if rep.by_provider.is_empty() { s.push_str("- Providers: None\n"); } else { for (p,c) in &rep.by_provider { s.push_str(&format!("- {} ({})\n", p, c)); } }
+    if rep.by_provider.is_empty() { s.push_str("- Providers: None\n"); } else { for (p,c) in &rep.by_provider { s.push_str(&format!("- {} ({}){}\n", p, c, crate::stat_suffix(crate::find_stat(&rep.provider_stats, p), tz, tfmt))); } }
     if rep.by_channel.is_empty() { s.push_str("- Channels: None\n"); } else { for (ch,c) in &rep.by_channel { s.push_str(&format!("- {} ({})\n", ch, c)); } }
-    if rep.by_event_id.is_empty() { s.push_str("- Common Event IDs: None\n\n"); } else { s.push_str("- Common Event IDs:\n"); for (id,c) in &rep.by_event_id { s.push_str(&format!("  - {} ({})\n", id, c)); } s.push('\n'); }
+    if crate::multi_computer(rep) { s.push_str("- Computers:\n"); for (comp,c) in &rep.by_computer { s.push_str(&format!("  - {} ({}){}{}\n", comp, c, crate::stat_suffix(crate::find_stat(&rep.computer_stats, comp), tz, tfmt), crate::computer_top_suffix(rep, comp))); } }
+    if !rep.by_user.is_empty() { s.push_str("- Users:\n"); for (user,c) in &rep.by_user { s.push_str(&format!("  - {} ({})\n", user, c)); } }
+    if !rep.by_source.is_empty() { s.push_str("- EVTX Sources:\n"); for (src,c) in &rep.by_source { s.push_str(&format!("  - {} ({}{})\n", src, c, crate::source_scanned(rep, src))); } }
+    if rep.by_event_id.is_empty() { s.push_str("- Common Event IDs: None\n\n"); } else { s.push_str("- Common Event IDs:\n"); for (id,c) in &rep.by_event_id { s.push_str(&format!("  - {} ({}){}\n", id, c, crate::stat_suffix(crate::find_stat(&rep.event_id_stats, &id.to_string()), tz, tfmt))); } s.push('\n'); }
+
+    if !rep.services_down.is_empty() {
+        s.push_str("## Services Down Now\n");
+        for sv in &rep.services_down { s.push_str(&format!("- {} ({}): {}{}{}\n", sv.display_name, sv.name, sv.state, sv.exit_code.filter(|_| sv.failed()).map(|c| format!(", exit code {}", c)).unwrap_or_default(), sv.last_event.as_ref().map(|e| format!(" — last SCM event: {}", e)).unwrap_or_default())); }
+        s.push('\n');
+    }
+
+    if !rep.channel_health.is_empty() {
+        s.push_str("## Channel Health\n");
+        for c in &rep.channel_health { s.push_str(&format!("- {}: {}\n", c.name, crate::channel_health::detail(c))); }
+        s.push('\n');
+    }
+    if !rep.log_integrity.is_empty() {
+        s.push_str("## Log Integrity\n");
+        for c in &rep.log_integrity.clears { s.push_str(&format!("- {}: {}\n", crate::format_ts(c.time, tz, tfmt), crate::integrity::clear_line(c))); }
+        for r in &rep.log_integrity.resets { s.push_str(&format!("- {}: {}\n", crate::format_ts(r.at, tz, tfmt), crate::integrity::reset_line(r))); }
+        s.push('\n');
+    }
+
+    if let Some(pol) = rep.policy.as_ref() {
+        s.push_str("## Group Policy\n");
+        s.push_str(&format!("- Last successful refresh: {}\n", pol.last_success.map(|t| crate::format_ts(t, tz, tfmt)).unwrap_or_else(|| "unknown".to_string())));
+        if let Some(t) = pol.last_failure { s.push_str(&format!("- Last failed refresh: {}\n", crate::format_ts(t, tz, tfmt))); }
+        for x in &pol.failed_extensions { s.push_str(&format!("- Failed extension: {} (error {})\n", if x.name.is_empty() { &x.guid } else { &x.name }, x.error)); }
+        if !pol.gpos.is_empty() { s.push_str(&format!("- Applied GPOs: {}\n", pol.gpos.join(", "))); }
+        s.push('\n');
+    }
+
+    if let Some(dev) = rep.dev_env.as_ref() {
+        s.push_str("## WSL / Docker\n");
+        s.push_str(&format!("- WSL: {}\n", dev.wsl_status.as_deref().unwrap_or("not installed or not responding")));
+        for (p, n) in &dev.vhdx { s.push_str(&format!("- {} ({:.1} GiB)\n", p, *n as f64 / 1073741824.0)); }
+        for l in dev.wsl_findings.iter().chain(dev.docker_findings.iter()) { s.push_str(&format!("  - `{}`\n", l)); }
+        s.push('\n');
+    }
+
+    if !rep.sync.is_empty() {
+        s.push_str("## OneDrive\n");
+        for a in &rep.sync {
+            s.push_str(&format!("- {}{}{}\n", a.name, a.user_folder.as_ref().map(|f| format!(" → {}", f)).unwrap_or_default(), if a.kfm.is_empty() { String::new() } else { format!(" ({})", a.kfm.join(", ")) }));
+            for e in &a.errors { s.push_str(&format!("  - `{}`\n", e)); }
+        }
+        s.push('\n');
+    }
+
+    if let Some(b) = rep.battery.as_ref() {
+        s.push_str("## Battery\n");
+        for l in crate::battery::lines(b) { s.push_str(&format!("- {}\n", l)); }
+        s.push('\n');
+    }
 
     s.push_str("## Diagnostics\n");
     if rep.novice_hints.is_empty() { s.push_str("None\n\n"); } else {
@@ -64,6 +123,25 @@ pub fn render_markdown(rep: &ReportSummary, tz: TimeZone, tfmt: Option<&str>) ->
         s.push('\n');
     }
 
+    if !rep.crashing_apps.is_empty() {
+        s.push_str("## Top Crashing Applications\n");
+        for a in &rep.crashing_apps { s.push_str(&format!("- {}: {} crashes, {} hangs ({} → {}){}{}\n", a.app, a.crashes, a.hangs, crate::format_ts(a.first_seen, tz, tfmt), crate::format_ts(a.last_seen, tz, tfmt), a.top_module.as_ref().map(|m| format!(" — module {}", m)).unwrap_or_default(), a.top_exception.as_ref().map(|x| format!(", exception {}", x)).unwrap_or_default())); }
+        s.push('\n');
+    }
+
+    if !rep.noise.is_empty() {
+        s.push_str("## Noise Report\n");
+        for n in &rep.noise { s.push_str(&format!("- {} {}: {} events over {} h, worst {} (score {:.1}){}\n", n.provider, n.event_id, n.count, n.active_hours, crate::level_name(n.level), n.score, if n.chronic { " — chronic" } else { "" })); }
+        if rep.noise.iter().any(|n| n.chronic) { s.push_str(&format!("\n```json\n{}\n```\n", crate::noise::suppress_snippet(&rep.noise))); }
+        s.push('\n');
+    }
+
+    if !rep.storms.is_empty() {
+        s.push_str("## Event Storms\n");
+        for st in &rep.storms { s.push_str(&format!("- {} {}: {} events, peak {}/min ({} → {})\n", st.provider, st.event_id, st.total, st.peak_per_minute, crate::format_ts(st.start, tz, tfmt), crate::format_ts(st.end, tz, tfmt))); }
+        s.push('\n');
+    }
+
     if !rep.perf_metrics.is_empty() {
         s.push_str("## Performance Metrics\n");
         for (name, avg, max, count) in &rep.perf_metrics { s.push_str(&format!("- {}: avg {} ms, max {} ms ({} samples)\n", name, avg, max, count)); }
@@ -110,6 +188,12 @@ pub fn render_fix_markdown(rep: &ReportSummary, tz: TimeZone, tfmt: Option<&str>
     if rep.likely_causes.is_empty() { s.push_str("- None detected\n\n"); } else { for c in &rep.likely_causes { s.push_str(&format!("- {}\n", c)); } s.push('\n'); }
     s.push_str("## Recommendations\n");
     if rep.recommendations.is_empty() { s.push_str("- No specific actions\n\n"); } else { for r in &rep.recommendations { s.push_str(&format!("- [ ] {}\n", r)); } s.push('\n'); }
+    if !rep.defender.is_empty() {
+        s.push_str("## Microsoft Defender\n");
+        for d in &rep.defender { s.push_str(&format!("- {} {} ({}){}{}{}\n", crate::format_ts(d.time, tz, tfmt), d.kind, d.event_id, d.threat.as_ref().map(|t| format!(": {}", t)).unwrap_or_default(), d.path.as_ref().map(|p| format!(" at {}", p)).unwrap_or_default(), d.action.as_ref().map(|a| format!(" — {}", a)).unwrap_or_default())); }
+        if rep.defender.iter().any(|d| d.event_id == 1118 || d.event_id == 5001 || d.event_id == 5010) { s.push_str("- [ ] Turn real-time protection back on and run a full scan; remove threats Defender could not clean (offline scan if needed)\n"); }
+        s.push('\n');
+    }
     s.push_str("## Performance\n");
     s.push_str(&format!("- Score: {}\n", rep.performance_score));
     if !rep.perf_metrics.is_empty() { for (name, avg, max, count) in &rep.perf_metrics { s.push_str(&format!("- {}: avg {} ms, max {} ms ({} samples)\n", name, avg, max, count)); } }
@@ -122,3 +206,16 @@ pub fn render_fix_markdown(rep: &ReportSummary, tz: TimeZone, tfmt: Option<&str>
     if let Some(pred) = rep.smart_failure_predicted && pred { s.push_str("- SMART: Predicts failure on one or more drives\n"); }
     s
 }
+
+// Event catalog of one provider, as a table to copy event IDs and EventData names from.
+pub fn render_catalog(p: &crate::windows_live::ProviderInfo) -> String {
+    let cell = |v: &str| v.replace('|', "\\|").split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut s = format!("# Event Catalog: {}\n\n", p.name);
+    if !p.channels.is_empty() { s.push_str(&format!("Channels: {}\n\n", p.channels.join(", "))); }
+    s.push_str(&format!("{} events\n\n", p.events.len()));
+    s.push_str("| ID | Version | Level | Channel | Keywords | Fields | Message |\n|---|---|---|---|---|---|---|\n");
+    for e in &p.events {
+        s.push_str(&format!("| {} | {} | {} | {} | {} | {} | {} |\n", e.id, e.version, crate::level_name(e.level), cell(e.channel.as_deref().unwrap_or("")), cell(&crate::keywords::display(e.keywords)), cell(&e.fields.join(", ")), cell(&e.description)));
+    }
+    s
+}
@@ -0,0 +1,156 @@
+use crate::{AggStat, ReportSummary};
+use std::collections::HashMap;
+
+// A report's origin: its host, else its only source machine, else the file name.
+pub fn label(rep: &ReportSummary, path: &str) -> String {
+    if let Some(h) = rep.system_info.as_ref().and_then(|s| s.host.clone()).filter(|h| !h.is_empty()) { return h; }
+    if let [(c, _)] = rep.by_computer.as_slice() { return c.clone(); }
+    std::path::Path::new(path).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| path.to_string())
+}
+
+fn sum<K: Clone + Eq + std::hash::Hash + Ord>(lists: impl Iterator<Item = Vec<(K, usize)>>) -> Vec<(K, usize)> {
+    let mut m: HashMap<K, usize> = HashMap::new();
+    for (k, n) in lists.flatten() { *m.entry(k).or_insert(0) += n; }
+    let mut v: Vec<(K, usize)> = m.into_iter().collect();
+    v.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    v
+}
+
+fn merge_stats(lists: impl Iterator<Item = Vec<AggStat>>) -> Vec<AggStat> {
+    let mut out: Vec<AggStat> = Vec::new();
+    for s in lists.flatten() {
+        match out.iter_mut().find(|o| o.key == s.key) {
+            Some(o) => { o.errors += s.errors; o.warnings += s.warnings; o.first_seen = o.first_seen.min(s.first_seen); o.last_seen = o.last_seen.max(s.last_seen); }
+            None => out.push(s),
+        }
+    }
+    out.sort_by(|a, b| (b.errors + b.warnings).cmp(&(a.errors + a.warnings)).then(a.key.cmp(&b.key)));
+    out
+}
+
+fn rank(severity: &str) -> u8 { match severity { "high" => 2, "medium" => 1, _ => 0 } }
+
+// Counts add up, samples and hints are unioned (hint evidence prefixed with its origin), and risk is recomputed from the merged hints.
+// Machine-specific sections (system info, policy, counters) survive only when a single report is merged.
+pub fn merge(reports: &[(String, ReportSummary)]) -> Option<ReportSummary> {
+        let mut out = reports.first()?.1.clone();
+    let lists = |f: fn(&ReportSummary) -> Vec<(String, usize)>| reports.iter().map(|(_, r)| f(r)).collect::<Vec<_>>().into_iter();
+    out.window_start = reports.iter().map(|(_, r)| r.window_start).min().unwrap_or(out.window_start);
+    out.window_end = reports.iter().map(|(_, r)| r.window_end).max().unwrap_or(out.window_end);
+    out.total = reports.iter().map(|(_, r)| r.total).sum();
+    out.criticals = reports.iter().map(|(_, r)| r.criticals).sum();
+    out.errors = reports.iter().map(|(_, r)| r.errors).sum();
+    out.warnings = reports.iter().map(|(_, r)| r.warnings).sum();
+    out.scanned_records = reports.iter().map(|(_, r)| r.scanned_records).sum();
+    out.parsed_events = reports.iter().map(|(_, r)| r.parsed_events).sum();
+    out.by_provider = sum(lists(|r| r.by_provider.clone()));
+    out.by_channel = sum(lists(|r| r.by_channel.clone()));
+    out.by_event_id = sum(reports.iter().map(|(_, r)| r.by_event_id.clone()));
+    out.by_device = sum(lists(|r| r.by_device.clone()));
+    out.by_domain = sum(lists(|r| r.by_domain.clone()));
+    out.matched_terms = sum(lists(|r| r.matched_terms.clone()));
+    out.file_matched_terms = sum(lists(|r| r.file_matched_terms.clone()));
+    out.by_source = sum(lists(|r| r.by_source.clone()));
+    out.scanned_by_source = sum(lists(|r| r.scanned_by_source.clone()));
+    out.by_user = sum(lists(|r| r.by_user.clone()));
+    // A report without per-machine counts is attributed to its label as a whole.
+    out.by_computer = sum(reports.iter().map(|(l, r)| if r.by_computer.is_empty() { vec![(l.clone(), r.total)] } else { r.by_computer.clone() }));
+    out.provider_stats = merge_stats(reports.iter().map(|(_, r)| r.provider_stats.clone()));
+    out.event_id_stats = merge_stats(reports.iter().map(|(_, r)| r.event_id_stats.clone()));
+    out.computer_stats = merge_stats(reports.iter().map(|(_, r)| r.computer_stats.clone()));
+    let mut timeline: std::collections::BTreeMap<String, (usize, usize)> = std::collections::BTreeMap::new();
+    for (k, e, w) in reports.iter().flat_map(|(_, r)| r.timeline.clone()) { let t = timeline.entry(k).or_insert((0, 0)); t.0 += e; t.1 += w; }
+    out.timeline = timeline.into_iter().map(|(k, (e, w))| (k, e, w)).collect();
+    out.heatmap = reports.iter().map(|(_, r)| &r.heatmap).filter(|h| !h.is_empty()).fold(Vec::new(), |acc: Vec<Vec<usize>>, h| {
+        if acc.is_empty() { return h.clone(); }
+        acc.iter().zip(h).map(|(a, b)| a.iter().zip(b).map(|(x, y)| x + y).collect()).collect()
+    });
+    out.samples = reports.iter().flat_map(|(l, r)| r.samples.iter().cloned().map(move |mut e| { if e.computer.is_empty() { e.computer = l.clone(); } e })).collect();
+    out.samples.sort_by_key(|e| std::cmp::Reverse(e.time));
+    out.file_samples = reports.iter().flat_map(|(_, r)| r.file_samples.clone()).collect();
+    let mut hints: Vec<crate::hints::NoviceHint> = Vec::new();
+    for (l, r) in reports {
+        for h in &r.novice_hints {
+            let evidence: Vec<String> = h.evidence.iter().map(|e| format!("[{}] {}", l, e)).collect();
+            match hints.iter_mut().find(|o| o.category == h.category && o.message == h.message) {
+                Some(o) => {
+                    o.count += h.count;
+                    o.probability = o.probability.max(h.probability);
+                    if rank(&h.severity) > rank(&o.severity) { o.severity = h.severity.clone(); }
+                    o.evidence.extend(evidence);
+                }
+                None => hints.push(crate::hints::NoviceHint { evidence, ..h.clone() }),
+            }
+        }
+    }
+    out.novice_hints = hints;
+    out.recommendations = crate::perf::generate_recommendations(&out.novice_hints);
+    out.likely_causes = crate::perf::compute_root_causes(&out.novice_hints);
+    out.by_category = crate::perf::compute_by_category(&out.novice_hints);
+    out.performance_score = reports.iter().map(|(_, r)| r.performance_score).max().unwrap_or(0);
+    let mut signals: Vec<(String, u8)> = Vec::new();
+    for (k, v) in reports.iter().flat_map(|(_, r)| r.degradation_signals.clone()) {
+        match signals.iter_mut().find(|(s, _)| *s == k) { Some(s) => s.1 = s.1.max(v), None => signals.push((k, v)) }
+    }
+    out.degradation_signals = signals;
+    out.risk_grade = crate::risk_grade(out.performance_score, &out.novice_hints);
+    out.perf_metrics = reports.iter().flat_map(|(_, r)| r.perf_metrics.clone()).collect();
+    out.computer_top_providers = reports.iter().flat_map(|(_, r)| r.computer_top_providers.clone()).collect();
+    out.max_record_ids = reports.iter().flat_map(|(l, r)| r.max_record_ids.iter().map(move |(k, v)| (format!("{}/{}", l, k), *v))).collect();
+    out.partial = reports.iter().any(|(_, r)| r.partial);
+    out.completion = reports.iter().flat_map(|(l, r)| r.completion.iter().map(move |(k, v)| (format!("{}/{}", l, k), *v))).collect();
+    out.services_down = reports.iter().flat_map(|(_, r)| r.services_down.clone()).collect();
+    out.sync = reports.iter().flat_map(|(_, r)| r.sync.clone()).collect();
+    out.storms = reports.iter().flat_map(|(_, r)| r.storms.clone()).collect();
+    out.noise = reports.iter().flat_map(|(_, r)| r.noise.clone()).collect();
+    out.crashing_apps = reports.iter().flat_map(|(_, r)| r.crashing_apps.clone()).collect();
+    out.defender = reports.iter().flat_map(|(_, r)| r.defender.clone()).collect();
+    out.defender.sort_by_key(|d| std::cmp::Reverse(d.time));
+    out.channel_health = reports.iter().flat_map(|(_, r)| r.channel_health.clone()).collect();
+    out.log_integrity = crate::integrity::LogIntegrity { clears: reports.iter().flat_map(|(_, r)| r.log_integrity.clears.clone()).collect(), resets: reports.iter().flat_map(|(_, r)| r.log_integrity.resets.clone()).collect() };
+    out.collection_errors = reports.iter().flat_map(|(_, r)| r.collection_errors.clone()).collect();
+    out.smart_failure_predicted = reports.iter().filter_map(|(_, r)| r.smart_failure_predicted).reduce(|a, b| a || b);
+    out.group_by = None;
+    out.compare = None;
+    if reports.len() > 1 {
+        out.system_info = None;
+        out.perf_counters = None;
+        out.policy = None;
+        out.dev_env = None;
+        out.battery = None;
+        out.mode = Some(format!("Merged ({})", reports.iter().map(|(l, _)| l.as_str()).collect::<Vec<_>>().join(", ")));
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_sums_counts_and_labels_hints() {
+        let rep = |host: &str, errors: usize, score: u8| -> ReportSummary {
+            let mut r = ReportSummary { novice_hints: vec![crate::hints::NoviceHint { category: "Storage".to_string(), severity: "medium".to_string(), message: "Disk errors".to_string(), evidence: vec!["Disk 7".to_string()], count: 1, probability: 50 }], ..crate::test_summary() };
+            r.total = errors;
+            r.errors = errors;
+            r.performance_score = score;
+            r.by_provider = vec![("Disk".to_string(), errors)];
+            r.samples = vec![crate::EventItem { provider: "Disk".to_string(), level: 2, ..Default::default() }];
+            r.system_info = Some(crate::sysinfo::SystemInfo { host: Some(host.to_string()), ..Default::default() });
+            r
+        };
+        let (a, b) = (rep("ws01", 3, 20), rep("ws02", 4, 65));
+        assert_eq!(label(&a, "x/a.json"), "ws01");
+        let mut c = rep("", 1, 0);
+        c.system_info = None;
+        assert_eq!(label(&c, "x/night.json"), "night");
+        let m = merge(&[("ws01".to_string(), a), ("ws02".to_string(), b)]).unwrap();
+        assert_eq!((m.total, m.errors, m.by_provider.clone()), (7, 7, vec![("Disk".to_string(), 7)]));
+        assert_eq!(m.by_computer, vec![("ws02".to_string(), 4), ("ws01".to_string(), 3)]);
+        assert_eq!(m.samples.iter().map(|e| e.computer.as_str()).collect::<Vec<_>>(), vec!["ws01", "ws02"]);
+        assert_eq!(m.novice_hints.len(), 1);
+        assert_eq!((m.novice_hints[0].count, m.novice_hints[0].evidence.clone()), (2, vec!["[ws01] Disk 7".to_string(), "[ws02] Disk 7".to_string()]));
+        assert_eq!((m.performance_score, m.risk_grade.as_str()), (65, "High"));
+        assert!(m.system_info.is_none());
+    }
+}
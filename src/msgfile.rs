@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use windows_sys::Win32::Foundation::{HMODULE, LocalFree};
+use windows_sys::Win32::System::Diagnostics::Debug::*;
+use windows_sys::Win32::System::LibraryLoader::{LOAD_LIBRARY_AS_DATAFILE, LOAD_LIBRARY_AS_IMAGE_RESOURCE, LoadLibraryExW};
+use windows_sys::Win32::System::Registry::{HKEY_LOCAL_MACHINE, RRF_NOEXPAND, RRF_RT_REG_EXPAND_SZ, RRF_RT_REG_SZ, RegGetValueW};
+
+// `--resolve-messages`: classic providers' messages from their registered message DLLs, as Event Viewer renders them.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+// Loaded message and parameter modules per "channel\provider"; modules stay loaded for the run.
+struct Modules { messages: Vec<usize>, params: Vec<usize> }
+static MODULES: Mutex<Option<HashMap<String, Option<Modules>>>> = Mutex::new(None);
+
+pub fn enable() { ENABLED.store(true, Ordering::Relaxed); }
+
+fn w(s: &str) -> Vec<u16> { let mut v = s.encode_utf16().collect::<Vec<u16>>(); v.push(0); v }
+
+// REG_EXPAND_SZ values such as `%SystemRoot%\System32\netmsg.dll`; unknown variables stay as they are.
+fn expand_env(s: &str, var: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(i) = rest.find('%') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i + 1..];
+        match rest.find('%').and_then(|j| var(&rest[..j]).map(|v| (j, v))) {
+            Some((j, v)) => { out.push_str(&v); rest = &rest[j + 1..]; }
+            None => out.push('%'),
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+// EventMessageFile may list several modules separated by ';'.
+fn module_paths(value: &str, var: impl Fn(&str) -> Option<String> + Copy) -> Vec<String> {
+    value.split(';').map(str::trim).filter(|p| !p.is_empty()).map(|p| expand_env(p, var)).collect()
+}
+
+// `%%1234` in insertion strings and messages refers to a parameter message (ParameterMessageFile, else the system table).
+fn fill_parameters(s: &str, lookup: impl Fn(u32) -> Option<String>) -> String {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(i) = rest.find("%%") {
+        out.push_str(&rest[..i]);
+        let digits = rest[i + 2..].chars().take_while(char::is_ascii_digit).count();
+        match rest[i + 2..i + 2 + digits].parse().ok().and_then(&lookup) {
+            Some(v) => out.push_str(v.trim_end()),
+            None => out.push_str(&rest[i..i + 2 + digits]),
+        }
+        rest = &rest[i + 2 + digits..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// Message ids in classic message files carry the severity in the top bits; EventID's Qualifiers hold them when the log kept them.
+fn message_ids(xml: &str, event_id: u32) -> Vec<u32> {
+    let qualifiers = xml.find("Qualifiers=\"").and_then(|i| xml[i + 12..].split('"').next()?.parse::<u32>().ok()).filter(|q| *q != 0);
+    let mut ids: Vec<u32> = qualifiers.map(|q| (q << 16) | event_id).into_iter().collect();
+    ids.extend([event_id, 0x4000_0000 | event_id, 0x8000_0000 | event_id, 0xC000_0000 | event_id]);
+    ids.dedup();
+    ids
+}
+
+fn env_var(name: &str) -> Option<String> { std::env::var(name).ok() }
+
+fn reg_string(key: &str, value: &str) -> Option<String> {
+    let (k, v) = (w(key), w(value));
+    let mut len = 0u32;
+    let flags = RRF_RT_REG_SZ | RRF_RT_REG_EXPAND_SZ | RRF_NOEXPAND;
+    if unsafe { RegGetValueW(HKEY_LOCAL_MACHINE, k.as_ptr(), v.as_ptr(), flags, std::ptr::null_mut(), std::ptr::null_mut(), &mut len) } != 0 || len == 0 { return None; }
+    let mut buf = vec![0u16; len as usize / 2 + 1];
+    if unsafe { RegGetValueW(HKEY_LOCAL_MACHINE, k.as_ptr(), v.as_ptr(), flags, std::ptr::null_mut(), buf.as_mut_ptr() as *mut core::ffi::c_void, &mut len) } != 0 { return None; }
+    Some(String::from_utf16_lossy(&buf[..buf.iter().position(|&c| c == 0).unwrap_or(buf.len())]))
+}
+
+fn load(paths: &[String]) -> Vec<usize> {
+    paths.iter().filter_map(|p| {
+        let h = unsafe { LoadLibraryExW(w(p).as_ptr(), std::ptr::null_mut(), LOAD_LIBRARY_AS_DATAFILE | LOAD_LIBRARY_AS_IMAGE_RESOURCE) };
+        if h.is_null() { log::debug!("message file {} could not be loaded", p); None } else { Some(h as usize) }
+    }).collect()
+}
+
+// FormatMessage from a module (or the system table with 0). Inserts read from `args`, padded so a message referring
+// to more insertion strings than the event recorded cannot read past the array.
+fn format(module: usize, id: u32, args: Option<&[String]>) -> Option<String> {
+    let wide: Vec<Vec<u16>> = args.unwrap_or_default().iter().map(|a| w(a)).chain(std::iter::repeat_with(|| vec![0u16]).take(99)).collect();
+    let ptrs: Vec<*const u16> = wide.iter().map(|v| v.as_ptr()).collect();
+    let source = if module == 0 { FORMAT_MESSAGE_FROM_SYSTEM } else { FORMAT_MESSAGE_FROM_HMODULE };
+    let inserts = if args.is_some() { FORMAT_MESSAGE_ARGUMENT_ARRAY } else { FORMAT_MESSAGE_IGNORE_INSERTS };
+    let mut buf: *mut u16 = std::ptr::null_mut();
+    let n = unsafe { FormatMessageW(FORMAT_MESSAGE_ALLOCATE_BUFFER | source | inserts, module as HMODULE, id, 0, &mut buf as *mut *mut u16 as *mut u16, 0, ptrs.as_ptr() as *const *const i8) };
+    if n == 0 || buf.is_null() { return None; }
+    let s = String::from_utf16_lossy(unsafe { std::slice::from_raw_parts(buf, n as usize) });
+    unsafe { LocalFree(buf as _) };
+    Some(s.trim_end().to_string())
+}
+
+fn modules_for(channel: &str, provider: &str) -> Option<Modules> {
+    let key = format!("SYSTEM\\CurrentControlSet\\Services\\EventLog\\{}\\{}", channel, provider);
+    let messages = load(&module_paths(&reg_string(&key, "EventMessageFile")?, env_var));
+    if messages.is_empty() { return None; }
+    let params = reg_string(&key, "ParameterMessageFile").map(|v| load(&module_paths(&v, env_var))).unwrap_or_default();
+    Some(Modules { messages, params })
+}
+
+// The event's message from its provider's EventMessageFile, with the EventData values as %1, %2, ...
+pub fn resolve(e: &crate::EventItem, xml: &str) -> Option<String> {
+    if !ENABLED.load(Ordering::Relaxed) || e.channel.is_empty() || e.provider.is_empty() { return None; }
+    let mut cache = MODULES.lock().ok()?;
+    let m = cache.get_or_insert_with(HashMap::new).entry(format!("{}\\{}", e.channel, e.provider)).or_insert_with(|| modules_for(&e.channel, &e.provider)).as_ref()?;
+    let param = |n: u32| m.params.iter().find_map(|&h| format(h, n, None)).or_else(|| format(0, n, None));
+    let args: Vec<String> = crate::event_xml::event_data_values(xml).iter().map(|a| fill_parameters(a, param)).collect();
+    let msg = message_ids(xml, e.event_id).into_iter().find_map(|id| m.messages.iter().find_map(|&h| format(h, id, Some(&args))))?;
+    Some(fill_parameters(&msg, param)).filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_file_values_and_parameter_inserts() {
+        let var = |k: &str| (k.eq_ignore_ascii_case("SystemRoot")).then(|| "C:\\Windows".to_string());
+        assert_eq!(module_paths("%SystemRoot%\\System32\\netmsg.dll; %NoSuch%\\x.dll;", var), vec!["C:\\Windows\\System32\\netmsg.dll", "%NoSuch%\\x.dll"]);
+        assert_eq!(expand_env("100%", var), "100%");
+        let lookup = |n: u32| (n == 1077).then(|| "Disabled\r\n".to_string());
+        assert_eq!(fill_parameters("start type changed to %%1077 (was %%9)", lookup), "start type changed to Disabled (was %%9)");
+        assert_eq!(message_ids("<EventID Qualifiers=\"49152\">7000</EventID>", 7000)[0], 0xC000_1B58);
+        assert_eq!(message_ids("<EventID>7000</EventID>", 7000)[0], 7000);
+    }
+}
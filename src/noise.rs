@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+static ENABLED: OnceLock<bool> = OnceLock::new();
+
+pub fn enable() { let _ = ENABLED.set(true); }
+
+pub fn enabled() -> bool { ENABLED.get().copied().unwrap_or(false) }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NoiseEntry {
+    pub provider: String,
+    pub event_id: u32,
+    pub count: usize,
+    // Most severe level seen for this pair.
+    pub level: u8,
+    pub active_hours: usize,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub score: f64,
+    pub chronic: bool,
+}
+
+// A repeat offender: present in this many distinct hours of the window.
+const CHRONIC_HOURS: usize = 3;
+const MIN_COUNT: usize = 5;
+
+// Volume divided by how much a single event of that level should matter.
+fn severity_weight(level: u8) -> f64 {
+    match level { 1 => 16.0, 2 => 4.0, 3 => 2.0, _ => 1.0 }
+}
+
+pub fn analyze(events: &[crate::EventItem], top: usize) -> Vec<NoiseEntry> {
+    let mut groups: HashMap<(&str, u32), Vec<&crate::EventItem>> = HashMap::new();
+    for e in events { groups.entry((e.provider.as_str(), e.event_id)).or_default().push(e); }
+    let mut out: Vec<NoiseEntry> = groups.into_iter().filter(|(_, v)| v.len() >= MIN_COUNT).map(|((provider, event_id), v)| {
+        // Level 0 (LogAlways) is treated like Information.
+        let level = v.iter().map(|e| if e.level == 0 { 4 } else { e.level }).min().unwrap_or(4);
+        let active_hours = v.iter().map(|e| e.time.timestamp().div_euclid(3600)).collect::<HashSet<_>>().len();
+        let first_seen = v.iter().map(|e| e.time).min().unwrap_or_default();
+        let last_seen = v.iter().map(|e| e.time).max().unwrap_or_default();
+        // Benign: warning or lower, and no built-in hint treats it as a serious symptom.
+        let benign = level >= 3 && crate::hints::generate_hints(&[v[0].clone()]).iter().all(|h| h.severity != "high");
+        NoiseEntry { provider: provider.to_string(), event_id, count: v.len(), level, active_hours, first_seen, last_seen, score: v.len() as f64 / severity_weight(level), chronic: benign && active_hours >= CHRONIC_HOURS }
+    }).collect();
+    out.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.provider.cmp(&b.provider)).then_with(|| a.event_id.cmp(&b.event_id)));
+    out.truncate(top);
+    out
+}
+
+// Ready to paste into rules.json.
+pub fn suppress_snippet(entries: &[NoiseEntry]) -> String {
+    let list: Vec<serde_json::Value> = entries.iter().filter(|n| n.chronic).map(|n| serde_json::json!({ "provider": n.provider, "event_id": n.event_id })).collect();
+    serde_json::to_string_pretty(&serde_json::json!({ "suppress": list })).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn chronic_benign_events_rank_first() {
+        let t0 = DateTime::parse_from_rfc3339("2025-11-30T00:00:00Z").unwrap().with_timezone(&Utc);
+        let ev = |p: &str, id: u32, level: u8, h: i64| crate::EventItem { provider: p.to_string(), event_id: id, level, time: t0 + Duration::hours(h), ..Default::default() };
+        let mut events: Vec<crate::EventItem> = (0..40).map(|i| ev("DistributedCOM", 10016, 3, i % 8)).collect();
+        events.extend((0..12).map(|i| ev("Disk", 7, 2, i)));
+        events.extend((0..6).map(|_| ev("Burst", 1, 3, 0)));
+        events.extend((0..3).map(|i| ev("Rare", 2, 3, i)));
+        let n = analyze(&events, 10);
+        assert_eq!(n.len(), 3);
+        assert_eq!((n[0].provider.as_str(), n[0].count, n[0].active_hours, n[0].chronic), ("DistributedCOM", 40, 8, true));
+        assert!(!n.iter().find(|x| x.provider == "Disk").unwrap().chronic);
+        assert!(!n.iter().find(|x| x.provider == "Burst").unwrap().chronic);
+        let snippet = suppress_snippet(&n);
+        assert!(snippet.contains("\"suppress\"") && snippet.contains("10016") && !snippet.contains("\"Disk\""));
+    }
+}
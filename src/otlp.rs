@@ -0,0 +1,101 @@
+use crate::EventItem;
+use serde_json::{json, Value};
+
+const LOGS_PATH: &str = "/v1/logs";
+const BATCH_BYTES: usize = 1_000_000;
+const ATTEMPTS: u32 = 4;
+
+// OTel SeverityNumber: FATAL 21, ERROR 17, WARN 13, INFO 9, DEBUG 5.
+fn severity(level: u8) -> u8 {
+    match level { 1 => 21, 2 => 17, 3 => 13, 4 => 9, _ => 5 }
+}
+
+fn attr(key: &str, value: Value) -> Value {
+    // OTLP/JSON encodes 64-bit integers as strings.
+    let v = match value { Value::Number(n) => json!({ "intValue": n.to_string() }), Value::String(s) => json!({ "stringValue": s }), v => json!({ "stringValue": v.to_string() }) };
+    json!({ "key": key, "value": v })
+}
+
+pub fn log_record(e: &EventItem) -> Value {
+    let nanos = e.time.timestamp_nanos_opt().unwrap_or(0).to_string();
+    let mut attrs = vec![attr("winlog.event_id", json!(e.event_id)), attr("winlog.provider_name", json!(e.provider)), attr("winlog.channel", json!(e.channel)), attr("windoctor.cause", json!(crate::event_cause_redacted(e)))];
+    if let Some(r) = e.record_id { attrs.push(attr("winlog.record_id", json!(r))); }
+    if let Some(u) = e.user.as_ref() { attrs.push(attr("user.name", json!(u))); }
+    json!({
+        "timeUnixNano": nanos,
+        "observedTimeUnixNano": nanos,
+        "severityNumber": severity(e.level),
+        "severityText": crate::event_level_name(e),
+        "body": { "stringValue": crate::event_message_redacted(e) },
+        "attributes": attrs,
+    })
+}
+
+// One ResourceLogs per computer, so events read from another machine are not attributed to this one.
+// `os_version` describes the local machine and is only given when every event is local.
+pub fn request(records: &[(String, Value)], os_version: Option<&str>) -> Value {
+    let mut hosts: Vec<(&str, Vec<&Value>)> = Vec::new();
+    for (host, r) in records {
+        match hosts.iter_mut().find(|(h, _)| h == host) { Some((_, v)) => v.push(r), None => hosts.push((host, vec![r])) }
+    }
+    let resource_logs: Vec<Value> = hosts.into_iter().map(|(host, logs)| {
+        let mut attrs = vec![attr("service.name", json!("windoctor")), attr("service.version", json!(env!("CARGO_PKG_VERSION"))), attr("os.type", json!("windows"))];
+        if !host.is_empty() { attrs.push(attr("host.name", json!(host))); }
+        if let Some(v) = os_version { attrs.push(attr("os.version", json!(v))); }
+        json!({
+            "resource": { "attributes": attrs },
+            "scopeLogs": [{ "scope": { "name": "windoctor", "version": env!("CARGO_PKG_VERSION") }, "logRecords": logs }],
+        })
+    }).collect();
+    json!({ "resourceLogs": resource_logs })
+}
+
+pub struct Otlp { url: String, os_version: Option<String>, batch: crate::forward::Batch<(String, Value)> }
+
+impl Otlp {
+    // `http://collector:4318` gets `/v1/logs` appended, like OTEL_EXPORTER_OTLP_ENDPOINT.
+    pub fn new(endpoint: &str, os_version: Option<String>) -> Result<Otlp, String> {
+        Ok(Otlp { url: crate::http::default_path(endpoint, LOGS_PATH)?, os_version, batch: crate::forward::Batch::new(BATCH_BYTES) })
+    }
+
+    fn post(&mut self) -> Result<(), (usize, std::io::Error)> {
+        let records = self.batch.take();
+        let fail = |m: String| (records.len(), std::io::Error::other(m));
+        let body = request(&records, self.os_version.as_deref()).to_string();
+        match crate::http::post_with_retry(&self.url, &[("Content-Type", "application/json".to_string())], body.as_bytes(), ATTEMPTS) {
+            Ok(r) if r.status / 100 == 2 => Ok(()),
+            Ok(r) => Err(fail(format!("HTTP {}: {}", r.status, r.body.trim()))),
+            Err(e) => Err(fail(e)),
+        }
+    }
+}
+
+impl crate::forward::Sink for Otlp {
+    fn send(&mut self, e: &EventItem) -> Result<(), (usize, std::io::Error)> {
+        let r = log_record(e);
+        let len = r.to_string().len();
+        if self.batch.push((e.computer.clone(), r), len) { self.post() } else { Ok(()) }
+    }
+
+    fn flush(&mut self) -> Result<(), (usize, std::io::Error)> { if self.batch.is_empty() { Ok(()) } else { self.post() } }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_records_group_by_host_with_severity() {
+        let t = chrono::DateTime::parse_from_rfc3339("2025-11-30T12:00:00Z").unwrap().with_timezone(&chrono::Utc);
+        let e = |computer: &str, level: u8| EventItem { time: t, level, provider: "Disk".to_string(), channel: "System".to_string(), event_id: 7, computer: computer.to_string(), content: "Bad block".to_string(), record_id: Some(42), ..Default::default() };
+        let r = log_record(&e("ws01", 2));
+        assert_eq!((r["timeUnixNano"].as_str(), r["severityNumber"].as_u64(), r["body"]["stringValue"].as_str()), (Some("1764504000000000000"), Some(17), Some("Bad block")));
+        assert_eq!(r["attributes"][0], json!({ "key": "winlog.event_id", "value": { "intValue": "7" } }));
+        let req = request(&[("ws01".to_string(), r), ("ws02".to_string(), log_record(&e("ws02", 1))), ("ws01".to_string(), log_record(&e("ws01", 3)))], Some("10.0.22631"));
+        let rl = req["resourceLogs"].as_array().unwrap();
+        assert_eq!(rl.len(), 2);
+        assert_eq!(rl[0]["scopeLogs"][0]["logRecords"].as_array().unwrap().len(), 2);
+        assert!(rl[1]["resource"]["attributes"].as_array().unwrap().contains(&json!({ "key": "host.name", "value": { "stringValue": "ws02" } })));
+        assert_eq!(rl[1]["scopeLogs"][0]["logRecords"][0]["severityNumber"], 21);
+    }
+}
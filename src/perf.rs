@@ -135,6 +135,30 @@ pub fn generate_recommendations(hints: &[crate::hints::NoviceHint]) -> Vec<Strin
     if any("Network") {
         recs.push("Check DNS settings; test with public DNS; inspect NIC drivers".to_string());
     }
+    if any_msg("dhcp lease") || any_msg("dhcp server") {
+        recs.push("Check the DHCP server and its free addresses; renew with ipconfig /release then ipconfig /renew".to_string());
+    }
+    if any_msg("address conflict") {
+        recs.push("Find the device holding the conflicting IP (arp -a) and remove the static/DHCP overlap".to_string());
+    }
+    if any_msg("bluetooth") {
+        recs.push("Remove and re-pair the Bluetooth device; update or reinstall the Bluetooth adapter driver".to_string());
+    }
+    if any_msg("audio") {
+        recs.push("Restart Windows Audio and Audio Endpoint Builder; update the audio driver and check the default playback device".to_string());
+    }
+    if any_msg("hyper-v") {
+        recs.push("Check Hyper-V VM configuration and virtual disk paths (Get-VM, Test-Path on the .vhdx), host free space, and replication health (Measure-VMReplication)".to_string());
+    }
+    if any_msg("wsl service") || any_msg("vmcompute") {
+        recs.push("Restart WSL and container services: wsl --shutdown, Restart-Service LxssManager,vmcompute; run wsl --update if it keeps failing".to_string());
+    }
+    if any_msg("docker desktop") {
+        recs.push("Restart Docker Desktop; if it keeps crashing, update it and use Troubleshoot > Clean / Purge data".to_string());
+    }
+    if any("Battery") {
+        recs.push("Run powercfg /batteryreport and powercfg /energy to see capacity history and power-hungry devices; replace a worn battery".to_string());
+    }
     if any("Services") {
         recs.push("Review failing services; check dependencies and startup type".to_string());
     }
@@ -144,6 +168,21 @@ pub fn generate_recommendations(hints: &[crate::hints::NoviceHint]) -> Vec<Strin
     if any("GPU") {
         recs.push("Update GPU drivers; monitor for TDRs; consider lowering overclock".to_string());
     }
+    if any("Platform Security") {
+        recs.push("Update the system firmware (BIOS/UEFI) and TPM firmware; if the TPM stays in error, back up BitLocker recovery keys first, then clear the TPM from tpm.msc".to_string());
+    }
+    if any_msg("secure boot") {
+        recs.push("Make sure Secure Boot is on (Confirm-SecureBootUEFI) and pending Secure Boot DB/DBX updates apply after a firmware update".to_string());
+    }
+    if any_msg("bitlocker") {
+        recs.push("Confirm the BitLocker recovery key is backed up (manage-bde -protectors -get C:), check recent firmware/BIOS or boot changes, and review who changed key protectors".to_string());
+    }
+    if any_msg("defender") {
+        recs.push("Run a Microsoft Defender full scan, review quarantined items, and keep real-time protection on".to_string());
+    }
+    for (code, _, rec) in crate::hints::UPDATE_ERRORS {
+        if any_msg(code) { recs.push(rec.to_string()); }
+    }
     recs.truncate(8);
     recs
 }
@@ -153,6 +192,8 @@ pub fn compute_root_causes(hints: &[crate::hints::NoviceHint]) -> Vec<String> {
     if hints.iter().any(|h| h.category == "Storage" && h.severity == "high") { causes.push("Storage subsystem instability or failing disk".to_string()); }
     if hints.iter().any(|h| h.category == "Hardware" && h.severity == "high") { causes.push("Underlying hardware fault (CPU/Memory/Bus)".to_string()); }
     if hints.iter().any(|h| h.category == "Thermal" || h.category == "Cooling") { causes.push("Thermal issues causing throttling and errors".to_string()); }
+    if hints.iter().any(|h| h.category == "Security" && h.severity == "high" && h.message.contains("Defender")) { causes.push("Malware activity or disabled antivirus protection".to_string()); }
+    if hints.iter().any(|h| h.message.starts_with("BitLocker")) { causes.push("Boot, firmware or TPM change tripping BitLocker, or its key protectors being changed".to_string()); }
     if hints.iter().any(|h| h.category == "Network") { causes.push("Network/DNS misconfiguration or intermittent connectivity".to_string()); }
     if hints.iter().any(|h| h.category == "Policy" || h.category == "Permissions") { causes.push("Policy/permission misconfiguration impacting services".to_string()); }
     if causes.is_empty() { causes.push("General system instability indicated by error patterns".to_string()); }
@@ -177,6 +218,19 @@ pub fn compute_timeline(events: &[EventItem], since: DateTime<Utc>, until: DateT
     buckets.into_iter().map(|(k,(e,w))| (k, e, w)).collect()
 }
 
+// Errors (critical + error) by weekday (Mon..Sun) x hour-of-day in `tz`; empty when there are none.
+pub fn compute_heatmap(events: &[EventItem], tz: crate::TimeZone) -> Vec<Vec<usize>> {
+    use chrono::{Datelike, Timelike};
+    let mut grid = vec![vec![0usize; 24]; 7];
+    let mut any = false;
+    for e in events.iter().filter(|e| e.level == 1 || e.level == 2) {
+        let (day, hour) = match tz { crate::TimeZone::Local => { let t = e.time.with_timezone(&chrono::Local); (t.weekday(), t.hour()) } crate::TimeZone::Utc => (e.time.weekday(), e.time.hour()) };
+        grid[day.num_days_from_monday() as usize][hour as usize] += 1;
+        any = true;
+    }
+    if any { grid } else { vec![] }
+}
+
 pub fn compute_by_category(hints: &[crate::hints::NoviceHint]) -> Vec<(String, usize)> {
     let mut m: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
     for h in hints { *m.entry(h.category.clone()).or_insert(0) += h.count.max(1); }
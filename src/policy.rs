@@ -0,0 +1,108 @@
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use crate::EventItem;
+use serde::{Serialize, Deserialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExtensionStatus {
+    pub name: String,
+    pub guid: String,
+    pub error: u32,
+    pub end_time: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PolicyStatus {
+    pub gpos: Vec<String>,
+    pub failed_extensions: Vec<ExtensionStatus>,
+    pub last_success: Option<DateTime<Utc>>,
+    pub last_failure: Option<DateTime<Utc>>,
+}
+
+// CIM datetimes look like `20251130120000.000000+060` (offset in minutes).
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn parse_cim_datetime(s: &str) -> Option<DateTime<Utc>> {
+    let naive = NaiveDateTime::parse_from_str(s.get(..14)?, "%Y%m%d%H%M%S").ok()?;
+    let offset: i64 = s.get(21..).and_then(|o| o.parse().ok()).unwrap_or(0);
+    Some(DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc) - Duration::minutes(offset))
+}
+
+// Reads the computer RSoP namespace written by the last policy refresh.
+#[cfg(target_os = "windows")]
+pub fn collect_rsop() -> PolicyStatus {
+    use wmi::WMIConnection;
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct ExtRow { extensionGuid: Option<String>, displayName: Option<String>, error: Option<u32>, endTime: Option<String> }
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct GpoRow { name: Option<String> }
+    let mut out = PolicyStatus::default();
+    let Ok(wmi) = WMIConnection::with_namespace_path("root\\RSOP\\Computer") else { return out };
+    if let Ok(rows) = wmi.raw_query::<GpoRow>("SELECT name FROM RSOP_GPO") {
+        out.gpos = rows.into_iter().filter_map(|r| r.name).filter(|n| !n.is_empty()).collect();
+        out.gpos.sort();
+        out.gpos.dedup();
+    }
+    if let Ok(rows) = wmi.raw_query::<ExtRow>("SELECT extensionGuid, displayName, error, endTime FROM RSOP_ExtensionStatus") {
+        for r in rows {
+            let end = r.endTime.as_deref().and_then(parse_cim_datetime);
+            let err = r.error.unwrap_or(0);
+            if err == 0 {
+                if end > out.last_success { out.last_success = end; }
+            } else {
+                if end > out.last_failure { out.last_failure = end; }
+                out.failed_extensions.push(ExtensionStatus { name: r.displayName.unwrap_or_default(), guid: r.extensionGuid.unwrap_or_default(), error: err, end_time: end });
+            }
+        }
+    }
+    out
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn collect_rsop() -> PolicyStatus { PolicyStatus::default() }
+
+// GroupPolicy 1500-1503 report a successful refresh (informational, so only seen with --include-info);
+// 1085/1096/1125/1129/7016/7320 a failed one.
+pub fn merge_events(status: &mut PolicyStatus, events: &[EventItem]) {
+    for e in events.iter().filter(|e| e.provider == "Microsoft-Windows-GroupPolicy") {
+        let slot = match e.event_id { 1500..=1503 => &mut status.last_success, 1085 | 1096 | 1125 | 1129 | 7016 | 7320 => &mut status.last_failure, _ => continue };
+        if slot.is_none_or(|t| e.time > t) { *slot = Some(e.time); }
+    }
+}
+
+pub fn policy_hints(status: &PolicyStatus) -> Vec<crate::hints::NoviceHint> {
+    let mut out = Vec::new();
+    for x in &status.failed_extensions {
+        out.push(crate::hints::NoviceHint { category: "Policy".to_string(), severity: "medium".to_string(), message: format!("Group Policy extension '{}' failed during the last refresh (error {})", if x.name.is_empty() { &x.guid } else { &x.name }, x.error), evidence: vec![x.guid.clone()], count: 1, probability: 70 });
+    }
+    if let (Some(f), s) = (status.last_failure, status.last_success) && s.is_none_or(|s| f > s) {
+        let since = s.map(|t| format!("last success {}", t.format("%Y-%m-%d %H:%M"))).unwrap_or_else(|| "no successful refresh seen".to_string());
+        out.push(crate::hints::NoviceHint { category: "Policy".to_string(), severity: "high".to_string(), message: format!("Group Policy has not applied successfully since its last failure ({})", since), evidence: vec![], count: 1, probability: 75 });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn cim_datetime_with_offset() {
+        assert_eq!(parse_cim_datetime("20251130120000.000000+060"), Some(Utc.with_ymd_and_hms(2025, 11, 30, 11, 0, 0).unwrap()));
+        assert_eq!(parse_cim_datetime("garbage"), None);
+    }
+
+    #[test]
+    fn failure_after_success_is_flagged() {
+        let t = Utc.with_ymd_and_hms(2025, 11, 30, 12, 0, 0).unwrap();
+        let ev = |id: u32, level: u8, h: i64| EventItem { time: t + Duration::hours(h), level, provider: "Microsoft-Windows-GroupPolicy".to_string(), event_id: id, ..Default::default() };
+        let mut st = PolicyStatus { failed_extensions: vec![ExtensionStatus { name: "Security".to_string(), guid: "{827D319E-6EAC-11D2-A4EA-00C04F79F83A}".to_string(), error: 1332, end_time: None }], ..Default::default() };
+        merge_events(&mut st, &[ev(1502, 4, 0), ev(1129, 2, 2)]);
+        assert_eq!(st.last_success, Some(t));
+        assert_eq!(st.last_failure, Some(t + Duration::hours(2)));
+        let hints = policy_hints(&st);
+        assert_eq!(hints.len(), 2);
+        assert!(hints.iter().any(|h| h.severity == "high"));
+    }
+}
@@ -0,0 +1,114 @@
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ChannelAccess {
+    pub channel: String,
+    pub readable: bool,
+    pub error: Option<u32>,
+    pub guidance: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Preflight {
+    pub elevated: Option<bool>,
+    pub channels: Vec<ChannelAccess>,
+}
+
+impl Preflight {
+    pub fn ok(&self) -> bool { self.channels.iter().all(|c| c.readable) }
+}
+
+#[cfg(target_os = "windows")]
+pub fn is_elevated() -> Option<bool> {
+    use windows_sys::Win32::Security::{GetTokenInformation, TokenElevation, TOKEN_ELEVATION};
+    // GetCurrentProcessToken() is the -4 pseudo-handle; it needs no OpenProcessToken or CloseHandle.
+    let token = -4isize as windows_sys::Win32::Foundation::HANDLE;
+    let mut el = TOKEN_ELEVATION { TokenIsElevated: 0 };
+    let mut len = 0u32;
+    let ok = unsafe { GetTokenInformation(token, TokenElevation, &mut el as *mut _ as *mut core::ffi::c_void, std::mem::size_of::<TOKEN_ELEVATION>() as u32, &mut len) };
+    if ok == 0 { None } else { Some(el.TokenIsElevated != 0) }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn is_elevated() -> Option<bool> { None }
+
+pub fn guidance(channel: &str, code: u32, elevated: Option<bool>) -> Option<String> {
+    match code {
+        5 if channel.eq_ignore_ascii_case("Security") && elevated != Some(true) => Some("Security needs Administrator rights: run from an elevated prompt or add --elevate".to_string()),
+        5 if elevated != Some(true) => Some("Access denied: run elevated (--elevate) or add this account to the 'Event Log Readers' group".to_string()),
+        5 => Some("Access denied even when elevated: the channel's security descriptor (ChannelAccess) excludes Administrators".to_string()),
+        15007 => Some("Channel does not exist on this machine; see --list-channels".to_string()),
+        1722 | 1753 => Some("Remote host unreachable over RPC: enable the 'Remote Event Log Management' firewall rule".to_string()),
+        _ => None,
+    }
+}
+
+pub fn check(channels: &[String]) -> Preflight {
+    let elevated = is_elevated();
+    let channels = channels.iter().map(|ch| match crate::windows_live::probe_channel(ch) {
+        Ok(()) => ChannelAccess { channel: ch.clone(), readable: true, error: None, guidance: None },
+        Err(code) => ChannelAccess { channel: ch.clone(), readable: false, error: Some(code), guidance: guidance(ch, code, elevated) },
+    }).collect();
+    Preflight { elevated, channels }
+}
+
+pub fn print(p: &Preflight) {
+    println!("Elevated: {}", match p.elevated { Some(true) => "yes", Some(false) => "no", None => "unknown" });
+    for c in &p.channels {
+        if c.readable { println!("• {} — readable", c.channel); continue; }
+        println!("• {} — not readable (error {}){}", c.channel, c.error.unwrap_or(0), c.guidance.as_ref().map(|g| format!(": {}", g)).unwrap_or_default());
+    }
+}
+
+// Windows command-line quoting (CommandLineToArgvW rules) for the relaunched copy.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn quote_arg(a: &str) -> String {
+    if !a.is_empty() && !a.contains([' ', '\t', '"']) { return a.to_string(); }
+    let mut out = String::from("\"");
+    let mut slashes = 0;
+    for ch in a.chars() {
+        match ch {
+            '\\' => slashes += 1,
+            '"' => { out.push_str(&"\\".repeat(slashes * 2 + 1)); out.push('"'); slashes = 0; }
+            _ => { out.push_str(&"\\".repeat(slashes)); out.push(ch); slashes = 0; }
+        }
+    }
+    out.push_str(&"\\".repeat(slashes * 2));
+    out.push('"');
+    out
+}
+
+// Starts an elevated copy with the same arguments (minus --elevate) through the UAC prompt.
+#[cfg(target_os = "windows")]
+pub fn relaunch_elevated() -> Result<(), String> {
+    use windows_sys::Win32::UI::Shell::ShellExecuteW;
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let params = std::env::args().skip(1).filter(|a| a != "--elevate").map(|a| quote_arg(&a)).collect::<Vec<_>>().join(" ");
+    let w = |s: &str| s.encode_utf16().chain([0]).collect::<Vec<u16>>();
+    // The elevated copy would otherwise start in System32, breaking relative --evtx-path, --export-dir and --config.
+    let cwd = std::env::current_dir().map_err(|e| e.to_string())?;
+    let (verb, file, params, dir) = (w("runas"), w(&exe.to_string_lossy()), w(&params), w(&cwd.to_string_lossy()));
+    // SW_SHOWNORMAL; values above 32 mean success, anything else is an error code (1223 when UAC was declined).
+    let r = unsafe { ShellExecuteW(std::ptr::null_mut(), verb.as_ptr(), file.as_ptr(), params.as_ptr(), dir.as_ptr(), 1) } as isize;
+    if r > 32 { Ok(()) } else { Err(format!("ShellExecute runas failed ({})", r)) }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn relaunch_elevated() -> Result<(), String> { Err("--elevate is only supported on Windows".to_string()) }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guidance_and_quoting() {
+        assert!(guidance("Security", 5, Some(false)).unwrap().contains("--elevate"));
+        assert!(guidance("Application", 5, Some(false)).unwrap().contains("Event Log Readers"));
+        assert!(guidance("Nope/Operational", 15007, Some(true)).unwrap().contains("--list-channels"));
+        assert!(guidance("System", 87, None).is_none());
+        assert_eq!(quote_arg("--hours"), "--hours");
+        assert_eq!(quote_arg("C:\\My Logs\\"), "\"C:\\My Logs\\\\\"");
+        assert_eq!(quote_arg("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quote_arg(""), "\"\"");
+    }
+}
@@ -0,0 +1,239 @@
+use crate::EventItem;
+use regex::Regex;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Op { Eq, Ne, Lt, Le, Gt, Ge, Match, NotMatch, In }
+
+// `provider=Disk AND (event_id IN (7,11) OR message ~ "bad block") AND level<=2`
+#[derive(Clone, Debug)]
+pub enum Query {
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Not(Box<Query>),
+    Cmp { field: String, op: Op, values: Vec<String>, re: Option<Regex> },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Tok { Word(String), Str(String), Op(String), LParen, RParen, Comma }
+
+fn tokenize(s: &str) -> Result<Vec<Tok>, String> {
+    let mut out = Vec::new();
+    let cs: Vec<char> = s.chars().collect();
+    let mut i = 0;
+    while i < cs.len() {
+        let c = cs[i];
+        match c {
+            _ if c.is_whitespace() => { i += 1; }
+            '(' => { out.push(Tok::LParen); i += 1; }
+            ')' => { out.push(Tok::RParen); i += 1; }
+            ',' => { out.push(Tok::Comma); i += 1; }
+            '"' | '\'' => {
+                let mut v = String::new();
+                i += 1;
+                loop {
+                    match cs.get(i) {
+                        None => return Err("unterminated string".to_string()),
+                        Some('\\') if cs.get(i + 1) == Some(&c) => { v.push(c); i += 2; }
+                        Some(&ch) if ch == c => { i += 1; break; }
+                        Some(&ch) => { v.push(ch); i += 1; }
+                    }
+                }
+                out.push(Tok::Str(v));
+            }
+            '=' | '!' | '<' | '>' | '~' => {
+                let two: String = cs[i..(i + 2).min(cs.len())].iter().collect();
+                let op = if ["!=", "<=", ">=", "!~", "=="].contains(&two.as_str()) { two } else if c == '!' { return Err("expected != or !~".to_string()) } else { c.to_string() };
+                i += op.len();
+                out.push(Tok::Op(if op == "==" { "=".to_string() } else { op }));
+            }
+            _ => {
+                let start = i;
+                while i < cs.len() && !cs[i].is_whitespace() && !"()=!<>~,\"'".contains(cs[i]) { i += 1; }
+                out.push(Tok::Word(cs[start..i].iter().collect()));
+            }
+        }
+    }
+    Ok(out)
+}
+
+struct Parser { toks: Vec<Tok>, pos: usize }
+
+impl Parser {
+    fn peek(&self) -> Option<&Tok> { self.toks.get(self.pos) }
+    fn next(&mut self) -> Option<Tok> { let t = self.toks.get(self.pos).cloned(); self.pos += 1; t }
+    fn keyword(&mut self, kw: &str) -> bool {
+        if let Some(Tok::Word(w)) = self.peek() && w.eq_ignore_ascii_case(kw) { self.pos += 1; return true; }
+        false
+    }
+
+    fn or(&mut self) -> Result<Query, String> {
+        let mut q = self.and()?;
+        while self.keyword("OR") { q = Query::Or(Box::new(q), Box::new(self.and()?)); }
+        Ok(q)
+    }
+
+    fn and(&mut self) -> Result<Query, String> {
+        let mut q = self.not()?;
+        while self.keyword("AND") { q = Query::And(Box::new(q), Box::new(self.not()?)); }
+        Ok(q)
+    }
+
+    fn not(&mut self) -> Result<Query, String> {
+        if self.keyword("NOT") { return Ok(Query::Not(Box::new(self.not()?))); }
+        if self.peek() == Some(&Tok::LParen) {
+            self.pos += 1;
+            let q = self.or()?;
+            if self.next() != Some(Tok::RParen) { return Err("missing ')'".to_string()); }
+            return Ok(q);
+        }
+        self.cmp()
+    }
+
+    fn value(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Tok::Word(w)) | Some(Tok::Str(w)) => Ok(w),
+            t => Err(format!("expected a value, found {:?}", t)),
+        }
+    }
+
+    fn cmp(&mut self) -> Result<Query, String> {
+        let field = match self.next() {
+            Some(Tok::Word(w)) => canonical_field(&w),
+            t => return Err(format!("expected a field name, found {:?}", t)),
+        };
+        let op = if self.keyword("IN") { Op::In } else {
+            match self.next() {
+                Some(Tok::Op(o)) => match o.as_str() { "=" => Op::Eq, "!=" => Op::Ne, "<" => Op::Lt, "<=" => Op::Le, ">" => Op::Gt, ">=" => Op::Ge, "~" => Op::Match, _ => Op::NotMatch },
+                t => return Err(format!("expected an operator after '{}', found {:?}", field, t)),
+            }
+        };
+        let mut values = Vec::new();
+        if op == Op::In {
+            if self.next() != Some(Tok::LParen) { return Err("IN expects a list like (7,11)".to_string()); }
+            loop {
+                values.push(self.value()?);
+                match self.next() { Some(Tok::Comma) => continue, Some(Tok::RParen) => break, t => return Err(format!("expected ',' or ')' in IN list, found {:?}", t)) }
+            }
+        } else { values.push(self.value()?); }
+        if field == "level" { values = values.into_iter().map(|v| level_value(&v)).collect(); }
+        if matches!(op, Op::Lt | Op::Le | Op::Gt | Op::Ge) && values[0].parse::<i64>().is_err() { return Err(format!("'{}' needs a number", field)); }
+        let re = match op { Op::Match | Op::NotMatch => Some(Regex::new(&format!("(?i){}", values[0])).map_err(|e| format!("invalid regex '{}': {}", values[0], e))?), _ => None };
+        Ok(Query::Cmp { field, op, values, re })
+    }
+}
+
+fn canonical_field(w: &str) -> String {
+    match w.to_lowercase().as_str() {
+        "id" | "eventid" | "event_id" => "event_id".to_string(),
+        "msg" | "message" | "content" => "message".to_string(),
+        "record" | "recordid" | "record_id" => "record_id".to_string(),
+        "severity" | "level" => "level".to_string(),
+        f @ ("provider" | "channel" | "computer" | "user") => f.to_string(),
+        // Anything else is an EventData field, e.g. DeviceName.
+        _ => w.to_string(),
+    }
+}
+
+fn level_value(v: &str) -> String {
+    match v.to_lowercase().as_str() { "critical" => "1", "error" => "2", "warning" => "3", "information" | "info" => "4", "verbose" => "5", _ => v }.to_string()
+}
+
+pub fn parse_query(s: &str) -> Result<Query, String> {
+    let mut p = Parser { toks: tokenize(s)?, pos: 0 };
+    if p.toks.is_empty() { return Err("empty query".to_string()); }
+    let q = p.or()?;
+    if let Some(t) = p.peek() { return Err(format!("unexpected {:?} at token {}", t, p.pos + 1)); }
+    Ok(q)
+}
+
+fn field_value(e: &EventItem, field: &str, data: &mut Option<HashMap<String, String>>) -> Option<String> {
+    Some(match field {
+        "provider" => e.provider.clone(),
+        "channel" => e.channel.clone(),
+        "event_id" => e.event_id.to_string(),
+        "level" => e.level.to_string(),
+        "message" => e.content.clone(),
+        "computer" => e.computer.clone(),
+        "user" => e.user.clone()?,
+        "record_id" => e.record_id?.to_string(),
+        _ => {
+            let map = data.get_or_insert_with(|| e.raw_xml.as_deref().map(crate::event_xml::event_data_pairs_or_fallback).unwrap_or_default());
+            map.iter().find(|(k, _)| k.eq_ignore_ascii_case(field)).map(|(_, v)| v.clone())?
+        }
+    })
+}
+
+fn equals(actual: &str, want: &str) -> bool {
+    match (actual.parse::<i64>(), want.parse::<i64>()) { (Ok(a), Ok(b)) => a == b, _ => actual.eq_ignore_ascii_case(want) }
+}
+
+impl Query {
+    pub fn matches(&self, e: &EventItem) -> bool { self.eval(e, &mut None) }
+
+    // EventData fields are read from the raw XML, which callers otherwise drop.
+    pub fn uses_event_data(&self) -> bool {
+        match self {
+            Query::And(a, b) | Query::Or(a, b) => a.uses_event_data() || b.uses_event_data(),
+            Query::Not(q) => q.uses_event_data(),
+            Query::Cmp { field, .. } => !["provider", "channel", "event_id", "level", "message", "computer", "user", "record_id"].contains(&field.as_str()),
+        }
+    }
+
+    fn eval(&self, e: &EventItem, data: &mut Option<HashMap<String, String>>) -> bool {
+        match self {
+            Query::And(a, b) => a.eval(e, data) && b.eval(e, data),
+            Query::Or(a, b) => a.eval(e, data) || b.eval(e, data),
+            Query::Not(q) => !q.eval(e, data),
+            Query::Cmp { field, op, values, re } => {
+                let Some(v) = field_value(e, field, data) else { return matches!(op, Op::Ne | Op::NotMatch) };
+                let num = |w: &str| v.parse::<i64>().ok().zip(w.parse::<i64>().ok());
+                match op {
+                    Op::Eq => equals(&v, &values[0]),
+                    Op::Ne => !equals(&v, &values[0]),
+                    Op::In => values.iter().any(|w| equals(&v, w)),
+                    Op::Lt => num(&values[0]).is_some_and(|(a, b)| a < b),
+                    Op::Le => num(&values[0]).is_some_and(|(a, b)| a <= b),
+                    Op::Gt => num(&values[0]).is_some_and(|(a, b)| a > b),
+                    Op::Ge => num(&values[0]).is_some_and(|(a, b)| a >= b),
+                    Op::Match => re.as_ref().is_some_and(|r| r.is_match(&v)),
+                    Op::NotMatch => !re.as_ref().is_some_and(|r| r.is_match(&v)),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ev(provider: &str, id: u32, level: u8, msg: &str) -> EventItem {
+        EventItem { provider: provider.to_string(), event_id: id, level, content: msg.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn boolean_query_with_in_and_regex() {
+        let q = parse_query(r#"provider=Disk AND (event_id IN (7,11) OR message ~ "bad block") AND level<=error"#).unwrap();
+        assert!(q.matches(&ev("Disk", 7, 2, "")));
+        assert!(q.matches(&ev("disk", 153, 1, "The device has a Bad Block")));
+        assert!(!q.matches(&ev("Disk", 153, 2, "retried")));
+        assert!(!q.matches(&ev("Disk", 7, 3, "")));
+        assert!(!q.matches(&ev("Ntfs", 7, 2, "")));
+        let q = parse_query("NOT provider = 'Service Control Manager' AND DeviceName ~ Harddisk1").unwrap();
+        let mut e = ev("Disk", 7, 2, "");
+        e.raw_xml = Some("<Event><EventData><Data Name=\"DeviceName\">\\Device\\Harddisk1\\DR1</Data></EventData></Event>".to_string());
+        assert!(q.matches(&e));
+        assert!(q.uses_event_data());
+        assert!(!parse_query("id=7").unwrap().uses_event_data());
+    }
+
+    #[test]
+    fn query_errors_are_reported() {
+        assert!(parse_query("provider=").is_err());
+        assert!(parse_query("(level<2").is_err());
+        assert!(parse_query("level < high").is_err());
+        assert!(parse_query("message ~ \"(\"").is_err());
+        assert!(parse_query("provider=Disk extra").is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use crate::EventItem;
+use crate::rules::RulesConfig;
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RuleMatch {
+    pub source: String,
+    pub provider: String,
+    pub event_id: u32,
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RuleResult {
+    pub rule: usize,
+    pub label: String,
+    pub error: Option<String>,
+    pub matches: Vec<RuleMatch>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TestReport {
+    pub events: usize,
+    pub suppressed: Vec<(String, usize)>,
+    pub rules: Vec<RuleResult>,
+    pub hints: Vec<crate::hints::NoviceHint>,
+}
+
+// `wevtutil qe /f:xml` and `Get-WinEvent | % ToXml` write events back to back without a root element.
+fn split_events(text: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("<Event").filter(|&i| rest[i + 6..].starts_with([' ', '>'])) {
+        let Some(len) = rest[start..].find("</Event>") else { break };
+        out.push(&rest[start..start + len + 8]);
+        rest = &rest[start + len + 8..];
+    }
+    out
+}
+
+// Each path is an event XML file or a directory of them (*.xml); events are labelled `file#n`.
+pub fn load_xml(paths: &[String]) -> Result<Vec<(String, EventItem)>, String> {
+    let mut files = Vec::new();
+    for p in paths {
+        let path = std::path::PathBuf::from(p);
+        if path.is_dir() {
+            let mut found: Vec<_> = std::fs::read_dir(&path).map_err(|e| format!("{}: {}", p, e))?.filter_map(|d| d.ok().map(|d| d.path())).filter(|f| f.extension().is_some_and(|x| x.eq_ignore_ascii_case("xml"))).collect();
+            found.sort();
+            files.extend(found);
+        } else { files.push(path); }
+    }
+    let mut out = Vec::new();
+    for f in files {
+        let text = std::fs::read_to_string(&f).map_err(|e| format!("{}: {}", f.display(), e))?;
+        for (i, xml) in split_events(&text).into_iter().enumerate() {
+            let Some(mut e) = crate::parse_event_xml(xml, "") else { log::warn!("{}#{}: not an event", f.display(), i + 1); continue };
+            if let Some(msg) = crate::decoder::decode_event(&e.provider, e.event_id, xml) { e.content = msg; }
+            e.raw_xml = Some(xml.to_string());
+            out.push((format!("{}#{}", f.display(), i + 1), e));
+        }
+    }
+    Ok(out)
+}
+
+// Level overrides and suppressions run first, as in a scan; thresholds only show in the resulting hints.
+pub fn run(cfg: &RulesConfig, events: Vec<(String, EventItem)>) -> TestReport {
+    let (sources, mut items): (Vec<String>, Vec<EventItem>) = events.into_iter().unzip();
+    crate::rules::apply_level_overrides(&mut items, cfg);
+    let dropped = crate::rules::suppression_of(&items, cfg);
+    let suppressed = sources.iter().zip(&dropped).filter_map(|(s, d)| d.map(|i| (s.clone(), i + 1))).collect();
+    let kept: Vec<(&String, &EventItem)> = sources.iter().zip(&items).zip(&dropped).filter(|(_, d)| d.is_none()).map(|(p, _)| p).collect();
+    let rules = cfg.hint_rules.iter().flatten().enumerate().map(|(i, r)| {
+        let re = r.regex.as_deref().map(regex::Regex::new);
+        let error = match &re { Some(Err(e)) => Some(format!("invalid regex: {}", e)), _ => None };
+        let re = re.and_then(Result::ok);
+        let matches = kept.iter().filter_map(|(s, e)| crate::rules::match_reason(r, re.as_ref(), e).map(|reason| RuleMatch { source: s.to_string(), provider: e.provider.clone(), event_id: e.event_id, reason })).collect();
+        RuleResult { rule: i + 1, label: r.name.clone().unwrap_or_else(|| r.message.clone()), error, matches }
+    }).collect();
+    let kept: Vec<EventItem> = kept.into_iter().map(|(_, e)| e.clone()).collect();
+    TestReport { events: sources.len(), suppressed, hints: crate::rules::apply_hint_rules(&kept, cfg), rules }
+}
+
+pub fn print(rep: &TestReport) {
+    println!("{} events, {} suppressed", rep.events, rep.suppressed.len());
+    for (source, i) in &rep.suppressed { println!("  {}: suppress #{}", source, i); }
+    for r in &rep.rules {
+        let status = match (&r.error, r.matches.len()) { (Some(e), _) => e.clone(), (None, 0) => "no match".to_string(), (None, n) => format!("matched {} event{}", n, if n == 1 { "" } else { "s" }) };
+        println!("Rule {} \"{}\": {}", r.rule, r.label, status);
+        for m in &r.matches { println!("  {}  {} {}: {}", m.source, m.provider, m.event_id, m.reason); }
+    }
+    println!("Hints raised: {}", rep.hints.len());
+    for h in &rep.hints { println!("  [{}] {}: {} (x{}){}", h.severity, h.category, h.message, h.count, if h.evidence.is_empty() { String::new() } else { format!(" — {}", h.evidence.join("; ")) }); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_test_explains_matches_and_suppressions() {
+        let text = "<?xml version=\"1.0\"?>\n<Event xmlns=\"http://schemas.microsoft.com/win/2004/08/events/event\"><System><Provider Name=\"Service Control Manager\"/><EventID>7031</EventID><Level>2</Level><TimeCreated SystemTime=\"2025-11-30T12:00:00.000Z\"/><Channel>System</Channel></System><RenderingInfo><Message>The Spooler service terminated unexpectedly.</Message></RenderingInfo></Event>\r\n<Event><System><Provider Name=\"DistributedCOM\"/><EventID>10016</EventID><Level>3</Level><TimeCreated SystemTime=\"2025-11-30T12:01:00.000Z\"/><Channel>System</Channel></System></Event><EventData/>";
+        assert_eq!(split_events(text).len(), 2);
+        let events: Vec<(String, EventItem)> = split_events(text).into_iter().enumerate().map(|(i, x)| (format!("s.xml#{}", i + 1), crate::parse_event_xml(x, "").unwrap())).collect();
+        let cfg: RulesConfig = serde_json::from_str(r#"{"suppress":[{"provider":"DistributedCOM","event_id":10016}],"hint_rules":[{"provider":"Service Control Manager","contains_any":["terminated unexpectedly"],"message":"Service crashed"},{"regex":"(","message":"Broken"}]}"#).unwrap();
+        let rep = run(&cfg, events);
+        assert_eq!(rep.suppressed, vec![("s.xml#2".to_string(), 1)]);
+        assert_eq!(rep.rules[0].matches.len(), 1);
+        assert_eq!(rep.rules[0].matches[0].reason, "provider Service Control Manager, contains \"terminated unexpectedly\"");
+        assert!(rep.rules[1].error.as_deref().unwrap().starts_with("invalid regex"));
+        assert_eq!(rep.hints.len(), 1);
+    }
+}
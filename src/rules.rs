@@ -5,6 +5,40 @@ pub struct RulesConfig {
     pub event_patterns: Option<Vec<String>>,
     pub file_patterns: Option<Vec<String>>,
     pub hint_rules: Option<Vec<HintRule>>,    
+    pub level_overrides: Option<Vec<LevelOverride>>,
+    pub suppress: Option<Vec<Suppress>>,
+    pub level_names: Option<Vec<LevelName>>,
+    pub decoders: Option<Vec<DecoderTemplate>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DecoderTemplate {
+    pub provider: String,                  // case-insensitive exact match
+    pub event_id: u32,
+    pub template: String,                  // "{EventDataKey}" placeholders; "{{" and "}}" are literal braces
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LevelName {
+    pub provider: Option<String>,          // case-insensitive exact match; none applies to every provider
+    pub level: u8,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Suppress {
+    pub provider: Option<String>,          // case-insensitive exact match
+    pub event_id: Option<u32>,
+    pub contains: Option<String>,          // case-insensitive substring match against event content
+    pub regex: Option<String>,             // regex against event content
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LevelOverride {
+    pub provider: Option<String>,          // case-insensitive exact match
+    pub event_id: Option<u32>,
+    pub from: Option<String>,              // only rewrite this level (name or number)
+    pub to: String,                        // "critical" | "error" | "warning" | "info" | "verbose" | 1-5
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -18,43 +52,394 @@ pub struct HintRule {
     pub message: String,
     pub name: Option<String>,              // optional rule name/label
     pub weight: Option<u8>,                // optional weight to boost probability
+    pub evidence_keys: Option<Vec<String>>, // EventData fields attached to the hint as "Key=Value"
+    pub evidence_group: Option<String>,    // capture group of `regex` (name or number) attached to the hint
+    pub min_count: Option<usize>,          // fire once, only when this many matches occur (within `within_minutes` if set)
+    pub within_minutes: Option<u32>,
+    pub replace_builtin: Option<bool>,     // the events this rule matches no longer feed built-in hints
 }
 
-pub fn load_rules(path_opt: Option<&str>) -> Option<RulesConfig> {
-    let path = path_opt
+pub fn rules_path(path_opt: Option<&str>) -> String {
+    path_opt
         .map(|s| s.to_string())
         .or_else(|| std::env::var("WINDOCTOR_RULES_PATH").ok())
-        .unwrap_or_else(|| "rules.json".to_string());
-    let p = std::path::PathBuf::from(&path);
-    let data = match std::fs::read(&p) { Ok(d) => d, Err(e) => { log::warn!("Failed to read rules file {}: {}", p.to_string_lossy(), e); return None } };
-    let cfg: RulesConfig = match serde_json::from_slice(&data) { Ok(c) => c, Err(e) => { log::warn!("Failed to parse rules file {}: {}", p.to_string_lossy(), e); return None } };
-    Some(cfg)
+        .unwrap_or_else(|| "rules.json".to_string())
+}
+
+// Problems are logged with their location; only a file that does not parse is rejected.
+pub fn load_rules(path_opt: Option<&str>) -> Option<RulesConfig> {
+    let path = rules_path(path_opt);
+    let data = match std::fs::read_to_string(&path) { Ok(d) => d, Err(e) => { log::warn!("Failed to read rules file {}: {}", path, e); return None } };
+    for d in check(&data) { log::warn!("{}:{}", path, d); }
+    serde_json::from_str(&data).ok()
+}
+
+const TOP_KEYS: &[&str] = &["event_patterns", "file_patterns", "hint_rules", "level_overrides", "suppress", "level_names", "decoders"];
+const HINT_KEYS: &[&str] = &["provider", "event_id", "contains_any", "regex", "category", "severity", "message", "name", "weight", "evidence_keys", "evidence_group", "min_count", "within_minutes", "replace_builtin"];
+const SUPPRESS_KEYS: &[&str] = &["provider", "event_id", "contains", "regex"];
+const OVERRIDE_KEYS: &[&str] = &["provider", "event_id", "from", "to"];
+const LEVEL_NAME_KEYS: &[&str] = &["provider", "level", "name"];
+const DECODER_KEYS: &[&str] = &["provider", "event_id", "template"];
+
+// `line:column:` of the first `"key":` in the file, for diagnostics about a misspelled key.
+fn key_location(text: &str, key: &str) -> String {
+    let Some(at) = text.find(&format!("\"{}\"", key)) else { return String::new() };
+    let line = text[..at].matches('\n').count() + 1;
+    let col = at - text[..at].rfind('\n').map_or(0, |i| i + 1) + 1;
+    format!("{}:{}:", line, col)
+}
+
+fn check_regex(v: Option<&serde_json::Value>, path: &str, out: &mut Vec<String>) {
+    if let Some(rx) = v.and_then(|v| v.as_str()) && let Err(e) = regex::Regex::new(rx) { out.push(format!(" {}: invalid regex: {}", path, e.to_string().lines().last().unwrap_or_default())); }
+}
+
+// Every problem in a rules file: syntax and type errors (with line and column), unknown keys, invalid regexes and values, and rules that can never fire.
+pub fn check(text: &str) -> Vec<String> {
+    let value: serde_json::Value = match serde_json::from_str(text) { Ok(v) => v, Err(e) => return vec![format!("{}:{}: {}", e.line(), e.column(), e)] };
+    let mut out = Vec::new();
+    if let Err(e) = serde_json::from_value::<RulesConfig>(value.clone()) { out.push(format!(" {}", e)); }
+    let Some(top) = value.as_object() else { return out };
+    let unknown = |obj: &serde_json::Map<String, serde_json::Value>, known: &[&str], path: &str, out: &mut Vec<String>| {
+        for k in obj.keys().filter(|k| !known.contains(&k.as_str())) { out.push(format!("{} unknown field \"{}\" in {}", key_location(text, k), k, path)); }
+    };
+    unknown(top, TOP_KEYS, "the top level", &mut out);
+    for section in ["event_patterns", "file_patterns"] {
+        for (i, v) in top.get(section).and_then(|v| v.as_array()).into_iter().flatten().enumerate() { check_regex(Some(v), &format!("{}[{}]", section, i), &mut out); }
+    }
+    let entries = |section: &str| top.get(section).and_then(|v| v.as_array()).into_iter().flatten().enumerate().filter_map(|(i, v)| v.as_object().map(|o| (format!("{}[{}]", section, i), o))).collect::<Vec<_>>();
+    for (path, r) in entries("hint_rules") {
+        unknown(r, HINT_KEYS, &path, &mut out);
+        check_regex(r.get("regex"), &format!("{}.regex", path), &mut out);
+        if let Some(sev) = r.get("severity").and_then(|v| v.as_str()) && !["high", "medium", "low"].contains(&sev) { out.push(format!(" {}.severity: \"{}\" is not high, medium or low", path, sev)); }
+        if r.get("contains_any").is_none() && r.get("regex").is_none() { out.push(format!(" {}: never fires without contains_any or regex", path)); }
+        if r.get("evidence_group").is_some() && r.get("regex").is_none() { out.push(format!(" {}.evidence_group: needs a regex", path)); }
+        if r.get("within_minutes").is_some() && r.get("min_count").is_none() { out.push(format!(" {}.within_minutes: has no effect without min_count", path)); }
+    }
+    for (path, r) in entries("suppress") {
+        unknown(r, SUPPRESS_KEYS, &path, &mut out);
+        check_regex(r.get("regex"), &format!("{}.regex", path), &mut out);
+        if !SUPPRESS_KEYS.iter().any(|k| r.contains_key(*k)) { out.push(format!(" {}: no condition, ignored", path)); }
+    }
+    for (path, r) in entries("level_overrides") {
+        unknown(r, OVERRIDE_KEYS, &path, &mut out);
+        for k in ["from", "to"] {
+            if let Some(l) = r.get(k).and_then(|v| v.as_str()) && parse_level(l).is_none() { out.push(format!(" {}.{}: \"{}\" is not a level (critical, error, warning, info, verbose or 1-5)", path, k, l)); }
+        }
+    }
+    for (path, r) in entries("level_names") { unknown(r, LEVEL_NAME_KEYS, &path, &mut out); }
+    for (path, r) in entries("decoders") { unknown(r, DECODER_KEYS, &path, &mut out); }
+    out
+}
+
+// `--rules-check`: prints every diagnostic; non-zero when there is any.
+pub fn check_file(path_opt: Option<&str>) -> i32 {
+    let path = rules_path(path_opt);
+    let text = match std::fs::read_to_string(&path) { Ok(t) => t, Err(e) => { eprintln!("{}: {}", path, e); return 2; } };
+    let diags = check(&text);
+    for d in &diags { eprintln!("{}:{}", path, d); }
+    if diags.is_empty() { println!("{}: OK", path); 0 } else { eprintln!("{}: {} problem{}", path, diags.len(), if diags.len() == 1 { "" } else { "s" }); 1 }
+}
+
+pub fn parse_level(s: &str) -> Option<u8> {
+    match s.trim().to_lowercase().as_str() {
+        "critical" => Some(1), "error" => Some(2), "warning" | "warn" => Some(3), "info" | "information" => Some(4), "verbose" => Some(5),
+        n => n.parse::<u8>().ok().filter(|v| (1..=5).contains(v)),
+    }
+}
+
+// First matching override wins.
+fn overridden_level(rules: &[LevelOverride], e: &crate::EventItem) -> Option<u8> {
+    rules.iter().find_map(|r| {
+        if r.provider.as_ref().is_some_and(|p| !e.provider.eq_ignore_ascii_case(p)) || r.event_id.is_some_and(|id| e.event_id != id) || r.from.as_ref().is_some_and(|f| parse_level(f) != Some(e.level)) { return None; }
+        parse_level(&r.to)
+    })
+}
+
+// Applied before counting, hints and scoring.
+pub fn apply_level_overrides(events: &mut [crate::EventItem], cfg: &RulesConfig) {
+    let rules = match &cfg.level_overrides { Some(r) if !r.is_empty() => r, _ => return };
+    for e in events.iter_mut() {
+        if let Some(l) = overridden_level(rules, e) { e.level = l; }
+    }
+}
+
+static LEVEL_OVERRIDES: std::sync::OnceLock<Vec<LevelOverride>> = std::sync::OnceLock::new();
+
+pub fn set_level_overrides(cfg: &RulesConfig) { if let Some(v) = cfg.level_overrides.as_ref() { let _ = LEVEL_OVERRIDES.set(v.clone()); } }
+
+// The level the collection filters see, so an override also decides whether an event is kept.
+pub fn effective_level(e: &crate::EventItem) -> u8 {
+    LEVEL_OVERRIDES.get().and_then(|r| overridden_level(r, e)).unwrap_or(e.level)
+}
+
+static LEVEL_NAMES: std::sync::OnceLock<Vec<LevelName>> = std::sync::OnceLock::new();
+
+pub fn set_level_names(cfg: &RulesConfig) { if let Some(v) = cfg.level_names.as_ref() { let _ = LEVEL_NAMES.set(v.clone()); } }
+
+// rules.json `level_names`; a provider-specific entry wins over a global one.
+pub fn level_name_for(provider: &str, level: u8) -> Option<String> {
+    let names = LEVEL_NAMES.get()?;
+    let at = names.iter().filter(|n| n.level == level);
+    at.clone().find(|n| n.provider.as_ref().is_some_and(|p| p.eq_ignore_ascii_case(provider))).or_else(|| at.clone().find(|n| n.provider.is_none())).map(|n| n.name.clone())
+}
+
+static DECODERS: std::sync::OnceLock<Vec<DecoderTemplate>> = std::sync::OnceLock::new();
+
+pub fn set_decoders(cfg: &RulesConfig) { if let Some(v) = cfg.decoders.as_ref() { let _ = DECODERS.set(v.clone()); } }
+
+// rules.json `decoders`: the message for an event the built-in decoders do not know. Unknown keys render empty.
+pub fn template_message(decoders: &[DecoderTemplate], provider: &str, event_id: u32, data: &std::collections::HashMap<String, String>) -> Option<String> {
+    let d = decoders.iter().find(|d| d.event_id == event_id && d.provider.eq_ignore_ascii_case(provider))?;
+    let mut out = String::new();
+    let mut rest = d.template.as_str();
+    while let Some(i) = rest.find(['{', '}']) {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        if rest.starts_with("{{") || rest.starts_with("}}") { out.push_str(&rest[..1]); rest = &rest[2..]; continue; }
+        match rest.find('}').filter(|_| rest.starts_with('{')) {
+            Some(end) => {
+                let key = &rest[1..end];
+                if let Some(v) = data.get(key).or_else(|| data.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, v)| v)) { out.push_str(v); }
+                rest = &rest[end + 1..];
+            }
+            None => { out.push_str(&rest[..1]); rest = &rest[1..]; }
+        }
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+pub fn decoder_message(provider: &str, event_id: u32, data: &std::collections::HashMap<String, String>) -> Option<String> {
+    template_message(DECODERS.get()?, provider, event_id, data)
+}
+
+static EVENT_DATA: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+// EVTX scans keep each record's XML only when something reads its EventData; `evidence_keys` does.
+pub fn set_uses_event_data(cfg: &RulesConfig) {
+    let uses = cfg.hint_rules.as_ref().is_some_and(|rules| rules.iter().any(|r| r.evidence_keys.as_ref().is_some_and(|k| !k.is_empty())));
+    EVENT_DATA.store(uses, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn uses_event_data() -> bool { EVENT_DATA.load(std::sync::atomic::Ordering::Relaxed) }
+
+// An entry needs at least one condition; one with a bad regex is skipped. Entries keep their index in `suppress`.
+fn compile_suppressions(cfg: &RulesConfig) -> Vec<(usize, &Suppress, Option<String>, Option<regex::Regex>)> {
+    let Some(rules) = cfg.suppress.as_ref() else { return vec![] };
+    rules.iter().enumerate()
+        .filter(|(_, r)| r.provider.is_some() || r.event_id.is_some() || r.contains.is_some() || r.regex.is_some())
+        .filter_map(|(i, r)| match r.regex.as_deref().map(regex::Regex::new) {
+            Some(Err(e)) => { log::warn!("Ignoring suppress entry with invalid regex: {}", e); None }
+            re => Some((i, r, r.contains.as_ref().map(|c| c.to_lowercase()), re.and_then(Result::ok))),
+        }).collect()
+}
+
+fn suppressed_by(rules: &[(usize, &Suppress, Option<String>, Option<regex::Regex>)], e: &crate::EventItem) -> Option<usize> {
+    let lower = e.content.to_lowercase();
+    rules.iter().find(|(_, r, contains, re)| r.provider.as_ref().is_none_or(|p| e.provider.eq_ignore_ascii_case(p)) && r.event_id.is_none_or(|id| e.event_id == id) && contains.as_ref().is_none_or(|c| lower.contains(c.as_str())) && re.as_ref().is_none_or(|re| re.is_match(&e.content))).map(|(i, ..)| *i)
+}
+
+// Known-benign events never reach counts, hints or scoring.
+pub fn apply_suppressions(events: &mut Vec<crate::EventItem>, cfg: &RulesConfig) {
+    let rules = compile_suppressions(cfg);
+    if rules.is_empty() { return; }
+    events.retain(|e| suppressed_by(&rules, e).is_none());
+}
+
+// For `rules test`: the index of the first suppress entry dropping each event.
+pub fn suppression_of(events: &[crate::EventItem], cfg: &RulesConfig) -> Vec<Option<usize>> {
+    let rules = compile_suppressions(cfg);
+    events.iter().map(|e| suppressed_by(&rules, e)).collect()
+}
+
+// Why `e` matches `r`, or None; a rule needs a `contains_any` or `regex` hit on top of its provider/event ID.
+pub fn match_reason(r: &HintRule, re: Option<&regex::Regex>, e: &crate::EventItem) -> Option<String> {
+    if let Some(p) = r.provider.as_ref() && e.provider != *p { return None; }
+    if let Some(id) = r.event_id.as_ref() && e.event_id != *id { return None; }
+    let content_lower = e.content.to_lowercase();
+    let hit = r.contains_any.as_ref().and_then(|list| list.iter().find(|k| content_lower.contains(&k.to_lowercase())).map(|k| format!("contains \"{}\"", k)))
+        .or_else(|| re.and_then(|re| re.find(&e.content)).map(|m| format!("regex matched \"{}\"", m.as_str())))?;
+    let scope = r.provider.iter().map(|p| format!("provider {}", p)).chain(r.event_id.iter().map(|id| format!("event {}", id)));
+    Some(scope.chain(std::iter::once(hit)).collect::<Vec<_>>().join(", "))
 }
 
 pub fn apply_hint_rules(events: &[crate::EventItem], cfg: &RulesConfig) -> Vec<crate::hints::NoviceHint> {
     let mut out: Vec<crate::hints::NoviceHint> = vec![];
     let rules = match &cfg.hint_rules { Some(r) => r, None => return out };
     for r in rules {
-        for e in events {
-            if let Some(p) = r.provider.as_ref() && e.provider != *p { continue; }
-            if let Some(id) = r.event_id.as_ref() && e.event_id != *id { continue; }
-            let mut matched = false;
-            let content_lower = e.content.to_lowercase();
-            if let Some(list) = r.contains_any.as_ref() {
-                for k in list { if content_lower.contains(&k.to_lowercase()) { matched = true; break; } }
+        let re = r.regex.as_ref().and_then(|rx| regex::Regex::new(rx).ok());
+        let mut hits: Vec<&crate::EventItem> = vec![];
+        for e in events { if match_reason(r, re.as_ref(), e).is_some() { hits.push(e); } }
+        let sev = r.severity.clone().unwrap_or_else(|| "medium".to_string());
+        let cat = r.category.clone().unwrap_or_else(|| "General".to_string());
+        let msg = if let Some(n) = r.name.as_ref() { format!("{} [{}]", r.message, n) } else { r.message.clone() };
+        let mut prob = 50u8;
+        if let Some(w) = r.weight { prob = prob.saturating_add(w); }
+        let hint = match r.min_count {
+            None if hits.is_empty() => continue,
+            None => {
+                let mut evidence: Vec<String> = vec![];
+                for e in &hits { for v in rule_evidence(r, re.as_ref(), e) { if evidence.len() < 3 && !evidence.contains(&v) { evidence.push(v); } } }
+                crate::hints::NoviceHint { category: cat, severity: sev, message: msg, evidence, count: hits.len(), probability: prob }
             }
-            if !matched
-                && let Some(rx) = r.regex.as_ref()
-                && let Ok(re) = regex::Regex::new(rx) && re.is_match(&e.content) { matched = true; }
-            if matched {
-                let sev = r.severity.clone().unwrap_or_else(|| "medium".to_string());
-                let cat = r.category.clone().unwrap_or_else(|| "General".to_string());
-                let msg = if let Some(n) = r.name.as_ref() { format!("{} [{}]", r.message, n) } else { r.message.clone() };
-                let mut prob = 50u8;
-                if let Some(w) = r.weight { prob = prob.saturating_add(w); }
-                out.push(crate::hints::NoviceHint { category: cat, severity: sev, message: msg, evidence: vec![], count: 1, probability: prob });
+            // Thresholded: fires once `min_count` hits fall inside some `within_minutes` span.
+            Some(min) => {
+                let Some((peak, burst)) = densest_window(&mut hits, r.within_minutes) else { continue };
+                if peak < min.max(1) { continue; }
+                let mut evidence = vec![match r.within_minutes { Some(m) => format!("{} events within {} min from {}", peak, m, burst[0].time.format("%Y-%m-%d %H:%M")), None => format!("{} events", peak) }];
+                for e in burst { for v in rule_evidence(r, re.as_ref(), e) { if evidence.len() < 6 && !evidence.contains(&v) { evidence.push(v); } } }
+                crate::hints::NoviceHint { category: cat, severity: sev, message: msg, evidence, count: hits.len(), probability: prob }
             }
+        };
+        // Rules sharing a category, severity and message add up into one hint.
+        match out.iter_mut().find(|h| h.category == hint.category && h.severity == hint.severity && h.message == hint.message) {
+            Some(h) => { h.count += hint.count; h.probability = h.probability.max(hint.probability); for v in hint.evidence { if h.evidence.len() < 3 && !h.evidence.contains(&v) { h.evidence.push(v); } } }
+            None => out.push(hint),
+        }
+    }
+    out
+}
+
+// Built-in hints plus rule hints. Events matched by a `replace_builtin` rule are left out of the built-in analysis,
+// and a rule hint with a built-in's category and message is folded into that hint instead of listed twice.
+pub fn hints_with_rules(events: &[crate::EventItem], cfg: &RulesConfig) -> Vec<crate::hints::NoviceHint> {
+    let replacing: Vec<(&HintRule, Option<regex::Regex>)> = cfg.hint_rules.iter().flatten().filter(|r| r.replace_builtin == Some(true)).map(|r| (r, r.regex.as_ref().and_then(|rx| regex::Regex::new(rx).ok()))).collect();
+    let mut hints = if replacing.is_empty() { crate::hints::generate_hints(events) } else {
+        let rest: Vec<crate::EventItem> = events.iter().filter(|e| !replacing.iter().any(|(r, re)| match_reason(r, re.as_ref(), e).is_some())).cloned().collect();
+        crate::hints::generate_hints(&rest)
+    };
+    for h in apply_hint_rules(events, cfg) {
+        match hints.iter_mut().find(|b| b.category.eq_ignore_ascii_case(&h.category) && b.message.eq_ignore_ascii_case(&h.message)) {
+            Some(b) => { b.count = b.count.max(h.count); b.probability = b.probability.max(h.probability); for v in h.evidence { if b.evidence.len() < 3 && !b.evidence.contains(&v) { b.evidence.push(v); } } }
+            None => hints.push(h),
+        }
+    }
+    hints
+}
+
+// Most hits inside any `minutes`-long window (all of them without a window), with the events of that window.
+fn densest_window<'a>(hits: &mut [&'a crate::EventItem], minutes: Option<u32>) -> Option<(usize, Vec<&'a crate::EventItem>)> {
+    if hits.is_empty() { return None; }
+    hits.sort_by_key(|e| e.time);
+    let Some(m) = minutes else { return Some((hits.len(), hits.to_vec())) };
+    let span = chrono::Duration::minutes(m as i64);
+    let (mut best, mut start, mut lo) = (0, 0, 0);
+    for hi in 0..hits.len() {
+        while hits[hi].time - hits[lo].time > span { lo += 1; }
+        if hi + 1 - lo > best { best = hi + 1 - lo; start = lo; }
+    }
+    Some((best, hits[start..start + best].to_vec()))
+}
+
+fn rule_evidence(r: &HintRule, re: Option<&regex::Regex>, e: &crate::EventItem) -> Vec<String> {
+    let mut out = Vec::new();
+    if let Some(keys) = r.evidence_keys.as_ref().filter(|k| !k.is_empty()) {
+        let data = crate::event_xml::event_data_pairs_or_fallback(e.raw_xml.as_deref().unwrap_or(&e.content));
+        for k in keys {
+            if let Some((name, v)) = data.iter().find(|(name, v)| name.eq_ignore_ascii_case(k) && !v.is_empty()) { out.push(format!("{}={}", name, v)); }
         }
     }
+    if let (Some(g), Some(caps)) = (r.evidence_group.as_ref(), re.and_then(|re| re.captures(&e.content))) {
+        let m = match g.parse::<usize>() { Ok(i) => caps.get(i), Err(_) => caps.name(g) };
+        if let Some(m) = m.filter(|m| !m.as_str().is_empty()) { out.push(m.as_str().to_string()); }
+    }
     out
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ev(provider: &str, event_id: u32, level: u8) -> crate::EventItem {
+        crate::EventItem { time: chrono::Utc::now(), level, channel: "System".to_string(), provider: provider.to_string(), event_id, content: String::new(), raw_xml: None, ..Default::default() }
+    }
+
+    #[test]
+    fn level_names_prefer_the_provider_entry() {
+        let cfg: RulesConfig = serde_json::from_str(r#"{"level_names":[{"level":20,"name":"Trace"},{"provider":"Contoso-Agent","level":20,"name":"Critical-Custom"}]}"#).unwrap();
+        set_level_names(&cfg);
+        assert_eq!(level_name_for("contoso-agent", 20).as_deref(), Some("Critical-Custom"));
+        assert_eq!(level_name_for("Disk", 20).as_deref(), Some("Trace"));
+        assert_eq!(level_name_for("Disk", 21), None);
+    }
+
+    #[test]
+    fn level_overrides_remap_matching_events() {
+        let cfg: RulesConfig = serde_json::from_str(r#"{"level_overrides":[{"provider":"DistributedCOM","event_id":10016,"to":"info"},{"provider":"Disk","from":"warning","to":"error"}]}"#).unwrap();
+        let mut events = vec![ev("DistributedCOM", 10016, 2), ev("disk", 153, 3), ev("Disk", 7, 2), ev("DistributedCOM", 10010, 2)];
+        apply_level_overrides(&mut events, &cfg);
+        let levels: Vec<u8> = events.iter().map(|e| e.level).collect();
+        assert_eq!(levels, vec![4, 2, 2, 2]);
+        // The collection filters see the overridden level, so a demoted event is dropped like any Information event.
+        set_level_overrides(&cfg);
+        assert_eq!((effective_level(&ev("DistributedCOM", 10016, 2)), effective_level(&ev("Disk", 7, 1))), (4, 1));
+    }
+
+    #[test]
+    fn hint_rules_attach_eventdata_and_captures_as_evidence() {
+        let cfg: RulesConfig = serde_json::from_str(r#"{"hint_rules":[{"provider":"Contoso","regex":"job (?P<job>\\w+) failed","message":"Backup failed","evidence_keys":["targetPath","Missing"],"evidence_group":"job"}]}"#).unwrap();
+        let e = crate::EventItem { content: "Backup job nightly failed".to_string(), raw_xml: Some("<Event><EventData><Data Name=\"TargetPath\">D:\\Backups</Data></EventData></Event>".to_string()), ..ev("Contoso", 5, 2) };
+        set_uses_event_data(&cfg);
+        assert!(uses_event_data());
+        let hints = apply_hint_rules(&[e], &cfg);
+        assert_eq!(hints[0].evidence, vec!["TargetPath=D:\\Backups".to_string(), "nightly".to_string()]);
+    }
+
+    #[test]
+    fn threshold_rules_need_enough_hits_in_the_window() {
+        let cfg: RulesConfig = serde_json::from_str(r#"{"hint_rules":[{"provider":"DNS","contains_any":["timed out"],"message":"DNS failures","min_count":3,"within_minutes":10},{"provider":"DNS","contains_any":["timed out"],"message":"Any DNS failures","min_count":5}]}"#).unwrap();
+        let t0 = chrono::Utc::now();
+        let at = |m: i64| crate::EventItem { time: t0 + chrono::Duration::minutes(m), content: "Name resolution timed out".to_string(), ..ev("DNS", 1014, 3) };
+        let sparse = vec![at(0), at(11), at(22), at(33)];
+        assert!(apply_hint_rules(&sparse, &cfg).is_empty());
+        let burst = vec![at(0), at(30), at(34), at(39), at(50)];
+        let hints = apply_hint_rules(&burst, &cfg);
+        assert_eq!(hints.iter().map(|h| (h.message.as_str(), h.count)).collect::<Vec<_>>(), vec![("DNS failures", 5), ("Any DNS failures", 5)]);
+        assert!(hints[0].evidence[0].starts_with("3 events within 10 min from "));
+    }
+
+    #[test]
+    fn check_reports_locations_unknown_keys_and_bad_regexes() {
+        assert_eq!(check("{\n  \"hint_rules\": [\n    { \"message\": \"x\" \n"), vec!["4:0: EOF while parsing an object at line 4 column 0".to_string()]);
+        let text = "{\n  \"suppress\": [{ \"provider\": \"A\", \"regex\": \"(\" }],\n  \"hint_rules\": [{ \"message\": \"m\", \"contains_any\": [\"a\"], \"evidnce_keys\": [\"X\"], \"severity\": \"urgent\" }, { \"message\": \"n\" }]\n}";
+        let diags = check(text);
+        assert!(diags.contains(&" suppress[0].regex: invalid regex: error: unclosed group".to_string()), "{:?}", diags);
+        assert!(diags.contains(&"3:59: unknown field \"evidnce_keys\" in hint_rules[0]".to_string()), "{:?}", diags);
+        assert!(diags.contains(&" hint_rules[0].severity: \"urgent\" is not high, medium or low".to_string()));
+        assert!(diags.contains(&" hint_rules[1]: never fires without contains_any or regex".to_string()));
+        assert_eq!(diags.len(), 4);
+    }
+
+    #[test]
+    fn rule_hits_merge_and_can_replace_builtin_hints() {
+        let dns = |q: &str| crate::EventItem { content: format!("Name resolution for the name {} timed out", q), ..ev("Microsoft-Windows-DNS-Client", 1014, 3) };
+        let events = vec![dns("a.example"), dns("b.example"), dns("c.example")];
+        let same: RulesConfig = serde_json::from_str(r#"{"hint_rules":[{"provider":"Microsoft-Windows-DNS-Client","contains_any":["timed out"],"category":"Network","message":"DNS name resolution failure"}]}"#).unwrap();
+        let hints = hints_with_rules(&events, &same);
+        let dns_hints: Vec<_> = hints.iter().filter(|h| h.message == "DNS name resolution failure").collect();
+        assert_eq!((dns_hints.len(), dns_hints[0].count), (1, 3));
+        let replace: RulesConfig = serde_json::from_str(r#"{"hint_rules":[{"provider":"Microsoft-Windows-DNS-Client","contains_any":["timed out"],"category":"Network","message":"VPN DNS outage","replace_builtin":true}]}"#).unwrap();
+        let hints = hints_with_rules(&events, &replace);
+        assert!(hints.iter().all(|h| h.message != "DNS name resolution failure"));
+        assert_eq!(hints.iter().filter(|h| h.message == "VPN DNS outage").map(|h| h.count).collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn decoder_templates_fill_eventdata_placeholders() {
+        let cfg: RulesConfig = serde_json::from_str(r#"{"decoders":[{"provider":"Contoso-Backup","event_id":12,"template":"Backup of {Volume} failed: {reason} {{code {Missing}}}"}]}"#).unwrap();
+        let data: std::collections::HashMap<String, String> = [("Volume".to_string(), "D:".to_string()), ("Reason".to_string(), "disk full".to_string())].into_iter().collect();
+        let d = cfg.decoders.unwrap();
+        assert_eq!(template_message(&d, "contoso-backup", 12, &data).as_deref(), Some("Backup of D: failed: disk full {code }"));
+        assert_eq!(template_message(&d, "Contoso-Backup", 13, &data), None);
+    }
+
+    #[test]
+    fn suppress_drops_matching_events() {
+        let cfg: RulesConfig = serde_json::from_str(r#"{"suppress":[{"provider":"DistributedCOM","event_id":10016},{"event_id":1014},{},{"provider":"PrintService","contains":"OFFLINE"},{"event_id":36,"regex":"^Spooler \\d+$"},{"regex":"("}]}"#).unwrap();
+        let with = |provider: &str, event_id: u32, content: &str| crate::EventItem { content: content.to_string(), ..ev(provider, event_id, 3) };
+        let mut events = vec![ev("distributedcom", 10016, 3), ev("DistributedCOM", 10010, 2), ev("DNS Client Events", 1014, 3), ev("Disk", 7, 2), with("PrintService", 808, "Printer is offline"), with("PrintService", 809, "Driver failed"), with("X", 36, "Spooler 12"), with("X", 36, "Spooler twelve")];
+        apply_suppressions(&mut events, &cfg);
+        let kept: Vec<u32> = events.iter().map(|e| e.event_id).collect();
+        assert_eq!(kept, vec![10010, 7, 809, 36]);
+    }
+}
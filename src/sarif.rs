@@ -0,0 +1,76 @@
+use crate::ReportSummary;
+use serde_json::{json, Value};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+fn hint_level(severity: &str) -> &'static str {
+    match severity { "high" => "error", "medium" => "warning", _ => "note" }
+}
+
+fn event_level(level: u8) -> &'static str {
+    match level { 1 | 2 => "error", 3 => "warning", _ => "note" }
+}
+
+// Hints become `hint/<Category>` results and sampled events `<Domain>/<EventID>` results; every rule id used is declared once in the driver.
+pub fn render(rep: &ReportSummary) -> Value {
+    let mut rules: Vec<(String, String)> = Vec::new();
+    let mut declare = |id: &str, text: String| if !rules.iter().any(|(r, _)| r == id) { rules.push((id.to_string(), text)); };
+    let mut results = Vec::new();
+    for h in &rep.novice_hints {
+        let id = format!("hint/{}", h.category);
+        declare(&id, format!("{} finding", h.category));
+        results.push(json!({
+            "ruleId": id,
+            "level": hint_level(&h.severity),
+            "message": { "text": h.message },
+            "properties": { "evidence": h.evidence, "count": h.count, "probability": h.probability },
+        }));
+    }
+    for e in &rep.samples {
+        let id = format!("{}/{}", crate::classify_domain(&e.provider, &e.channel, e.event_id, &e.content), e.event_id);
+        declare(&id, format!("{} event {}", e.provider, e.event_id));
+        let mut props = json!({ "time": e.time.to_rfc3339(), "severity": crate::event_level_name(e), "provider": e.provider, "channel": e.channel });
+        if let Some(r) = e.record_id { props["record_id"] = json!(r); }
+        let location = [e.computer.as_str(), e.channel.as_str(), e.provider.as_str()].iter().filter(|s| !s.is_empty()).copied().collect::<Vec<_>>().join("/");
+        results.push(json!({
+            "ruleId": id,
+            "level": event_level(e.level),
+            "message": { "text": crate::event_message_redacted(e) },
+            "locations": [{ "logicalLocations": [{ "fullyQualifiedName": location, "kind": "module" }] }],
+            "properties": props,
+        }));
+    }
+    let rules: Vec<Value> = rules.into_iter().map(|(id, text)| json!({ "id": id, "shortDescription": { "text": text } })).collect();
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": { "driver": { "name": "WinDoctor", "version": env!("CARGO_PKG_VERSION"), "rules": rules } },
+            "results": results,
+            "properties": { "window_start": rep.window_start.to_rfc3339(), "window_end": rep.window_end.to_rfc3339(), "risk_grade": rep.risk_grade, "total": rep.total, "partial": rep.partial },
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints_and_events_map_to_results() {
+        let mut rep = ReportSummary { total: 2, errors: 1, warnings: 1, novice_hints: vec![crate::hints::NoviceHint { category: "Storage".to_string(), severity: "high".to_string(), message: "Disk errors".to_string(), evidence: vec!["Disk 7".to_string()], count: 2, probability: 80 }], performance_score: 70, risk_grade: "High".to_string(), ..crate::test_summary() };
+        let e = |level: u8| crate::EventItem { provider: "Disk".to_string(), channel: "System".to_string(), event_id: 7, level, computer: "ws01".to_string(), content: "Bad block".to_string(), record_id: Some(42), ..Default::default() };
+        rep.samples = vec![e(2), e(3)];
+        let v = render(&rep);
+        assert_eq!(v["version"], "2.1.0");
+        let run = &v["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        let results = run["results"].as_array().unwrap();
+        assert_eq!((results[0]["ruleId"].as_str(), results[0]["level"].as_str()), (Some("hint/Storage"), Some("error")));
+        assert_eq!(results[1]["ruleId"].as_str().unwrap(), results[2]["ruleId"].as_str().unwrap());
+        assert!(results[1]["ruleId"].as_str().unwrap().ends_with("/7"));
+        assert_eq!((results[1]["level"].as_str(), results[2]["level"].as_str()), (Some("error"), Some("warning")));
+        assert_eq!(results[1]["locations"][0]["logicalLocations"][0]["fullyQualifiedName"], "ws01/System/Disk");
+        assert_eq!(results[1]["properties"]["record_id"], 42);
+    }
+}
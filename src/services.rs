@@ -0,0 +1,85 @@
+use crate::EventItem;
+use serde::{Serialize, Deserialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ServiceState {
+    pub name: String,
+    pub display_name: String,
+    pub state: String,
+    pub exit_code: Option<u32>,
+    pub last_event: Option<String>,
+}
+
+impl ServiceState {
+    pub fn failed(&self) -> bool { self.exit_code.is_some_and(|c| c != 0 && c != 1077) }
+}
+
+// Auto-start services that are not running right now. Exit code 1077 means "never started since boot".
+#[cfg(target_os = "windows")]
+pub fn collect_stopped_auto_services() -> Vec<ServiceState> {
+    use wmi::WMIConnection;
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct SvcRow { Name: String, DisplayName: Option<String>, State: Option<String>, ExitCode: Option<u32>, ServiceSpecificExitCode: Option<u32> }
+    let mut out = Vec::new();
+    if let Ok(wmi) = WMIConnection::new()
+        && let Ok(rows) = wmi.raw_query::<SvcRow>("SELECT Name, DisplayName, State, ExitCode, ServiceSpecificExitCode FROM Win32_Service WHERE StartMode='Auto' AND State<>'Running'") {
+        for r in rows {
+            let code = match (r.ExitCode, r.ServiceSpecificExitCode) { (Some(1066), Some(s)) if s != 0 => Some(s), (c, _) => c };
+            out.push(ServiceState { display_name: r.DisplayName.unwrap_or_else(|| r.Name.clone()), name: r.Name, state: r.State.unwrap_or_else(|| "Stopped".to_string()), exit_code: code, last_event: None });
+        }
+    }
+    out
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn collect_stopped_auto_services() -> Vec<ServiceState> { Vec::new() }
+
+// Attaches the most recent Service Control Manager event that names each service.
+pub fn attach_last_scm_events(services: &mut [ServiceState], events: &[EventItem]) {
+    let mut scm: Vec<&EventItem> = events.iter().filter(|e| e.provider.eq_ignore_ascii_case("Service Control Manager")).collect();
+    scm.sort_by_key(|e| std::cmp::Reverse(e.time));
+    for s in services.iter_mut() {
+        let name = s.name.to_lowercase();
+        let display = s.display_name.to_lowercase();
+        if let Some(e) = scm.iter().find(|e| { let c = e.content.to_lowercase(); c.contains(&display) || c.contains(&name) }) {
+            s.last_event = Some(format!("{} · {} · {}", e.time.format("%Y-%m-%d %H:%M"), e.event_id, crate::event_message(e)));
+        }
+    }
+}
+
+pub fn service_hints(services: &[ServiceState]) -> Vec<crate::hints::NoviceHint> {
+    services.iter().filter(|s| s.failed() || s.last_event.is_some()).map(|s| {
+        let (severity, what) = if s.failed() { ("high", format!("failed (exit code {})", s.exit_code.unwrap_or(0))) } else { ("medium", "stopped".to_string()) };
+        crate::hints::NoviceHint {
+            category: "Services".to_string(),
+            severity: severity.to_string(),
+            message: format!("Auto-start service '{}' is {} right now", s.display_name, what),
+            evidence: s.last_event.iter().cloned().collect(),
+            count: 1,
+            probability: if s.failed() { 80 } else { 60 },
+        }
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn down_service_gets_last_scm_event_and_hint() {
+        let t = chrono::Utc::now();
+        let ev = |mins: i64, content: &str| EventItem { time: t - chrono::Duration::minutes(mins), level: 2, channel: "System".to_string(), provider: "Service Control Manager".to_string(), event_id: 7031, content: content.to_string(), ..Default::default() };
+        let events = vec![ev(60, "The Print Spooler service terminated unexpectedly."), ev(5, "The Print Spooler service terminated unexpectedly (2nd)."), ev(1, "The Windows Update service entered the stopped state.")];
+        let mut svcs = vec![
+            ServiceState { name: "Spooler".to_string(), display_name: "Print Spooler".to_string(), state: "Stopped".to_string(), exit_code: Some(1067), last_event: None },
+            ServiceState { name: "sppsvc".to_string(), display_name: "Software Protection".to_string(), state: "Stopped".to_string(), exit_code: Some(0), last_event: None },
+        ];
+        attach_last_scm_events(&mut svcs, &events);
+        assert!(svcs[0].last_event.as_deref().unwrap().contains("(2nd)"));
+        assert!(svcs[1].last_event.is_none());
+        let hints = service_hints(&svcs);
+        assert_eq!(hints.len(), 1);
+        assert_eq!(hints[0].severity, "high");
+    }
+}
@@ -0,0 +1,60 @@
+use crate::EventItem;
+
+const VENDOR: &str = "Microsoft";
+const PRODUCT: &str = "Microsoft Windows";
+
+// CEF severity runs 0-10; LEEF's sev 1-10.
+fn severity(level: u8) -> u8 {
+    match level { 1 => 10, 2 => 8, 3 => 5, 4 => 3, _ => 1 }
+}
+
+fn cef_header(s: &str) -> String { s.replace('\\', "\\\\").replace('|', "\\|").replace(['\r', '\n'], " ") }
+
+fn cef_value(s: &str) -> String { s.replace('\\', "\\\\").replace('=', "\\=").replace("\r\n", "\\n").replace(['\r', '\n'], "\\n") }
+
+// LEEF 1.0 separates attributes with tabs, so tabs and line breaks inside values become spaces.
+fn leef_value(s: &str) -> String { s.replace(['\t', '\r', '\n'], " ") }
+
+fn fields(e: &EventItem) -> Vec<(&'static str, String)> {
+    let mut f = vec![("dvchost", e.computer.clone()), ("cs1Label", "Channel".to_string()), ("cs1", e.channel.clone()), ("cs2Label", "Provider".to_string()), ("cs2", e.provider.clone())];
+    if let Some(u) = e.user.as_ref() { f.push(("suser", u.clone())); }
+    if let Some(r) = e.record_id { f.push(("cn1Label", "RecordID".to_string())); f.push(("cn1", r.to_string())); }
+    f.retain(|(_, v)| !v.is_empty());
+    f
+}
+
+// `CEF:0|Microsoft|Microsoft Windows||<EventID>|<cause>|<severity>|rt=... msg=...`
+pub fn cef_line(e: &EventItem) -> String {
+    let mut ext = vec![format!("rt={}", e.time.timestamp_millis())];
+    ext.extend(fields(e).into_iter().map(|(k, v)| format!("{}={}", k, cef_value(&v))));
+    ext.push(format!("msg={}", cef_value(&crate::event_message_redacted(e))));
+    format!("CEF:0|{}|{}||{}|{}|{}|{}", VENDOR, PRODUCT, e.event_id, cef_header(&crate::event_cause_redacted(e)), severity(e.level), ext.join(" "))
+}
+
+// `LEEF:1.0|Microsoft|Microsoft Windows||<EventID>|devTime=...<TAB>sev=...`
+pub fn leef_line(e: &EventItem) -> String {
+    let mut attrs = vec![format!("devTime={}", e.time.format("%b %d %Y %H:%M:%S%.3f UTC")), "devTimeFormat=MMM dd yyyy HH:mm:ss.SSS z".to_string(), format!("sev={}", severity(e.level))];
+    attrs.extend(fields(e).into_iter().filter(|(k, _)| !k.ends_with("Label")).map(|(k, v)| {
+        let key = match k { "dvchost" => "identHostName", "cs1" => "channel", "cs2" => "provider", "suser" => "usrName", "cn1" => "recordId", k => k };
+        format!("{}={}", key, leef_value(&v))
+    }));
+    attrs.push(format!("msg={}", leef_value(&crate::event_message_redacted(e))));
+    format!("LEEF:1.0|{}|{}||{}|{}", VENDOR, PRODUCT, e.event_id, attrs.join("\t"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cef_and_leef_escape_and_map_fields() {
+        let t = chrono::DateTime::parse_from_rfc3339("2025-11-30T12:00:00Z").unwrap().with_timezone(&chrono::Utc);
+        let e = EventItem { time: t, level: 2, provider: "Disk".to_string(), channel: "System".to_string(), event_id: 7, computer: "ws01".to_string(), content: "Bad block on a=b\nretry|later".to_string(), record_id: Some(42), ..Default::default() };
+        let cef = cef_line(&e);
+        assert!(cef.starts_with("CEF:0|Microsoft|Microsoft Windows||7|"));
+        assert!(cef.contains("|8|rt=1764504000000 dvchost=ws01 cs1Label=Channel cs1=System cs2Label=Provider cs2=Disk cn1Label=RecordID cn1=42 msg=Bad block on a\\=b retry|later"));
+        let leef = leef_line(&e);
+        assert!(leef.starts_with("LEEF:1.0|Microsoft|Microsoft Windows||7|devTime=Nov 30 2025 12:00:00.000 UTC\t"));
+        assert!(leef.contains("\tsev=8\tidentHostName=ws01\tchannel=System\tprovider=Disk\trecordId=42\tmsg=Bad block on a=b retry|later"));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::EventItem;
+use serde_json::json;
+use std::io::Write;
+
+const SOURCETYPE: &str = "windoctor:event";
+const COLLECTOR_PATH: &str = "/services/collector/event";
+// HEC's default max_content_length is 1 MB; stay under it before compression.
+const BATCH_BYTES: usize = 800_000;
+const ATTEMPTS: u32 = 4;
+
+// Field names follow the Splunk Add-on for Windows so existing searches keep working.
+pub fn event(e: &EventItem) -> serde_json::Value {
+    let mut fields = json!({
+        "EventCode": e.event_id,
+        "SourceName": e.provider,
+        "LogName": e.channel,
+        "ComputerName": e.computer,
+        "Type": crate::event_level_name(e),
+        "Message": crate::event_message_redacted(e),
+        "Cause": crate::event_cause_redacted(e),
+    });
+    if let Some(r) = e.record_id { fields["RecordNumber"] = json!(r); }
+    if let Some(u) = e.user.as_ref() { fields["User"] = json!(u); }
+    json!({
+        "time": e.time.timestamp_millis() as f64 / 1000.0,
+        "host": if e.computer.is_empty() { "unknown" } else { e.computer.as_str() },
+        "source": format!("WinEventLog:{}", e.channel),
+        "sourcetype": SOURCETYPE,
+        "event": fields,
+    })
+}
+
+fn gzip(bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut z = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    z.write_all(bytes)?;
+    z.finish()
+}
+
+pub struct Hec { url: String, token: String, batch: crate::forward::Batch<String> }
+
+impl Hec {
+    pub fn new(url: &str, token: &str) -> Result<Hec, String> {
+        // A bare `https://host:8088` gets the JSON event endpoint appended.
+        Ok(Hec { url: crate::http::default_path(url, COLLECTOR_PATH)?, token: token.to_string(), batch: crate::forward::Batch::new(BATCH_BYTES) })
+    }
+
+    fn post(&mut self) -> Result<(), (usize, std::io::Error)> {
+        let events = self.batch.take();
+        let fail = |m: String| (events.len(), std::io::Error::other(m));
+        let z = gzip(events.concat().as_bytes()).map_err(|e| fail(e.to_string()))?;
+        let headers = [("Authorization", format!("Splunk {}", self.token)), ("Content-Type", "application/json".to_string()), ("Content-Encoding", "gzip".to_string())];
+        match crate::http::post_with_retry(&self.url, &headers, &z, ATTEMPTS) {
+            Ok(r) if r.status / 100 == 2 => Ok(()),
+            Ok(r) => Err(fail(format!("HTTP {}: {}", r.status, r.body.trim()))),
+            Err(e) => Err(fail(e)),
+        }
+    }
+}
+
+// Events are stacked JSON objects in one request body, gzip-compressed.
+impl crate::forward::Sink for Hec {
+    fn send(&mut self, e: &EventItem) -> Result<(), (usize, std::io::Error)> {
+        let ev = event(e).to_string();
+        let len = ev.len();
+        if self.batch.push(ev, len) { self.post() } else { Ok(()) }
+    }
+
+    fn flush(&mut self) -> Result<(), (usize, std::io::Error)> { if self.batch.is_empty() { Ok(()) } else { self.post() } }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hec_events_carry_metadata() {
+        let t = chrono::DateTime::parse_from_rfc3339("2025-11-30T12:00:00.500Z").unwrap().with_timezone(&chrono::Utc);
+        let e = EventItem { time: t, level: 2, provider: "Disk".to_string(), channel: "System".to_string(), event_id: 7, computer: "ws01".to_string(), content: "Bad block".to_string(), record_id: Some(42), ..Default::default() };
+        let v = event(&e);
+        assert_eq!((v["time"].as_f64(), v["host"].as_str(), v["source"].as_str(), v["sourcetype"].as_str()), (Some(1764504000.5), Some("ws01"), Some("WinEventLog:System"), Some("windoctor:event")));
+        assert_eq!((v["event"]["EventCode"].as_u64(), v["event"]["RecordNumber"].as_u64(), v["event"]["Type"].as_str()), (Some(7), Some(42), Some("Error")));
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+static THRESHOLD: OnceLock<usize> = OnceLock::new();
+
+pub fn set_threshold(per_minute: usize) { let _ = THRESHOLD.set(per_minute.max(1)); }
+
+pub fn threshold() -> usize { THRESHOLD.get().copied().unwrap_or(60) }
+
+// One burst: consecutive minutes in which a provider/event ID pair logged more than the threshold.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Storm {
+    pub provider: String,
+    pub event_id: u32,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub total: usize,
+    pub peak_per_minute: usize,
+}
+
+pub fn detect(events: &[crate::EventItem], per_minute: usize) -> Vec<Storm> {
+    let mut buckets: HashMap<(&str, u32), HashMap<i64, usize>> = HashMap::new();
+    for e in events { *buckets.entry((e.provider.as_str(), e.event_id)).or_default().entry(e.time.timestamp().div_euclid(60)).or_insert(0) += 1; }
+    let mut out = Vec::new();
+    for ((provider, event_id), minutes) in buckets {
+        let mut hot: Vec<(i64, usize)> = minutes.into_iter().filter(|(_, c)| *c > per_minute).collect();
+        hot.sort();
+        let mut i = 0;
+        while i < hot.len() {
+            let mut j = i;
+            while j + 1 < hot.len() && hot[j + 1].0 == hot[j].0 + 1 { j += 1; }
+            let run = &hot[i..=j];
+            let Some(start) = DateTime::from_timestamp(run[0].0 * 60, 0) else { i = j + 1; continue };
+            out.push(Storm { provider: provider.to_string(), event_id, start, end: start + Duration::minutes(run.len() as i64), total: run.iter().map(|(_, c)| c).sum(), peak_per_minute: run.iter().map(|(_, c)| *c).max().unwrap_or(0) });
+            i = j + 1;
+        }
+    }
+    out.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.start.cmp(&b.start)));
+    out
+}
+
+pub fn hints(storms: &[Storm]) -> Vec<crate::hints::NoviceHint> {
+    if storms.is_empty() { return vec![]; }
+    let evidence = storms.iter().take(3).map(|s| format!("{} {}: {} events, peak {}/min", s.provider, s.event_id, s.total, s.peak_per_minute)).collect();
+    vec![crate::hints::NoviceHint { category: "Logging".to_string(), severity: "medium".to_string(), message: "Event storm — possible log flooding".to_string(), evidence, count: storms.len(), probability: 60 }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bursts_are_merged_per_provider_and_id() {
+        let t0 = DateTime::parse_from_rfc3339("2025-11-30T12:00:00Z").unwrap().with_timezone(&Utc);
+        let ev = |p: &str, id: u32, secs: i64| crate::EventItem { provider: p.to_string(), event_id: id, time: t0 + Duration::seconds(secs), ..Default::default() };
+        let mut events: Vec<crate::EventItem> = (0..180).map(|i| ev("Tcpip", 4227, i)).collect();
+        events.extend((0..20).map(|i| ev("Tcpip", 4227, 600 + i)));
+        events.extend((0..30).map(|i| ev("Disk", 7, i)));
+        let storms = detect(&events, 25);
+        assert_eq!(storms.len(), 2);
+        assert_eq!((storms[0].provider.as_str(), storms[0].total, storms[0].peak_per_minute), ("Tcpip", 180, 60));
+        assert_eq!(storms[0].end - storms[0].start, Duration::minutes(3));
+        assert_eq!(storms[1].provider, "Disk");
+        assert_eq!(hints(&storms)[0].count, 2);
+    }
+}
@@ -0,0 +1,88 @@
+#![cfg_attr(not(target_os = "windows"), allow(dead_code))]
+
+use serde::{Serialize, Deserialize};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SyncAccount {
+    pub name: String,
+    pub user_folder: Option<String>,
+    pub kfm: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+fn nonzero(v: &str) -> bool { !matches!(v.trim().to_lowercase().as_str(), "" | "0" | "0x0" | "none" | "false") }
+
+// Parses `reg query HKCU\Software\Microsoft\OneDrive\Accounts /s`: one block per account key, values as `name    TYPE    data`.
+pub fn parse_reg_accounts(text: &str) -> Vec<SyncAccount> {
+    let mut out: Vec<SyncAccount> = Vec::new();
+    let mut in_account = false;
+    for line in text.lines() {
+        if line.starts_with("HKEY_") {
+            let (parent, name) = line.trim().rsplit_once('\\').unwrap_or(("", ""));
+            in_account = parent.to_lowercase().ends_with("\\onedrive\\accounts");
+            if in_account { out.push(SyncAccount { name: name.to_string(), ..Default::default() }); }
+            continue;
+        }
+        let Some(acct) = out.last_mut().filter(|_| in_account) else { continue };
+        let parts: Vec<&str> = line.trim().splitn(3, "    ").map(str::trim).collect();
+        let [name, _, data] = parts[..] else { continue };
+        let lower = name.to_lowercase();
+        if lower == "userfolder" { acct.user_folder = Some(data.to_string()); }
+        else if lower.starts_with("kfm") { acct.kfm.push(format!("{}={}", name, data)); }
+        if lower.contains("error") && nonzero(data) { acct.errors.push(format!("{}={}", name, data)); }
+    }
+    out
+}
+
+// SyncDiagnostics.log is a `key = value` (or `key: value`) dump; keep the error counters/codes that are set.
+pub fn parse_sync_diagnostics(text: &str) -> Vec<String> {
+    text.lines().filter_map(|l| {
+        let (k, v) = l.split_once(" = ").or_else(|| l.split_once(": "))?;
+        (k.to_lowercase().contains("error") && nonzero(v)).then(|| format!("{}={}", k.trim(), v.trim()))
+    }).collect()
+}
+
+#[cfg(target_os = "windows")]
+pub fn collect_onedrive() -> Vec<SyncAccount> {
+    let Ok(out) = std::process::Command::new("reg.exe").args(["query", "HKCU\\Software\\Microsoft\\OneDrive\\Accounts", "/s"]).output() else { return vec![] };
+    let mut accounts = parse_reg_accounts(&String::from_utf8_lossy(&out.stdout));
+    if let Ok(local) = std::env::var("LOCALAPPDATA") {
+        for a in accounts.iter_mut() {
+            let p = std::path::Path::new(&local).join("Microsoft").join("OneDrive").join("logs").join(&a.name).join("SyncDiagnostics.log");
+            if let Ok(text) = std::fs::read_to_string(&p) { a.errors.extend(parse_sync_diagnostics(&text)); }
+        }
+    }
+    accounts.retain(|a| a.user_folder.is_some() || !a.errors.is_empty());
+    accounts
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn collect_onedrive() -> Vec<SyncAccount> { Vec::new() }
+
+pub fn sync_hints(accounts: &[SyncAccount]) -> Vec<crate::hints::NoviceHint> {
+    accounts.iter().filter(|a| !a.errors.is_empty()).map(|a| crate::hints::NoviceHint {
+        category: "Sync".to_string(),
+        severity: "medium".to_string(),
+        message: format!("OneDrive account '{}' reports sync or Known Folder Move errors", a.name),
+        evidence: a.errors.iter().take(3).cloned().collect(),
+        count: a.errors.len(),
+        probability: 65,
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_accounts_and_diagnostics() {
+        let reg = "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\OneDrive\\Accounts\r\n    LastUpdate    REG_DWORD    0x1\r\n\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\OneDrive\\Accounts\\Business1\r\n    UserFolder    REG_SZ    C:\\Users\\me\\OneDrive - Contoso\r\n    KfmFoldersProtectedNow    REG_DWORD    0x1c\r\n    KfmLastError    REG_DWORD    0x80070005\r\n    LastSyncError    REG_DWORD    0x0\r\n\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\OneDrive\\Accounts\\Business1\\Tenants\r\n    Contoso    REG_SZ    x\r\n";
+        let accts = parse_reg_accounts(reg);
+        assert_eq!(accts.len(), 1);
+        assert_eq!(accts[0].user_folder.as_deref(), Some("C:\\Users\\me\\OneDrive - Contoso"));
+        assert_eq!(accts[0].kfm.len(), 2);
+        assert_eq!(accts[0].errors, vec!["KfmLastError=0x80070005".to_string()]);
+        assert_eq!(parse_sync_diagnostics("SyncProgressState = 0\nUploadErrorCount = 4\nDownloadErrorCount = 0\n"), vec!["UploadErrorCount=4".to_string()]);
+        assert_eq!(sync_hints(&accts)[0].category, "Sync");
+    }
+}
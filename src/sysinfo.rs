@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiskInfo {
+    pub name: String,
+    pub file_system: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub free_bytes: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SystemInfo {
+    pub host: Option<String>,
+    pub os: Option<String>,
+    pub version: Option<String>,
+    pub build: Option<String>,
+    pub last_boot: Option<DateTime<Utc>>,
+    pub uptime_secs: Option<u64>,
+    pub model: Option<String>,
+    pub cpu: Option<String>,
+    pub cores: Option<u32>,
+    pub logical_processors: Option<u32>,
+    pub ram_bytes: Option<u64>,
+    pub disks: Vec<DiskInfo>,
+    pub drive_models: Vec<String>,
+}
+
+// WMI hands uint64 properties over as strings.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn num(s: Option<String>) -> Option<u64> { s.and_then(|v| v.trim().parse().ok()) }
+
+#[cfg(target_os = "windows")]
+pub fn collect_system_info() -> Option<SystemInfo> {
+    use wmi::WMIConnection;
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct OsRow { Caption: Option<String>, Version: Option<String>, BuildNumber: Option<String>, LastBootUpTime: Option<String>, CSName: Option<String> }
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct CsRow { Manufacturer: Option<String>, Model: Option<String>, TotalPhysicalMemory: Option<String> }
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct CpuRow { Name: Option<String>, NumberOfCores: Option<u32>, NumberOfLogicalProcessors: Option<u32> }
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct DiskRow { DeviceID: String, FileSystem: Option<String>, Size: Option<String>, FreeSpace: Option<String> }
+    #[allow(non_snake_case)]
+    #[derive(Debug, Deserialize)]
+    struct DriveRow { Model: Option<String>, Size: Option<String> }
+    let wmi = WMIConnection::new().ok()?;
+    let mut out = SystemInfo::default();
+    if let Ok(rows) = wmi.raw_query::<OsRow>("SELECT Caption, Version, BuildNumber, LastBootUpTime, CSName FROM Win32_OperatingSystem")
+        && let Some(r) = rows.into_iter().next() {
+        out.host = r.CSName;
+        out.os = r.Caption.map(|c| c.trim().to_string());
+        out.version = r.Version;
+        out.build = r.BuildNumber;
+        out.last_boot = r.LastBootUpTime.as_deref().and_then(crate::policy::parse_cim_datetime);
+        out.uptime_secs = out.last_boot.map(|b| (Utc::now() - b).num_seconds().max(0) as u64);
+    }
+    if let Ok(rows) = wmi.raw_query::<CsRow>("SELECT Manufacturer, Model, TotalPhysicalMemory FROM Win32_ComputerSystem")
+        && let Some(r) = rows.into_iter().next() {
+        out.model = match (r.Manufacturer, r.Model) { (Some(m), Some(n)) => Some(format!("{} {}", m.trim(), n.trim())), (m, n) => m.or(n) };
+        out.ram_bytes = num(r.TotalPhysicalMemory);
+    }
+    if let Ok(rows) = wmi.raw_query::<CpuRow>("SELECT Name, NumberOfCores, NumberOfLogicalProcessors FROM Win32_Processor") {
+        out.cpu = rows.first().and_then(|r| r.Name.as_ref()).map(|n| n.trim().to_string());
+        out.cores = rows.iter().filter_map(|r| r.NumberOfCores).reduce(|a, b| a + b);
+        out.logical_processors = rows.iter().filter_map(|r| r.NumberOfLogicalProcessors).reduce(|a, b| a + b);
+    }
+    // Fixed local disks only; network and removable drives change from run to run.
+    if let Ok(rows) = wmi.raw_query::<DiskRow>("SELECT DeviceID, FileSystem, Size, FreeSpace FROM Win32_LogicalDisk WHERE DriveType=3") {
+        out.disks = rows.into_iter().map(|r| DiskInfo { name: r.DeviceID, file_system: r.FileSystem, size_bytes: num(r.Size), free_bytes: num(r.FreeSpace) }).collect();
+    }
+    if let Ok(rows) = wmi.raw_query::<DriveRow>("SELECT Model, Size FROM Win32_DiskDrive") {
+        out.drive_models = rows.into_iter().filter_map(|r| { let size = num(r.Size); r.Model.map(|m| match size { Some(b) => format!("{} ({})", m.trim(), gib(b)), None => m.trim().to_string() }) }).collect();
+    }
+    Some(out)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn collect_system_info() -> Option<SystemInfo> { None }
+
+pub fn gib(bytes: u64) -> String { format!("{:.1} GiB", bytes as f64 / 1073741824.0) }
+
+pub fn format_uptime(secs: u64) -> String {
+    let (d, h, m) = (secs / 86400, secs % 86400 / 3600, secs % 3600 / 60);
+    if d > 0 { format!("{}d {}h {}m", d, h, m) } else if h > 0 { format!("{}h {}m", h, m) } else { format!("{}m", m) }
+}
+
+// `label: value` pairs shared by the text, HTML and Markdown renderers.
+pub fn lines(si: &SystemInfo) -> Vec<(&'static str, String)> {
+    let mut out = Vec::new();
+    if let Some(h) = &si.host { out.push(("Computer", h.clone())); }
+    if let Some(os) = &si.os { out.push(("OS", format!("{}{}", os, si.build.as_ref().map(|b| format!(" (build {})", b)).unwrap_or_default()))); }
+    if let Some(u) = si.uptime_secs { out.push(("Uptime", format_uptime(u))); }
+    if let Some(m) = &si.model { out.push(("Model", m.clone())); }
+    if let Some(c) = &si.cpu { out.push(("CPU", format!("{}{}", c, match (si.cores, si.logical_processors) { (Some(c), Some(l)) => format!(" ({} cores, {} threads)", c, l), _ => String::new() }))); }
+    if let Some(r) = si.ram_bytes { out.push(("RAM", gib(r))); }
+    for d in &si.disks {
+        let space = match (d.free_bytes, d.size_bytes) { (Some(f), Some(s)) => format!("{} free of {}", gib(f), gib(s)), (_, Some(s)) => gib(s), _ => String::new() };
+        out.push(("Disk", format!("{} {}{}", d.name, d.file_system.as_deref().unwrap_or(""), if space.is_empty() { String::new() } else { format!(" — {}", space) }).replace("  ", " ")));
+    }
+    for m in &si.drive_models { out.push(("Drive", m.clone())); }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn system_info_lines() {
+        assert_eq!(format_uptime(3 * 86400 + 4 * 3600 + 12 * 60 + 5), "3d 4h 12m");
+        assert_eq!(format_uptime(59), "0m");
+        assert_eq!(num(Some("17179869184".to_string())), Some(17179869184));
+        let si = SystemInfo { os: Some("Microsoft Windows 11 Pro".to_string()), build: Some("26100".to_string()), ram_bytes: Some(17179869184), disks: vec![DiskInfo { name: "C:".to_string(), file_system: Some("NTFS".to_string()), size_bytes: Some(512 * 1073741824), free_bytes: Some(64 * 1073741824) }], ..Default::default() };
+        let l = lines(&si);
+        assert_eq!(l[0], ("OS", "Microsoft Windows 11 Pro (build 26100)".to_string()));
+        assert_eq!(l[1], ("RAM", "16.0 GiB".to_string()));
+        assert_eq!(l[2], ("Disk", "C: NTFS — 64.0 GiB free of 512.0 GiB".to_string()));
+    }
+}
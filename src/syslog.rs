@@ -0,0 +1,55 @@
+use crate::EventItem;
+use crate::forward::Transport;
+
+// RFC 5424 private enterprise number reserved for documentation; identifies our structured data element.
+const SD_ID: &str = "windoctor@32473";
+// Facility 1 (user-level messages).
+const FACILITY: u8 = 1;
+
+fn severity(level: u8) -> u8 {
+    match level { 1 => 2, 2 => 3, 3 => 4, 4 => 6, _ => 7 }
+}
+
+// Header fields are printable ASCII without spaces; "-" is the nil value.
+fn header_field(s: &str, max: usize) -> String {
+    let v: String = s.chars().filter(|c| c.is_ascii_graphic()).take(max).collect();
+    if v.is_empty() { "-".to_string() } else { v }
+}
+
+fn sd_value(s: &str) -> String { s.replace('\\', "\\\\").replace('"', "\\\"").replace(']', "\\]") }
+
+// `<PRI>1 TIMESTAMP HOSTNAME APP-NAME PROCID MSGID [SD] MSG`
+pub fn message(e: &EventItem) -> String {
+    let sd = format!("[{} provider=\"{}\" eventId=\"{}\" channel=\"{}\"{}]", SD_ID, sd_value(&e.provider), e.event_id, sd_value(&e.channel), e.record_id.map(|r| format!(" recordId=\"{}\"", r)).unwrap_or_default());
+    format!("<{}>1 {} {} WinDoctor {} {} {} {}", FACILITY * 8 + severity(e.level), e.time.to_rfc3339_opts(chrono::SecondsFormat::Millis, true), header_field(&e.computer, 255), std::process::id(), e.event_id, sd, crate::event_message_redacted(e))
+}
+
+pub struct Syslog { transport: Transport }
+
+impl Syslog {
+    pub fn connect(target: &str) -> Result<Syslog, String> { Transport::connect(target, false).map(|transport| Syslog { transport }) }
+}
+
+impl crate::forward::Sink for Syslog {
+    // TCP uses octet-counting framing (RFC 6587), so messages may contain line breaks.
+    fn send(&mut self, e: &EventItem) -> Result<(), (usize, std::io::Error)> {
+        let msg = message(e);
+        let sent = if self.transport.is_tcp() { self.transport.send(format!("{} {}", msg.len(), msg).as_bytes()) } else { self.transport.send(msg.as_bytes()) };
+        sent.map_err(|err| (1, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc5424_message_carries_structured_data() {
+        let t = chrono::DateTime::parse_from_rfc3339("2025-11-30T12:00:00Z").unwrap().with_timezone(&chrono::Utc);
+        let e = EventItem { time: t, level: 2, provider: "Disk \"x\"".to_string(), channel: "System".to_string(), event_id: 7, computer: "ws01".to_string(), content: "Bad block".to_string(), record_id: Some(42), ..Default::default() };
+        let m = message(&e);
+        assert!(m.starts_with("<11>1 2025-11-30T12:00:00.000Z ws01 WinDoctor "));
+        assert!(m.ends_with(" 7 [windoctor@32473 provider=\"Disk \\\"x\\\"\" eventId=\"7\" channel=\"System\" recordId=\"42\"] Bad block"));
+        assert_eq!(header_field("", 255), "-");
+    }
+}
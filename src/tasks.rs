@@ -0,0 +1,105 @@
+use crate::windows_live::TaskNames;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+static CACHE: OnceLock<Mutex<HashMap<String, Option<TaskNames>>>> = OnceLock::new();
+
+// Opcodes 0-10 and 240 are defined by winmeta.xml for every provider.
+fn standard_opcode(op: u8) -> Option<&'static str> {
+    match op {
+        0 => Some("Info"),
+        1 => Some("Start"),
+        2 => Some("Stop"),
+        3 => Some("DCStart"),
+        4 => Some("DCStop"),
+        5 => Some("Extension"),
+        6 => Some("Reply"),
+        7 => Some("Resume"),
+        8 => Some("Suspend"),
+        9 => Some("Send"),
+        240 => Some("Receive"),
+        _ => None,
+    }
+}
+
+// Publisher metadata is opened once per provider and run.
+fn with_names<T>(provider: &str, f: impl FnOnce(&TaskNames) -> Option<T>) -> Option<T> {
+    let cache = CACHE.get_or_init(Default::default);
+    let mut c = cache.lock().ok()?;
+    let names = c.entry(provider.to_string()).or_insert_with(|| lookup(provider));
+    names.as_ref().and_then(f)
+}
+
+#[cfg(target_os = "windows")]
+fn lookup(provider: &str) -> Option<TaskNames> { crate::windows_live::task_names(provider) }
+
+#[cfg(not(target_os = "windows"))]
+fn lookup(_provider: &str) -> Option<TaskNames> { None }
+
+pub fn task_name(provider: &str, task: u16) -> Option<String> {
+    if task == 0 { return None; }
+    with_names(provider, |n| n.tasks.iter().find(|(t, _)| *t == task).map(|(_, s)| s.clone()))
+}
+
+pub fn opcode_name(provider: &str, task: u16, opcode: u8) -> Option<String> {
+    let declared = with_names(provider, |n| {
+        n.opcodes.iter().find(|(t, o, _)| *t == task && *o == opcode).or_else(|| n.opcodes.iter().find(|(t, o, _)| *t == 0 && *o == opcode)).map(|(_, _, s)| s.clone())
+    });
+    declared.or_else(|| standard_opcode(opcode).map(str::to_string))
+}
+
+// Names of the provider-defined keywords set in `mask`; the standard high bits are named by keywords::display.
+pub fn keyword_names(provider: &str, mask: u64) -> Vec<(u64, String)> {
+    if mask & crate::keywords::PROVIDER_BITS == 0 { return vec![]; }
+    with_names(provider, |n| Some(n.keywords.iter().filter(|(v, _)| *v != 0 && v & !crate::keywords::PROVIDER_BITS == 0 && mask & v == *v).cloned().collect())).unwrap_or_default()
+}
+
+// Levels 16-255 are provider-defined; 0-15 are reserved for the standard levels.
+pub fn level_name(provider: &str, level: u8) -> Option<String> {
+    if level < 16 { return None; }
+    with_names(provider, |n| n.levels.iter().find(|(l, _)| *l == level).map(|(_, s)| s.clone()))
+}
+
+// Name when the publisher declares one, the number otherwise; empty for task 0 (none).
+pub fn task_label(provider: &str, task: u16) -> String {
+    if task == 0 { return String::new(); }
+    task_name(provider, task).unwrap_or_else(|| task.to_string())
+}
+
+pub fn opcode_label(provider: &str, task: u16, opcode: u8) -> String {
+    opcode_name(provider, task, opcode).unwrap_or_else(|| opcode.to_string())
+}
+
+// `--tasks 12810,"Logon"` matches either the task number or its name.
+pub fn task_matches(filters: &[String], provider: &str, task: u16) -> bool {
+    if filters.is_empty() { return true; }
+    if task == 0 { return false; }
+    if filters.iter().any(|f| f.trim().parse::<u16>().ok() == Some(task)) { return true; }
+    task_name(provider, task).is_some_and(|n| filters.iter().any(|f| f.trim().eq_ignore_ascii_case(&n)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tasks_match_by_number_and_opcodes_fall_back() {
+        // Seeded so the test never opens live publisher metadata.
+        let names = TaskNames { tasks: vec![(12544, "Logon".to_string())], opcodes: vec![(12544, 30, "Retry".to_string())], ..Default::default() };
+        let mut c = CACHE.get_or_init(Default::default).lock().unwrap();
+        c.insert("Test-Tasks-Seeded".to_string(), Some(names));
+        c.insert("Test-Tasks-Unknown".to_string(), None);
+        drop(c);
+        let f = vec!["logon".to_string(), " 7 ".to_string()];
+        assert!(task_matches(&f, "Test-Tasks-Seeded", 12544));
+        assert!(task_matches(&f, "Test-Tasks-Unknown", 7));
+        assert!(!task_matches(&f, "Test-Tasks-Unknown", 8));
+        assert!(!task_matches(&f, "Test-Tasks-Unknown", 0));
+        assert!(task_matches(&[], "x", 0));
+        assert_eq!(task_label("Test-Tasks-Seeded", 12544), "Logon");
+        assert_eq!(opcode_label("Test-Tasks-Seeded", 12544, 30), "Retry");
+        assert_eq!(opcode_label("Test-Tasks-Unknown", 0, 1), "Start");
+        assert_eq!(opcode_label("Test-Tasks-Unknown", 0, 77), "77");
+        assert_eq!(task_label("Test-Tasks-Unknown", 0), "");
+    }
+}
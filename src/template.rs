@@ -0,0 +1,133 @@
+use serde_json::Value;
+
+// A Handlebars subset: `{{path}}` (HTML-escaped), `{{{path}}}` (raw), `{{! comment}}`,
+// `{{#each}}`/`{{#if}}`/`{{#unless}}`/`{{#with}}` blocks with `{{else}}`, and `this`, `../`, `@root`, `@index`, `@first`, `@last` paths.
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Var { path: String, raw: bool },
+    Block { helper: String, path: String, body: Vec<Node>, alt: Vec<Node> },
+}
+
+// A block whose closing tag has not been seen yet; the root is a nameless block.
+#[derive(Default)]
+struct Open { helper: String, path: String, body: Vec<Node>, alt: Vec<Node>, in_else: bool }
+
+impl Open {
+    fn push(&mut self, n: Node) { if self.in_else { self.alt.push(n) } else { self.body.push(n) } }
+}
+
+struct Frame<'a> { value: &'a Value, index: Option<(usize, usize)> }
+
+fn parse(src: &str) -> Result<Vec<Node>, String> {
+    let mut stack = vec![Open::default()];
+    let push = |stack: &mut Vec<Open>, n: Node| stack.last_mut().expect("root block").push(n);
+    let mut rest = src;
+    while let Some(start) = rest.find("{{") {
+        if start > 0 { push(&mut stack, Node::Text(rest[..start].to_string())); }
+        let raw = rest[start..].starts_with("{{{");
+        let (open, close) = if raw { (3, "}}}") } else { (2, "}}") };
+        let inner_start = start + open;
+        let end = rest[inner_start..].find(close).ok_or_else(|| format!("unclosed tag at byte {}", src.len() - rest.len() + start))? + inner_start;
+        let tag = rest[inner_start..end].trim();
+        rest = &rest[end + close.len()..];
+        if raw { push(&mut stack, Node::Var { path: tag.to_string(), raw: true }); continue; }
+        if tag.starts_with('!') { continue; }
+        if let Some(open) = tag.strip_prefix('#') {
+            let (helper, path) = open.split_once(char::is_whitespace).map(|(h, p)| (h, p.trim())).unwrap_or((open, "this"));
+            if !matches!(helper, "each" | "if" | "unless" | "with") { return Err(format!("unknown block helper '{}'", helper)); }
+            stack.push(Open { helper: helper.to_string(), path: path.to_string(), ..Default::default() });
+        } else if let Some(close) = tag.strip_prefix('/') {
+            if stack.len() == 1 || stack.last().is_some_and(|b| b.helper != close.trim()) { return Err(format!("unexpected {{{{/{}}}}}", close.trim())); }
+            let Open { helper, path, body, alt, .. } = stack.pop().expect("checked above");
+            push(&mut stack, Node::Block { helper, path, body, alt });
+        } else if tag == "else" {
+            if stack.len() == 1 { return Err("{{else}} outside a block".to_string()); }
+            if let Some(top) = stack.last_mut() { top.in_else = true; }
+        } else {
+            push(&mut stack, Node::Var { path: tag.to_string(), raw: false });
+        }
+    }
+    if !rest.is_empty() { push(&mut stack, Node::Text(rest.to_string())); }
+    if stack.len() > 1 { return Err(format!("unclosed {{{{#{}}}}}", stack[stack.len() - 1].helper)); }
+    Ok(stack.pop().map(|b| b.body).unwrap_or_default())
+}
+
+// Loop variables of the innermost `each`.
+fn loop_var(frames: &[Frame], path: &str) -> Option<Value> {
+    let (i, n) = frames.last()?.index?;
+    match path { "@index" => Some(Value::from(i)), "@first" => Some(Value::Bool(i == 0)), "@last" => Some(Value::Bool(i + 1 == n)), _ => None }
+}
+
+fn lookup<'a>(frames: &[Frame<'a>], path: &str) -> Option<&'a Value> {
+    let (mut depth, mut p) = (frames.len().checked_sub(1)?, path);
+    if let Some(r) = p.strip_prefix("@root") { depth = 0; p = r.trim_start_matches('.'); }
+    while let Some(r) = p.strip_prefix("../") { depth = depth.checked_sub(1)?; p = r; }
+    let p = p.strip_prefix("this").map(|r| r.trim_start_matches('.')).unwrap_or(p);
+    let mut v = frames[depth].value;
+    for seg in p.split('.').filter(|s| !s.is_empty()) {
+        v = match v { Value::Array(a) => a.get(seg.parse::<usize>().ok()?)?, _ => v.get(seg)? };
+    }
+    Some(v)
+}
+
+fn truthy(v: Option<&Value>) -> bool {
+    match v { None | Some(Value::Null) | Some(Value::Bool(false)) => false, Some(Value::Number(n)) => n.as_f64() != Some(0.0), Some(Value::String(s)) => !s.is_empty(), Some(Value::Array(a)) => !a.is_empty(), _ => true }
+}
+
+fn text(v: &Value) -> String { match v { Value::String(s) => s.clone(), Value::Null => String::new(), other => other.to_string() } }
+
+fn escape(s: &str) -> String { s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&#x27;") }
+
+fn render_nodes<'a>(nodes: &[Node], frames: &mut Vec<Frame<'a>>, out: &mut String) {
+    for n in nodes {
+        match n {
+            Node::Text(t) => out.push_str(t),
+            Node::Var { path, raw } => {
+                let s = loop_var(frames, path).or_else(|| lookup(frames, path).cloned()).map(|v| text(&v)).unwrap_or_default();
+                out.push_str(&if *raw { s } else { escape(&s) });
+            }
+            Node::Block { helper, path, body, alt } => {
+                let special = loop_var(frames, path);
+                let v = lookup(frames, path);
+                let on = if special.is_some() { truthy(special.as_ref()) } else { truthy(v) };
+                match (helper.as_str(), v) {
+                    ("if", _) => render_nodes(if on { body } else { alt }, frames, out),
+                    ("unless", _) => render_nodes(if on { alt } else { body }, frames, out),
+                    (_, Some(v)) if on => {
+                        let items: Vec<&'a Value> = match (helper.as_str(), v) { ("each", Value::Array(a)) => a.iter().collect(), ("each", Value::Object(m)) => m.values().collect(), _ => vec![v] };
+                        let n = items.len();
+                        for (i, item) in items.into_iter().enumerate() {
+                            frames.push(Frame { value: item, index: (helper == "each").then_some((i, n)) });
+                            render_nodes(body, frames, out);
+                            frames.pop();
+                        }
+                    }
+                    _ => render_nodes(alt, frames, out),
+                }
+            }
+        }
+    }
+}
+
+pub fn render(template: &str, data: &Value) -> Result<String, String> {
+    let nodes = parse(template)?;
+    let mut out = String::new();
+    render_nodes(&nodes, &mut vec![Frame { value: data, index: None }], &mut out);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_paths_blocks_and_escaping() {
+        let data = serde_json::json!({ "risk_grade": "High", "total": 3, "novice_hints": [{ "message": "Disk <errors>" }, { "message": "DNS" }], "compare": null });
+        let t = "Risk: {{risk_grade}} ({{total}}){{! note }}\n{{#each novice_hints}}{{@index}}. {{message}} [{{{message}}}] {{../risk_grade}}{{#unless @last}}, {{/unless}}{{/each}}\n{{#if compare}}diff{{else}}no baseline{{/if}}";
+        assert_eq!(render(t, &data).unwrap(), "Risk: High (3)\n0. Disk &lt;errors&gt; [Disk <errors>] High, 1. DNS [DNS] High\nno baseline");
+        assert_eq!(render("{{#with novice_hints.1}}{{this.message}}/{{@root.total}}{{/with}}", &data).unwrap(), "DNS/3");
+        assert!(render("{{#each x}}", &data).is_err());
+        assert!(render("{{#bogus x}}{{/bogus}}", &data).is_err());
+    }
+}
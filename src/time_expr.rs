@@ -0,0 +1,178 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone as _, Utc, Weekday};
+use crate::TimeZone;
+use std::sync::OnceLock;
+
+static LAST_BOOT: OnceLock<Option<DateTime<Utc>>> = OnceLock::new();
+
+// Boot time taken from the EVTX files being scanned; without it the live System log is asked once.
+pub fn set_last_boot(t: Option<DateTime<Utc>>) { let _ = LAST_BOOT.set(t); }
+
+#[cfg(target_os = "windows")]
+fn last_boot() -> Option<DateTime<Utc>> { *LAST_BOOT.get_or_init(crate::windows_live::last_boot_time) }
+
+#[cfg(not(target_os = "windows"))]
+fn last_boot() -> Option<DateTime<Utc>> { LAST_BOOT.get().copied().flatten() }
+
+pub fn mentions_boot(s: &str) -> bool { matches!(s.trim().to_lowercase().split_whitespace().collect::<Vec<_>>().as_slice(), ["boot"] | ["last", "boot"]) }
+
+// EventLog 6005 (event log service started) or Kernel-General 12 (OS started) mark a boot.
+pub fn is_boot_event(provider: &str, event_id: u32) -> bool {
+    (event_id == 6005 && provider.eq_ignore_ascii_case("EventLog")) || (event_id == 12 && provider.ends_with("Kernel-General"))
+}
+
+// Accepts RFC3339, `YYYY-MM-DD[ HH:MM[:SS]]`, `now`, `today`/`yesterday [HH:MM]`,
+// `<N><unit> ago`, `last <weekday> [HH:MM]` and `last boot`. Naive values are read in `tz`.
+pub fn parse_time_expr(s: &str, now: DateTime<Utc>, tz: TimeZone) -> Result<DateTime<Utc>, String> { parse_time_expr_with(s, now, tz, last_boot) }
+
+// `boot` is only asked when the expression mentions the last boot.
+fn parse_time_expr_with(s: &str, now: DateTime<Utc>, tz: TimeZone, boot: impl FnOnce() -> Option<DateTime<Utc>>) -> Result<DateTime<Utc>, String> {
+    let raw = s.trim();
+    if raw.is_empty() { return Err("empty time expression".to_string()); }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(raw) { return Ok(dt.with_timezone(&Utc)); }
+    if raw.ends_with('Z') && let Some(dt) = crate::parse_system_time(raw) { return Ok(dt); }
+    if let Some(naive) = parse_naive_datetime(raw) { return to_utc(naive, tz); }
+    let lower = raw.to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+    match words.as_slice() {
+        ["now"] => return Ok(now),
+        ["boot"] | ["last", "boot"] => return boot().ok_or_else(|| "could not determine the last boot time (no EventLog 6005 or Kernel-General 12 event found)".to_string()),
+        ["today", rest @ ..] | ["yesterday", rest @ ..] => {
+            let days_back = if words[0] == "yesterday" { 1 } else { 0 };
+            let date = local_date(now, tz) - Duration::days(days_back);
+            return to_utc(date.and_time(parse_clock(rest)?), tz);
+        }
+        ["last", day, rest @ ..] => {
+            let wd = parse_weekday(day).ok_or_else(|| format!("unknown weekday '{}'", day))?;
+            let today = local_date(now, tz);
+            let mut back = (today.weekday().num_days_from_monday() as i64 - wd.num_days_from_monday() as i64).rem_euclid(7);
+            if back == 0 { back = 7; }
+            return to_utc((today - Duration::days(back)).and_time(parse_clock(rest)?), tz);
+        }
+        _ => {}
+    }
+    if let Some(d) = parse_ago(&words)? { return now.checked_sub_signed(d).ok_or_else(|| format!("'{}' is too far back", raw)); }
+    Err(format!("unrecognized time expression '{}' (try RFC3339, 'YYYY-MM-DD HH:MM', '2h ago', 'yesterday 14:00', 'last monday' or 'last boot')", raw))
+}
+
+fn parse_naive_datetime(s: &str) -> Option<NaiveDateTime> {
+    for f in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"] {
+        if let Ok(n) = NaiveDateTime::parse_from_str(s, f) { return Some(n); }
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").ok().map(|d| d.and_time(NaiveTime::MIN))
+}
+
+fn parse_clock(rest: &[&str]) -> Result<NaiveTime, String> {
+    match rest {
+        [] => Ok(NaiveTime::MIN),
+        [t] => NaiveTime::parse_from_str(t, "%H:%M").or_else(|_| NaiveTime::parse_from_str(t, "%H:%M:%S")).map_err(|_| format!("invalid time of day '{}'", t)),
+        _ => Err(format!("unexpected trailing input '{}'", rest.join(" "))),
+    }
+}
+
+fn parse_weekday(s: &str) -> Option<Weekday> {
+    let w = match s { "mon" | "monday" => Weekday::Mon, "tue" | "tues" | "tuesday" => Weekday::Tue, "wed" | "wednesday" => Weekday::Wed, "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu, "fri" | "friday" => Weekday::Fri, "sat" | "saturday" => Weekday::Sat, "sun" | "sunday" => Weekday::Sun, _ => return None };
+    Some(w)
+}
+
+fn parse_ago(words: &[&str]) -> Result<Option<Duration>, String> {
+    let (amount, unit) = match words {
+        [n, "ago"] => split_amount(n),
+        [n, u, "ago"] => (n.to_string(), u.to_string()),
+        _ => return Ok(None),
+    };
+    unit_duration(&amount, &unit).map(Some)
+}
+
+// Accepts `90s`, `5m`, `1h`, `2 days`; a bare number is seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let words: Vec<&str> = s.split_whitespace().collect();
+    let (amount, unit) = match words.as_slice() {
+        [n] => { let (a, u) = split_amount(n); (a, u.to_lowercase()) }
+        [n, u] => (n.to_string(), u.to_lowercase()),
+        _ => return Err(format!("invalid duration '{}'", s)),
+    };
+    let d = unit_duration(&amount, if unit.is_empty() { "s" } else { &unit })?;
+    if d <= Duration::zero() { return Err(format!("duration must be positive: '{}'", s)); }
+    Ok(d)
+}
+
+fn unit_duration(amount: &str, unit: &str) -> Result<Duration, String> {
+    let n: i64 = amount.parse().map_err(|_| format!("invalid amount '{}'", amount))?;
+    let d = match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => Duration::try_seconds(n),
+        "m" | "min" | "mins" | "minute" | "minutes" => Duration::try_minutes(n),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Duration::try_hours(n),
+        "d" | "day" | "days" => Duration::try_days(n),
+        "w" | "wk" | "week" | "weeks" => Duration::try_weeks(n),
+        _ => return Err(format!("unknown time unit '{}'", unit)),
+    };
+    d.ok_or_else(|| format!("duration '{}{}' is out of range", amount, unit))
+}
+
+fn split_amount(s: &str) -> (String, String) {
+    let idx = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    (s[..idx].to_string(), s[idx..].to_string())
+}
+
+fn local_date(now: DateTime<Utc>, tz: TimeZone) -> NaiveDate {
+    match tz { TimeZone::Local => now.with_timezone(&Local).date_naive(), TimeZone::Utc => now.date_naive() }
+}
+
+fn to_utc(naive: NaiveDateTime, tz: TimeZone) -> Result<DateTime<Utc>, String> {
+    match tz {
+        TimeZone::Utc => Ok(Utc.from_utc_datetime(&naive)),
+        TimeZone::Local => Local.from_local_datetime(&naive).earliest().map(|d| d.with_timezone(&Utc)).ok_or_else(|| format!("local time {} does not exist", naive)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> { Utc.with_ymd_and_hms(2025, 11, 30, 12, 0, 0).unwrap() } // a Sunday
+
+    #[test]
+    fn last_boot_uses_recorded_boot_time() {
+        let boot = Utc.with_ymd_and_hms(2025, 11, 30, 7, 15, 0).unwrap();
+        assert_eq!(parse_time_expr_with("last boot", now(), TimeZone::Utc, || Some(boot)).unwrap(), boot);
+        assert!(parse_time_expr_with("boot", now(), TimeZone::Utc, || None).is_err());
+        assert!(mentions_boot(" Last  Boot "));
+        assert!(is_boot_event("Microsoft-Windows-Kernel-General", 12));
+        assert!(!is_boot_event("EventLog", 6006));
+    }
+
+    #[test]
+    fn relative_ago_forms() {
+        assert_eq!(parse_time_expr("2h ago", now(), TimeZone::Utc).unwrap(), now() - Duration::hours(2));
+        assert_eq!(parse_time_expr("90 minutes ago", now(), TimeZone::Utc).unwrap(), now() - Duration::minutes(90));
+        assert_eq!(parse_time_expr("now", now(), TimeZone::Utc).unwrap(), now());
+    }
+
+    #[test]
+    fn calendar_forms() {
+        assert_eq!(parse_time_expr("yesterday 14:00", now(), TimeZone::Utc).unwrap(), Utc.with_ymd_and_hms(2025, 11, 29, 14, 0, 0).unwrap());
+        assert_eq!(parse_time_expr("last monday", now(), TimeZone::Utc).unwrap(), Utc.with_ymd_and_hms(2025, 11, 24, 0, 0, 0).unwrap());
+        assert_eq!(parse_time_expr("last sunday", now(), TimeZone::Utc).unwrap(), Utc.with_ymd_and_hms(2025, 11, 23, 0, 0, 0).unwrap());
+        assert_eq!(parse_time_expr("2025-11-29", now(), TimeZone::Utc).unwrap(), Utc.with_ymd_and_hms(2025, 11, 29, 0, 0, 0).unwrap());
+        assert_eq!(parse_time_expr("2025-11-29 13:00", now(), TimeZone::Utc).unwrap(), Utc.with_ymd_and_hms(2025, 11, 29, 13, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("60s").unwrap(), Duration::seconds(60));
+        assert_eq!(parse_duration("90").unwrap(), Duration::seconds(90));
+        assert_eq!(parse_duration("2 min").unwrap(), Duration::minutes(2));
+        assert!(parse_duration("0s").is_err());
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("99999999999999w").is_err());
+        assert!(parse_time_expr("99999999999999w ago", now(), TimeZone::Utc).is_err());
+        assert!(parse_time_expr("9999999999 days ago", now(), TimeZone::Utc).is_err());
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(parse_time_expr("whenever", now(), TimeZone::Utc).is_err());
+        assert!(parse_time_expr("3 fortnights ago", now(), TimeZone::Utc).is_err());
+        assert!(parse_time_expr("yesterday 25:00", now(), TimeZone::Utc).is_err());
+    }
+}
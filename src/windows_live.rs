@@ -10,58 +10,222 @@ impl Drop for Handle { fn drop(&mut self) { unsafe { EvtClose(self.0); } } }
 
 fn w(s: &str) -> Vec<u16> { let mut v = s.encode_utf16().collect::<Vec<u16>>(); v.push(0); v }
 
-pub fn query_live_events(channels: &[String], since: DateTime<Utc>) -> Vec<EventItem> {
+static SESSION: std::sync::OnceLock<EVT_HANDLE> = std::sync::OnceLock::new();
+
+// 0 is the local machine.
+fn session() -> EVT_HANDLE { SESSION.get().copied().unwrap_or(0) }
+
+// `--computer`: every later Evt* call goes to this host. Without a user the caller's own logon is used;
+// `DOMAIN\user` is split, `user@domain` is passed through as is.
+pub fn open_remote_session(host: &str, user: Option<&str>, password: Option<&str>) -> Result<(), u32> {
+    let (domain, user) = match user.and_then(|u| u.split_once('\\')) { Some((d, u)) => (Some(d), Some(u)), None => (None, user) };
+    let mut server = w(host);
+    let mut user_w = user.map(w);
+    let mut domain_w = domain.map(w);
+    let mut password_w = password.map(w);
+    let ptr_of = |v: &mut Option<Vec<u16>>| v.as_mut().map(|b| b.as_mut_ptr()).unwrap_or(ptr::null_mut());
+    let login = EVT_RPC_LOGIN { Server: server.as_mut_ptr(), User: ptr_of(&mut user_w), Domain: ptr_of(&mut domain_w), Password: ptr_of(&mut password_w), Flags: EvtRpcLoginAuthNegotiate };
+    let h = unsafe { EvtOpenSession(EvtRpcLogin, &login as *const EVT_RPC_LOGIN as *const core::ffi::c_void, 0, 0) };
+    if let Some(p) = password_w.as_mut() { p.fill(0); }
+    if h == 0 { return Err(unsafe { GetLastError() }); }
+    let _ = SESSION.set(h);
+    Ok(())
+}
+
+// `--query-batch-size` / `--query-timeout-ms`: handles per EvtNext call and how long one call may wait.
+static QUERY_BATCH: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(64);
+static QUERY_TIMEOUT_MS: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(100);
+// Last query per channel: (channel, milliseconds, events read).
+static QUERY_TIMINGS: std::sync::Mutex<Vec<(String, u32, usize)>> = std::sync::Mutex::new(Vec::new());
+
+// Failed Evt* calls per channel, operation and Win32 error; `records` counts events lost to it (0 when unknown).
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct CollectionError {
+    pub channel: String,
+    pub operation: String,
+    pub code: u32,
+    pub records: usize,
+}
+
+static COLLECTION_ERRORS: std::sync::Mutex<Vec<CollectionError>> = std::sync::Mutex::new(Vec::new());
+
+fn record_error(ch: &str, operation: &str, code: u32, records: usize) {
+    if let Ok(mut errs) = COLLECTION_ERRORS.lock() {
+        match errs.iter_mut().find(|e| e.channel == ch && e.operation == operation && e.code == code) {
+            Some(e) => e.records += records,
+            None => errs.push(CollectionError { channel: ch.to_string(), operation: operation.to_string(), code, records }),
+        }
+    }
+}
+
+pub fn collection_errors() -> Vec<CollectionError> { COLLECTION_ERRORS.lock().map(|e| e.clone()).unwrap_or_default() }
+
+pub fn set_query_tuning(batch: usize, timeout_ms: u32) {
+    QUERY_BATCH.store(batch.clamp(1, 1024), std::sync::atomic::Ordering::Relaxed);
+    QUERY_TIMEOUT_MS.store(timeout_ms, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn query_timings() -> Vec<(String, u32, usize)> { QUERY_TIMINGS.lock().map(|t| t.clone()).unwrap_or_default() }
+
+fn record_timing(ch: &str, ms: u32, count: usize) {
+    if let Ok(mut t) = QUERY_TIMINGS.lock() {
+        match t.iter_mut().find(|(c, _, _)| c == ch) { Some(e) => *e = (ch.to_string(), ms, count), None => t.push((ch.to_string(), ms, count)) }
+    }
+}
+
+// Reads a result set to the end; 259 (ERROR_NO_MORE_ITEMS) is the normal finish, 1460 means one batch took longer than the timeout.
+fn drain(h: &Handle, ch: &str, out: &mut Vec<EventItem>) {
+    let mut arr: Vec<EVT_HANDLE> = vec![0; QUERY_BATCH.load(std::sync::atomic::Ordering::Relaxed)];
+    let timeout = QUERY_TIMEOUT_MS.load(std::sync::atomic::Ordering::Relaxed);
+    loop {
+        if crate::budget_exhausted() { break; }
+        let mut returned: u32 = 0;
+        if unsafe { EvtNext(h.0, arr.len() as u32, arr.as_mut_ptr(), timeout, 0, &mut returned) } == 0 {
+            match unsafe { GetLastError() } {
+                0 | 259 => {}
+                1460 => { log::error!("EvtNext timed out after {} ms on {}; raise --query-timeout-ms", timeout, ch); record_error(ch, "EvtNext", 1460, 0); }
+                code => { log::error!("EvtNext error: {}", code); record_error(ch, "EvtNext", code, 0); }
+            }
+            break;
+        }
+        if returned == 0 { break; }
+        for &ev in arr.iter().take(returned as usize) {
+            unsafe {
+                if let Some(item) = live_item(ev, ch) { out.push(item); }
+                EvtClose(ev);
+            }
+        }
+    }
+}
+
+fn query_channel(ch: &str, since: DateTime<Utc>, after: &str) -> Vec<EventItem> {
     let mut out = Vec::new();
-    for ch in channels {
-        unsafe {
-            let ts = since.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
-            let q = format!("<QueryList><Query Id=\"0\"><Select Path=\"{}\">*[System[TimeCreated[@SystemTime &gt;= '{}']]]</Select></Query></QueryList>", ch, ts);
-            let h = EvtQuery(0, std::ptr::null(), w(&q).as_ptr(), 0);
-            if h == 0 {
+    let started = std::time::Instant::now();
+    unsafe {
+        let ts = since.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+        let q = format!("<QueryList><Query Id=\"0\"><Select Path=\"{}\">*[System[TimeCreated[@SystemTime &gt;= '{}']{}]]</Select></Query></QueryList>", ch, ts, after);
+        let mut h = EvtQuery(session(), std::ptr::null(), w(&q).as_ptr(), 0);
+        if h == 0 {
+            let code = GetLastError();
+            h = EvtQuery(session(), w(ch).as_ptr(), std::ptr::null(), EvtQueryChannelPath);
+            if h == 0 { log::error!("EvtQuery failed for {}: {}", ch, code); record_error(ch, "EvtQuery", code, 0); return out; }
+        }
+        drain(&Handle(h), ch, &mut out);
+    }
+    record_timing(ch, started.elapsed().as_millis().min(u32::MAX as u128) as u32, out.len());
+    out
+}
+
+// Upper bound on channels read at once; each worker holds one query handle and one EvtNext batch.
+const MAX_QUERY_WORKERS: usize = 8;
+
+// Channels are read concurrently and merged back in the order given, so output does not depend on scheduling.
+pub fn query_live_events(channels: &[String], since: DateTime<Utc>, after_record: Option<u64>) -> Vec<EventItem> {
+    // Record IDs are per channel, so the service skips already-seen records before rendering them.
+    let after = after_record.map(|n| format!(" and EventRecordID &gt; {}", n)).unwrap_or_default();
+    let workers = channels.len().min(std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)).clamp(1, MAX_QUERY_WORKERS);
+    if workers == 1 { return channels.iter().flat_map(|ch| query_channel(ch, since, &after)).collect(); }
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let mut results: Vec<(usize, Vec<EventItem>)> = std::thread::scope(|s| {
+        let handles: Vec<_> = (0..workers).map(|_| s.spawn(|| {
+            let mut done = Vec::new();
+            loop {
+                let i = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                let Some(ch) = channels.get(i) else { break };
+                done.push((i, query_channel(ch, since, &after)));
+            }
+            done
+        })).collect();
+        handles.into_iter().flat_map(|h| h.join().unwrap_or_default()).collect()
+    });
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().flat_map(|(_, v)| v).collect()
+}
+
+// Trace files (.etl) go through the same query API as channels; it only reads them oldest first.
+// Rendering uses the local publisher metadata, so decoded messages need the providers installed here.
+pub fn query_trace_file(path: &str, label: &str, max: usize) -> Result<Vec<EventItem>, u32> {
+    let mut out = Vec::new();
+    unsafe {
+        let h = EvtQuery(0, w(path).as_ptr(), w("*").as_ptr(), EvtQueryFilePath | EvtQueryForwardDirection);
+        if h == 0 { return Err(GetLastError()); }
+        let h = Handle(h);
+        let mut arr: [EVT_HANDLE; 64] = [0; 64];
+        while out.len() < max && !crate::budget_exhausted() {
+            let mut returned: u32 = 0;
+            if EvtNext(h.0, arr.len() as u32, arr.as_mut_ptr(), 1000, 0, &mut returned) == 0 {
                 let code = GetLastError();
-                let h2 = EvtQuery(0, w(ch).as_ptr(), std::ptr::null(), EvtQueryChannelPath);
-                if h2 == 0 { log::error!("EvtQuery failed for {}: {}", ch, code); continue; }
-                let h = Handle(h2);
-                let mut arr: [EVT_HANDLE; 64] = [0; 64];
-                loop {
-                    let mut returned: u32 = 0;
-                    let ok = EvtNext(h.0, arr.len() as u32, arr.as_mut_ptr(), 100, 0, &mut returned);
-                    if ok == 0 { let code = GetLastError(); if code != 259 && code != 0 { log::error!("EvtNext error: {}", code); } break; }
-                    if returned == 0 { break; }
-                    for &ev in arr.iter().take(returned as usize) {
-                        if let Some(xml) = render_xml(ev) && let Some(mut item) = parse_event_xml(&xml, ch) {
-                            if let Some(msg) = crate::decoder::decode_event(&item.provider, item.event_id, &xml) { item.content = msg; }
-                            item.raw_xml = Some(xml.clone());
-                            out.push(item);
-                        }
-                        EvtClose(ev);
-                    }
-                }
-                continue;
+                if code != 259 && code != 0 { log::error!("EvtNext error for {}: {}", path, code); record_error(label, "EvtNext", code, 0); }
+                break;
             }
-            let h = Handle(h);
-            let mut arr: [EVT_HANDLE; 64] = [0; 64];
-            loop {
-                let mut returned: u32 = 0;
-                let ok = EvtNext(h.0, arr.len() as u32, arr.as_mut_ptr(), 100, 0, &mut returned);
-                if ok == 0 {
-                    let code = GetLastError();
-                    if code != 259 && code != 0 { log::error!("EvtNext error: {}", code); }
-                    break;
-                }
-                if returned == 0 { break; }
-                for &ev in arr.iter().take(returned as usize) {
-                    if let Some(xml) = render_xml(ev) && let Some(mut item) = parse_event_xml(&xml, ch) {
-                        if let Some(msg) = crate::decoder::decode_event(&item.provider, item.event_id, &xml) { item.content = msg; }
-                        item.raw_xml = Some(xml.clone());
-                        out.push(item);
-                    }
-                    EvtClose(ev);
-                }
+            if returned == 0 { break; }
+            for &ev in arr.iter().take(returned as usize) {
+                if let Some(item) = live_item(ev, label) { out.push(item); }
+                EvtClose(ev);
             }
         }
     }
-    out
+    Ok(out)
+}
+
+// Reading Security needs SeSecurityPrivilege, which elevated administrators hold but have disabled by default.
+// False when the token does not hold it; the Security query then fails with access denied and pre-flight explains why.
+pub fn enable_security_privilege() -> bool {
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE, LUID};
+    use windows_sys::Win32::Security::{AdjustTokenPrivileges, LookupPrivilegeValueW, LUID_AND_ATTRIBUTES, SE_PRIVILEGE_ENABLED, TOKEN_ADJUST_PRIVILEGES, TOKEN_PRIVILEGES, TOKEN_QUERY};
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+    unsafe {
+        let mut token: HANDLE = ptr::null_mut();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_ADJUST_PRIVILEGES | TOKEN_QUERY, &mut token) == 0 { return false; }
+        let mut luid = LUID { LowPart: 0, HighPart: 0 };
+        let ok = LookupPrivilegeValueW(ptr::null(), w("SeSecurityPrivilege").as_ptr(), &mut luid) != 0 && {
+            let tp = TOKEN_PRIVILEGES { PrivilegeCount: 1, Privileges: [LUID_AND_ATTRIBUTES { Luid: luid, Attributes: SE_PRIVILEGE_ENABLED }] };
+            // Succeeds even when the privilege is not held; ERROR_NOT_ALL_ASSIGNED (1300) says so.
+            AdjustTokenPrivileges(token, 0, &tp, 0, ptr::null_mut(), ptr::null_mut()) != 0 && GetLastError() != 1300
+        };
+        CloseHandle(token);
+        ok
+    }
+}
+
+// Copies the events a structured query selects into a new .evtx; the target must not exist yet.
+pub fn export_log(query: &str, target: &str) -> Result<(), u32> {
+    unsafe {
+        if EvtExportLog(session(), ptr::null(), w(query).as_ptr(), w(target).as_ptr(), EvtExportLogChannelPath) == 0 { return Err(GetLastError()); }
+    }
+    Ok(())
+}
+
+// Opening a query is where missing read access shows up (5 = access denied, 15007 = no such channel).
+pub fn probe_channel(ch: &str) -> Result<(), u32> {
+    unsafe {
+        let h = EvtQuery(session(), w(ch).as_ptr(), w("*").as_ptr(), EvtQueryChannelPath);
+        if h == 0 { return Err(GetLastError()); }
+        drop(Handle(h));
+    }
+    Ok(())
+}
+
+// Newest boot marker in the live System log.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn last_boot_time() -> Option<DateTime<Utc>> {
+    first_event_time("System", "*[System[(Provider[@Name='EventLog'] and EventID=6005) or (Provider[@Name='Microsoft-Windows-Kernel-General'] and EventID=12)]]", EvtQueryReverseDirection)
+}
+
+// Oldest record still in the channel, i.e. how far back its history reaches.
+pub fn oldest_event_time(ch: &str) -> Option<DateTime<Utc>> { first_event_time(ch, "*", EvtQueryForwardDirection) }
+
+fn first_event_time(ch: &str, query: &str, direction: u32) -> Option<DateTime<Utc>> {
+    unsafe {
+        let h = EvtQuery(session(), w(ch).as_ptr(), w(query).as_ptr(), EvtQueryChannelPath | direction);
+        if h == 0 { return None; }
+        let h = Handle(h);
+        let mut ev: EVT_HANDLE = 0;
+        let mut returned: u32 = 0;
+        if EvtNext(h.0, 1, &mut ev, 1000, 0, &mut returned) == 0 || returned == 0 { return None; }
+        let ev = Handle(ev);
+        render_xml(ev.0).and_then(|xml| parse_event_xml(&xml, ch)).map(|e| e.time)
+    }
 }
 
 unsafe fn render_xml(ev: EVT_HANDLE) -> Option<String> {
@@ -77,18 +241,60 @@ unsafe fn render_xml(ev: EVT_HANDLE) -> Option<String> {
     } else { None }
 }
 
-pub fn subscribe_events(channels: &[String], duration_secs: u64) -> Vec<EventItem> {
-    use std::sync::mpsc::{channel, Sender};
-    let (tx, rx) = channel::<(String, String)>();
-    #[repr(C)]
-    struct CallbackCtx { tx: Sender<(String, String)>, ch: String }
-    let mut subs: Vec<Handle> = vec![];
-    let mut ctx_ptrs: Vec<*mut CallbackCtx> = vec![];
+static RENDER_MESSAGES: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(true);
+
+pub fn set_render_messages(on: bool) { RENDER_MESSAGES.store(on, std::sync::atomic::Ordering::Relaxed); }
+
+// Publisher metadata handles stay open for the whole run; 0 marks a publisher that cannot be opened.
+static PUBLISHER_META: std::sync::OnceLock<std::sync::Mutex<std::collections::HashMap<String, EVT_HANDLE>>> = std::sync::OnceLock::new();
+
+fn publisher_meta(provider: &str) -> EVT_HANDLE {
+    let cache = PUBLISHER_META.get_or_init(Default::default);
+    let Ok(mut c) = cache.lock() else { return 0 };
+    *c.entry(provider.to_string()).or_insert_with(|| unsafe { EvtOpenPublisherMetadata(session(), w(provider).as_ptr(), ptr::null(), 0, 0) })
+}
+
+// The provider's message with all inserts substituted, in the user's UI language.
+unsafe fn event_message(ev: EVT_HANDLE, provider: &str) -> Option<String> {
+    if !RENDER_MESSAGES.load(std::sync::atomic::Ordering::Relaxed) { return None; }
+    let meta = publisher_meta(provider);
+    if meta == 0 { return None; }
+    unsafe { format_message(meta, ev, 0, EvtFormatMessageEvent) }.filter(|m| !m.is_empty())
+}
+
+// Decoders win because they explain the event; otherwise the rendered message replaces the raw EventData.
+unsafe fn live_item(ev: EVT_HANDLE, ch: &str) -> Option<EventItem> {
+    let Some(xml) = (unsafe { render_xml(ev) }) else { record_error(ch, "EvtRender", unsafe { GetLastError() }, 1); return None };
+    let Some(mut item) = parse_event_xml(&xml, ch) else { record_error(ch, "parse", 0, 1); return None };
+    if let Some(msg) = crate::decoder::decode_event(&item.provider, item.event_id, &xml).or_else(|| unsafe { event_message(ev, &item.provider) }) { item.content = msg; }
+    item.raw_xml = Some(xml);
+    Some(item)
+}
+
+#[repr(C)]
+struct CallbackCtx { tx: std::sync::mpsc::SyncSender<EventItem>, ch: String }
+
+// Events waiting for the consumer; when full, the callback blocks instead of growing memory without limit.
+const FEED_CAPACITY: usize = 4096;
+
+struct Contexts(Vec<*mut CallbackCtx>);
+
+impl Drop for Contexts {
+    fn drop(&mut self) { for ptr in self.0.drain(..) { unsafe { let _ = Box::from_raw(ptr); } } }
+}
+
+// Push subscriptions on each channel. Fields drop in order: the receiver first, so a callback blocked on a
+// full feed wakes up, then the subscriptions, and only then the contexts their callbacks borrow.
+struct Subscription { rx: std::sync::mpsc::Receiver<EventItem>, subs: Vec<Handle>, ctxs: Contexts }
+
+fn subscribe(channels: &[String]) -> Subscription {
+    let (tx, rx) = std::sync::mpsc::sync_channel::<EventItem>(FEED_CAPACITY);
+    let mut sub = Subscription { rx, subs: vec![], ctxs: Contexts(vec![]) };
+    // Rendering needs the event handle, which is only valid inside the callback.
     unsafe extern "system" fn callback(action: EVT_SUBSCRIBE_NOTIFY_ACTION, user: *const core::ffi::c_void, event: EVT_HANDLE) -> u32 {
-        if action == EvtSubscribeActionDeliver
-            && let Some(xml) = unsafe { crate::windows_live::render_xml(event) } {
+        if action == EvtSubscribeActionDeliver {
             let c = unsafe { &*(user as *const CallbackCtx) };
-            let _ = c.tx.send((c.ch.clone(), xml));
+            if let Some(item) = unsafe { live_item(event, &c.ch) } { let _ = c.tx.send(item); }
         }
         0
     }
@@ -96,21 +302,307 @@ pub fn subscribe_events(channels: &[String], duration_secs: u64) -> Vec<EventIte
         for ch in channels {
             let path_w = w(ch);
             let ctx = Box::into_raw(Box::new(CallbackCtx { tx: tx.clone(), ch: ch.clone() }));
-            ctx_ptrs.push(ctx);
-            let h = EvtSubscribe(0, std::ptr::null_mut(), path_w.as_ptr(), w("*").as_ptr(), 0, ctx as *const _, Some(callback), EvtSubscribeToFutureEvents);
-            if h == 0 { continue; }
-            subs.push(Handle(h));
+            sub.ctxs.0.push(ctx);
+            let h = EvtSubscribe(session(), std::ptr::null_mut(), path_w.as_ptr(), w("*").as_ptr(), 0, ctx as *const _, Some(callback), EvtSubscribeToFutureEvents);
+            if h == 0 { log::warn!("Subscribe failed for {} ({})", ch, GetLastError()); continue; }
+            sub.subs.push(Handle(h));
+        }
+    }
+    sub
+}
+
+pub enum Feed { Event(EventItem), Tick }
+
+// Hands each event to `on_feed` as it arrives, plus a Tick every `tick` (if any), until `duration` has passed.
+pub fn subscribe_events(channels: &[String], duration: StdDuration, tick: Option<StdDuration>, mut on_feed: impl FnMut(Feed)) {
+    let sub = subscribe(channels);
+    if sub.subs.is_empty() { return; }
+    let start = std::time::Instant::now();
+    let end = start + duration;
+    let mut next_tick = tick.map(|t| start + t);
+    loop {
+        let now = std::time::Instant::now();
+        // A tick due at the deadline still fires, so the last interval gets its summary.
+        if let (Some(at), Some(t)) = (next_tick, tick) && now >= at { on_feed(Feed::Tick); next_tick = Some(at + t); continue; }
+        if now >= end { break; }
+        let wake = next_tick.map_or(end, |at| at.min(end));
+        match sub.rx.recv_timeout(wake - now) {
+            Ok(item) => on_feed(Feed::Event(item)),
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
         }
     }
-    std::thread::sleep(StdDuration::from_secs(duration_secs));
-    let mut out = vec![];
-    while let Ok((ch, xml)) = rx.try_recv() {
-        if let Some(mut item) = parse_event_xml(&xml, if ch.is_empty() { "" } else { &ch }) {
-            if let Some(msg) = crate::decoder::decode_event(&item.provider, item.event_id, &xml) { item.content = msg; }
-            item.raw_xml = Some(xml.clone());
-            out.push(item);
+    // Whatever the callbacks queued before the deadline still counts.
+    for item in sub.rx.try_iter() { on_feed(Feed::Event(item)); }
+}
+
+// Never returns once a subscription is open; the user stops it with Ctrl-C. False when no channel could be subscribed.
+pub fn follow_events(channels: &[String], mut on_event: impl FnMut(EventItem)) -> bool {
+    let sub = subscribe(channels);
+    if sub.subs.is_empty() { return false; }
+    for item in sub.rx.iter() { on_event(item); }
+    true
+}
+
+#[derive(Clone, Debug, Default, serde::Serialize)]
+pub struct ChannelInfo {
+    pub name: String,
+    pub enabled: Option<bool>,
+    pub max_size_bytes: Option<u64>,
+    pub file_size_bytes: Option<u64>,
+    pub records: Option<u64>,
+    pub last_write: Option<DateTime<Utc>>,
+    // Retention set: old events are kept rather than overwritten; with auto-backup the full log is archived first.
+    pub retention: Option<bool>,
+    pub auto_backup: Option<bool>,
+    pub full: Option<bool>,
+    // EvtChannelConfigType: 0 Admin, 1 Operational, 2 Analytic, 3 Debug.
+    pub kind: Option<u32>,
+}
+
+// FILETIME counts 100ns ticks since 1601-01-01.
+pub fn filetime_to_utc(ft: u64) -> Option<DateTime<Utc>> {
+    if ft == 0 { return None; }
+    DateTime::from_timestamp((ft / 10_000_000) as i64 - 11_644_473_600, ((ft % 10_000_000) * 100) as u32)
+}
+
+// Evt*Property getters are called once for the size and again into an 8-byte aligned buffer.
+unsafe fn get_variant(get: impl Fn(u32, *mut EVT_VARIANT, *mut u32) -> i32) -> Option<Vec<u64>> {
+    let mut used: u32 = 0;
+    get(0, ptr::null_mut(), &mut used);
+    if used == 0 { return None; }
+    let mut buf = vec![0u64; (used as usize).div_ceil(8)];
+    if get(used, buf.as_mut_ptr() as *mut EVT_VARIANT, &mut used) == 0 { return None; }
+    Some(buf)
+}
+
+unsafe fn variant_u64(buf: &[u64]) -> Option<u64> {
+    let v = unsafe { &*(buf.as_ptr() as *const EVT_VARIANT) };
+    let t = v.Type as i32;
+    unsafe {
+        if t == EvtVarTypeUInt64 || t == EvtVarTypeFileTime { Some(v.Anonymous.UInt64Val) }
+        else if t == EvtVarTypeUInt32 { Some(v.Anonymous.UInt32Val as u64) }
+        else if t == EvtVarTypeBoolean { Some((v.Anonymous.BooleanVal != 0) as u64) }
+        else { None }
+    }
+}
+
+unsafe fn variant_string(buf: &[u64]) -> Option<String> {
+    let v = unsafe { &*(buf.as_ptr() as *const EVT_VARIANT) };
+    if v.Type as i32 != EvtVarTypeString { return None; }
+    let p = unsafe { v.Anonymous.StringVal };
+    if p.is_null() { return None; }
+    let len = (0..).take_while(|&i| unsafe { *p.add(i) } != 0).count();
+    Some(String::from_utf16_lossy(unsafe { std::slice::from_raw_parts(p, len) }))
+}
+
+// EvtNextChannelPath / EvtNextPublisherId style enumerators; None once the enumeration is exhausted.
+unsafe fn next_name(next: impl Fn(u32, *mut u16, *mut u32) -> i32) -> Option<String> {
+    let mut buf: Vec<u16> = vec![0; 512];
+    let mut used: u32 = 0;
+    if next(buf.len() as u32, buf.as_mut_ptr(), &mut used) == 0 {
+        if unsafe { GetLastError() } != 122 { return None; }
+        buf = vec![0; used as usize];
+        if next(buf.len() as u32, buf.as_mut_ptr(), &mut used) == 0 { return None; }
+    }
+    Some(String::from_utf16_lossy(&buf[..(used as usize).saturating_sub(1)]))
+}
+
+pub fn channel_info(name: &str) -> ChannelInfo {
+    let mut info = ChannelInfo { name: name.to_string(), ..Default::default() };
+    let path = w(name);
+    unsafe {
+        let cfg = EvtOpenChannelConfig(session(), path.as_ptr(), 0);
+        if cfg != 0 {
+            let cfg = Handle(cfg);
+            let prop = |id| get_variant(|n, b, u| EvtGetChannelConfigProperty(cfg.0, id, 0, n, b, u)).and_then(|b| variant_u64(&b));
+            info.enabled = prop(EvtChannelConfigEnabled).map(|v| v != 0);
+            info.max_size_bytes = prop(EvtChannelLoggingConfigMaxSize);
+            info.retention = prop(EvtChannelLoggingConfigRetention).map(|v| v != 0);
+            info.auto_backup = prop(EvtChannelLoggingConfigAutoBackup).map(|v| v != 0);
+            info.kind = prop(EvtChannelConfigType).map(|v| v as u32);
+        }
+        // Disabled or restricted channels (e.g. Security without admin rights) have no readable log.
+        let log = EvtOpenLog(session(), path.as_ptr(), EvtOpenChannelPath);
+        if log != 0 {
+            let log = Handle(log);
+            let prop = |id| get_variant(|n, b, u| EvtGetLogInfo(log.0, id, n, b, u)).and_then(|b| variant_u64(&b));
+            info.file_size_bytes = prop(EvtLogFileSize);
+            info.records = prop(EvtLogNumberOfLogRecords);
+            info.last_write = prop(EvtLogLastWriteTime).and_then(filetime_to_utc);
+            info.full = prop(EvtLogFull).map(|v| v != 0);
         }
     }
-    for ptr in ctx_ptrs { unsafe { let _ = Box::from_raw(ptr); } }
+    info
+}
+
+pub fn list_channels() -> Vec<ChannelInfo> {
+    let mut out = Vec::new();
+    unsafe {
+        let e = EvtOpenChannelEnum(session(), 0);
+        if e == 0 { log::error!("EvtOpenChannelEnum failed: {}", GetLastError()); return out; }
+        let e = Handle(e);
+        while let Some(name) = next_name(|n, b, u| EvtNextChannelPath(e.0, n, b, u)) { out.push(channel_info(&name)); }
+    }
+    out.sort_by_key(|c| c.name.to_lowercase());
     out
 }
+
+#[derive(Clone, Debug, Default, serde::Serialize)]
+pub struct EventMeta {
+    pub id: u32,
+    pub version: u8,
+    pub level: u8,
+    pub channel: Option<String>,
+    pub description: String,
+    pub keywords: u64,
+    // EventData names from the event's template, in order.
+    pub fields: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, serde::Serialize)]
+pub struct ProviderInfo {
+    pub name: String,
+    pub channels: Vec<String>,
+    pub events: Vec<EventMeta>,
+}
+
+// Unresolved inserts or parameters (15027-15029) still fill the buffer with what could be formatted.
+unsafe fn format_message(meta: EVT_HANDLE, ev: EVT_HANDLE, message_id: u32, flags: u32) -> Option<String> {
+    let mut used: u32 = 0;
+    unsafe { EvtFormatMessage(meta, ev, message_id, 0, ptr::null(), flags, 0, ptr::null_mut(), &mut used) };
+    if used == 0 { return None; }
+    let mut buf: Vec<u16> = vec![0; used as usize];
+    if unsafe { EvtFormatMessage(meta, ev, message_id, 0, ptr::null(), flags, used, buf.as_mut_ptr(), &mut used) } == 0 && !(15027..=15029).contains(&unsafe { GetLastError() }) { return None; }
+    Some(String::from_utf16_lossy(&buf).trim_matches(char::from(0)).trim().to_string())
+}
+
+// Message text with its %1-style inserts left in place.
+unsafe fn format_message_id(meta: EVT_HANDLE, message_id: u32) -> Option<String> {
+    if message_id == u32::MAX { return None; }
+    unsafe { format_message(meta, 0, message_id, EvtFormatMessageId) }
+}
+
+// Publisher metadata arrays (channels, tasks, opcodes) are object arrays read element by element.
+unsafe fn object_array<T>(meta: EVT_HANDLE, prop: EVT_PUBLISHER_METADATA_PROPERTY_ID, mut item: impl FnMut(EVT_HANDLE, u32) -> Option<T>) -> Vec<T> {
+    let mut out = Vec::new();
+    let Some(arr) = (unsafe { get_variant(|n, b, u| EvtGetPublisherMetadataProperty(meta, prop, 0, n, b, u)) }) else { return out };
+    let v = unsafe { &*(arr.as_ptr() as *const EVT_VARIANT) };
+    if v.Type as i32 != EvtVarTypeEvtHandle { return out; }
+    let h = unsafe { v.Anonymous.EvtHandleVal };
+    let mut size: u32 = 0;
+    if unsafe { EvtGetObjectArraySize(h, &mut size) } != 0 { out.extend((0..size).filter_map(|i| item(h, i))); }
+    unsafe { EvtClose(h) };
+    out
+}
+
+unsafe fn array_u64(h: EVT_HANDLE, prop: EVT_PUBLISHER_METADATA_PROPERTY_ID, i: u32) -> Option<u64> {
+    unsafe { get_variant(|n, b, u| EvtGetObjectArrayProperty(h, prop as u32, i, 0, n, b, u)).and_then(|b| variant_u64(&b)) }
+}
+
+unsafe fn array_string(h: EVT_HANDLE, prop: EVT_PUBLISHER_METADATA_PROPERTY_ID, i: u32) -> Option<String> {
+    unsafe { get_variant(|n, b, u| EvtGetObjectArrayProperty(h, prop as u32, i, 0, n, b, u)).and_then(|b| variant_string(&b)) }
+}
+
+// Channel references map the numeric channel value in event metadata to a channel path.
+unsafe fn channel_refs(meta: EVT_HANDLE) -> Vec<(u32, String)> {
+    unsafe { object_array(meta, EvtPublisherMetadataChannelReferences, |h, i| Some((array_u64(h, EvtPublisherMetadataChannelReferenceID, i)? as u32, array_string(h, EvtPublisherMetadataChannelReferencePath, i)?))) }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TaskNames {
+    pub tasks: Vec<(u16, String)>,
+    // (task, opcode, name): opcodes may be declared for a single task or for all tasks (task 0).
+    pub opcodes: Vec<(u16, u8, String)>,
+    // Provider-defined keyword masks (low 48 bits) and their names.
+    pub keywords: Vec<(u64, String)>,
+    pub levels: Vec<(u8, String)>,
+}
+
+// Localized task, opcode, keyword and level names a publisher declares; None when its metadata cannot be opened.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn task_names(provider: &str) -> Option<TaskNames> {
+    unsafe {
+        let meta = EvtOpenPublisherMetadata(session(), w(provider).as_ptr(), ptr::null(), 0, 0);
+        if meta == 0 { return None; }
+        let meta = Handle(meta);
+        // Display names come from the message table; the symbolic name is the fallback.
+        let name = |h, i, msg, sym| array_u64(h, msg, i).and_then(|m| format_message_id(meta.0, m as u32)).filter(|s| !s.is_empty()).or_else(|| array_string(h, sym, i));
+        let tasks = object_array(meta.0, EvtPublisherMetadataTasks, |h, i| Some((array_u64(h, EvtPublisherMetadataTaskValue, i)? as u16, name(h, i, EvtPublisherMetadataTaskMessageID, EvtPublisherMetadataTaskName)?)));
+        // The opcode value packs the opcode in the high word and its task in the low word.
+        let opcodes = object_array(meta.0, EvtPublisherMetadataOpcodes, |h, i| { let v = array_u64(h, EvtPublisherMetadataOpcodeValue, i)?; Some(((v & 0xFFFF) as u16, (v >> 16) as u8, name(h, i, EvtPublisherMetadataOpcodeMessageID, EvtPublisherMetadataOpcodeName)?)) });
+        let keywords = object_array(meta.0, EvtPublisherMetadataKeywords, |h, i| Some((array_u64(h, EvtPublisherMetadataKeywordValue, i)?, name(h, i, EvtPublisherMetadataKeywordMessageID, EvtPublisherMetadataKeywordName)?)));
+        let levels = object_array(meta.0, EvtPublisherMetadataLevels, |h, i| Some((array_u64(h, EvtPublisherMetadataLevelValue, i)? as u8, name(h, i, EvtPublisherMetadataLevelMessageID, EvtPublisherMetadataLevelName)?)));
+        Some(TaskNames { tasks, opcodes, keywords, levels })
+    }
+}
+
+// All publishers (or only `only`, when non-empty) with the events they declare; `channel` keeps publishers writing to that channel and only their events for it.
+pub fn list_providers(channel: Option<&str>, only: &[String]) -> Vec<ProviderInfo> {
+    let mut out = Vec::new();
+    unsafe {
+        let e = EvtOpenPublisherEnum(session(), 0);
+        if e == 0 { log::error!("EvtOpenPublisherEnum failed: {}", GetLastError()); return out; }
+        let e = Handle(e);
+        while let Some(name) = next_name(|n, b, u| EvtNextPublisherId(e.0, n, b, u)) {
+            if !only.is_empty() && !only.iter().any(|p| p.eq_ignore_ascii_case(&name)) { continue; }
+            match provider_info(&name, channel) {
+                Ok(Some(info)) => out.push(info),
+                // Publishers whose message DLL is missing cannot be opened; list them by name only.
+                Err(_) if channel.is_none() => out.push(ProviderInfo { name, ..Default::default() }),
+                _ => {}
+            }
+        }
+    }
+    out.sort_by_key(|p| p.name.to_lowercase());
+    out
+}
+
+// Every event a single provider declares; the error is the Win32 code from opening its metadata.
+pub fn provider_catalog(name: &str) -> Result<ProviderInfo, u32> {
+    unsafe { provider_info(name, None).map(|p| p.unwrap_or_default()) }
+}
+
+// Ok(None) when a channel is given and the provider does not write to it.
+unsafe fn provider_info(name: &str, channel: Option<&str>) -> Result<Option<ProviderInfo>, u32> {
+    unsafe {
+        let meta = EvtOpenPublisherMetadata(session(), w(name).as_ptr(), ptr::null(), 0, 0);
+        if meta == 0 { return Err(GetLastError()); }
+        let meta = Handle(meta);
+        let refs = channel_refs(meta.0);
+        let mut info = ProviderInfo { name: name.to_string(), channels: refs.iter().map(|(_, p)| p.clone()).collect(), events: vec![] };
+        if let Some(ch) = channel && !info.channels.iter().any(|c| c.eq_ignore_ascii_case(ch)) { return Ok(None); }
+        let em = EvtOpenEventMetadataEnum(meta.0, 0);
+        if em != 0 {
+            let em = Handle(em);
+            loop {
+                let ev = EvtNextEventMetadata(em.0, 0);
+                if ev == 0 { break; }
+                let ev = Handle(ev);
+                let raw = |id: EVT_EVENT_METADATA_PROPERTY_ID| get_variant(|n, b, u| EvtGetEventMetadataProperty(ev.0, id, 0, n, b, u));
+                let prop = |id| raw(id).and_then(|b| variant_u64(&b));
+                let chan_val = prop(EventMetadataEventChannel).unwrap_or(0) as u32;
+                let chan = refs.iter().find(|(id, _)| *id == chan_val).map(|(_, p)| p.clone());
+                if let Some(ch) = channel && !chan.as_deref().is_some_and(|c| c.eq_ignore_ascii_case(ch)) { continue; }
+                let description = prop(EventMetadataEventMessageID).and_then(|m| format_message_id(meta.0, m as u32)).unwrap_or_default();
+                let fields = raw(EventMetadataEventTemplate).and_then(|b| variant_string(&b)).map(|t| crate::event_xml::template_fields(&t)).unwrap_or_default();
+                // The low 16 bits are the event ID shown in Event Viewer; the rest are qualifiers.
+                info.events.push(EventMeta { id: prop(EventMetadataEventID).unwrap_or(0) as u32 & 0xFFFF, version: prop(EventMetadataEventVersion).unwrap_or(0) as u8, level: prop(EventMetadataEventLevel).unwrap_or(0) as u8, channel: chan, description, keywords: prop(EventMetadataEventKeyword).unwrap_or(0), fields });
+            }
+        }
+        info.events.sort_by_key(|m| (m.id, m.version));
+        Ok(Some(info))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filetime_converts_to_utc() {
+        assert_eq!(filetime_to_utc(116_444_736_000_000_000).unwrap().timestamp(), 0);
+        assert_eq!(filetime_to_utc(133_801_632_000_000_000).unwrap().to_rfc3339(), "2025-01-01T00:00:00+00:00");
+        assert!(filetime_to_utc(0).is_none());
+    }
+}
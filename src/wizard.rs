@@ -0,0 +1,78 @@
+use crate::Args;
+use std::io::IsTerminal;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Problem { Crashes, Slowness, Network, Everything }
+
+pub struct WizardChoices {
+    pub hours: i64,
+    pub problem: Problem,
+    pub html: String,
+}
+
+impl Default for WizardChoices {
+    fn default() -> Self { WizardChoices { hours: 24, problem: Problem::Everything, html: "WinDoctor-report.html".to_string() } }
+}
+
+fn parse_problem(s: &str) -> Option<Problem> {
+    match s.trim().to_lowercase().as_str() {
+        "1" | "crash" | "crashes" | "bsod" => Some(Problem::Crashes),
+        "2" | "slow" | "slowness" | "performance" => Some(Problem::Slowness),
+        "3" | "network" | "internet" | "wifi" => Some(Problem::Network),
+        "4" | "" | "all" | "everything" => Some(Problem::Everything),
+        _ => None,
+    }
+}
+
+pub fn ask() -> WizardChoices {
+    let mut c = WizardChoices::default();
+    if !std::io::stdin().is_terminal() { return c; }
+    println!("WinDoctor wizard — press Enter to accept the value in brackets.");
+    c.hours = crate::init::prompt("How many hours back should I look?", &c.hours.to_string()).parse().unwrap_or(c.hours);
+    println!("What are you chasing?  1) crashes / blue screens  2) slowness  3) network problems  4) everything");
+    c.problem = parse_problem(&crate::init::prompt("Choice", "4")).unwrap_or(Problem::Everything);
+    c.html = crate::init::prompt("Save the HTML report as", &c.html);
+    c
+}
+
+// Turns the answers into the same flags an experienced user would pass.
+pub fn apply(args: &mut Args, c: &WizardChoices) {
+    args.hours = c.hours.max(1);
+    args.html = Some(c.html.clone());
+    args.no_open = false;
+    args.channels = vec!["System".to_string(), "Application".to_string()];
+    let providers: &[&str] = match c.problem {
+        Problem::Crashes => &["Application Error", "Application Hang", "Windows Error Reporting", "Microsoft-Windows-WER-SystemErrorReporting", "Microsoft-Windows-Kernel-Power", "BugCheck", "Microsoft-Windows-WHEA-Logger"],
+        Problem::Slowness => &["Microsoft-Windows-Diagnostics-Performance", "Microsoft-Windows-Resource-Exhaustion-Detector", "Disk", "Ntfs", "Microsoft-Windows-Ntfs", "storahci", "stornvme", "volmgr", "Microsoft-Windows-Kernel-Processor-Power"],
+        Problem::Network => &["Microsoft-Windows-DNS-Client", "Tcpip", "NetBT", "Microsoft-Windows-Dhcp-Client", "Microsoft-Windows-WLAN-AutoConfig", "Microsoft-Windows-NDIS", "Microsoft-Windows-NCSI", "Schannel"],
+        Problem::Everything => &[],
+    };
+    args.providers = providers.iter().map(|p| p.to_string()).collect();
+    match c.problem {
+        Problem::Slowness => {
+            args.channels.push("Microsoft-Windows-Diagnostics-Performance/Operational".to_string());
+            args.collect_perf = true;
+            args.smart_check = true;
+        }
+        Problem::Network => args.channels.push("Microsoft-Windows-WLAN-AutoConfig/Operational".to_string()),
+        Problem::Everything => { args.service_health = true; args.collect_perf = true; }
+        Problem::Crashes => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn choices_become_filters() {
+        assert_eq!(parse_problem(" Slow "), Some(Problem::Slowness));
+        assert_eq!(parse_problem("9"), None);
+        let mut a = Args::default();
+        apply(&mut a, &WizardChoices { hours: 6, problem: Problem::Network, ..Default::default() });
+        assert_eq!(a.hours, 6);
+        assert!(a.providers.iter().any(|p| p == "Microsoft-Windows-DNS-Client"));
+        assert_eq!(a.channels.len(), 3);
+        assert_eq!(a.html.as_deref(), Some("WinDoctor-report.html"));
+    }
+}